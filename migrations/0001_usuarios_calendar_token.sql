@@ -0,0 +1,3 @@
+-- Token personal para los feeds iCalendar (GET /api/eventos/calendar.ics?token=..., /api/theme-of-the-day/calendar.ics?token=...).
+ALTER TABLE usuarios ADD COLUMN IF NOT EXISTS calendar_token TEXT;
+CREATE UNIQUE INDEX IF NOT EXISTS usuarios_calendar_token_key ON usuarios (calendar_token);
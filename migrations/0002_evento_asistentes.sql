@@ -0,0 +1,12 @@
+-- Cupo de eventos y asistencia (RSVP) con lista de espera.
+ALTER TABLE eventos ADD COLUMN IF NOT EXISTS capacity INTEGER CHECK (capacity IS NULL OR capacity > 0);
+
+CREATE TABLE IF NOT EXISTS evento_asistentes (
+    evento_id UUID NOT NULL REFERENCES eventos(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES usuarios(id) ON DELETE CASCADE,
+    status TEXT NOT NULL CHECK (status IN ('confirmed', 'waitlisted')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (evento_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_evento_asistentes_user ON evento_asistentes (user_id);
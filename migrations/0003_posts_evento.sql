@@ -0,0 +1,3 @@
+-- Galería de eventos: un post puede pertenecer opcionalmente a un evento.
+ALTER TABLE posts ADD COLUMN IF NOT EXISTS evento_id UUID REFERENCES eventos(id) ON DELETE SET NULL;
+CREATE INDEX IF NOT EXISTS idx_posts_evento_created ON posts (evento_id, created_at DESC);
@@ -0,0 +1,2 @@
+-- Zona horaria del usuario (nombre IANA o desfase ±HH:MM) para calcular el tema del día en su fecha local.
+ALTER TABLE usuarios ADD COLUMN IF NOT EXISTS time_zone TEXT;
@@ -0,0 +1,53 @@
+-- Temas del día por fecha completa (una instancia por día y año; MMdd queda como búsqueda).
+-- La tabla anterior (clave MMdd) se conserva como theme_of_the_day_mmdd_legacy para verificación.
+DO $$
+BEGIN
+    IF to_regclass('theme_of_the_day_mmdd_legacy') IS NULL
+       AND EXISTS (SELECT 1 FROM information_schema.columns
+                   WHERE table_schema = current_schema()
+                     AND table_name = 'theme_of_the_day' AND column_name = 'id') THEN
+        ALTER TABLE theme_of_the_day RENAME TO theme_of_the_day_mmdd_legacy;
+    END IF;
+END $$;
+
+CREATE TABLE IF NOT EXISTS theme_of_the_day (
+    date DATE PRIMARY KEY,
+    mmdd TEXT NOT NULL,
+    name TEXT NOT NULL,
+    url TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS idx_theme_of_the_day_mmdd ON theme_of_the_day (mmdd, date DESC);
+
+-- Cada tema anterior se instancia en el año actual y en cada año en que tuvo posts
+-- (0229 solo en años bisiestos; ids que no son fechas válidas se descartan).
+INSERT INTO theme_of_the_day (date, mmdd, name, url)
+SELECT DISTINCT ON (d.date) d.date, l.id, l.name, l.url
+FROM (
+    SELECT * FROM theme_of_the_day_mmdd_legacy
+    WHERE id ~ '^(0[1-9]|1[0-2])(0[1-9]|[12][0-9]|3[01])$'
+) l
+CROSS JOIN LATERAL (
+    SELECT EXTRACT(YEAR FROM now())::int AS year
+    UNION
+    SELECT EXTRACT(YEAR FROM p.created_at)::int
+    FROM posts p
+    WHERE p.theme_of_the_day_id = l.id AND p.created_at IS NOT NULL
+) y
+CROSS JOIN LATERAL (
+    -- Día 1 del mes + (dd - 1) días: no falla con fechas inexistentes; se descartan abajo.
+    SELECT (make_date(y.year, substr(l.id, 1, 2)::int, 1)
+            + (substr(l.id, 3, 2)::int - 1) * INTERVAL '1 day')::date AS date
+) d
+WHERE EXTRACT(MONTH FROM d.date) = substr(l.id, 1, 2)::int
+ON CONFLICT (date) DO NOTHING;
+
+-- Galerías: cada post se enlaza a la instancia del tema de su año.
+ALTER TABLE posts ADD COLUMN IF NOT EXISTS theme_date DATE
+    REFERENCES theme_of_the_day(date) ON DELETE SET NULL;
+UPDATE posts p SET theme_date = t.date
+FROM theme_of_the_day t
+WHERE t.mmdd = p.theme_of_the_day_id
+  AND EXTRACT(YEAR FROM t.date) = EXTRACT(YEAR FROM p.created_at)
+  AND p.theme_date IS NULL;
+CREATE INDEX IF NOT EXISTS idx_posts_theme_date ON posts (theme_date, created_at DESC);
@@ -0,0 +1,15 @@
+-- Bolsa de temas sin fecha para la rotación automática cuando no hay tema programado.
+CREATE TABLE IF NOT EXISTS theme_of_the_day_pool (
+    id UUID PRIMARY KEY,
+    name TEXT NOT NULL,
+    url TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- Los temas elegidos por fallback se guardan como instancia de ese día:
+-- el id sigue siendo la fecha y la elección no cambia aunque cambie la bolsa.
+ALTER TABLE theme_of_the_day ADD COLUMN IF NOT EXISTS fallback TEXT;
+ALTER TABLE theme_of_the_day ADD COLUMN IF NOT EXISTS pool_entry_id UUID
+    REFERENCES theme_of_the_day_pool(id) ON DELETE SET NULL;
+-- Rotación de la bolsa: última vez que se usó cada entrada.
+CREATE INDEX IF NOT EXISTS idx_theme_of_the_day_pool_entry ON theme_of_the_day (pool_entry_id, date DESC);
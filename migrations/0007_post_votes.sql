@@ -0,0 +1,20 @@
+-- Votos del reto diario (uno por usuario y post) y ganador de cada día.
+CREATE TABLE IF NOT EXISTS post_votes (
+    post_id UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES usuarios(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (post_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_post_votes_user ON post_votes (user_id);
+
+-- Se rellena al consultar el ranking de un día ya cerrado; no cambia después.
+CREATE TABLE IF NOT EXISTS theme_of_the_day_winners (
+    theme_date DATE PRIMARY KEY,
+    post_id UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    user_id UUID,
+    votes BIGINT NOT NULL,
+    decided_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_posts_user_theme_date ON posts (user_id, theme_date);
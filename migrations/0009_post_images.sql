@@ -0,0 +1,14 @@
+-- Varias imágenes por post (carrusel), en orden. Cada imagen se guarda como {id}.{ext}.
+CREATE TABLE IF NOT EXISTS post_images (
+    id UUID PRIMARY KEY,
+    post_id UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    -- DEFERRABLE: reordenar y compactar mueven varias posiciones en una sola sentencia.
+    CONSTRAINT post_images_post_position_key UNIQUE (post_id, position) DEFERRABLE INITIALLY IMMEDIATE
+);
+
+-- Posts existentes: su imagen ({post_id}.{ext}) pasa a ser la primera del carrusel.
+INSERT INTO post_images (id, post_id, position)
+SELECT id, id, 0 FROM posts WHERE url IS NOT NULL
+ON CONFLICT (id) DO NOTHING;
@@ -0,0 +1,42 @@
+-- Nombre canónico de un hashtag en SQL: misma normalización que Hashtag::normalize_name (sin `#`, minúsculas y
+-- sin tildes). Las dos listas deben coincidir con HASHTAG_ACCENTS / HASHTAG_ACCENTS_FOLDED.
+CREATE OR REPLACE FUNCTION hashtag_key(name TEXT) RETURNS TEXT
+LANGUAGE sql IMMUTABLE PARALLEL SAFE AS $$
+    SELECT translate(
+        lower(ltrim(btrim(name), '#')),
+        'áàäâãéèëêíìïîóòöôõúùüûçÁÀÄÂÃÉÈËÊÍÌÏÎÓÒÖÔÕÚÙÜÛÇÑ',
+        'aaaaaeeeeiiiiooooouuuucaaaaaeeeeiiiiooooouuuucñ'
+    )
+$$;
+
+-- Hashtags repetidos (ej. "Retrato" y "retrato"): se fusionan en uno. Se conserva el que ya tiene el nombre
+-- canónico (o el primero por nombre) y se le pasan los enlaces de poses y posts.
+CREATE TEMP TABLE hashtag_dupes AS
+SELECT id AS source_id, target_id FROM (
+    SELECT id, first_value(id) OVER (
+        PARTITION BY hashtag_key(name) ORDER BY (name = hashtag_key(name)) DESC, name, id
+    ) AS target_id
+    FROM hashtags
+) ranked
+WHERE id <> target_id;
+
+INSERT INTO hashtag_image (pose_id, hashtag_id)
+SELECT DISTINCT hi.pose_id, d.target_id
+FROM hashtag_image hi JOIN hashtag_dupes d ON d.source_id = hi.hashtag_id
+WHERE NOT EXISTS (SELECT 1 FROM hashtag_image t WHERE t.hashtag_id = d.target_id AND t.pose_id = hi.pose_id)
+ON CONFLICT DO NOTHING;
+
+INSERT INTO hashtag_pose (post_id, hashtag_id)
+SELECT DISTINCT hp.post_id, d.target_id
+FROM hashtag_pose hp JOIN hashtag_dupes d ON d.source_id = hp.hashtag_id
+WHERE NOT EXISTS (SELECT 1 FROM hashtag_pose t WHERE t.hashtag_id = d.target_id AND t.post_id = hp.post_id)
+ON CONFLICT DO NOTHING;
+
+DELETE FROM hashtag_image WHERE hashtag_id IN (SELECT source_id FROM hashtag_dupes);
+DELETE FROM hashtag_pose WHERE hashtag_id IN (SELECT source_id FROM hashtag_dupes);
+DELETE FROM hashtags WHERE id IN (SELECT source_id FROM hashtag_dupes);
+
+DROP TABLE hashtag_dupes;
+
+-- Un solo hashtag por nombre canónico: las altas concurrentes usan ON CONFLICT sobre este índice.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_hashtags_name_key ON hashtags (hashtag_key(name));
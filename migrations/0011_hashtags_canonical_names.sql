@@ -0,0 +1,5 @@
+-- Nombres existentes en forma canónica ("#Retrato" → "retrato"), como los crea y renombra la API.
+-- 0010 ya fusionó los repetidos, así que no hay choques con idx_hashtags_name_key.
+-- Los nombres con caracteres no válidos en un #hashtag (ej. espacios) se conservan: se corrigen renombrando.
+UPDATE hashtags SET name = hashtag_key(name)
+WHERE name <> hashtag_key(name) AND hashtag_key(name) <> '';
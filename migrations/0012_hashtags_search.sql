@@ -0,0 +1,9 @@
+-- Autocompletado de hashtags sobre el nombre canónico (hashtag_key, 0010): por prefijo y por similitud
+-- (trigramas). text_pattern_ops permite usar el índice con `LIKE 'prefijo%'` sea cual sea la collation.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS idx_hashtags_key_prefix ON hashtags (hashtag_key(name) text_pattern_ops);
+CREATE INDEX IF NOT EXISTS idx_hashtags_key_trgm ON hashtags USING gin (hashtag_key(name) gin_trgm_ops);
+
+-- Co-ocurrencia de hashtags en poses (hashtags relacionados).
+CREATE INDEX IF NOT EXISTS idx_hashtag_image_pose ON hashtag_image (pose_id);
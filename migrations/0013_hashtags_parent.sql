@@ -0,0 +1,5 @@
+-- Taxonomía de hashtags: cada hashtag puede tener un padre (ej. parejas > sentados).
+-- Al borrar un padre, sus hijos pasan a ser raíces.
+ALTER TABLE hashtags ADD COLUMN IF NOT EXISTS parent_id UUID REFERENCES hashtags(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_hashtags_parent ON hashtags (parent_id);
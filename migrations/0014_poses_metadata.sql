@@ -0,0 +1,13 @@
+-- Metadatos de las poses: título, descripción/instrucciones, número de personas, dificultad,
+-- orientación y crédito de la fuente (todos opcionales).
+ALTER TABLE poses ADD COLUMN IF NOT EXISTS title TEXT;
+ALTER TABLE poses ADD COLUMN IF NOT EXISTS description TEXT;
+ALTER TABLE poses ADD COLUMN IF NOT EXISTS people_count INTEGER CHECK (people_count > 0);
+ALTER TABLE poses ADD COLUMN IF NOT EXISTS difficulty TEXT
+    CHECK (difficulty IN ('easy', 'medium', 'hard'));
+ALTER TABLE poses ADD COLUMN IF NOT EXISTS orientation TEXT
+    CHECK (orientation IN ('portrait', 'landscape'));
+ALTER TABLE poses ADD COLUMN IF NOT EXISTS source_credit TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_poses_difficulty ON poses (difficulty);
+CREATE INDEX IF NOT EXISTS idx_poses_orientation ON poses (orientation);
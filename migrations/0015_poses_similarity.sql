@@ -0,0 +1,14 @@
+-- Hash perceptual de 64 bits de la imagen de cada pose (dHash), calculado al subir la imagen.
+-- Sirve para la similitud visual en GET /api/poses/{id}/similar; las poses sin hash solo se comparan por hashtags.
+ALTER TABLE poses ADD COLUMN IF NOT EXISTS image_hash BIGINT;
+
+-- Peso de cada hashtag según su rareza, para la similitud y las recomendaciones de poses:
+-- ln(1 + total de poses / poses con el hashtag). Se materializa para no recalcularlo en cada petición;
+-- la API lo refresca periódicamente (REFRESH ... CONCURRENTLY necesita el índice único).
+CREATE MATERIALIZED VIEW IF NOT EXISTS hashtag_idf AS
+SELECT hi.hashtag_id,
+    ln(1 + (SELECT GREATEST(COUNT(*), 1) FROM poses)::FLOAT8 / COUNT(DISTINCT hi.pose_id)) AS weight
+FROM hashtag_image hi
+GROUP BY hi.hashtag_id;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_hashtag_idf_hashtag_id ON hashtag_idf (hashtag_id);
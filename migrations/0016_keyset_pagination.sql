@@ -0,0 +1,13 @@
+-- Paginación por cursor (keyset) sobre (created_at, id) en poses, posts e imágenes del portfolio.
+-- created_at pasa a ser obligatorio para que el orden sea total.
+UPDATE poses SET created_at = now() WHERE created_at IS NULL;
+ALTER TABLE poses ALTER COLUMN created_at SET DEFAULT now(), ALTER COLUMN created_at SET NOT NULL;
+UPDATE posts SET created_at = now() WHERE created_at IS NULL;
+ALTER TABLE posts ALTER COLUMN created_at SET DEFAULT now(), ALTER COLUMN created_at SET NOT NULL;
+UPDATE portfolio_image SET created_at = now() WHERE created_at IS NULL;
+ALTER TABLE portfolio_image ALTER COLUMN created_at SET DEFAULT now(), ALTER COLUMN created_at SET NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_poses_created_at_id ON poses (created_at DESC, id DESC);
+CREATE INDEX IF NOT EXISTS idx_posts_created_at_id ON posts (created_at DESC, id DESC);
+CREATE INDEX IF NOT EXISTS idx_portfolio_image_category_created_at_id
+    ON portfolio_image (portfolio_category_id, created_at DESC, id DESC);
@@ -1,14 +1,14 @@
 // Autenticación JWT: login contra tabla usuarios y extractor Bearer para proteger rutas.
 
 use axum::{
-    extract::{FromRef, FromRequestParts, State},
+    extract::{FromRef, FromRequestParts, Query, State},
     http::{header::AUTHORIZATION, request::Parts, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 
 use uuid::Uuid;
 
@@ -30,6 +30,29 @@ pub async fn user_id_from_auth(state: &AppState, email: &str) -> Result<Uuid, Ap
     })
 }
 
+/// Resuelve el usuario autenticado y si es administrador (email en ADMIN_EMAILS).
+/// Usado por handlers que comprueban autoría (editar/borrar posts y comentarios).
+pub async fn actor_from_auth(state: &AppState, email: &str) -> Result<crate::domain::Actor, ApiError> {
+    let user_id = user_id_from_auth(state, email).await?;
+    let email = email.trim().to_lowercase();
+    Ok(crate::domain::Actor {
+        user_id,
+        is_admin: state.admin_emails.contains(&email),
+    })
+}
+
+/// Como `actor_from_auth`, pero solo admite administradores (403 si no lo es).
+/// Usado por endpoints que modifican datos compartidos (renombrar, fusionar o borrar hashtags).
+pub async fn require_admin(state: &AppState, email: &str) -> Result<crate::domain::Actor, ApiError> {
+    let actor = actor_from_auth(state, email).await?;
+    if !actor.is_admin {
+        return Err(ApiError(crate::domain::DomainError::Forbidden(
+            "Solo un administrador puede realizar esta acción".to_string(),
+        )));
+    }
+    Ok(actor)
+}
+
 /// Claims del JWT (sub = email del usuario).
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
@@ -110,6 +133,54 @@ where
     }
 }
 
+/// Query de los feeds iCalendar: token personal del feed (alternativa al header Bearer).
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct CalendarTokenQuery {
+    /// Token del feed (GET /api/profile/calendar). No es necesario si se envía Bearer token.
+    pub token: Option<String>,
+}
+
+/// Extractor para feeds iCalendar: acepta `Authorization: Bearer <token>` o `?token=<token del feed>`,
+/// ya que las apps de calendario se suscriben a una URL y no pueden enviar headers.
+#[derive(Debug, Clone)]
+pub struct CalendarFeedAuth;
+
+impl<S> FromRequestParts<S> for CalendarFeedAuth
+where
+    S: Send + Sync,
+    AppState: FromRef<S>,
+{
+    type Rejection = Response;
+
+    fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> impl std::future::Future<Output = Result<Self, Self::Rejection>> + Send {
+        let state = AppState::from_ref(state);
+        let auth = parts.headers.get(AUTHORIZATION).cloned();
+        let token = Query::<CalendarTokenQuery>::try_from_uri(&parts.uri)
+            .ok()
+            .and_then(|q| q.0.token)
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty());
+        async move {
+            if let Some(token) = token {
+                let user = state
+                    .usuarios_repo
+                    .get_by_calendar_token(&token)
+                    .await
+                    .map_err(|e| ApiError(e).into_response())?;
+                return user
+                    .map(|_| CalendarFeedAuth)
+                    .ok_or_else(|| AuthError::Invalid.into_response());
+            }
+            BearerAuth::from_header_and_secret(auth.as_ref(), state.jwt_secret.as_bytes())
+                .map(|_| CalendarFeedAuth)
+                .map_err(IntoResponse::into_response)
+        }
+    }
+}
+
 /// Genera un JWT para el usuario dado (sub = email).
 fn create_token(email: &str, secret: &[u8], exp_secs: i64) -> Result<String, jsonwebtoken::errors::Error> {
     let exp = chrono::Utc::now().timestamp() + exp_secs;
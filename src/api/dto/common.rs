@@ -1,6 +1,6 @@
 // DTOs compartidos (error, etc.)
 
-use serde::Serialize;
+use serde::{Deserialize, Deserializer, Serialize};
 use utoipa::ToSchema;
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -14,3 +14,71 @@ pub struct ErrorResponse {
 pub struct IsPoseFavoriteResponse {
     pub is_favorite: bool,
 }
+
+/// Distingue un campo ausente (None) de uno enviado como `null` (Some(None)).
+pub(crate) fn deserialize_some<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+/// Página de un listado. Con page/limit incluye count, page y total_pages; con cursor solo
+/// next_cursor (no se cuenta el total).
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Paginated<T> {
+    pub items: Vec<T>,
+    /// Total de elementos (solo con page/limit; con cursor no se cuenta).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+    /// Página actual (0-based; solo con page/limit).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    /// Tamaño de página usado.
+    pub limit: u32,
+    /// Total de páginas (solo con page/limit).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_pages: Option<u32>,
+    /// Cursor opaco para pedir la página siguiente (`?cursor=`); null si no hay más.
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Paginated<T> {
+    /// Página `page` de `limit` elementos sobre un total de `count`.
+    pub fn page(items: Vec<T>, count: u64, page: u32, limit: u32) -> Self {
+        Paginated {
+            items,
+            count: Some(count),
+            page: Some(page),
+            limit,
+            total_pages: Some(count.div_ceil(u64::from(limit.max(1))) as u32),
+            next_cursor: None,
+        }
+    }
+
+    /// Página por cursor (sin total).
+    pub fn cursor(items: Vec<T>, limit: u32, next_cursor: Option<String>) -> Self {
+        Paginated {
+            items,
+            count: None,
+            page: None,
+            limit,
+            total_pages: None,
+            next_cursor,
+        }
+    }
+
+    pub fn with_next_cursor(mut self, next_cursor: Option<String>) -> Self {
+        self.next_cursor = next_cursor;
+        self
+    }
+}
+
+/// Listado con paginación opcional: la lista completa o, si se pide page/limit, un `Paginated`.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum ListResponse<T> {
+    List(Vec<T>),
+    Paginated(Paginated<T>),
+}
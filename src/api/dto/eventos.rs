@@ -10,8 +10,10 @@ pub struct CreateEventoRequest {
     pub place: String,
     /// Imagen en base64 (acepta prefijo `data:image/xxx;base64,` o solo el payload). La URL será /api/eventos/{id}/image.
     pub image_base64: String,
-    /// Fecha en formato MMdd (ej: "1024")
+    /// Fecha en formato MMdd (ej: "1024"); debe ser un día real del calendario (0229 se celebra el 28/02 en años no bisiestos).
     pub mmdd: String,
+    /// Cupo máximo de asistentes confirmados (omitido o 0 = sin límite).
+    pub capacity: Option<i32>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -20,7 +22,10 @@ pub struct UpdateEventoRequest {
     pub place: Option<String>,
     /// Si se envía, reemplaza la imagen del evento (base64).
     pub image_base64: Option<String>,
+    /// Fecha en formato MMdd (validada como en la creación).
     pub mmdd: Option<String>,
+    /// Nuevo cupo; 0 elimina el límite. Si aumenta, se confirma a la lista de espera en orden.
+    pub capacity: Option<i32>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -31,6 +36,12 @@ pub struct EventoResponse {
     pub mmdd: String,
     pub url: String,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Cupo máximo (null = sin límite).
+    pub capacity: Option<i32>,
+    /// Asistentes confirmados.
+    pub attendees_count: i64,
+    /// Posts en la galería del evento.
+    pub posts_count: i64,
 }
 
 impl From<crate::domain::Evento> for EventoResponse {
@@ -42,6 +53,67 @@ impl From<crate::domain::Evento> for EventoResponse {
             mmdd: e.mmdd,
             url: e.url,
             created_at: e.created_at,
+            capacity: e.capacity,
+            attendees_count: e.attendees_count,
+            posts_count: e.posts_count,
         }
     }
 }
+
+/// Asistencia del usuario autenticado a un evento.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AttendanceResponse {
+    pub evento_id: Uuid,
+    /// "confirmed" o "waitlisted"
+    pub status: String,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<crate::domain::EventoAsistente> for AttendanceResponse {
+    fn from(a: crate::domain::EventoAsistente) -> Self {
+        AttendanceResponse {
+            evento_id: a.evento_id,
+            status: a.status.as_str().to_string(),
+            created_at: a.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EventoAttendeeResponse {
+    pub user_id: Uuid,
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<crate::domain::EventoAsistente> for EventoAttendeeResponse {
+    fn from(a: crate::domain::EventoAsistente) -> Self {
+        EventoAttendeeResponse {
+            user_id: a.user_id,
+            name: a.name,
+            url: a.url,
+            created_at: a.created_at,
+        }
+    }
+}
+
+/// Asistentes de un evento: confirmados y lista de espera (en orden de llegada).
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EventoAttendeesResponse {
+    pub evento_id: Uuid,
+    pub capacity: Option<i32>,
+    pub confirmed: Vec<EventoAttendeeResponse>,
+    pub waitlist: Vec<EventoAttendeeResponse>,
+}
+
+/// Evento al que asiste el usuario, con su fecha concreta y el estado de la asistencia.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UpcomingEventoResponse {
+    #[serde(flatten)]
+    pub evento: EventoResponse,
+    /// Próxima fecha del evento (YYYY-MM-DD).
+    pub date: chrono::NaiveDate,
+    /// "confirmed" o "waitlisted"
+    pub status: String,
+}
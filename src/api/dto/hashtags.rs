@@ -4,15 +4,67 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use super::common::deserialize_some;
+
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateHashtagRequest {
     pub name: String,
+    /// Hashtag padre en la taxonomía (opcional).
+    pub parent_id: Option<Uuid>,
+}
+
+/// Edición de un hashtag: los campos omitidos no cambian.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateHashtagRequest {
+    /// Nuevo nombre (se guarda normalizado).
+    pub name: Option<String>,
+    /// Nuevo padre; `null` lo convierte en raíz.
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[schema(value_type = Option<Uuid>)]
+    pub parent_id: Option<Option<Uuid>>,
+}
+
+/// Hashtag destino de la fusión.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MergeHashtagRequest {
+    pub target_id: Uuid,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct HashtagResponse {
     pub id: Uuid,
     pub name: String,
+    /// Hashtag padre (None = raíz).
+    pub parent_id: Option<Uuid>,
+}
+
+/// Nodo del árbol de hashtags (con su uso y sus hijos).
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HashtagTreeNodeResponse {
+    #[serde(flatten)]
+    pub hashtag: HashtagUsageResponse,
+    #[schema(no_recursion)]
+    pub children: Vec<HashtagTreeNodeResponse>,
+}
+
+/// Hashtag relacionado con otro (co-ocurrencia en poses).
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RelatedHashtagResponse {
+    #[serde(flatten)]
+    pub hashtag: HashtagResponse,
+    /// Poses etiquetadas con ambos hashtags.
+    pub shared_poses: i64,
+}
+
+/// Hashtag con su uso (listado).
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HashtagUsageResponse {
+    #[serde(flatten)]
+    pub hashtag: HashtagResponse,
+    /// Poses etiquetadas.
+    pub poses_count: i64,
+    /// Posts etiquetados.
+    pub posts_count: i64,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -20,11 +72,46 @@ pub struct AddHashtagsToPostRequest {
     pub hashtag_ids: Vec<Uuid>,
 }
 
+/// Reemplaza la lista de hashtags de un post.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdatePostHashtagsRequest {
+    pub hashtag_ids: Vec<Uuid>,
+}
+
 impl From<crate::domain::Hashtag> for HashtagResponse {
     fn from(h: crate::domain::Hashtag) -> Self {
         HashtagResponse {
             id: h.id,
             name: h.name,
+            parent_id: h.parent_id,
+        }
+    }
+}
+
+impl From<crate::domain::HashtagUsage> for HashtagUsageResponse {
+    fn from(u: crate::domain::HashtagUsage) -> Self {
+        HashtagUsageResponse {
+            hashtag: HashtagResponse::from(u.hashtag),
+            poses_count: u.poses_count,
+            posts_count: u.posts_count,
+        }
+    }
+}
+
+impl From<crate::domain::RelatedHashtag> for RelatedHashtagResponse {
+    fn from(r: crate::domain::RelatedHashtag) -> Self {
+        RelatedHashtagResponse {
+            hashtag: HashtagResponse::from(r.hashtag),
+            shared_poses: r.shared_poses,
+        }
+    }
+}
+
+impl From<crate::domain::HashtagTreeNode> for HashtagTreeNodeResponse {
+    fn from(n: crate::domain::HashtagTreeNode) -> Self {
+        HashtagTreeNodeResponse {
+            hashtag: HashtagUsageResponse::from(n.hashtag),
+            children: n.children.into_iter().map(HashtagTreeNodeResponse::from).collect(),
         }
     }
 }
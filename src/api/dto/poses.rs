@@ -2,6 +2,9 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use super::common::deserialize_some;
+use super::{HashtagResponse, SesionResponse};
+
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct CreatePoseRequest {
     /// Imagen en base64 (acepta prefijo `data:image/xxx;base64,` o solo el payload).
@@ -9,23 +12,118 @@ pub struct CreatePoseRequest {
     /// IDs de hashtags a asociar a la pose (se insertan en la tabla de relación pose-hashtag).
     #[serde(default)]
     pub hashtag_ids: Option<Vec<Uuid>>,
+    pub title: Option<String>,
+    /// Descripción o instrucciones para dirigir la pose.
+    pub description: Option<String>,
+    /// Número de personas (1 a 50).
+    pub people_count: Option<i32>,
+    /// easy, medium o hard.
+    pub difficulty: Option<String>,
+    /// portrait o landscape.
+    pub orientation: Option<String>,
+    /// Crédito de la fuente (autor o enlace de la referencia original).
+    pub source_credit: Option<String>,
+}
+
+/// Edición de metadatos: los campos ausentes no cambian y `null` (o texto vacío) los borra.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdatePoseRequest {
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[schema(value_type = Option<String>)]
+    pub title: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[schema(value_type = Option<String>)]
+    pub description: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[schema(value_type = Option<i32>)]
+    pub people_count: Option<Option<i32>>,
+    /// easy, medium o hard.
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[schema(value_type = Option<String>)]
+    pub difficulty: Option<Option<String>>,
+    /// portrait o landscape.
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[schema(value_type = Option<String>)]
+    pub orientation: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[schema(value_type = Option<String>)]
+    pub source_credit: Option<Option<String>>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct PoseResponse {
     pub id: Uuid,
     pub url: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub people_count: Option<i32>,
+    /// easy, medium o hard.
+    pub difficulty: Option<String>,
+    /// portrait o landscape.
+    pub orientation: Option<String>,
+    pub source_credit: Option<String>,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Solo con `?include=hashtags`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hashtags: Option<Vec<HashtagResponse>>,
+    /// Si está en favoritos del usuario; solo con `?include=favorite`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub favorite: Option<bool>,
+    /// Sesiones que contienen la pose; solo con `?include=sesiones`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(no_recursion)]
+    pub sesiones: Option<Vec<SesionResponse>>,
 }
 
-/// Respuesta paginada de poses (GET /api/poses/paginated y GET /api/hashtags/{hashtag_id}/poses/paginated).
+impl PoseResponse {
+    /// Campos admitidos en `?fields=`.
+    pub const FIELDS: &'static [&'static str] = &[
+        "id",
+        "url",
+        "title",
+        "description",
+        "people_count",
+        "difficulty",
+        "orientation",
+        "source_credit",
+        "created_at",
+        "hashtags",
+        "favorite",
+        "sesiones",
+    ];
+
+    /// Respuesta con las relaciones del lote ya resueltas (las no pedidas quedan en None).
+    pub fn with_relations(p: crate::domain::Pose, relations: &mut crate::domain::PoseRelations) -> Self {
+        let id = p.id;
+        let mut response = PoseResponse::from(p);
+        response.hashtags = relations.hashtags.as_mut().map(|by_pose| {
+            by_pose
+                .remove(&id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(HashtagResponse::from)
+                .collect()
+        });
+        response.favorite = relations.favorites.as_ref().map(|ids| ids.contains(&id));
+        response.sesiones = relations.sesiones.as_mut().map(|by_pose| {
+            by_pose
+                .remove(&id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(SesionResponse::from)
+                .collect()
+        });
+        response
+    }
+}
+
+/// Pose recomendada (GET /api/poses/{id}/similar y GET /api/poses/recommended).
 #[derive(Debug, Serialize, ToSchema)]
-pub struct PosesPaginatedResponse {
-    pub items: Vec<PoseResponse>,
-    pub count: u64,
-    pub page: u32,
-    pub limit: u32,
-    pub total_pages: u32,
+pub struct ScoredPoseResponse {
+    #[serde(flatten)]
+    pub pose: PoseResponse,
+    /// Relevancia (mayor = más parecida o más recomendada).
+    pub score: f64,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -38,7 +136,103 @@ impl From<crate::domain::Pose> for PoseResponse {
         PoseResponse {
             id: p.id,
             url: p.url,
+            title: p.metadata.title,
+            description: p.metadata.description,
+            people_count: p.metadata.people_count,
+            difficulty: p.metadata.difficulty.map(|d| d.as_str().to_string()),
+            orientation: p.metadata.orientation.map(|o| o.as_str().to_string()),
+            source_credit: p.metadata.source_credit,
             created_at: p.created_at,
+            hashtags: None,
+            favorite: None,
+            sesiones: None,
+        }
+    }
+}
+
+impl From<crate::domain::ScoredPose> for ScoredPoseResponse {
+    fn from(p: crate::domain::ScoredPose) -> Self {
+        ScoredPoseResponse {
+            pose: PoseResponse::from(p.pose),
+            score: p.score,
+        }
+    }
+}
+
+/// Operación de POST /api/poses/batch (campo `op`).
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum PoseBatchOperationRequest {
+    AddTags { hashtag_ids: Vec<Uuid> },
+    RemoveTags { hashtag_ids: Vec<Uuid> },
+    AddToSesion { sesion_id: Uuid },
+    /// Solo puede ser la última operación.
+    Delete,
+}
+
+impl From<PoseBatchOperationRequest> for crate::domain::PoseBatchOperation {
+    fn from(op: PoseBatchOperationRequest) -> Self {
+        match op {
+            PoseBatchOperationRequest::AddTags { hashtag_ids } => Self::AddTags(hashtag_ids),
+            PoseBatchOperationRequest::RemoveTags { hashtag_ids } => Self::RemoveTags(hashtag_ids),
+            PoseBatchOperationRequest::AddToSesion { sesion_id } => Self::AddToSesion(sesion_id),
+            PoseBatchOperationRequest::Delete => Self::Delete,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PoseBatchRequest {
+    /// Poses del lote (máximo 200; los repetidos se ignoran).
+    pub pose_ids: Vec<Uuid>,
+    /// Operaciones que se aplican, en orden, a cada pose.
+    pub operations: Vec<PoseBatchOperationRequest>,
+}
+
+/// Resultado de una pose del lote.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PoseBatchItemResponse {
+    pub pose_id: Uuid,
+    /// ok o not_found (la pose no existe y no se le aplicó nada).
+    pub status: String,
+    pub tags_added: u32,
+    pub tags_removed: u32,
+    pub sesiones_added: u32,
+    pub deleted: bool,
+}
+
+impl From<crate::domain::PoseBatchItem> for PoseBatchItemResponse {
+    fn from(item: crate::domain::PoseBatchItem) -> Self {
+        PoseBatchItemResponse {
+            pose_id: item.pose_id,
+            status: item.status.as_str().to_string(),
+            tags_added: item.tags_added,
+            tags_removed: item.tags_removed,
+            sesiones_added: item.sesiones_added,
+            deleted: item.deleted,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PoseBatchResponse {
+    /// Poses a las que se aplicó el lote.
+    pub processed: u32,
+    /// Poses que no existen.
+    pub not_found: u32,
+    pub items: Vec<PoseBatchItemResponse>,
+}
+
+impl From<Vec<crate::domain::PoseBatchItem>> for PoseBatchResponse {
+    fn from(items: Vec<crate::domain::PoseBatchItem>) -> Self {
+        let not_found = items
+            .iter()
+            .filter(|i| i.status == crate::domain::PoseBatchStatus::NotFound)
+            .count() as u32;
+        PoseBatchResponse {
+            processed: items.len() as u32 - not_found,
+            not_found,
+            items: items.into_iter().map(PoseBatchItemResponse::from).collect(),
         }
     }
 }
@@ -2,33 +2,196 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use super::HashtagResponse;
+
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct CreatePostRequest {
+    /// Los `#hashtags` escritos aquí se enlazan al post (se crean si no existen).
     pub description: Option<String>,
-    /// Imagen en base64 (acepta prefijo `data:image/xxx;base64,` o solo el payload). Si se envía, la URL del post será /api/posts/{id}/image.
+    /// Imagen en base64 (acepta prefijo `data:image/xxx;base64,` o solo el payload). Es la primera del carrusel;
+    /// la URL del post (/api/posts/{id}/image) sirve siempre la primera imagen.
+    #[serde(default)]
     pub image_base64: String,
-    /// Id del tema del día (ej. MMdd). Requerido.
+    /// Más imágenes en base64, en orden, tras image_base64 (carrusel; máximo 10 en total).
+    #[serde(default)]
+    pub images_base64: Vec<String>,
+    /// Tema del día: MMdd (se enlaza a la instancia más reciente de ese día) o `YYYY-MM-DD` (instancia exacta). Requerido.
     pub theme_of_the_day_id: String,
+    /// UUID del evento a cuya galería se sube el post (opcional).
+    pub evento_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AddPostImageRequest {
+    /// Imagen en base64 (acepta prefijo `data:image/xxx;base64,` o solo el payload).
+    pub image_base64: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReorderPostImagesRequest {
+    /// Todas las imágenes del post en el nuevo orden (la primera pasa a ser `url`).
+    pub image_ids: Vec<Uuid>,
+}
+
+/// Imagen del carrusel de un post.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PostImageResponse {
+    pub id: Uuid,
+    pub url: String,
+}
+
+impl From<crate::domain::PostImage> for PostImageResponse {
+    fn from(i: crate::domain::PostImage) -> Self {
+        PostImageResponse { id: i.id, url: i.url }
+    }
+}
+
+/// Edición de un post: los campos omitidos no cambian.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdatePostRequest {
+    /// Cadena vacía elimina la descripción. Sus `#hashtags` se re-sincronizan con el post.
+    pub description: Option<String>,
+    /// Nuevo tema: MMdd o `YYYY-MM-DD` (se enlaza a la instancia igual que al crear).
+    pub theme_of_the_day_id: Option<String>,
+    /// Imagen nueva en base64 (reemplaza la primera del carrusel; la URL no cambia).
+    pub image_base64: Option<String>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct PostResponse {
     pub id: Uuid,
     pub description: Option<String>,
+    /// Primera imagen (para clientes de una sola imagen).
     pub url: Option<String>,
+    /// Todas las imágenes, en orden.
+    pub images: Vec<PostImageResponse>,
     pub user_id: Option<Uuid>,
+    /// MMdd del tema del día.
     pub theme_of_the_day_id: Option<String>,
+    /// Fecha de la instancia del tema (galería a la que pertenece el post).
+    pub theme_date: Option<chrono::NaiveDate>,
+    pub evento_id: Option<Uuid>,
+    /// Hashtags del post, por nombre.
+    pub hashtags: Vec<HashtagResponse>,
+    /// Votos del reto diario.
+    pub votes_count: i64,
+    pub likes_count: i64,
+    pub comments_count: i64,
+    /// Si el usuario autenticado ha dado like al post.
+    pub liked_by_me: bool,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
-/// Respuesta paginada de posts (GET /api/posts/paginated).
+/// Resultado de dar like a un post.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PostLikeResponse {
+    pub post_id: Uuid,
+    pub likes_count: i64,
+    pub liked_by_me: bool,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreatePostCommentRequest {
+    pub body: String,
+    /// Comentario al que se responde (del mismo post). Omitido = comentario de primer nivel.
+    pub parent_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdatePostCommentRequest {
+    pub body: String,
+}
+
+/// Comentario con sus respuestas anidadas.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PostCommentResponse {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub user_id: Uuid,
+    pub user_name: Option<String>,
+    pub user_url: Option<String>,
+    pub parent_id: Option<Uuid>,
+    /// Vacío si el comentario se eliminó (se conserva porque tiene respuestas).
+    pub body: String,
+    pub deleted: bool,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[schema(no_recursion)]
+    pub replies: Vec<PostCommentResponse>,
+}
+
+impl From<crate::domain::PostComment> for PostCommentResponse {
+    fn from(c: crate::domain::PostComment) -> Self {
+        PostCommentResponse {
+            id: c.id,
+            post_id: c.post_id,
+            user_id: c.user_id,
+            user_name: c.user_name,
+            user_url: c.user_url,
+            parent_id: c.parent_id,
+            body: c.body,
+            deleted: c.deleted,
+            created_at: c.created_at,
+            updated_at: c.updated_at,
+            replies: Vec::new(),
+        }
+    }
+}
+
+impl From<crate::domain::PostCommentThread> for PostCommentResponse {
+    fn from(t: crate::domain::PostCommentThread) -> Self {
+        let mut response = PostCommentResponse::from(t.comment);
+        response.replies = t.replies.into_iter().map(PostCommentResponse::from).collect();
+        response
+    }
+}
+
+/// Resultado de votar un post.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PostVoteResponse {
+    pub post_id: Uuid,
+    pub votes_count: i64,
+}
+
+/// Post en el ranking (rank 1 = más votado; empates comparten posición).
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LeaderboardEntryResponse {
+    pub rank: u32,
+    #[serde(flatten)]
+    pub post: PostResponse,
+}
+
+/// Ganador del día (fijado al cerrarse la votación).
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DailyWinnerResponse {
+    pub post_id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub votes: i64,
+    pub decided_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<crate::domain::DailyWinner> for DailyWinnerResponse {
+    fn from(w: crate::domain::DailyWinner) -> Self {
+        DailyWinnerResponse {
+            post_id: w.post_id,
+            user_id: w.user_id,
+            votes: w.votes,
+            decided_at: w.decided_at,
+        }
+    }
+}
+
+/// Ranking de votos de un tema del día (GET /api/theme-of-the-day/{id}/leaderboard).
 #[derive(Debug, Serialize, ToSchema)]
-pub struct PostsPaginatedResponse {
-    pub items: Vec<PostResponse>,
-    pub count: u64,
-    pub page: u32,
-    pub limit: u32,
-    pub total_pages: u32,
+pub struct ThemeLeaderboardResponse {
+    /// Fecha de la instancia del tema.
+    pub theme_date: chrono::NaiveDate,
+    /// true cuando el día terminó en todas las zonas horarias (ya no se admiten votos).
+    pub closed: bool,
+    pub closes_at: chrono::DateTime<chrono::Utc>,
+    /// Solo presente cuando la votación está cerrada y el día tuvo posts.
+    pub winner: Option<DailyWinnerResponse>,
+    pub items: Vec<LeaderboardEntryResponse>,
 }
 
 impl From<crate::domain::Post> for PostResponse {
@@ -37,8 +200,16 @@ impl From<crate::domain::Post> for PostResponse {
             id: p.id,
             description: p.description,
             url: p.url,
+            images: p.images.into_iter().map(PostImageResponse::from).collect(),
             user_id: p.user_id,
             theme_of_the_day_id: p.theme_of_the_day_id,
+            theme_date: p.theme_date,
+            evento_id: p.evento_id,
+            hashtags: p.hashtags.into_iter().map(HashtagResponse::from).collect(),
+            votes_count: p.votes_count,
+            likes_count: p.likes_count,
+            comments_count: p.comments_count,
+            liked_by_me: p.liked_by_me,
             created_at: p.created_at,
         }
     }
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use super::PoseResponse;
+
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateSesionRequest {
     pub name: String,
@@ -30,6 +32,30 @@ pub struct SesionResponse {
     pub name: String,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
     pub cover_url: String,
+    /// Poses de la sesión en el orden en que se añadieron; solo con `?include=poses`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(no_recursion)]
+    pub poses: Option<Vec<PoseResponse>>,
+}
+
+impl SesionResponse {
+    /// Campos admitidos en `?fields=`.
+    pub const FIELDS: &'static [&'static str] = &["id", "name", "created_at", "cover_url", "poses"];
+
+    /// Respuesta con las relaciones del lote ya resueltas (las no pedidas quedan en None).
+    pub fn with_relations(s: crate::domain::Sesion, relations: &mut crate::domain::SesionRelations) -> Self {
+        let id = s.id;
+        let mut response = SesionResponse::from(s);
+        response.poses = relations.poses.as_mut().map(|by_sesion| {
+            by_sesion
+                .remove(&id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(PoseResponse::from)
+                .collect()
+        });
+        response
+    }
 }
 
 impl From<crate::domain::Sesion> for SesionResponse {
@@ -39,6 +65,7 @@ impl From<crate::domain::Sesion> for SesionResponse {
             name: s.name,
             created_at: s.created_at,
             cover_url: s.cover_url,
+            poses: None,
         }
     }
 }
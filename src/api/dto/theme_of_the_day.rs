@@ -5,7 +5,8 @@ use utoipa::ToSchema;
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateThemeOfTheDayRequest {
-    /// Id en formato MMdd (4 caracteres, ej: "1024")
+    /// Fecha del tema: `YYYY-MM-DD` (permite programar con antelación) o MMdd (ej: "1024"), que
+    /// programa la próxima ocurrencia de ese día. "0229" solo es tema del día en años bisiestos.
     pub id: String,
     pub name: String,
     /// Imagen en base64 (puede incluir prefijo data:image/png;base64, o solo los datos).
@@ -21,17 +22,73 @@ pub struct UpdateThemeOfTheDayRequest {
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct ThemeOfTheDayResponse {
+    /// Fecha de la instancia (YYYY-MM-DD).
     pub id: String,
+    pub date: chrono::NaiveDate,
+    /// Día del año (MMdd).
+    pub mmdd: String,
     pub name: String,
     pub url: String,
+    /// Posts enlazados a esta instancia del tema.
+    pub posts_count: i64,
+    /// true si no había tema programado para ese día y se eligió uno automáticamente (hueco en la programación).
+    pub auto_selected: bool,
+    /// Estrategia usada cuando auto_selected: "same_day", "pool" o "popular".
+    pub fallback: Option<String>,
+    /// Entrada de la bolsa de la que salen el nombre y la imagen (fallback "pool").
+    pub pool_entry_id: Option<uuid::Uuid>,
+}
+
+/// Tema del día de hoy junto con la fecha local y la zona horaria usadas para resolverlo.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ThemeOfTheDayTodayResponse {
+    #[serde(flatten)]
+    pub theme: ThemeOfTheDayResponse,
+    /// Fecha local (YYYY-MM-DD) en la zona horaria resuelta.
+    pub local_date: chrono::NaiveDate,
+    /// Zona horaria usada (nombre IANA o desfase ±HH:MM).
+    pub time_zone: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AddThemeOfTheDayToPoolRequest {
+    pub name: String,
+    /// Imagen en base64 (puede incluir prefijo data:image/png;base64, o solo los datos).
+    pub image_base64: String,
+}
+
+/// Tema sin fecha de la bolsa de rotación.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ThemeOfTheDayPoolEntryResponse {
+    pub id: uuid::Uuid,
+    pub name: String,
+    pub url: String,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<crate::domain::ThemeOfTheDayPoolEntry> for ThemeOfTheDayPoolEntryResponse {
+    fn from(e: crate::domain::ThemeOfTheDayPoolEntry) -> Self {
+        ThemeOfTheDayPoolEntryResponse {
+            id: e.id,
+            name: e.name,
+            url: e.url,
+            created_at: e.created_at,
+        }
+    }
 }
 
 impl From<crate::domain::ThemeOfTheDay> for ThemeOfTheDayResponse {
     fn from(t: crate::domain::ThemeOfTheDay) -> Self {
         ThemeOfTheDayResponse {
             id: t.id,
+            date: t.date,
+            mmdd: t.mmdd,
             name: t.name,
             url: t.url,
+            posts_count: t.posts_count,
+            auto_selected: t.fallback.is_some(),
+            fallback: t.fallback.map(|f| f.as_str().to_string()),
+            pool_entry_id: t.pool_entry_id,
         }
     }
 }
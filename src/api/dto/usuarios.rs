@@ -6,7 +6,10 @@ use uuid::Uuid;
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateUsuarioRequest {
+    /// Nuevo nombre; omitido = sin cambios.
     pub name: Option<String>,
+    /// Zona horaria (nombre IANA como "America/Bogota" o desfase "-05:00"). Cadena vacía la elimina; omitido = sin cambios.
+    pub time_zone: Option<String>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -21,6 +24,7 @@ pub struct UsuarioResponse {
     pub name: Option<String>,
     pub email: Option<String>,
     pub url: Option<String>,
+    pub time_zone: Option<String>,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
@@ -31,7 +35,30 @@ impl From<crate::domain::Usuario> for UsuarioResponse {
             name: u.name,
             email: u.email,
             url: u.url,
+            time_zone: u.time_zone,
             created_at: u.created_at,
         }
     }
 }
+
+/// Racha del usuario en el reto diario (días consecutivos con post).
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PostingStreakResponse {
+    /// Días seguidos hasta hoy (o hasta ayer, si hoy aún no ha publicado).
+    pub current: u32,
+    pub longest: u32,
+    pub last_posted_date: Option<chrono::NaiveDate>,
+    /// Fecha local usada como "hoy".
+    pub local_date: chrono::NaiveDate,
+    pub time_zone: String,
+}
+
+/// URLs personales de los feeds iCalendar (se pueden abrir sin header Bearer; el token las autoriza).
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CalendarFeedResponse {
+    pub token: String,
+    /// URL del feed de eventos (GET /api/eventos/calendar.ics?token=...).
+    pub eventos_url: String,
+    /// URL del feed de temas del día (GET /api/theme-of-the-day/calendar.ics?token=...).
+    pub theme_of_the_day_url: String,
+}
@@ -14,6 +14,7 @@ impl IntoResponse for ApiError {
         let (status, message) = match &self.0 {
             DomainError::NotFound(_) => (StatusCode::NOT_FOUND, self.0.to_string()),
             DomainError::Validation(_) => (StatusCode::BAD_REQUEST, self.0.to_string()),
+            DomainError::Forbidden(_) => (StatusCode::FORBIDDEN, self.0.to_string()),
             DomainError::Repository(_) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "Error interno del servidor".to_string(),
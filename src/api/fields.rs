@@ -0,0 +1,151 @@
+// Parámetros `?fields=` (recorte de campos) e `?include=` (datos relacionados) de poses y sesiones
+
+use std::sync::Arc;
+
+use serde::{Serialize, Serializer};
+
+use crate::api::ApiError;
+use crate::domain::DomainError;
+
+/// `fields` y `include`. Los nombres admitidos dependen del recurso (ver la descripción de cada endpoint);
+/// un nombre fuera de la lista devuelve 400.
+#[derive(Debug, serde::Deserialize, utoipa::IntoParams)]
+pub struct FieldsParams {
+    /// Campos a devolver separados por comas (ej. `url,title`). `id` se devuelve siempre; sin valor, todos.
+    pub fields: Option<String>,
+    /// Datos relacionados a incluir separados por comas (ej. `hashtags,favorite`).
+    pub include: Option<String>,
+}
+
+impl FieldsParams {
+    /// Valida `fields` contra los campos del recurso.
+    pub fn fields(&self, allowed: &[&'static str]) -> Result<FieldSet, ApiError> {
+        let Some(raw) = self.fields.as_deref() else {
+            return Ok(FieldSet(None));
+        };
+        let names = raw
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(|name| {
+                allowed
+                    .iter()
+                    .copied()
+                    .find(|a| a.eq_ignore_ascii_case(name))
+                    .ok_or_else(|| {
+                        DomainError::Validation(format!(
+                            "Campo no permitido en fields: {} (campos: {})",
+                            name,
+                            allowed.join(", ")
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if names.is_empty() {
+            return Ok(FieldSet(None));
+        }
+        Ok(FieldSet(Some(names.into())))
+    }
+}
+
+/// Campos pedidos en `?fields=` (None = todos).
+#[derive(Debug, Clone, Default)]
+pub struct FieldSet(Option<Arc<[&'static str]>>);
+
+impl FieldSet {
+    pub fn apply<T>(&self, value: T) -> Sparse<T> {
+        Sparse {
+            value,
+            fields: self.clone(),
+        }
+    }
+}
+
+/// Respuesta que al serializarse conserva solo `id` y los campos del `FieldSet`.
+#[derive(Debug)]
+pub struct Sparse<T> {
+    value: T,
+    fields: FieldSet,
+}
+
+impl<T: Serialize> Serialize for Sparse<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Some(fields) = &self.fields.0 else {
+            return self.value.serialize(serializer);
+        };
+        match serde_json::to_value(&self.value).map_err(serde::ser::Error::custom)? {
+            serde_json::Value::Object(mut map) => {
+                map.retain(|key, _| key == "id" || fields.contains(&key.as_str()));
+                map.serialize(serializer)
+            }
+            other => other.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+
+    const ALLOWED: &[&str] = &["id", "url", "title", "description"];
+
+    #[derive(Serialize)]
+    struct Item {
+        id: u32,
+        url: &'static str,
+        title: &'static str,
+        description: Option<&'static str>,
+    }
+
+    const ITEM: Item = Item {
+        id: 7,
+        url: "/api/poses/7/image",
+        title: "Contraluz",
+        description: None,
+    };
+
+    fn params(fields: Option<&str>) -> FieldsParams {
+        FieldsParams {
+            fields: fields.map(str::to_string),
+            include: None,
+        }
+    }
+
+    fn sparse(fields: Option<&str>) -> serde_json::Value {
+        let fields = params(fields).fields(ALLOWED).unwrap();
+        serde_json::to_value(fields.apply(ITEM)).unwrap()
+    }
+
+    #[test]
+    fn without_fields_everything_is_kept() {
+        let all = serde_json::json!({
+            "id": 7,
+            "url": "/api/poses/7/image",
+            "title": "Contraluz",
+            "description": null,
+        });
+        assert_eq!(sparse(None), all);
+        assert_eq!(sparse(Some(" , ")), all);
+    }
+
+    #[test]
+    fn requested_fields_are_kept_along_with_id() {
+        assert_eq!(
+            sparse(Some(" URL,title ")),
+            serde_json::json!({ "id": 7, "url": "/api/poses/7/image", "title": "Contraluz" })
+        );
+        assert_eq!(
+            sparse(Some("description")),
+            serde_json::json!({ "id": 7, "description": null })
+        );
+        assert_eq!(sparse(Some("id")), serde_json::json!({ "id": 7 }));
+    }
+
+    #[test]
+    fn unknown_field_is_a_bad_request() {
+        let err = params(Some("url,password")).fields(ALLOWED).unwrap_err();
+        assert_eq!(err.into_response().status(), StatusCode::BAD_REQUEST);
+    }
+}
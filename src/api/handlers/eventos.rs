@@ -1,6 +1,6 @@
 use axum::{
     body::Body,
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::{header, StatusCode},
     response::IntoResponse,
     Json,
@@ -12,16 +12,27 @@ use uuid::Uuid;
 use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
 use utoipa::{Modify, OpenApi};
 
-use crate::api::auth::{BearerAuth, LoginRequest, LoginResponse};
+use crate::api::auth::{
+    user_id_from_auth, BearerAuth, CalendarFeedAuth, CalendarTokenQuery, LoginRequest,
+    LoginResponse,
+};
 use crate::api::{
-    dto::{CreateEventoRequest, ErrorResponse, EventoResponse, UpdateEventoRequest},
+    dto::{
+        AttendanceResponse, CreateEventoRequest, ErrorResponse, EventoAttendeeResponse,
+        EventoAttendeesResponse, EventoResponse, ListResponse, Paginated, UpcomingEventoResponse,
+        UpdateEventoRequest,
+    },
+    ical::{self, IcsEvent},
+    list_query::{ListParams, PaginationQuery},
     state::AppState,
     ApiError,
 };
 use crate::application::{
-    CreateEventoUseCase, DeleteEventoUseCase, GetEventoByIdUseCase, GetEventosUseCase,
-    UpdateEventoUseCase,
+    AttendEventoUseCase, CancelEventoAttendanceUseCase, CreateEventoUseCase, DeleteEventoUseCase,
+    GetEventoAttendeesUseCase, GetEventoByIdUseCase, GetEventosPaginatedUseCase, GetEventosUseCase,
+    GetMyUpcomingEventosUseCase, GetPlacesUseCase, UpdateEventoUseCase,
 };
+use crate::domain::{AttendanceStatus, Evento, ListQuery};
 
 /// Añade el esquema de seguridad Bearer JWT al OpenAPI.
 struct SecurityAddon;
@@ -49,12 +60,38 @@ impl Modify for SecurityAddon {
     paths(
         crate::api::auth::login,
         list_eventos,
+        get_eventos_calendar,
         get_evento,
         get_evento_image,
         create_evento,
         update_evento,
         delete_evento,
+        attend_evento,
+        cancel_evento_attendance,
+        get_evento_attendees,
+        list_my_upcoming_eventos,
         crate::api::handlers::theme_of_the_day::list_theme_of_the_day,
+        crate::api::handlers::theme_of_the_day::get_theme_of_the_day_calendar,
+        crate::api::handlers::theme_of_the_day::get_theme_of_the_day_archive,
+        crate::api::handlers::theme_of_the_day::list_theme_of_the_day_pool,
+        crate::api::handlers::posts::get_theme_of_the_day_leaderboard,
+        crate::api::handlers::posts::vote_post,
+        crate::api::handlers::posts::remove_post_vote,
+        crate::api::handlers::posts::update_post,
+        crate::api::handlers::posts::get_post_image_by_id,
+        crate::api::handlers::posts::add_post_image,
+        crate::api::handlers::posts::reorder_post_images,
+        crate::api::handlers::posts::remove_post_image,
+        crate::api::handlers::posts::like_post,
+        crate::api::handlers::posts::unlike_post,
+        crate::api::handlers::posts::list_post_comments,
+        crate::api::handlers::posts::create_post_comment,
+        crate::api::handlers::posts::update_post_comment,
+        crate::api::handlers::posts::delete_post_comment,
+        crate::api::handlers::usuarios::get_profile_streak,
+        crate::api::handlers::theme_of_the_day::add_theme_of_the_day_to_pool,
+        crate::api::handlers::theme_of_the_day::get_theme_of_the_day_pool_image,
+        crate::api::handlers::theme_of_the_day::delete_theme_of_the_day_from_pool,
         crate::api::handlers::theme_of_the_day::get_theme_of_the_day_today,
         crate::api::handlers::theme_of_the_day::get_theme_of_the_day_by_date,
         crate::api::handlers::theme_of_the_day::get_theme_of_the_day,
@@ -67,19 +104,32 @@ impl Modify for SecurityAddon {
         crate::api::handlers::hashtags::create_hashtag,
         crate::api::handlers::hashtags::delete_hashtag,
         crate::api::handlers::hashtags::get_hashtags_by_pose,
+        crate::api::handlers::hashtags::get_hashtag_tree,
+        crate::api::handlers::hashtags::suggest_hashtags,
+        crate::api::handlers::hashtags::get_related_hashtags,
+        crate::api::handlers::hashtags::update_hashtag,
+        crate::api::handlers::hashtags::merge_hashtag,
+        crate::api::handlers::hashtags::get_hashtags_by_post,
         crate::api::handlers::hashtags::add_hashtags_to_post,
+        crate::api::handlers::hashtags::update_post_hashtags,
         crate::api::handlers::poses::list_poses,
         crate::api::handlers::poses::list_poses_paginated,
         crate::api::handlers::poses::get_pose,
+        crate::api::handlers::poses::get_similar_poses,
+        crate::api::handlers::poses::get_recommended_poses,
         crate::api::handlers::poses::get_pose_image,
         crate::api::handlers::poses::create_pose,
+        crate::api::handlers::poses::update_pose,
         crate::api::handlers::poses::delete_pose,
+        crate::api::handlers::poses::batch_poses,
         crate::api::handlers::poses::get_poses_by_hashtag,
         crate::api::handlers::poses::get_poses_by_hashtag_paginated,
         crate::api::handlers::poses::update_pose_hashtags,
         crate::api::handlers::posts::list_posts,
         crate::api::handlers::posts::list_posts_paginated,
+        crate::api::handlers::posts::get_posts_by_hashtag,
         crate::api::handlers::posts::get_posts_by_theme_of_the_day,
+        crate::api::handlers::posts::get_posts_by_evento,
         crate::api::handlers::posts::get_post,
         crate::api::handlers::posts::get_post_image,
         crate::api::handlers::posts::create_post,
@@ -117,6 +167,8 @@ impl Modify for SecurityAddon {
         crate::api::handlers::usuarios::update_profile,
         crate::api::handlers::usuarios::get_profile_avatar,
         crate::api::handlers::usuarios::update_profile_avatar,
+        crate::api::handlers::usuarios::get_calendar_feed,
+        crate::api::handlers::usuarios::regenerate_calendar_feed,
     ),
     components(schemas(
         LoginRequest,
@@ -124,16 +176,48 @@ impl Modify for SecurityAddon {
         EventoResponse,
         CreateEventoRequest,
         UpdateEventoRequest,
+        AttendanceResponse,
+        EventoAttendeeResponse,
+        EventoAttendeesResponse,
+        UpcomingEventoResponse,
         ErrorResponse,
         crate::api::dto::ThemeOfTheDayResponse,
+        crate::api::dto::ThemeOfTheDayTodayResponse,
+        crate::api::dto::ThemeOfTheDayPoolEntryResponse,
+        crate::api::dto::PostVoteResponse,
+        crate::api::dto::UpdatePostRequest,
+        crate::api::dto::AddPostImageRequest,
+        crate::api::dto::ReorderPostImagesRequest,
+        crate::api::dto::PostImageResponse,
+        crate::api::dto::PostLikeResponse,
+        crate::api::dto::PostCommentResponse,
+        crate::api::dto::CreatePostCommentRequest,
+        crate::api::dto::UpdatePostCommentRequest,
+        crate::api::dto::LeaderboardEntryResponse,
+        crate::api::dto::DailyWinnerResponse,
+        crate::api::dto::ThemeLeaderboardResponse,
+        crate::api::dto::PostingStreakResponse,
+        crate::api::dto::AddThemeOfTheDayToPoolRequest,
         crate::api::dto::CreateThemeOfTheDayRequest,
         crate::api::dto::UpdateThemeOfTheDayRequest,
         crate::api::dto::HashtagResponse,
         crate::api::dto::CreateHashtagRequest,
         crate::api::dto::AddHashtagsToPostRequest,
+        crate::api::dto::UpdatePostHashtagsRequest,
+        crate::api::dto::UpdateHashtagRequest,
+        crate::api::dto::MergeHashtagRequest,
+        crate::api::dto::HashtagUsageResponse,
+        crate::api::dto::RelatedHashtagResponse,
+        crate::api::dto::HashtagTreeNodeResponse,
         crate::api::dto::PoseResponse,
+        crate::api::dto::ScoredPoseResponse,
         crate::api::dto::CreatePoseRequest,
+        crate::api::dto::UpdatePoseRequest,
         crate::api::dto::UpdatePoseHashtagsRequest,
+        crate::api::dto::PoseBatchRequest,
+        crate::api::dto::PoseBatchOperationRequest,
+        crate::api::dto::PoseBatchResponse,
+        crate::api::dto::PoseBatchItemResponse,
         crate::api::dto::PostResponse,
         crate::api::dto::CreatePostRequest,
         crate::api::dto::PortfolioCategoryResponse,
@@ -152,6 +236,7 @@ impl Modify for SecurityAddon {
         crate::api::dto::UsuarioResponse,
         crate::api::dto::UpdateUsuarioRequest,
         crate::api::dto::UpdateUsuarioAvatarRequest,
+        crate::api::dto::CalendarFeedResponse,
     )),
     tags(
         (name = "auth", description = "Autenticación JWT"),
@@ -205,13 +290,18 @@ fn save_evento_image_base64(
     Ok(format!("/api/eventos/{}/image", id))
 }
 
-/// Lista todos los eventos (requiere Bearer token).
+/// Lista los eventos (requiere Bearer token), por día del año y nombre. Con `page` o `limit` devuelve
+/// la respuesta paginada (items, count, page, limit, total_pages); si no, una lista con los primeros 100.
+///
+/// `sort`: mmdd (asc por defecto), name, created_at, capacity, attendees_count. `filter`: place, mmdd.
 #[utoipa::path(
     get,
     path = "/api/eventos",
     tag = "eventos",
+    params(PaginationQuery, ListParams),
     responses(
-        (status = 200, description = "Lista de eventos", body = [EventoResponse]),
+        (status = 200, description = "Lista de eventos (paginada con page/limit)", body = ListResponse<EventoResponse>),
+        (status = 400, description = "sort o filter inválido", body = ErrorResponse),
         (status = 401, description = "No autorizado", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
@@ -219,12 +309,76 @@ fn save_evento_image_base64(
 pub async fn list_eventos(
     _auth: BearerAuth,
     State(state): State<AppState>,
-) -> Result<Json<Vec<EventoResponse>>, ApiError> {
-    let uc = GetEventosUseCase::new(Arc::clone(&state.eventos_repo));
-    let eventos = uc.execute().await?;
-    Ok(Json(
-        eventos.into_iter().map(EventoResponse::from).collect(),
-    ))
+    Query(p): Query<PaginationQuery>,
+    Query(l): Query<ListParams>,
+) -> Result<Json<ListResponse<EventoResponse>>, ApiError> {
+    let list = l.to_query(&Evento::LIST_SPEC)?;
+    let (page, limit) = p.page_and_limit();
+    let uc = GetEventosPaginatedUseCase::new(Arc::clone(&state.eventos_repo));
+    let (eventos, count) = uc.execute(&list, page, limit).await?;
+    let items = eventos.into_iter().map(EventoResponse::from).collect();
+    if !p.is_requested() {
+        return Ok(Json(ListResponse::List(items)));
+    }
+    Ok(Json(ListResponse::Paginated(Paginated::page(
+        items, count, page, limit,
+    ))))
+}
+
+/// Feed iCalendar (RFC 5545) con todos los eventos. Acepta Bearer token o `?token=` (feed personal)
+/// para que las apps de calendario puedan suscribirse. La ubicación sale del lugar (places) con ese nombre.
+#[utoipa::path(
+    get,
+    path = "/api/eventos/calendar.ics",
+    tag = "eventos",
+    security(("bearer_auth" = [])),
+    params(CalendarTokenQuery),
+    responses(
+        (status = 200, description = "Feed iCalendar de eventos", content_type = "text/calendar"),
+        (status = 401, description = "No autorizado / token de feed inválido", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn get_eventos_calendar(
+    _auth: CalendarFeedAuth,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ApiError> {
+    let eventos = GetEventosUseCase::new(Arc::clone(&state.eventos_repo))
+        .execute(&ListQuery::default())
+        .await?;
+    let places = GetPlacesUseCase::new(Arc::clone(&state.places_repo))
+        .execute(&ListQuery::default())
+        .await?;
+    let now = chrono::Utc::now();
+    let today = now.date_naive();
+    let events: Vec<IcsEvent> = eventos
+        .into_iter()
+        .filter_map(|e| {
+            let date = e.date(today)?;
+            let place = places
+                .iter()
+                .find(|p| p.name.trim().eq_ignore_ascii_case(e.place.trim()));
+            let location = match place {
+                Some(p) if !p.address.trim().is_empty() => Some(format!("{}, {}", p.name, p.address)),
+                _ => Some(e.place.clone()).filter(|p| !p.trim().is_empty()),
+            };
+            Some(IcsEvent {
+                uid: format!("evento-{}@danphoto", e.id),
+                summary: e.name,
+                date,
+                rrule: None,
+                location,
+                geo: place.map(|p| (p.latitude, p.longitude)),
+                attach: state
+                    .public_base_url
+                    .as_deref()
+                    .map(|base| ical::absolute_url(base, &e.url)),
+                dtstamp: e.created_at.unwrap_or(now),
+            })
+        })
+        .collect();
+    let body = ical::render_calendar("DanPhoto - Eventos", &events);
+    Ok(ical::calendar_response("eventos.ics", body))
 }
 
 /// Obtiene un evento por ID (requiere Bearer token).
@@ -261,7 +415,7 @@ pub async fn get_evento(
     responses(
         (status = 200, description = "Evento creado", body = EventoResponse),
         (status = 401, description = "No autorizado", body = ErrorResponse),
-        (status = 400, description = "Validación fallida (mmdd vacío o imagen base64 inválida)", body = ErrorResponse),
+        (status = 400, description = "Validación fallida (mmdd vacío o inválido, cupo negativo o imagen base64 inválida)", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
 )]
@@ -279,7 +433,7 @@ pub async fn create_evento(
     let url = save_evento_image_base64(&state.eventos_images_dir, &id, &body.image_base64)?;
     let uc = CreateEventoUseCase::new(Arc::clone(&state.eventos_repo));
     let evento = uc
-        .execute_with_id(id, &body.name, &body.place, &url, &body.mmdd)
+        .execute_with_id(id, &body.name, &body.place, &url, &body.mmdd, body.capacity)
         .await?;
     Ok(Json(EventoResponse::from(evento)))
 }
@@ -294,6 +448,7 @@ pub async fn create_evento(
     request_body = UpdateEventoRequest,
     responses(
         (status = 200, description = "Evento actualizado", body = EventoResponse),
+        (status = 400, description = "Validación fallida (mmdd no es una fecha válida, cupo negativo)", body = ErrorResponse),
         (status = 401, description = "No autorizado", body = ErrorResponse),
         (status = 404, description = "Evento no encontrado", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
@@ -322,6 +477,7 @@ pub async fn update_evento(
             body.place.as_deref(),
             url.as_deref(),
             body.mmdd.as_deref(),
+            body.capacity,
         )
         .await?;
     Ok(Json(EventoResponse::from(evento)))
@@ -389,3 +545,118 @@ pub async fn delete_evento(
     uc.execute(id).await?;
     Ok(axum::http::StatusCode::NO_CONTENT)
 }
+
+/// Apunta al usuario autenticado al evento: confirmado si hay cupo, si no en lista de espera.
+/// Idempotente: si ya estaba apuntado devuelve su estado actual.
+#[utoipa::path(
+    post,
+    path = "/api/eventos/{id}/attendance",
+    tag = "eventos",
+    security(("bearer_auth" = [])),
+    params(("id" = Uuid, Path, description = "UUID del evento")),
+    responses(
+        (status = 200, description = "Asistencia registrada (confirmed o waitlisted)", body = AttendanceResponse),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 404, description = "Evento no encontrado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn attend_evento(
+    auth: BearerAuth,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<AttendanceResponse>, ApiError> {
+    let user_id = user_id_from_auth(&state, &auth.0).await?;
+    let uc = AttendEventoUseCase::new(Arc::clone(&state.eventos_repo));
+    let asistente = uc.execute(id, user_id).await?;
+    Ok(Json(AttendanceResponse::from(asistente)))
+}
+
+/// Cancela la asistencia del usuario autenticado. El primero de la lista de espera pasa a confirmado.
+#[utoipa::path(
+    delete,
+    path = "/api/eventos/{id}/attendance",
+    tag = "eventos",
+    security(("bearer_auth" = [])),
+    params(("id" = Uuid, Path, description = "UUID del evento")),
+    responses(
+        (status = 204, description = "Asistencia cancelada"),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 404, description = "Evento no encontrado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn cancel_evento_attendance(
+    auth: BearerAuth,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let user_id = user_id_from_auth(&state, &auth.0).await?;
+    let uc = CancelEventoAttendanceUseCase::new(Arc::clone(&state.eventos_repo));
+    uc.execute(id, user_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Lista los asistentes de un evento: confirmados y lista de espera (requiere Bearer token).
+#[utoipa::path(
+    get,
+    path = "/api/eventos/{id}/attendees",
+    tag = "eventos",
+    security(("bearer_auth" = [])),
+    params(("id" = Uuid, Path, description = "UUID del evento")),
+    responses(
+        (status = 200, description = "Asistentes del evento", body = EventoAttendeesResponse),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 404, description = "Evento no encontrado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn get_evento_attendees(
+    _auth: BearerAuth,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<EventoAttendeesResponse>, ApiError> {
+    let uc = GetEventoAttendeesUseCase::new(Arc::clone(&state.eventos_repo));
+    let (evento, attendees) = uc.execute(id).await?;
+    let (confirmed, waitlist): (Vec<_>, Vec<_>) = attendees
+        .into_iter()
+        .partition(|a| a.status == AttendanceStatus::Confirmed);
+    Ok(Json(EventoAttendeesResponse {
+        evento_id: evento.id,
+        capacity: evento.capacity,
+        confirmed: confirmed.into_iter().map(EventoAttendeeResponse::from).collect(),
+        waitlist: waitlist.into_iter().map(EventoAttendeeResponse::from).collect(),
+    }))
+}
+
+/// Próximos eventos a los que asiste el usuario autenticado (incluye lista de espera), por fecha.
+#[utoipa::path(
+    get,
+    path = "/api/eventos/attending",
+    tag = "eventos",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Próximos eventos del usuario", body = [UpcomingEventoResponse]),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn list_my_upcoming_eventos(
+    auth: BearerAuth,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<UpcomingEventoResponse>>, ApiError> {
+    let user_id = user_id_from_auth(&state, &auth.0).await?;
+    let today = chrono::Utc::now().date_naive();
+    let uc = GetMyUpcomingEventosUseCase::new(Arc::clone(&state.eventos_repo));
+    let eventos = uc.execute(user_id, today).await?;
+    Ok(Json(
+        eventos
+            .into_iter()
+            .map(|(evento, date, status)| UpcomingEventoResponse {
+                evento: EventoResponse::from(evento),
+                date,
+                status: status.as_str().to_string(),
+            })
+            .collect(),
+    ))
+}
@@ -1,31 +1,40 @@
 // Handlers de Favoritos (Kotlin domain/cases/favorites). user_id desde JWT (email -> auth_repository).
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     Json,
 };
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::api::{
-    dto::{ErrorResponse, IsPoseFavoriteResponse, PoseResponse},
+    dto::{ErrorResponse, IsPoseFavoriteResponse, ListResponse, Paginated, PoseResponse},
+    fields::{FieldsParams, Sparse},
+    handlers::poses::PoseView,
+    list_query::PaginationQuery,
     state::AppState,
     ApiError,
 };
 use crate::api::auth::user_id_from_auth;
 use crate::application::{
-    GetFavoritePosesUseCase, IsPoseFavoriteUseCase, RemovePoseFromFavoritesUseCase,
+    GetFavoritePosesPaginatedUseCase, IsPoseFavoriteUseCase, RemovePoseFromFavoritesUseCase,
     TogglePoseFavoriteUseCase,
 };
 
-/// Lista poses favoritas del usuario (JWT).
+/// Lista poses favoritas del usuario (JWT), de la favorita más reciente a la más antigua. Con `page` o
+/// `limit` devuelve la respuesta paginada; si no, una lista con las primeras 100.
+///
+/// `fields`: id, url, title, description, people_count, difficulty, orientation, source_credit, created_at,
+/// hashtags, favorite, sesiones. `include`: hashtags, favorite, sesiones.
 #[utoipa::path(
     get,
     path = "/api/favorites/poses",
     tag = "favorites",
     security(("bearer_auth" = [])),
+    params(PaginationQuery, FieldsParams),
     responses(
-        (status = 200, description = "Lista de poses favoritas", body = [PoseResponse]),
+        (status = 200, description = "Lista de poses favoritas (paginada con page/limit)", body = ListResponse<PoseResponse>),
+        (status = 400, description = "fields o include inválido", body = ErrorResponse),
         (status = 401, description = "No autorizado / Usuario no encontrado", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
@@ -33,11 +42,21 @@ use crate::application::{
 pub async fn get_favorite_poses(
     auth: crate::api::auth::BearerAuth,
     State(state): State<AppState>,
-) -> Result<Json<Vec<PoseResponse>>, ApiError> {
+    Query(p): Query<PaginationQuery>,
+    Query(v): Query<FieldsParams>,
+) -> Result<Json<ListResponse<Sparse<PoseResponse>>>, ApiError> {
+    let view = PoseView::parse(&v)?;
     let user_id = user_id_from_auth(&state, &auth.0).await?;
-    let uc = GetFavoritePosesUseCase::new(Arc::clone(&state.favorites_repo));
-    let items = uc.execute(user_id).await?;
-    Ok(Json(items.into_iter().map(PoseResponse::from).collect()))
+    let (page, limit) = p.page_and_limit();
+    let uc = GetFavoritePosesPaginatedUseCase::new(Arc::clone(&state.favorites_repo));
+    let (items, count) = uc.execute(user_id, page, limit).await?;
+    let items = view.render(&state, &auth, items).await?;
+    if !p.is_requested() {
+        return Ok(Json(ListResponse::List(items)));
+    }
+    Ok(Json(ListResponse::Paginated(Paginated::page(
+        items, count, page, limit,
+    ))))
 }
 
 /// Indica si la pose está en la tabla de favoritos del usuario (consulta favoritos por user_id y pose_id).
@@ -1,30 +1,60 @@
 // Handlers de hashtags (alineados con Kotlin domain/cases/hashtags)
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     Json,
 };
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::api::{
-    dto::{AddHashtagsToPostRequest, CreateHashtagRequest, ErrorResponse, HashtagResponse},
+    auth::{actor_from_auth, require_admin},
+    dto::{
+        AddHashtagsToPostRequest, CreateHashtagRequest, ErrorResponse, HashtagResponse,
+        HashtagTreeNodeResponse, HashtagUsageResponse, ListResponse, MergeHashtagRequest, Paginated,
+        RelatedHashtagResponse, UpdateHashtagRequest, UpdatePostHashtagsRequest,
+    },
+    list_query::{ListParams, PaginationQuery},
     state::AppState,
     ApiError,
 };
 use crate::application::{
     AddHashtagsToPostUseCase, CreateHashtagUseCase, DeleteHashtagUseCase, GetHashtagByIdUseCase,
-    GetHashtagsByPoseUseCase, GetHashtagsUseCase,
+    GetHashtagTreeUseCase, GetHashtagsByPoseUseCase, GetHashtagsByPostUseCase, GetHashtagsPaginatedUseCase,
+    GetRelatedHashtagsUseCase, MergeHashtagUseCase, SuggestHashtagsUseCase, UpdateHashtagUseCase,
+    UpdatePostHashtagsUseCase, UpdatePostUseCase,
 };
+use crate::domain::HashtagUsage;
 
-/// Lista todos los hashtags (GetHashtagsUseCase).
+#[derive(Debug, serde::Deserialize, utoipa::IntoParams)]
+pub struct HashtagSuggestQuery {
+    /// Lo que el usuario lleva escrito (con o sin `#`). Vacío = los más usados.
+    pub prefix: Option<String>,
+    /// Máximo de sugerencias (por defecto 10, máximo 50).
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, serde::Deserialize, utoipa::IntoParams)]
+pub struct RelatedHashtagsQuery {
+    /// Máximo de hashtags (por defecto 10, máximo 50).
+    pub limit: Option<u32>,
+}
+
+/// Lista los hashtags con su uso en poses y posts. `?sort=popular` ordena por uso.
+/// Con `page` o `limit` devuelve la respuesta paginada (items, count, page, limit, total_pages); si no, una
+/// lista con los primeros 100.
+///
+/// `sort`: name (asc por defecto), popular, poses_count, posts_count. `filter`: parent_id.
+/// Los hashtags no tienen fecha de creación: `created_after` y `created_before` devuelven 400.
 #[utoipa::path(
     get,
     path = "/api/hashtags",
     tag = "hashtags",
     security(("bearer_auth" = [])),
+    params(PaginationQuery, ListParams),
     responses(
-        (status = 200, description = "Lista de hashtags", body = [HashtagResponse]),
+        (status = 200, description = "Lista de hashtags (paginada con page/limit)", body = ListResponse<HashtagUsageResponse>),
+        (status = 400, description = "sort o filter inválido", body = ErrorResponse),
         (status = 401, description = "No autorizado", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
@@ -32,10 +62,91 @@ use crate::application::{
 pub async fn list_hashtags(
     _auth: crate::api::auth::BearerAuth,
     State(state): State<AppState>,
-) -> Result<Json<Vec<HashtagResponse>>, ApiError> {
-    let uc = GetHashtagsUseCase::new(Arc::clone(&state.hashtags_repo));
+    Query(p): Query<PaginationQuery>,
+    Query(l): Query<ListParams>,
+) -> Result<Json<ListResponse<HashtagUsageResponse>>, ApiError> {
+    let list = l.to_query(&HashtagUsage::LIST_SPEC)?;
+    let (page, limit) = p.page_and_limit();
+    let uc = GetHashtagsPaginatedUseCase::new(Arc::clone(&state.hashtags_repo));
+    let (items, count) = uc.execute(&list, page, limit).await?;
+    let items = items.into_iter().map(HashtagUsageResponse::from).collect();
+    if !p.is_requested() {
+        return Ok(Json(ListResponse::List(items)));
+    }
+    Ok(Json(ListResponse::Paginated(Paginated::page(
+        items, count, page, limit,
+    ))))
+}
+
+/// Árbol de hashtags para el menú de navegación: raíces con sus hijos anidados (por nombre) y su uso.
+#[utoipa::path(
+    get,
+    path = "/api/hashtags/tree",
+    tag = "hashtags",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Árbol de hashtags", body = [HashtagTreeNodeResponse]),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn get_hashtag_tree(
+    _auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<HashtagTreeNodeResponse>>, ApiError> {
+    let uc = GetHashtagTreeUseCase::new(Arc::clone(&state.hashtags_repo));
     let items = uc.execute().await?;
-    Ok(Json(items.into_iter().map(HashtagResponse::from).collect()))
+    Ok(Json(items.into_iter().map(HashtagTreeNodeResponse::from).collect()))
+}
+
+/// Autocompletado: hashtags que empiezan por `prefix` (o parecidos), los más usados primero.
+#[utoipa::path(
+    get,
+    path = "/api/hashtags/suggest",
+    tag = "hashtags",
+    security(("bearer_auth" = [])),
+    params(HashtagSuggestQuery),
+    responses(
+        (status = 200, description = "Sugerencias", body = [HashtagUsageResponse]),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn suggest_hashtags(
+    _auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Query(q): Query<HashtagSuggestQuery>,
+) -> Result<Json<Vec<HashtagUsageResponse>>, ApiError> {
+    let limit = q.limit.unwrap_or(10).clamp(1, 50);
+    let uc = SuggestHashtagsUseCase::new(Arc::clone(&state.hashtags_repo));
+    let items = uc.execute(q.prefix.as_deref().unwrap_or(""), limit).await?;
+    Ok(Json(items.into_iter().map(HashtagUsageResponse::from).collect()))
+}
+
+/// Hashtags relacionados: los que más aparecen junto a `{id}` en las mismas poses.
+#[utoipa::path(
+    get,
+    path = "/api/hashtags/{id}/related",
+    tag = "hashtags",
+    security(("bearer_auth" = [])),
+    params(("id" = Uuid, Path, description = "UUID del hashtag"), RelatedHashtagsQuery),
+    responses(
+        (status = 200, description = "Hashtags relacionados", body = [RelatedHashtagResponse]),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 404, description = "Hashtag no encontrado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn get_related_hashtags(
+    _auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(q): Query<RelatedHashtagsQuery>,
+) -> Result<Json<Vec<RelatedHashtagResponse>>, ApiError> {
+    let limit = q.limit.unwrap_or(10).clamp(1, 50);
+    let uc = GetRelatedHashtagsUseCase::new(Arc::clone(&state.hashtags_repo));
+    let items = uc.execute(id, limit).await?;
+    Ok(Json(items.into_iter().map(RelatedHashtagResponse::from).collect()))
 }
 
 /// Obtiene un hashtag por id.
@@ -62,7 +173,7 @@ pub async fn get_hashtag(
     Ok(Json(HashtagResponse::from(item)))
 }
 
-/// Crea un hashtag (CreateHashtagUseCase).
+/// Crea un hashtag (CreateHashtagUseCase). El nombre se normaliza: sin `#`, en minúsculas y sin tildes.
 #[utoipa::path(
     post,
     path = "/api/hashtags",
@@ -82,11 +193,69 @@ pub async fn create_hashtag(
     Json(body): Json<CreateHashtagRequest>,
 ) -> Result<Json<HashtagResponse>, ApiError> {
     let uc = CreateHashtagUseCase::new(Arc::clone(&state.hashtags_repo));
-    let item = uc.execute(&body.name).await?;
+    let item = uc.execute(&body.name, body.parent_id).await?;
     Ok(Json(HashtagResponse::from(item)))
 }
 
-/// Elimina un hashtag (DeleteHashtagUseCase).
+/// Edita un hashtag: nombre (se normaliza como al crear) y/o padre (`null` = raíz). Rechaza ciclos. Solo admin.
+#[utoipa::path(
+    put,
+    path = "/api/hashtags/{id}",
+    tag = "hashtags",
+    security(("bearer_auth" = [])),
+    params(("id" = Uuid, Path, description = "UUID del hashtag")),
+    request_body = UpdateHashtagRequest,
+    responses(
+        (status = 200, description = "Hashtag actualizado", body = HashtagResponse),
+        (status = 400, description = "Nombre inválido o ya usado, padre inexistente o ciclo", body = ErrorResponse),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 403, description = "Solo administradores (ADMIN_EMAILS)", body = ErrorResponse),
+        (status = 404, description = "Hashtag no encontrado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn update_hashtag(
+    auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<UpdateHashtagRequest>,
+) -> Result<Json<HashtagResponse>, ApiError> {
+    require_admin(&state, &auth.0).await?;
+    let uc = UpdateHashtagUseCase::new(Arc::clone(&state.hashtags_repo));
+    let item = uc.execute(id, body.name.as_deref(), body.parent_id).await?;
+    Ok(Json(HashtagResponse::from(item)))
+}
+
+/// Fusiona el hashtag `{id}` en `target_id`: sus poses y posts pasan al destino y `{id}` se elimina. Solo admin.
+#[utoipa::path(
+    post,
+    path = "/api/hashtags/{id}/merge",
+    tag = "hashtags",
+    security(("bearer_auth" = [])),
+    params(("id" = Uuid, Path, description = "UUID del hashtag que se fusiona (se elimina)")),
+    request_body = MergeHashtagRequest,
+    responses(
+        (status = 200, description = "Hashtag destino", body = HashtagResponse),
+        (status = 400, description = "Origen y destino son el mismo", body = ErrorResponse),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 403, description = "Solo administradores (ADMIN_EMAILS)", body = ErrorResponse),
+        (status = 404, description = "Hashtag no encontrado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn merge_hashtag(
+    auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<MergeHashtagRequest>,
+) -> Result<Json<HashtagResponse>, ApiError> {
+    require_admin(&state, &auth.0).await?;
+    let uc = MergeHashtagUseCase::new(Arc::clone(&state.hashtags_repo));
+    let item = uc.execute(id, body.target_id).await?;
+    Ok(Json(HashtagResponse::from(item)))
+}
+
+/// Elimina un hashtag (DeleteHashtagUseCase). Solo admin.
 #[utoipa::path(
     delete,
     path = "/api/hashtags/{id}",
@@ -96,15 +265,17 @@ pub async fn create_hashtag(
     responses(
         (status = 204, description = "Hashtag eliminado"),
         (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 403, description = "Solo administradores (ADMIN_EMAILS)", body = ErrorResponse),
         (status = 404, description = "Hashtag no encontrado", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
 )]
 pub async fn delete_hashtag(
-    _auth: crate::api::auth::BearerAuth,
+    auth: crate::api::auth::BearerAuth,
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
 ) -> Result<axum::http::StatusCode, ApiError> {
+    require_admin(&state, &auth.0).await?;
     let uc = DeleteHashtagUseCase::new(Arc::clone(&state.hashtags_repo));
     uc.execute(id).await?;
     Ok(axum::http::StatusCode::NO_CONTENT)
@@ -133,6 +304,33 @@ pub async fn get_hashtags_by_pose(
     Ok(Json(items.into_iter().map(HashtagResponse::from).collect()))
 }
 
+/// Hashtags de un post (por nombre).
+#[utoipa::path(
+    get,
+    path = "/api/posts/{post_id}/hashtags",
+    tag = "hashtags",
+    security(("bearer_auth" = [])),
+    params(("post_id" = Uuid, Path, description = "UUID del post")),
+    responses(
+        (status = 200, description = "Hashtags del post", body = [HashtagResponse]),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 404, description = "Post no encontrado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn get_hashtags_by_post(
+    _auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Path(post_id): Path<Uuid>,
+) -> Result<Json<Vec<HashtagResponse>>, ApiError> {
+    let uc = GetHashtagsByPostUseCase::new(
+        Arc::clone(&state.hashtags_repo),
+        Arc::clone(&state.posts_repo),
+    );
+    let items = uc.execute(post_id).await?;
+    Ok(Json(items.into_iter().map(HashtagResponse::from).collect()))
+}
+
 /// Añade hashtags a un post (AddHashtagsToPostUseCase).
 #[utoipa::path(
     post,
@@ -157,3 +355,42 @@ pub async fn add_hashtags_to_post(
     uc.execute(post_id, &body.hashtag_ids).await?;
     Ok(axum::http::StatusCode::NO_CONTENT)
 }
+
+/// Reemplaza los hashtags de un post (autor o admin). Devuelve la lista resultante.
+#[utoipa::path(
+    put,
+    path = "/api/posts/{post_id}/hashtags",
+    tag = "hashtags",
+    security(("bearer_auth" = [])),
+    params(("post_id" = Uuid, Path, description = "UUID del post")),
+    request_body = UpdatePostHashtagsRequest,
+    responses(
+        (status = 200, description = "Hashtags actualizados", body = [HashtagResponse]),
+        (status = 400, description = "Algún hashtag no existe", body = ErrorResponse),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 403, description = "No es el autor del post ni administrador", body = ErrorResponse),
+        (status = 404, description = "Post no encontrado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn update_post_hashtags(
+    auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Path(post_id): Path<Uuid>,
+    Json(body): Json<UpdatePostHashtagsRequest>,
+) -> Result<Json<Vec<HashtagResponse>>, ApiError> {
+    let actor = actor_from_auth(&state, &auth.0).await?;
+    UpdatePostUseCase::new(
+        Arc::clone(&state.posts_repo),
+        Arc::clone(&state.hashtags_repo),
+        Arc::clone(&state.unit_of_work),
+    )
+    .authorize(post_id, &actor)
+    .await?;
+    let uc = UpdatePostHashtagsUseCase::new(
+        Arc::clone(&state.hashtags_repo),
+        Arc::clone(&state.unit_of_work),
+    );
+    let items = uc.execute(post_id, &body.hashtag_ids).await?;
+    Ok(Json(items.into_iter().map(HashtagResponse::from).collect()))
+}
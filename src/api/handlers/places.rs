@@ -2,7 +2,7 @@
 
 use axum::{
     body::Body,
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::{header, StatusCode},
     response::IntoResponse,
     Json,
@@ -13,15 +13,19 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::api::{
-    dto::{CreatePlaceRequest, ErrorResponse, PlaceResponse, UpdatePlaceRequest},
+    dto::{
+        CreatePlaceRequest, ErrorResponse, ListResponse, Paginated, PlaceResponse, UpdatePlaceRequest,
+    },
+    list_query::{ListParams, PaginationQuery},
     state::AppState,
     ApiError,
 };
 use crate::application::{
-    CreatePlaceUseCase, DeletePlaceUseCase, GetPlaceByIdUseCase, GetPlacesUseCase,
+    CreatePlaceUseCase, DeletePlaceUseCase, GetPlaceByIdUseCase, GetPlacesPaginatedUseCase,
     UpdatePlaceUseCase,
 };
 use crate::api::auth::BearerAuth;
+use crate::domain::Place;
 
 /// Decodifica imagen base64 y la guarda en dir/{id}.{ext}. Devuelve la URL: /api/places/{id}/image.
 fn save_place_image_base64(
@@ -58,14 +62,19 @@ fn save_place_image_base64(
     Ok(format!("/api/places/{}/image", id))
 }
 
-/// Lista todos los lugares.
+/// Lista los lugares. Con `page` o `limit` devuelve la respuesta paginada (items, count, page, limit,
+/// total_pages); si no, una lista con los primeros 100.
+///
+/// `sort`: created_at (desc por defecto), name, location. `filter`: name, location.
 #[utoipa::path(
     get,
     path = "/api/places",
     tag = "places",
     security(("bearer_auth" = [])),
+    params(PaginationQuery, ListParams),
     responses(
-        (status = 200, description = "Lista de lugares", body = [PlaceResponse]),
+        (status = 200, description = "Lista de lugares (paginada con page/limit)", body = ListResponse<PlaceResponse>),
+        (status = 400, description = "sort o filter inválido", body = ErrorResponse),
         (status = 401, description = "No autorizado", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
@@ -73,10 +82,20 @@ fn save_place_image_base64(
 pub async fn list_places(
     _auth: BearerAuth,
     State(state): State<AppState>,
-) -> Result<Json<Vec<PlaceResponse>>, ApiError> {
-    let uc = GetPlacesUseCase::new(Arc::clone(&state.places_repo));
-    let items = uc.execute().await?;
-    Ok(Json(items.into_iter().map(PlaceResponse::from).collect()))
+    Query(p): Query<PaginationQuery>,
+    Query(l): Query<ListParams>,
+) -> Result<Json<ListResponse<PlaceResponse>>, ApiError> {
+    let list = l.to_query(&Place::LIST_SPEC)?;
+    let (page, limit) = p.page_and_limit();
+    let uc = GetPlacesPaginatedUseCase::new(Arc::clone(&state.places_repo));
+    let (items, count) = uc.execute(&list, page, limit).await?;
+    let items = items.into_iter().map(PlaceResponse::from).collect();
+    if !p.is_requested() {
+        return Ok(Json(ListResponse::List(items)));
+    }
+    Ok(Json(ListResponse::Paginated(Paginated::page(
+        items, count, page, limit,
+    ))))
 }
 
 /// Obtiene un lugar por ID.
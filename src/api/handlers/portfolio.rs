@@ -14,27 +14,22 @@ use uuid::Uuid;
 
 use crate::api::{
     dto::{
-        AddPortfolioImageRequest, CreatePortfolioCategoryRequest,
-        PortfolioCategoryResponse, PortfolioImageResponse, PortfolioImagesPaginatedResponse,
+        AddPortfolioImageRequest, CreatePortfolioCategoryRequest, Paginated,
+        PortfolioCategoryResponse, PortfolioImageResponse,
         UpdatePortfolioCategoryRequest, UpdatePortfolioCoverRequest,
     },
+    list_query::{CursorQuery, PaginationQuery},
     state::AppState,
     ApiError,
 };
 use crate::application::{
     AddPortfolioImageUseCase, CreatePortfolioCategoryUseCase, DeletePortfolioCategoryUseCase,
     DeletePortfolioImageUseCase, GetPortfolioCategoriesUseCase,
-    GetPortfolioImagesByCategoryUseCase, UpdatePortfolioCategoryUseCase,
+    GetPortfolioImagesByCategoryUseCase, GetPortfolioImagesByCursorUseCase,
+    UpdatePortfolioCategoryUseCase,
     UpdatePortfolioCoverUseCase,
 };
-
-#[derive(Debug, serde::Deserialize, utoipa::IntoParams)]
-pub struct PaginationQuery {
-    /// Página (0-based). Por defecto 0.
-    pub page: Option<u32>,
-    /// Tamaño de página (máx. 100). Por defecto 20.
-    pub limit: Option<u32>,
-}
+use crate::domain::PageCursor;
 
 /// Decodifica imagen base64 y la guarda en dir/{id}.{ext}. Devuelve la URL: /api/portfolio/images/{id}/image.
 fn save_portfolio_image_base64(
@@ -92,7 +87,8 @@ pub async fn list_portfolio_categories(
     Ok(Json(items.into_iter().map(PortfolioCategoryResponse::from).collect()))
 }
 
-/// Imágenes de una categoría del portfolio (paginado). Query: ?page=0&limit=20. Devuelve items, count, page, limit y total_pages.
+/// Imágenes de una categoría del portfolio (paginado). Query: ?page=0&limit=20. Devuelve items, count, page, limit, total_pages y next_cursor.
+/// Con `?cursor=` pagina por (created_at, id) sin OFFSET ni conteo.
 #[utoipa::path(
     get,
     path = "/api/portfolio/categories/{category_id}/images",
@@ -100,10 +96,12 @@ pub async fn list_portfolio_categories(
     security(("bearer_auth" = [])),
     params(
         ("category_id" = Uuid, Path, description = "UUID de la categoría"),
-        PaginationQuery
+        PaginationQuery,
+        CursorQuery
     ),
     responses(
-        (status = 200, description = "Lista paginada de imágenes (items, count, page, limit, total_pages)", body = PortfolioImagesPaginatedResponse),
+        (status = 200, description = "Lista paginada de imágenes (items, count, page, limit, total_pages, next_cursor)", body = Paginated<PortfolioImageResponse>),
+        (status = 400, description = "Cursor inválido", body = crate::api::dto::ErrorResponse),
         (status = 401, description = "No autorizado", body = crate::api::dto::ErrorResponse),
         (status = 500, description = "Error interno", body = crate::api::dto::ErrorResponse),
     ),
@@ -113,23 +111,34 @@ pub async fn get_portfolio_images(
     State(state): State<AppState>,
     Path(category_id): Path<Uuid>,
     Query(q): Query<PaginationQuery>,
-) -> Result<Json<PortfolioImagesPaginatedResponse>, ApiError> {
-    let page = q.page.unwrap_or(0);
-    let limit = q.limit.unwrap_or(20).min(100);
+    Query(c): Query<CursorQuery>,
+) -> Result<Json<Paginated<PortfolioImageResponse>>, ApiError> {
+    let limit = q.limit();
+    if let Some(token) = c.cursor.as_deref() {
+        let after = PageCursor::from_query(token)?;
+        let uc = GetPortfolioImagesByCursorUseCase::new(Arc::clone(&state.portfolio_repo));
+        let (items, next) = uc.execute(category_id, after, limit).await?;
+        return Ok(Json(Paginated::cursor(
+            items.into_iter().map(PortfolioImageResponse::from).collect(),
+            limit,
+            next.map(|c| c.encode()),
+        )));
+    }
+    let page = q.page();
     let uc = GetPortfolioImagesByCategoryUseCase::new(Arc::clone(&state.portfolio_repo));
     let (items, count) = uc.execute(category_id, page, limit).await?;
-    let total_pages = if count == 0 {
-        0
+    let next_cursor = if (page as u64 + 1) * (limit as u64) < count {
+        items.last().and_then(|i| PageCursor::after(i.created_at, i.id))
     } else {
-        ((count as u32) + limit - 1) / limit
+        None
     };
-    Ok(Json(PortfolioImagesPaginatedResponse {
-        items: items.into_iter().map(PortfolioImageResponse::from).collect(),
+    Ok(Json(Paginated::page(
+        items.into_iter().map(PortfolioImageResponse::from).collect(),
         count,
         page,
         limit,
-        total_pages,
-    }))
+    )
+    .with_next_cursor(next_cursor.map(|c| c.encode()))))
 }
 
 /// Crea una categoría del portfolio.
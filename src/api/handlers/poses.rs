@@ -8,36 +8,167 @@ use axum::{
     Json,
 };
 use base64::Engine;
-use std::path::Path as StdPath;
+use std::path::{Path as StdPath, PathBuf};
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::api::{
     dto::{
-        CreatePoseRequest, ErrorResponse, PoseResponse, PosesPaginatedResponse,
-        UpdatePoseHashtagsRequest,
+        CreatePoseRequest, ErrorResponse, ListResponse, Paginated, PoseBatchRequest, PoseBatchResponse,
+        PoseResponse, ScoredPoseResponse, UpdatePoseHashtagsRequest, UpdatePoseRequest,
     },
+    auth::{user_id_from_auth, BearerAuth},
+    fields::{FieldSet, FieldsParams, Sparse},
+    list_query::{CursorQuery, ListParams, PaginationQuery},
     state::AppState,
     ApiError,
 };
 use crate::application::{
-    CreatePoseUseCase, DeletePoseUseCase, GetPoseByIdUseCase, GetPosesByHashtagPaginatedUseCase,
-    GetPosesByHashtagUseCase, GetPosesPaginatedUseCase, GetPosesUseCase, UpdatePoseHashtagsUseCase,
+    BatchPosesUseCase, CreatePoseUseCase, DeletePoseUseCase, GetPoseByIdUseCase, GetPosesByHashtagPaginatedUseCase,
+    GetPosesByCursorUseCase, GetPosesByHashtagUseCase, GetPosesByTagsUseCase, GetPosesPaginatedUseCase,
+    GetPoseRelationsUseCase, GetRecommendedPosesUseCase, GetSimilarPosesUseCase, UpdatePoseHashtagsUseCase,
+    UpdatePoseUseCase,
 };
+use crate::domain::{
+    DomainError, PageCursor, Pose, PoseBatch, PoseDifficulty, PoseFilter, PoseInclude, PoseMetadata,
+    PoseMetadataPatch, PoseOrientation, PoseTagFilter,
+};
+
+/// Filtros de GET /api/poses: listas de UUIDs de hashtags separadas por comas.
+#[derive(Debug, serde::Deserialize, utoipa::IntoParams)]
+pub struct PosesQuery {
+    /// La pose debe tener todos estos hashtags.
+    pub tags_all: Option<String>,
+    /// La pose debe tener al menos uno de estos hashtags.
+    pub tags_any: Option<String>,
+    /// La pose no debe tener ninguno de estos hashtags.
+    pub tags_none: Option<String>,
+}
 
+/// Filtros por metadatos de GET /api/poses y GET /api/poses/paginated.
 #[derive(Debug, serde::Deserialize, utoipa::IntoParams)]
-pub struct PaginationQuery {
-    pub page: Option<u32>,
+pub struct PoseFilterQuery {
+    /// easy, medium o hard.
+    pub difficulty: Option<String>,
+    /// portrait o landscape.
+    pub orientation: Option<String>,
+    /// Mínimo de personas en la pose.
+    pub min_people: Option<i32>,
+    /// Máximo de personas en la pose.
+    pub max_people: Option<i32>,
+    /// Texto a buscar en el título o la descripción.
+    pub q: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize, utoipa::IntoParams)]
+pub struct RecommendationQuery {
+    /// Máximo de poses (por defecto 20, máximo 50).
     pub limit: Option<u32>,
 }
 
+#[derive(Debug, serde::Deserialize, utoipa::IntoParams)]
+pub struct HashtagPosesQuery {
+    /// Incluye las poses de los hashtags descendientes (por defecto false).
+    pub include_children: Option<bool>,
+}
+
+/// Lista de UUIDs separada por comas (`a,b,c`); los huecos se ignoran.
+fn parse_uuid_list(name: &str, value: Option<&str>) -> Result<Vec<Uuid>, ApiError> {
+    value
+        .unwrap_or("")
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            Uuid::parse_str(s).map_err(|_| {
+                ApiError(DomainError::Validation(format!("{}: UUID inválido: {}", name, s)))
+            })
+        })
+        .collect()
+}
+
+fn parse_difficulty(value: Option<&str>) -> Result<Option<PoseDifficulty>, ApiError> {
+    value
+        .map(|s| {
+            PoseDifficulty::parse(s).ok_or_else(|| {
+                ApiError(DomainError::Validation(format!(
+                    "difficulty inválida: {} (usa easy, medium o hard)",
+                    s
+                )))
+            })
+        })
+        .transpose()
+}
+
+fn parse_orientation(value: Option<&str>) -> Result<Option<PoseOrientation>, ApiError> {
+    value
+        .map(|s| {
+            PoseOrientation::parse(s).ok_or_else(|| {
+                ApiError(DomainError::Validation(format!(
+                    "orientation inválida: {} (usa portrait o landscape)",
+                    s
+                )))
+            })
+        })
+        .transpose()
+}
+
+fn pose_filter(q: &PoseFilterQuery) -> Result<PoseFilter, ApiError> {
+    Ok(PoseFilter {
+        difficulty: parse_difficulty(q.difficulty.as_deref())?,
+        orientation: parse_orientation(q.orientation.as_deref())?,
+        min_people: q.min_people,
+        max_people: q.max_people,
+        q: q.q.as_deref().map(str::trim).filter(|s| !s.is_empty()).map(str::to_string),
+    })
+}
+
+/// `?fields=` e `?include=` de las respuestas de poses (validados antes de consultar).
+pub(crate) struct PoseView {
+    fields: FieldSet,
+    include: PoseInclude,
+}
+
+impl PoseView {
+    pub(crate) fn parse(params: &FieldsParams) -> Result<Self, ApiError> {
+        Ok(PoseView {
+            fields: params.fields(PoseResponse::FIELDS)?,
+            include: PoseInclude::parse(params.include.as_deref())?,
+        })
+    }
+
+    /// Convierte el lote en respuesta resolviendo cada relación pedida con una sola consulta.
+    pub(crate) async fn render(
+        &self,
+        state: &AppState,
+        auth: &BearerAuth,
+        poses: Vec<Pose>,
+    ) -> Result<Vec<Sparse<PoseResponse>>, ApiError> {
+        let user_id = match self.include.favorite {
+            true => Some(user_id_from_auth(state, &auth.0).await?),
+            false => None,
+        };
+        let ids: Vec<Uuid> = poses.iter().map(|p| p.id).collect();
+        let uc = GetPoseRelationsUseCase::new(
+            Arc::clone(&state.hashtags_repo),
+            Arc::clone(&state.favorites_repo),
+            Arc::clone(&state.sesiones_repo),
+        );
+        let mut relations = uc.execute(&ids, self.include, user_id).await?;
+        Ok(poses
+            .into_iter()
+            .map(|p| self.fields.apply(PoseResponse::with_relations(p, &mut relations)))
+            .collect())
+    }
+}
+
 /// Decodifica imagen base64 (acepta prefijo data:image/xxx;base64,) y la guarda en dir/{id}.{ext}.
-/// Devuelve la URL que debe guardarse en BD: /api/poses/{id}/image.
+/// Devuelve la URL que debe guardarse en BD (/api/poses/{id}/image) y la ruta del archivo escrito.
 fn save_pose_image_base64(
     dir: &str,
     id: &Uuid,
     image_base64: &str,
-) -> Result<String, ApiError> {
+) -> Result<(String, PathBuf), ApiError> {
     let (payload, ext) = if let Some(rest) = image_base64.strip_prefix("data:") {
         let (mime, b64) = rest
             .split_once(";base64,")
@@ -64,91 +195,219 @@ fn save_pose_image_base64(
     let path = StdPath::new(dir).join(&filename);
     std::fs::write(&path, &bytes).map_err(|e| ApiError(crate::domain::DomainError::Repository(anyhow::Error::from(e))))?;
 
-    Ok(format!("/api/poses/{}/image", id))
+    Ok((format!("/api/poses/{}/image", id), path))
 }
 
-/// Lista todas las poses.
+/// Lista las poses, filtrables por metadatos (difficulty, orientation, min_people, max_people, q).
+/// Con `page` o `limit`, o con `tags_all`, `tags_any` o `tags_none` (UUIDs separados por comas, filtro por
+/// hashtags), devuelve la respuesta paginada (items, count, page, limit, total_pages); si no, una lista
+/// con las primeras 100.
+///
+/// `sort`: created_at (desc por defecto), title, people_count, difficulty.
+/// `filter`: difficulty, orientation, people_count, source_credit.
+///
+/// `fields`: id, url, title, description, people_count, difficulty, orientation, source_credit, created_at,
+/// hashtags, favorite, sesiones. `include`: hashtags, favorite, sesiones.
 #[utoipa::path(
     get,
     path = "/api/poses",
     tag = "poses",
     security(("bearer_auth" = [])),
+    params(PosesQuery, PaginationQuery, PoseFilterQuery, ListParams, FieldsParams),
     responses(
-        (status = 200, description = "Lista de poses", body = [PoseResponse]),
+        (status = 200, description = "Lista de poses (paginada con page/limit o si se filtra por hashtags)", body = ListResponse<PoseResponse>),
+        (status = 400, description = "UUID inválido, hashtag repetido en tags_none, filtro de metadatos, sort, filter, fields o include inválido", body = ErrorResponse),
         (status = 401, description = "No autorizado", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
 )]
 pub async fn list_poses(
-    _auth: crate::api::auth::BearerAuth,
+    auth: BearerAuth,
     State(state): State<AppState>,
-) -> Result<Json<Vec<PoseResponse>>, ApiError> {
-    let uc = GetPosesUseCase::new(Arc::clone(&state.poses_repo));
-    let items = uc.execute().await?;
-    Ok(Json(items.into_iter().map(PoseResponse::from).collect()))
+    Query(q): Query<PosesQuery>,
+    Query(p): Query<PaginationQuery>,
+    Query(f): Query<PoseFilterQuery>,
+    Query(l): Query<ListParams>,
+    Query(v): Query<FieldsParams>,
+) -> Result<Json<ListResponse<Sparse<PoseResponse>>>, ApiError> {
+    let view = PoseView::parse(&v)?;
+    let metadata = pose_filter(&f)?;
+    let list = l.to_query(&Pose::LIST_SPEC)?;
+    let filter = PoseTagFilter {
+        all: parse_uuid_list("tags_all", q.tags_all.as_deref())?,
+        any: parse_uuid_list("tags_any", q.tags_any.as_deref())?,
+        none: parse_uuid_list("tags_none", q.tags_none.as_deref())?,
+    };
+    let paginated = p.is_requested() || !filter.is_empty();
+    let (page, limit) = if paginated {
+        (p.page(), p.limit())
+    } else {
+        p.page_and_limit()
+    };
+    let (items, count) = if !filter.is_empty() {
+        GetPosesByTagsUseCase::new(Arc::clone(&state.hashtags_repo))
+            .execute(filter, &metadata, &list, page, limit)
+            .await?
+    } else {
+        GetPosesPaginatedUseCase::new(Arc::clone(&state.poses_repo))
+            .execute(&metadata, &list, page, limit)
+            .await?
+    };
+    let items = view.render(&state, &auth, items).await?;
+    if !paginated {
+        return Ok(Json(ListResponse::List(items)));
+    }
+    Ok(Json(ListResponse::Paginated(Paginated::page(
+        items, count, page, limit,
+    ))))
 }
 
-/// Lista poses paginado (?page=0&limit=20). Devuelve items, count, page, limit y total_pages.
+/// Lista poses paginado (?page=0&limit=20), filtrables por metadatos. Devuelve items, count, page, limit,
+/// total_pages y next_cursor. Con `?cursor=` pagina por (created_at, id) sin OFFSET ni conteo.
+///
+/// `sort` (no combinable con `cursor`): created_at (desc por defecto), title, people_count, difficulty.
+/// `filter`: difficulty, orientation, people_count, source_credit. `fields` e `include`: como en GET /api/poses.
 #[utoipa::path(
     get,
     path = "/api/poses/paginated",
     tag = "poses",
     security(("bearer_auth" = [])),
-    params(PaginationQuery),
+    params(PaginationQuery, CursorQuery, PoseFilterQuery, ListParams, FieldsParams),
     responses(
-        (status = 200, description = "Lista paginada de poses (items, count, page, limit, total_pages)", body = PosesPaginatedResponse),
+        (status = 200, description = "Lista paginada de poses (items, count, page, limit, total_pages, next_cursor)", body = Paginated<PoseResponse>),
+        (status = 400, description = "Filtro de metadatos, sort, filter, cursor, fields o include inválido", body = ErrorResponse),
         (status = 401, description = "No autorizado", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
 )]
 pub async fn list_poses_paginated(
-    _auth: crate::api::auth::BearerAuth,
+    auth: BearerAuth,
     State(state): State<AppState>,
     Query(q): Query<PaginationQuery>,
-) -> Result<Json<PosesPaginatedResponse>, ApiError> {
-    let page = q.page.unwrap_or(0);
-    let limit = q.limit.unwrap_or(20).min(100);
+    Query(c): Query<CursorQuery>,
+    Query(f): Query<PoseFilterQuery>,
+    Query(l): Query<ListParams>,
+    Query(v): Query<FieldsParams>,
+) -> Result<Json<Paginated<Sparse<PoseResponse>>>, ApiError> {
+    let view = PoseView::parse(&v)?;
+    let limit = q.limit();
+    let filter = pose_filter(&f)?;
+    let list = l.to_query(&Pose::LIST_SPEC)?;
+    if let Some(token) = c.cursor.as_deref() {
+        let after = PageCursor::from_query(token)?;
+        let uc = GetPosesByCursorUseCase::new(Arc::clone(&state.poses_repo));
+        let (items, next) = uc.execute(&filter, &list, after, limit).await?;
+        return Ok(Json(Paginated::cursor(
+            view.render(&state, &auth, items).await?,
+            limit,
+            next.map(|c| c.encode()),
+        )));
+    }
+    let page = q.page();
     let uc = GetPosesPaginatedUseCase::new(Arc::clone(&state.poses_repo));
-    let (items, count) = uc.execute(page, limit).await?;
-    let total_pages = if count == 0 {
-        0
+    let (items, count) = uc.execute(&filter, &list, page, limit).await?;
+    // El cursor solo sirve con el orden por defecto (created_at, id).
+    let next_cursor = if list.sort.is_none() && (page as u64 + 1) * (limit as u64) < count {
+        items.last().and_then(|p| PageCursor::after(p.created_at, p.id))
     } else {
-        ((count as u32) + limit - 1) / limit
+        None
     };
-    Ok(Json(PosesPaginatedResponse {
-        items: items.into_iter().map(PoseResponse::from).collect(),
+    Ok(Json(Paginated::page(
+        view.render(&state, &auth, items).await?,
         count,
         page,
         limit,
-        total_pages,
-    }))
+    )
+    .with_next_cursor(next_cursor.map(|c| c.encode()))))
 }
 
 /// Obtiene una pose por id.
+///
+/// `fields`: id, url, title, description, people_count, difficulty, orientation, source_credit, created_at,
+/// hashtags, favorite, sesiones. `include`: hashtags, favorite, sesiones.
 #[utoipa::path(
     get,
     path = "/api/poses/{id}",
     tag = "poses",
     security(("bearer_auth" = [])),
-    params(("id" = Uuid, Path, description = "UUID de la pose")),
+    params(("id" = Uuid, Path, description = "UUID de la pose"), FieldsParams),
     responses(
         (status = 200, description = "Pose encontrada", body = PoseResponse),
+        (status = 400, description = "fields o include inválido", body = ErrorResponse),
         (status = 401, description = "No autorizado", body = ErrorResponse),
         (status = 404, description = "Pose no encontrada", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
 )]
 pub async fn get_pose(
-    _auth: crate::api::auth::BearerAuth,
+    auth: BearerAuth,
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-) -> Result<Json<PoseResponse>, ApiError> {
+    Query(v): Query<FieldsParams>,
+) -> Result<Json<Sparse<PoseResponse>>, ApiError> {
+    let view = PoseView::parse(&v)?;
     let uc = GetPoseByIdUseCase::new(Arc::clone(&state.poses_repo));
     let item = uc.execute(id).await?;
-    Ok(Json(PoseResponse::from(item)))
+    let mut items = view.render(&state, &auth, vec![item]).await?;
+    Ok(Json(items.remove(0)))
+}
+
+/// Poses parecidas ("más como esta"): hashtags compartidos ponderados por rareza y, si ambas poses tienen
+/// hash de imagen, similitud visual. Ordenadas por score.
+#[utoipa::path(
+    get,
+    path = "/api/poses/{id}/similar",
+    tag = "poses",
+    security(("bearer_auth" = [])),
+    params(("id" = Uuid, Path, description = "UUID de la pose"), RecommendationQuery),
+    responses(
+        (status = 200, description = "Poses parecidas", body = [ScoredPoseResponse]),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 404, description = "Pose no encontrada", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn get_similar_poses(
+    _auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(q): Query<RecommendationQuery>,
+) -> Result<Json<Vec<ScoredPoseResponse>>, ApiError> {
+    let limit = q.limit.unwrap_or(20).clamp(1, 50);
+    let uc = GetSimilarPosesUseCase::new(Arc::clone(&state.poses_repo));
+    let items = uc.execute(id, limit).await?;
+    Ok(Json(items.into_iter().map(ScoredPoseResponse::from).collect()))
 }
 
-/// Crea una pose (name opcional, image_base64 requerida). La imagen se guarda en disco y la URL devuelta es /api/poses/{id}/image.
+/// Poses recomendadas para el usuario (JWT) según los hashtags de sus favoritos y las sesiones en las que
+/// aparecen; no incluye sus favoritos. Sin favoritos, devuelve las más guardadas.
+#[utoipa::path(
+    get,
+    path = "/api/poses/recommended",
+    tag = "poses",
+    security(("bearer_auth" = [])),
+    params(RecommendationQuery),
+    responses(
+        (status = 200, description = "Poses recomendadas", body = [ScoredPoseResponse]),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn get_recommended_poses(
+    auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Query(q): Query<RecommendationQuery>,
+) -> Result<Json<Vec<ScoredPoseResponse>>, ApiError> {
+    let user_id = crate::api::auth::user_id_from_auth(&state, &auth.0).await?;
+    let limit = q.limit.unwrap_or(20).clamp(1, 50);
+    let uc = GetRecommendedPosesUseCase::new(Arc::clone(&state.poses_repo));
+    let items = uc.execute(user_id, limit).await?;
+    Ok(Json(items.into_iter().map(ScoredPoseResponse::from).collect()))
+}
+
+/// Crea una pose (image_base64 requerida; título, descripción y demás metadatos opcionales).
+/// La imagen se guarda en disco y la URL devuelta es /api/poses/{id}/image. La pose y sus hashtags se
+/// guardan en una transacción; si falla, se borra también la imagen.
 #[utoipa::path(
     post,
     path = "/api/poses",
@@ -158,7 +417,7 @@ pub async fn get_pose(
     responses(
         (status = 200, description = "Pose creada", body = PoseResponse),
         (status = 401, description = "No autorizado", body = ErrorResponse),
-        (status = 400, description = "Imagen base64 vacía o inválida", body = ErrorResponse),
+        (status = 400, description = "Imagen base64 vacía o inválida, o metadatos inválidos", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
 )]
@@ -172,21 +431,82 @@ pub async fn create_pose(
             "image_base64 es requerido".to_string(),
         )));
     }
+    // Se validan antes de escribir la imagen para no dejar archivos huérfanos.
+    let metadata = PoseMetadata {
+        title: body.title,
+        description: body.description,
+        people_count: body.people_count,
+        difficulty: parse_difficulty(body.difficulty.as_deref())?,
+        orientation: parse_orientation(body.orientation.as_deref())?,
+        source_credit: body.source_credit,
+    }
+    .validated()?;
     let id = Uuid::new_v4();
-    let url = save_pose_image_base64(&state.poses_images_dir, &id, &body.image_base64)?;
-    let uc = CreatePoseUseCase::new(Arc::clone(&state.poses_repo));
+    let (url, path) = save_pose_image_base64(&state.poses_images_dir, &id, &body.image_base64)?;
+    let dir = state.poses_images_dir.clone();
+    let image_hash = tokio::task::spawn_blocking(move || crate::api::image_hash::pose_image_hash(&dir, id))
+        .await
+        .ok()
+        .flatten();
+    let uc = CreatePoseUseCase::new(
+        Arc::clone(&state.poses_repo),
+        Arc::clone(&state.hashtags_repo),
+        Arc::clone(&state.unit_of_work),
+    );
+    let undo_image = Box::new(move || {
+        let _ = std::fs::remove_file(&path);
+    });
     let item = uc
-        .execute_with_id(id, &url)
+        .execute_with_id(
+            id,
+            &url,
+            metadata,
+            image_hash,
+            body.hashtag_ids.as_deref().unwrap_or(&[]),
+            undo_image,
+        )
         .await?;
-    if let Some(ids) = &body.hashtag_ids {
-        for &hashtag_id in ids {
-            state
-                .hashtags_repo
-                .add_hashtag_to_pose(item.id, hashtag_id)
-                .await
-                .map_err(ApiError)?;
-        }
-    }
+    Ok(Json(PoseResponse::from(item)))
+}
+
+/// Edita los metadatos de una pose: solo cambian los campos enviados; `null` o texto vacío los borra.
+#[utoipa::path(
+    put,
+    path = "/api/poses/{id}",
+    tag = "poses",
+    security(("bearer_auth" = [])),
+    params(("id" = Uuid, Path, description = "UUID de la pose")),
+    request_body = UpdatePoseRequest,
+    responses(
+        (status = 200, description = "Pose actualizada", body = PoseResponse),
+        (status = 400, description = "Metadatos inválidos", body = ErrorResponse),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 404, description = "Pose no encontrada", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn update_pose(
+    _auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<UpdatePoseRequest>,
+) -> Result<Json<PoseResponse>, ApiError> {
+    let patch = PoseMetadataPatch {
+        title: body.title,
+        description: body.description,
+        people_count: body.people_count,
+        difficulty: body
+            .difficulty
+            .map(|d| parse_difficulty(d.as_deref().filter(|s| !s.trim().is_empty())))
+            .transpose()?,
+        orientation: body
+            .orientation
+            .map(|o| parse_orientation(o.as_deref().filter(|s| !s.trim().is_empty())))
+            .transpose()?,
+        source_credit: body.source_credit,
+    };
+    let uc = UpdatePoseUseCase::new(Arc::clone(&state.poses_repo));
+    let item = uc.execute(id, patch).await?;
     Ok(Json(PoseResponse::from(item)))
 }
 
@@ -251,18 +571,57 @@ pub async fn delete_pose(
     let uc = DeletePoseUseCase::new(
         Arc::clone(&state.poses_repo),
         Arc::clone(&state.hashtags_repo),
+        Arc::clone(&state.unit_of_work),
     );
     uc.execute(id).await?;
     Ok(axum::http::StatusCode::NO_CONTENT)
 }
 
-/// Poses etiquetadas con un hashtag.
+/// Aplica operaciones en lote (add_tags, remove_tags, add_to_sesion, delete) a una lista de poses, en una
+/// sola transacción: si algo falla no se aplica nada. Las poses que no existen se informan como not_found.
+/// Solo admin.
+#[utoipa::path(
+    post,
+    path = "/api/poses/batch",
+    tag = "poses",
+    security(("bearer_auth" = [])),
+    request_body = PoseBatchRequest,
+    responses(
+        (status = 200, description = "Resultado por pose", body = PoseBatchResponse),
+        (status = 400, description = "Lote vacío, demasiado grande u operación inválida", body = ErrorResponse),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 403, description = "Solo administradores (ADMIN_EMAILS)", body = ErrorResponse),
+        (status = 404, description = "Hashtag o sesión no encontrados", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn batch_poses(
+    auth: BearerAuth,
+    State(state): State<AppState>,
+    Json(body): Json<PoseBatchRequest>,
+) -> Result<Json<PoseBatchResponse>, ApiError> {
+    crate::api::auth::require_admin(&state, &auth.0).await?;
+    let batch = PoseBatch::new(
+        body.pose_ids,
+        body.operations.into_iter().map(Into::into).collect(),
+    )?;
+    let uc = BatchPosesUseCase::new(
+        Arc::clone(&state.poses_repo),
+        Arc::clone(&state.hashtags_repo),
+        Arc::clone(&state.sesiones_repo),
+        Arc::clone(&state.unit_of_work),
+    );
+    let items = uc.execute(&batch).await?;
+    Ok(Json(PoseBatchResponse::from(items)))
+}
+
+/// Poses etiquetadas con un hashtag (`?include_children=true` incluye sus descendientes).
 #[utoipa::path(
     get,
     path = "/api/hashtags/{hashtag_id}/poses",
     tag = "poses",
     security(("bearer_auth" = [])),
-    params(("hashtag_id" = Uuid, Path, description = "UUID del hashtag")),
+    params(("hashtag_id" = Uuid, Path, description = "UUID del hashtag"), HashtagPosesQuery),
     responses(
         (status = 200, description = "Lista de poses", body = [PoseResponse]),
         (status = 401, description = "No autorizado", body = ErrorResponse),
@@ -273,9 +632,12 @@ pub async fn get_poses_by_hashtag(
     _auth: crate::api::auth::BearerAuth,
     State(state): State<AppState>,
     Path(hashtag_id): Path<Uuid>,
+    Query(h): Query<HashtagPosesQuery>,
 ) -> Result<Json<Vec<PoseResponse>>, ApiError> {
     let uc = GetPosesByHashtagUseCase::new(Arc::clone(&state.hashtags_repo));
-    let items = uc.execute(hashtag_id).await?;
+    let items = uc
+        .execute(hashtag_id, h.include_children.unwrap_or(false))
+        .await?;
     Ok(Json(items.into_iter().map(PoseResponse::from).collect()))
 }
 
@@ -285,9 +647,9 @@ pub async fn get_poses_by_hashtag(
     path = "/api/hashtags/{hashtag_id}/poses/paginated",
     tag = "poses",
     security(("bearer_auth" = [])),
-    params(("hashtag_id" = Uuid, Path), PaginationQuery),
+    params(("hashtag_id" = Uuid, Path), PaginationQuery, HashtagPosesQuery),
     responses(
-        (status = 200, description = "Lista paginada de poses (items, count, page, limit, total_pages)", body = PosesPaginatedResponse),
+        (status = 200, description = "Lista paginada de poses (items, count, page, limit, total_pages)", body = Paginated<PoseResponse>),
         (status = 401, description = "No autorizado", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
@@ -297,23 +659,20 @@ pub async fn get_poses_by_hashtag_paginated(
     State(state): State<AppState>,
     Path(hashtag_id): Path<Uuid>,
     Query(q): Query<PaginationQuery>,
-) -> Result<Json<PosesPaginatedResponse>, ApiError> {
-    let page = q.page.unwrap_or(0);
-    let limit = q.limit.unwrap_or(20).min(100);
+    Query(h): Query<HashtagPosesQuery>,
+) -> Result<Json<Paginated<PoseResponse>>, ApiError> {
+    let page = q.page();
+    let limit = q.limit();
     let uc = GetPosesByHashtagPaginatedUseCase::new(Arc::clone(&state.hashtags_repo));
-    let (items, count) = uc.execute(hashtag_id, page, limit).await?;
-    let total_pages = if count == 0 {
-        0
-    } else {
-        ((count as u32) + limit - 1) / limit
-    };
-    Ok(Json(PosesPaginatedResponse {
-        items: items.into_iter().map(PoseResponse::from).collect(),
+    let (items, count) = uc
+        .execute(hashtag_id, h.include_children.unwrap_or(false), page, limit)
+        .await?;
+    Ok(Json(Paginated::page(
+        items.into_iter().map(PoseResponse::from).collect(),
         count,
         page,
         limit,
-        total_pages,
-    }))
+    )))
 }
 
 /// Actualiza los hashtags de una pose (reemplaza la lista).
@@ -13,21 +13,45 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::api::{
-    dto::{CreatePostRequest, ErrorResponse, PostResponse, PostsPaginatedResponse},
+    auth::{actor_from_auth, user_id_from_auth},
+    dto::{
+        AddPostImageRequest, CreatePostCommentRequest, CreatePostRequest, DailyWinnerResponse, ErrorResponse,
+        LeaderboardEntryResponse, ListResponse, Paginated, PostCommentResponse, PostLikeResponse, PostResponse,
+        PostVoteResponse, ReorderPostImagesRequest, ThemeLeaderboardResponse,
+        UpdatePostCommentRequest, UpdatePostRequest,
+    },
+    list_query::{CursorQuery, ListParams, PaginationQuery},
     state::AppState,
     ApiError,
 };
 use crate::application::{
-    CreatePostUseCase, DeletePostUseCase, GetPostByIdUseCase, GetPostsByThemeOfTheDayIdUseCase,
-    GetPostsPaginatedUseCase, GetPostsUseCase,
+    AddPostImageUseCase, CreatePostCommentUseCase, CreatePostUseCase, DeletePostCommentUseCase, DeletePostUseCase,
+    GetEventoByIdUseCase, GetPostByIdUseCase, GetPostCommentsUseCase,
+    GetPostsByCursorUseCase, GetPostsByEventoPaginatedUseCase, GetPostsByHashtagPaginatedUseCase, GetPostsByThemeOfTheDayIdUseCase, GetPostsPaginatedUseCase,
+    GetThemeLeaderboardUseCase, GetThemeOfTheDayByIdUseCase, LikePostUseCase,
+    MarkPostsLikedByUserUseCase, RemovePostImageUseCase, RemovePostVoteUseCase,
+    ReorderPostImagesUseCase, ResolvePostThemeUseCase, ResolveTimeZoneUseCase, UnlikePostUseCase,
+    UpdatePostCommentUseCase, UpdatePostUseCase, VotePostUseCase,
 };
+use crate::domain::{PageCursor, Post};
 
 #[derive(Debug, serde::Deserialize, utoipa::IntoParams)]
-pub struct PaginationQuery {
-    pub page: Option<u32>,
+pub struct LeaderboardQuery {
+    /// Máximo de posts en el ranking (por defecto 10, máximo 100).
     pub limit: Option<u32>,
 }
 
+/// Rellena `liked_by_me` para el usuario autenticado.
+async fn mark_liked_by_me(
+    state: &AppState,
+    email: &str,
+    posts: Vec<Post>,
+) -> Result<Vec<Post>, ApiError> {
+    let user_id = user_id_from_auth(state, email).await?;
+    let uc = MarkPostsLikedByUserUseCase::new(Arc::clone(&state.posts_repo));
+    Ok(uc.execute(user_id, posts).await?)
+}
+
 /// Resuelve el directorio de imágenes: si es relativo, lo hace absoluto respecto al CWD actual.
 fn resolve_posts_dir(dir: &str) -> PathBuf {
     let p = StdPath::new(dir);
@@ -39,13 +63,8 @@ fn resolve_posts_dir(dir: &str) -> PathBuf {
         .join(p)
 }
 
-/// Decodifica imagen base64 y la guarda en dir/{id}.{ext}. Devuelve la URL: /api/posts/{id}/image.
-/// El directorio se resuelve (rutas relativas como "app/uploads/posts" se hacen absolutas respecto al CWD).
-fn save_post_image_base64(
-    dir: &str,
-    id: &Uuid,
-    image_base64: &str,
-) -> Result<String, ApiError> {
+/// Decodifica una imagen base64 (con o sin prefijo data:image/...). Devuelve los bytes y la extensión.
+fn decode_post_image_base64(image_base64: &str) -> Result<(Vec<u8>, &'static str), ApiError> {
     let (payload, ext) = if let Some(rest) = image_base64.strip_prefix("data:") {
         let (mime, b64) = rest
             .split_once(";base64,")
@@ -66,97 +85,278 @@ fn save_post_image_base64(
     if bytes.is_empty() {
         return Err(ApiError(crate::domain::DomainError::Validation("imagen vacía".to_string())));
     }
+    Ok((bytes, ext))
+}
 
+/// Guarda una imagen ya decodificada en dir/{image_id}.{ext}.
+/// El directorio se resuelve (rutas relativas como "app/uploads/posts" se hacen absolutas respecto al CWD).
+/// Al reemplazar una imagen se borra la anterior si tenía otra extensión.
+fn write_post_image(dir: &str, image_id: &Uuid, bytes: &[u8], ext: &str) -> Result<(), ApiError> {
     let base_dir = resolve_posts_dir(dir);
-    let file_path = base_dir.join(format!("{}.{}", id, ext));
+    let file_path = base_dir.join(format!("{}.{}", image_id, ext));
     if let Some(parent) = file_path.parent() {
         std::fs::create_dir_all(parent).map_err(|e| ApiError(crate::domain::DomainError::Repository(anyhow::Error::from(e))))?;
     }
-    std::fs::write(&file_path, &bytes).map_err(|e| ApiError(crate::domain::DomainError::Repository(anyhow::Error::from(e))))?;
+    std::fs::write(&file_path, bytes).map_err(|e| ApiError(crate::domain::DomainError::Repository(anyhow::Error::from(e))))?;
+    for other in ["png", "jpg", "jpeg"].into_iter().filter(|e| *e != ext) {
+        let _ = std::fs::remove_file(base_dir.join(format!("{}.{}", image_id, other)));
+    }
+    Ok(())
+}
 
-    Ok(format!("/api/posts/{}/image", id))
+/// Borra el archivo de una imagen (cualquier extensión).
+fn remove_post_image_file(dir: &str, image_id: &Uuid) {
+    let base_dir = resolve_posts_dir(dir);
+    for ext in ["png", "jpg", "jpeg"] {
+        let _ = std::fs::remove_file(base_dir.join(format!("{}.{}", image_id, ext)));
+    }
 }
 
-/// Lista todos los posts.
+/// Respuesta con el archivo dir/{image_id}.{ext} (404 si no existe).
+fn post_image_file_response(dir: &str, image_id: &Uuid) -> Result<impl IntoResponse, ApiError> {
+    let dir = resolve_posts_dir(dir);
+    let dir = dir.canonicalize().unwrap_or(dir);
+    for ext in ["png", "jpg", "jpeg"] {
+        let path = dir.join(format!("{}.{}", image_id, ext));
+        if path.exists() {
+            let bytes = std::fs::read(&path)
+                .map_err(|e| ApiError(crate::domain::DomainError::Repository(anyhow::Error::from(e))))?;
+            let content_type = if ext == "png" {
+                "image/png"
+            } else {
+                "image/jpeg"
+            };
+            return Ok((
+                StatusCode::OK,
+                [
+                    (header::CONTENT_TYPE, content_type),
+                    (header::CACHE_CONTROL, "public, max-age=86400"),
+                ],
+                Body::from(bytes),
+            ));
+        }
+    }
+    Err(ApiError(crate::domain::DomainError::NotFound(format!(
+        "Imagen no encontrada: {} (directorio: {})",
+        image_id,
+        dir.display()
+    ))))
+}
+
+/// Escribe la imagen que va a sustituir a {id} en dir/{id}.{ext}.new, sin tocar la actual.
+/// Devuelve la ruta del archivo preparado (ver `replace_post_image`).
+fn stage_post_image(dir: &str, id: &Uuid, bytes: &[u8], ext: &str) -> Result<PathBuf, ApiError> {
+    let base_dir = resolve_posts_dir(dir);
+    std::fs::create_dir_all(&base_dir).map_err(|e| ApiError(crate::domain::DomainError::Repository(anyhow::Error::from(e))))?;
+    let staged = base_dir.join(format!("{}.{}.new", id, ext));
+    std::fs::write(&staged, bytes).map_err(|e| ApiError(crate::domain::DomainError::Repository(anyhow::Error::from(e))))?;
+    Ok(staged)
+}
+
+/// Sustituye dir/{id}.{ext} por la imagen preparada con `stage_post_image` (y borra la de otra extensión).
+fn replace_post_image(dir: &str, id: &Uuid, staged: &StdPath, ext: &str) -> Result<(), ApiError> {
+    let base_dir = resolve_posts_dir(dir);
+    if let Err(e) = std::fs::rename(staged, base_dir.join(format!("{}.{}", id, ext))) {
+        let _ = std::fs::remove_file(staged);
+        return Err(ApiError(crate::domain::DomainError::Repository(anyhow::Error::from(e))));
+    }
+    for other in ["png", "jpg", "jpeg"].into_iter().filter(|e| *e != ext) {
+        let _ = std::fs::remove_file(base_dir.join(format!("{}.{}", id, other)));
+    }
+    Ok(())
+}
+
+/// Lista los posts. Con `page` o `limit` devuelve la respuesta paginada (items, count, page, limit,
+/// total_pages); si no, una lista con los primeros 100.
+///
+/// `sort`: created_at (desc por defecto), votes_count, likes_count, comments_count, theme_date.
+/// `filter`: user_id, evento_id, theme_of_the_day_id, theme_date.
 #[utoipa::path(
     get,
     path = "/api/posts",
     tag = "posts",
     security(("bearer_auth" = [])),
+    params(PaginationQuery, ListParams),
     responses(
-        (status = 200, description = "Lista de posts", body = [PostResponse]),
+        (status = 200, description = "Lista de posts (paginada con page/limit)", body = ListResponse<PostResponse>),
+        (status = 400, description = "sort o filter inválido", body = ErrorResponse),
         (status = 401, description = "No autorizado", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
 )]
 pub async fn list_posts(
-    _auth: crate::api::auth::BearerAuth,
+    auth: crate::api::auth::BearerAuth,
     State(state): State<AppState>,
-) -> Result<Json<Vec<PostResponse>>, ApiError> {
-    let uc = GetPostsUseCase::new(Arc::clone(&state.posts_repo));
-    let items = uc.execute().await?;
-    Ok(Json(items.into_iter().map(PostResponse::from).collect()))
+    Query(p): Query<PaginationQuery>,
+    Query(l): Query<ListParams>,
+) -> Result<Json<ListResponse<PostResponse>>, ApiError> {
+    let list = l.to_query(&Post::LIST_SPEC)?;
+    let (page, limit) = p.page_and_limit();
+    let uc = GetPostsPaginatedUseCase::new(Arc::clone(&state.posts_repo));
+    let (items, count) = uc.execute(&list, page, limit).await?;
+    let items = mark_liked_by_me(&state, &auth.0, items).await?;
+    let items = items.into_iter().map(PostResponse::from).collect();
+    if !p.is_requested() {
+        return Ok(Json(ListResponse::List(items)));
+    }
+    Ok(Json(ListResponse::Paginated(Paginated::page(
+        items, count, page, limit,
+    ))))
 }
 
-/// Lista posts paginado (?page=0&limit=20). Devuelve items, count, page, limit y total_pages.
+/// Lista posts paginado (?page=0&limit=20). Devuelve items, count, page, limit, total_pages y next_cursor.
+/// Con `?cursor=` pagina por (created_at, id) sin OFFSET ni conteo.
+///
+/// `sort` (no combinable con `cursor`): created_at (desc por defecto), votes_count, likes_count,
+/// comments_count, theme_date. `filter`: user_id, evento_id, theme_of_the_day_id, theme_date.
 #[utoipa::path(
     get,
     path = "/api/posts/paginated",
     tag = "posts",
     security(("bearer_auth" = [])),
-    params(PaginationQuery),
+    params(PaginationQuery, CursorQuery, ListParams),
     responses(
-        (status = 200, description = "Lista paginada de posts (items, count, page, limit, total_pages)", body = PostsPaginatedResponse),
+        (status = 200, description = "Lista paginada de posts (items, count, page, limit, total_pages, next_cursor)", body = Paginated<PostResponse>),
+        (status = 400, description = "sort, filter o cursor inválido", body = ErrorResponse),
         (status = 401, description = "No autorizado", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
 )]
 pub async fn list_posts_paginated(
-    _auth: crate::api::auth::BearerAuth,
+    auth: crate::api::auth::BearerAuth,
     State(state): State<AppState>,
     Query(q): Query<PaginationQuery>,
-) -> Result<Json<PostsPaginatedResponse>, ApiError> {
-    let page = q.page.unwrap_or(0);
-    let limit = q.limit.unwrap_or(20).min(100);
+    Query(c): Query<CursorQuery>,
+    Query(l): Query<ListParams>,
+) -> Result<Json<Paginated<PostResponse>>, ApiError> {
+    let limit = q.limit();
+    let list = l.to_query(&Post::LIST_SPEC)?;
+    if let Some(token) = c.cursor.as_deref() {
+        let after = PageCursor::from_query(token)?;
+        let uc = GetPostsByCursorUseCase::new(Arc::clone(&state.posts_repo));
+        let (items, next) = uc.execute(&list, after, limit).await?;
+        let items = mark_liked_by_me(&state, &auth.0, items).await?;
+        return Ok(Json(Paginated::cursor(
+            items.into_iter().map(PostResponse::from).collect(),
+            limit,
+            next.map(|c| c.encode()),
+        )));
+    }
+    let page = q.page();
     let uc = GetPostsPaginatedUseCase::new(Arc::clone(&state.posts_repo));
-    let (items, count) = uc.execute(page, limit).await?;
-    let total_pages = if count == 0 {
-        0
+    let (items, count) = uc.execute(&list, page, limit).await?;
+    let items = mark_liked_by_me(&state, &auth.0, items).await?;
+    // El cursor solo sirve con el orden por defecto (created_at, id).
+    let next_cursor = if list.sort.is_none() && (page as u64 + 1) * (limit as u64) < count {
+        items.last().and_then(|p| PageCursor::after(p.created_at, p.id))
     } else {
-        ((count as u32) + limit - 1) / limit
+        None
     };
-    Ok(Json(PostsPaginatedResponse {
-        items: items.into_iter().map(PostResponse::from).collect(),
+    Ok(Json(Paginated::page(
+        items.into_iter().map(PostResponse::from).collect(),
         count,
         page,
         limit,
-        total_pages,
-    }))
+    )
+    .with_next_cursor(next_cursor.map(|c| c.encode()))))
 }
 
-/// Posts por tema del día (MMdd).
+/// Posts etiquetados con un hashtag (paginado, más recientes primero).
+#[utoipa::path(
+    get,
+    path = "/api/hashtags/{hashtag_id}/posts",
+    tag = "posts",
+    security(("bearer_auth" = [])),
+    params(("hashtag_id" = Uuid, Path, description = "UUID del hashtag"), PaginationQuery),
+    responses(
+        (status = 200, description = "Lista paginada de posts (items, count, page, limit, total_pages)", body = Paginated<PostResponse>),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn get_posts_by_hashtag(
+    auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Path(hashtag_id): Path<Uuid>,
+    Query(q): Query<PaginationQuery>,
+) -> Result<Json<Paginated<PostResponse>>, ApiError> {
+    let page = q.page();
+    let limit = q.limit();
+    let uc = GetPostsByHashtagPaginatedUseCase::new(Arc::clone(&state.posts_repo));
+    let (items, count) = uc.execute(hashtag_id, page, limit).await?;
+    let items = mark_liked_by_me(&state, &auth.0, items).await?;
+    Ok(Json(Paginated::page(
+        items.into_iter().map(PostResponse::from).collect(),
+        count,
+        page,
+        limit,
+    )))
+}
+
+/// Posts por tema del día: `YYYY-MM-DD` (galería de esa instancia) o MMdd (ese día, todos los años).
 #[utoipa::path(
     get,
     path = "/api/posts/theme-of-the-day/{theme_of_the_day_id}",
     tag = "posts",
     security(("bearer_auth" = [])),
-    params(("theme_of_the_day_id" = String, Path, description = "Id del tema (MMdd)")),
+    params(("theme_of_the_day_id" = String, Path, description = "Id del tema (YYYY-MM-DD o MMdd)")),
     responses(
         (status = 200, description = "Lista de posts", body = [PostResponse]),
+        (status = 400, description = "Id inválido (no es una fecha YYYY-MM-DD ni MMdd)", body = ErrorResponse),
         (status = 401, description = "No autorizado", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
 )]
 pub async fn get_posts_by_theme_of_the_day(
-    _auth: crate::api::auth::BearerAuth,
+    auth: crate::api::auth::BearerAuth,
     State(state): State<AppState>,
     Path(theme_of_the_day_id): Path<String>,
 ) -> Result<Json<Vec<PostResponse>>, ApiError> {
     let uc = GetPostsByThemeOfTheDayIdUseCase::new(Arc::clone(&state.posts_repo));
     let items = uc.execute(&theme_of_the_day_id).await?;
+    let items = mark_liked_by_me(&state, &auth.0, items).await?;
     Ok(Json(items.into_iter().map(PostResponse::from).collect()))
 }
 
+/// Galería de un evento: posts paginados (?page=0&limit=20), más recientes primero.
+#[utoipa::path(
+    get,
+    path = "/api/eventos/{id}/posts",
+    tag = "posts",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "UUID del evento"),
+        PaginationQuery,
+    ),
+    responses(
+        (status = 200, description = "Posts del evento (items, count, page, limit, total_pages)", body = Paginated<PostResponse>),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 404, description = "Evento no encontrado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn get_posts_by_evento(
+    auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Path(evento_id): Path<Uuid>,
+    Query(q): Query<PaginationQuery>,
+) -> Result<Json<Paginated<PostResponse>>, ApiError> {
+    let page = q.page();
+    let limit = q.limit();
+    let uc = GetPostsByEventoPaginatedUseCase::new(
+        Arc::clone(&state.posts_repo),
+        Arc::clone(&state.eventos_repo),
+    );
+    let (items, count) = uc.execute(evento_id, page, limit).await?;
+    let items = mark_liked_by_me(&state, &auth.0, items).await?;
+    Ok(Json(Paginated::page(
+        items.into_iter().map(PostResponse::from).collect(),
+        count,
+        page,
+        limit,
+    )))
+}
+
 /// Obtiene un post por id.
 #[utoipa::path(
     get,
@@ -172,16 +372,43 @@ pub async fn get_posts_by_theme_of_the_day(
     ),
 )]
 pub async fn get_post(
-    _auth: crate::api::auth::BearerAuth,
+    auth: crate::api::auth::BearerAuth,
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<PostResponse>, ApiError> {
     let uc = GetPostByIdUseCase::new(Arc::clone(&state.posts_repo));
     let item = uc.execute(id).await?;
-    Ok(Json(PostResponse::from(item)))
+    let mut items = mark_liked_by_me(&state, &auth.0, vec![item]).await?;
+    Ok(Json(PostResponse::from(items.remove(0))))
+}
+
+/// Resuelve el tema de un post: MMdd y la instancia del tema en la fecha local del post (`posted_at` en la
+/// zona horaria del autor). NotFound si ese día no tiene tema; nunca crea instancias.
+async fn resolve_post_theme(
+    state: &AppState,
+    theme_of_the_day_id: &str,
+    author_id: Option<Uuid>,
+    posted_at: chrono::DateTime<chrono::Utc>,
+) -> Result<(String, chrono::NaiveDate), ApiError> {
+    let theme_key = crate::domain::ThemeOfTheDayKey::parse(theme_of_the_day_id)?;
+    let time_zone = match author_id {
+        Some(user_id) => {
+            ResolveTimeZoneUseCase::new(Arc::clone(&state.usuarios_repo))
+                .execute(user_id, None, state.default_time_zone)
+                .await?
+        }
+        None => state.default_time_zone,
+    };
+    let theme_date = ResolvePostThemeUseCase::new(Arc::clone(&state.theme_of_the_day_repo))
+        .execute(theme_key, time_zone.local_date(posted_at))
+        .await?;
+    Ok((theme_key.month_day().to_string(), theme_date))
 }
 
-/// Crea un post con imagen en base64 (user_id desde JWT si está autenticado). La imagen se guarda en disco y la URL es /api/posts/{id}/image.
+/// Crea un post con una o varias imágenes en base64 (user_id desde JWT si está autenticado). Las imágenes se guardan
+/// en disco en orden (image_base64 primero, luego images_base64); la URL del post (/api/posts/{id}/image) es la primera.
+/// Si se envía evento_id, el post se añade a la galería de ese evento. Los `#hashtags` de la descripción se
+/// crean (normalizados) y se enlazan al post.
 #[utoipa::path(
     post,
     path = "/api/posts",
@@ -191,7 +418,8 @@ pub async fn get_post(
     responses(
         (status = 200, description = "Post creado", body = PostResponse),
         (status = 401, description = "No autorizado", body = ErrorResponse),
-        (status = 400, description = "Imagen base64 vacía o inválida", body = ErrorResponse),
+        (status = 400, description = "Sin imágenes, demasiadas imágenes, base64 inválido o theme_of_the_day_id no es una fecha válida", body = ErrorResponse),
+        (status = 404, description = "Evento no encontrado (evento_id) o no hay tema del día en esa fecha", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
 )]
@@ -200,45 +428,96 @@ pub async fn create_post(
     State(state): State<AppState>,
     Json(body): Json<CreatePostRequest>,
 ) -> Result<Json<PostResponse>, ApiError> {
-    if body.image_base64.trim().is_empty() {
+    let images_base64: Vec<&str> = std::iter::once(body.image_base64.as_str())
+        .chain(body.images_base64.iter().map(String::as_str))
+        .map(str::trim)
+        .filter(|b64| !b64.is_empty())
+        .collect();
+    if images_base64.is_empty() {
         return Err(ApiError(crate::domain::DomainError::Validation(
-            "image_base64 es requerido".to_string(),
+            "image_base64 o images_base64 es requerido".to_string(),
         )));
     }
+    if images_base64.len() > crate::domain::MAX_POST_IMAGES {
+        return Err(ApiError(crate::domain::DomainError::Validation(format!(
+            "Un post admite como máximo {} imágenes",
+            crate::domain::MAX_POST_IMAGES
+        ))));
+    }
+    // Decodifica todas antes de escribir ninguna, para no dejar archivos sueltos si una es inválida.
+    let images = images_base64
+        .into_iter()
+        .map(decode_post_image_base64)
+        .collect::<Result<Vec<_>, _>>()?;
     if body.theme_of_the_day_id.trim().is_empty() {
         return Err(ApiError(crate::domain::DomainError::Validation(
             "theme_of_the_day_id es requerido".to_string(),
         )));
     }
+    if let Some(evento_id) = body.evento_id {
+        GetEventoByIdUseCase::new(Arc::clone(&state.eventos_repo))
+            .execute(evento_id)
+            .await?;
+    }
     let user = state
         .auth_repository
         .get_by_email(&auth.0)
         .await
         .map_err(ApiError::from)?;
     let user_id = user.map(|u| u.id);
+    // Enlaza el post a la instancia del tema de su fecha local (404 si ese día no tiene tema).
+    let (mmdd, theme_date) =
+        resolve_post_theme(&state, &body.theme_of_the_day_id, user_id, chrono::Utc::now()).await?;
     let id = Uuid::new_v4();
-    let url = save_post_image_base64(&state.posts_images_dir, &id, &body.image_base64)?;
-    let uc = CreatePostUseCase::new(Arc::clone(&state.posts_repo));
+    // La primera imagen usa el id del post ({id}.{ext}), como los posts de una sola imagen.
+    let image_ids: Vec<Uuid> = (0..images.len())
+        .map(|i| if i == 0 { id } else { Uuid::new_v4() })
+        .collect();
+    // Si falla una escritura o el alta en la base de datos, se borran los archivos ya escritos.
+    let remove_written = |dir: String, written: Vec<Uuid>| {
+        move || {
+            for image_id in &written {
+                remove_post_image_file(&dir, image_id);
+            }
+        }
+    };
+    for (i, (image_id, (bytes, ext))) in image_ids.iter().zip(&images).enumerate() {
+        if let Err(e) = write_post_image(&state.posts_images_dir, image_id, bytes, ext) {
+            remove_written(state.posts_images_dir.clone(), image_ids[..=i].to_vec())();
+            return Err(e);
+        }
+    }
+    let url = format!("/api/posts/{}/image", id);
+    let uc = CreatePostUseCase::new(
+        Arc::clone(&state.posts_repo),
+        Arc::clone(&state.hashtags_repo),
+        Arc::clone(&state.unit_of_work),
+    );
+    let undo_images = Box::new(remove_written(state.posts_images_dir.clone(), image_ids.clone()));
     let item = uc
         .execute_with_id(
             id,
             body.description.as_deref(),
             Some(&url),
             user_id,
-            body.theme_of_the_day_id.trim(),
+            &mmdd,
+            Some(theme_date),
+            body.evento_id,
+            &image_ids,
+            undo_images,
         )
         .await?;
     Ok(Json(PostResponse::from(item)))
 }
 
-/// Sirve la imagen de un post (público).
+/// Sirve la primera imagen de un post (público).
 #[utoipa::path(
     get,
     path = "/api/posts/{id}/image",
     tag = "posts",
     params(("id" = Uuid, Path, description = "UUID del post")),
     responses(
-        (status = 200, description = "Imagen del post", content_type = "image/*"),
+        (status = 200, description = "Primera imagen del post", content_type = "image/*"),
         (status = 404, description = "Imagen no encontrada"),
     ),
 )]
@@ -246,36 +525,114 @@ pub async fn get_post_image(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let dir = resolve_posts_dir(&state.posts_images_dir);
-    let dir = dir.canonicalize().unwrap_or_else(|_| dir);
-    for ext in ["png", "jpg", "jpeg"] {
-        let path = dir.join(format!("{}.{}", id, ext));
-        if path.exists() {
-            let bytes = std::fs::read(&path)
-                .map_err(|e| ApiError(crate::domain::DomainError::Repository(anyhow::Error::from(e))))?;
-            let content_type = if ext == "png" {
-                "image/png"
-            } else {
-                "image/jpeg"
-            };
-            return Ok((
-                StatusCode::OK,
-                [
-                    (header::CONTENT_TYPE, content_type),
-                    (header::CACHE_CONTROL, "public, max-age=86400"),
-                ],
-                Body::from(bytes),
-            ));
+    let uc = GetPostByIdUseCase::new(Arc::clone(&state.posts_repo));
+    let post = uc.execute(id).await?;
+    let image_id = post.images.first().map(|i| i.id).unwrap_or(post.id);
+    post_image_file_response(&state.posts_images_dir, &image_id)
+}
+
+/// Sirve una imagen del carrusel de un post (público).
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/images/{image_id}",
+    tag = "posts",
+    params(
+        ("id" = Uuid, Path, description = "UUID del post"),
+        ("image_id" = Uuid, Path, description = "UUID de la imagen"),
+    ),
+    responses(
+        (status = 200, description = "Imagen del post", content_type = "image/*"),
+        (status = 404, description = "Imagen no encontrada"),
+    ),
+)]
+pub async fn get_post_image_by_id(
+    State(state): State<AppState>,
+    Path((id, image_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, ApiError> {
+    let uc = GetPostByIdUseCase::new(Arc::clone(&state.posts_repo));
+    let post = uc.execute(id).await?;
+    if !post.images.iter().any(|i| i.id == image_id) {
+        return Err(ApiError(crate::domain::DomainError::NotFound(format!(
+            "Imagen no encontrada: {}",
+            image_id
+        ))));
+    }
+    post_image_file_response(&state.posts_images_dir, &image_id)
+}
+
+/// Edita un post: descripción, tema y (opcional) imagen en base64, que reemplaza la primera del carrusel.
+/// Solo el autor o un admin (ADMIN_EMAILS). Si cambia la descripción, se re-sincronizan sus `#hashtags`.
+#[utoipa::path(
+    put,
+    path = "/api/posts/{id}",
+    tag = "posts",
+    security(("bearer_auth" = [])),
+    params(("id" = Uuid, Path, description = "UUID del post")),
+    request_body = UpdatePostRequest,
+    responses(
+        (status = 200, description = "Post actualizado", body = PostResponse),
+        (status = 400, description = "Imagen base64 inválida o theme_of_the_day_id no es una fecha válida", body = ErrorResponse),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 403, description = "No es el autor del post ni administrador", body = ErrorResponse),
+        (status = 404, description = "Post no encontrado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn update_post(
+    auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<UpdatePostRequest>,
+) -> Result<Json<PostResponse>, ApiError> {
+    let actor = actor_from_auth(&state, &auth.0).await?;
+    let uc = UpdatePostUseCase::new(
+        Arc::clone(&state.posts_repo),
+        Arc::clone(&state.hashtags_repo),
+        Arc::clone(&state.unit_of_work),
+    );
+    // Permisos antes de tocar la imagen en disco.
+    let post = uc.authorize(id, &actor).await?;
+    let theme = match body.theme_of_the_day_id.as_deref() {
+        Some(key) => {
+            let posted_at = post.created_at.unwrap_or_else(chrono::Utc::now);
+            Some(resolve_post_theme(&state, key, post.user_id, posted_at).await?)
         }
+        None => None,
+    };
+    // La imagen nueva se escribe aparte y solo sustituye a la actual cuando la edición se confirma.
+    let image_id = post.images.first().map(|i| i.id).unwrap_or(id);
+    let staged = match body.image_base64.as_deref().map(str::trim) {
+        Some(b64) if !b64.is_empty() => {
+            let (bytes, ext) = decode_post_image_base64(b64)?;
+            Some((stage_post_image(&state.posts_images_dir, &image_id, &bytes, ext)?, ext))
+        }
+        _ => None,
+    };
+    let url = staged.as_ref().map(|_| format!("/api/posts/{}/image", id));
+    let undo_image = staged.as_ref().map(|(path, _)| {
+        let path = path.clone();
+        Box::new(move || {
+            let _ = std::fs::remove_file(&path);
+        }) as crate::domain::Compensation
+    });
+    let item = uc
+        .execute(
+            id,
+            &actor,
+            body.description.as_deref(),
+            url.as_deref(),
+            theme.as_ref().map(|(mmdd, date)| (mmdd.as_str(), Some(*date))),
+            undo_image,
+        )
+        .await?;
+    if let Some((path, ext)) = &staged {
+        replace_post_image(&state.posts_images_dir, &image_id, path, ext)?;
     }
-    Err(ApiError(crate::domain::DomainError::NotFound(format!(
-        "Imagen no encontrada para el post {} (directorio: {})",
-        id,
-        dir.display()
-    ))))
+    let mut items = mark_liked_by_me(&state, &auth.0, vec![item]).await?;
+    Ok(Json(PostResponse::from(items.remove(0))))
 }
 
-/// Elimina un post.
+/// Elimina un post. Solo el autor o un admin (ADMIN_EMAILS).
 #[utoipa::path(
     delete,
     path = "/api/posts/{id}",
@@ -285,16 +642,396 @@ pub async fn get_post_image(
     responses(
         (status = 204, description = "Post eliminado"),
         (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 403, description = "No es el autor del post ni administrador", body = ErrorResponse),
         (status = 404, description = "Post no encontrado", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
 )]
 pub async fn delete_post(
-    _auth: crate::api::auth::BearerAuth,
+    auth: crate::api::auth::BearerAuth,
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
 ) -> Result<axum::http::StatusCode, ApiError> {
+    let actor = actor_from_auth(&state, &auth.0).await?;
     let uc = DeletePostUseCase::new(Arc::clone(&state.posts_repo));
-    uc.execute(id).await?;
+    let post = uc.execute(id, &actor).await?;
+    for image in &post.images {
+        remove_post_image_file(&state.posts_images_dir, &image.id);
+    }
     Ok(axum::http::StatusCode::NO_CONTENT)
 }
+
+/// Vota un post del reto diario (un voto por usuario; votar de nuevo no suma). Se rechaza si el día ya cerró
+/// o si el post es del propio usuario.
+#[utoipa::path(
+    post,
+    path = "/api/posts/{id}/votes",
+    tag = "posts",
+    security(("bearer_auth" = [])),
+    params(("id" = Uuid, Path, description = "UUID del post")),
+    responses(
+        (status = 200, description = "Voto registrado", body = PostVoteResponse),
+        (status = 400, description = "La votación de ese día está cerrada o el post es propio", body = ErrorResponse),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 404, description = "Post no encontrado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn vote_post(
+    auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<PostVoteResponse>, ApiError> {
+    let user_id = user_id_from_auth(&state, &auth.0).await?;
+    let uc = VotePostUseCase::new(Arc::clone(&state.posts_repo));
+    let post = uc.execute(id, user_id).await?;
+    Ok(Json(PostVoteResponse {
+        post_id: post.id,
+        votes_count: post.votes_count,
+    }))
+}
+
+/// Retira el voto del usuario autenticado (solo mientras la votación siga abierta).
+#[utoipa::path(
+    delete,
+    path = "/api/posts/{id}/votes",
+    tag = "posts",
+    security(("bearer_auth" = [])),
+    params(("id" = Uuid, Path, description = "UUID del post")),
+    responses(
+        (status = 204, description = "Voto retirado"),
+        (status = 400, description = "La votación de ese día está cerrada", body = ErrorResponse),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 404, description = "Post no encontrado o sin voto del usuario", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn remove_post_vote(
+    auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let user_id = user_id_from_auth(&state, &auth.0).await?;
+    let uc = RemovePostVoteUseCase::new(Arc::clone(&state.posts_repo));
+    uc.execute(id, user_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Ranking de votos de un tema del día: `YYYY-MM-DD` o MMdd (instancia más reciente hasta hoy).
+/// Cuando el día termina en todas las zonas horarias se fija el ganador y se incluye en la respuesta.
+#[utoipa::path(
+    get,
+    path = "/api/theme-of-the-day/{id}/leaderboard",
+    tag = "posts",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = String, Path, description = "Id del tema (YYYY-MM-DD o MMdd)"),
+        LeaderboardQuery,
+    ),
+    responses(
+        (status = 200, description = "Ranking del tema (items, closed, winner)", body = ThemeLeaderboardResponse),
+        (status = 400, description = "Id inválido", body = ErrorResponse),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 404, description = "Tema no encontrado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn get_theme_of_the_day_leaderboard(
+    auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(q): Query<LeaderboardQuery>,
+) -> Result<Json<ThemeLeaderboardResponse>, ApiError> {
+    let theme = GetThemeOfTheDayByIdUseCase::new(Arc::clone(&state.theme_of_the_day_repo))
+        .execute(&id)
+        .await?;
+    let limit = q.limit.unwrap_or(10).clamp(1, 100);
+    let uc = GetThemeLeaderboardUseCase::new(Arc::clone(&state.posts_repo));
+    let (items, closed, winner) = uc.execute(theme.date, limit).await?;
+    let (ranks, posts): (Vec<u32>, Vec<Post>) = items.into_iter().map(|e| (e.rank, e.post)).unzip();
+    let posts = mark_liked_by_me(&state, &auth.0, posts).await?;
+    Ok(Json(ThemeLeaderboardResponse {
+        theme_date: theme.date,
+        closed,
+        closes_at: crate::domain::ThemeOfTheDay::closes_at(theme.date),
+        winner: winner.map(DailyWinnerResponse::from),
+        items: ranks
+            .into_iter()
+            .zip(posts)
+            .map(|(rank, post)| LeaderboardEntryResponse {
+                rank,
+                post: PostResponse::from(post),
+            })
+            .collect(),
+    }))
+}
+
+/// Da like a un post (uno por usuario; repetirlo no suma).
+#[utoipa::path(
+    post,
+    path = "/api/posts/{id}/likes",
+    tag = "posts",
+    security(("bearer_auth" = [])),
+    params(("id" = Uuid, Path, description = "UUID del post")),
+    responses(
+        (status = 200, description = "Like registrado", body = PostLikeResponse),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 404, description = "Post no encontrado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn like_post(
+    auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<PostLikeResponse>, ApiError> {
+    let user_id = user_id_from_auth(&state, &auth.0).await?;
+    let uc = LikePostUseCase::new(Arc::clone(&state.posts_repo));
+    let post = uc.execute(id, user_id).await?;
+    Ok(Json(PostLikeResponse {
+        post_id: post.id,
+        likes_count: post.likes_count,
+        liked_by_me: post.liked_by_me,
+    }))
+}
+
+/// Quita el like del usuario autenticado.
+#[utoipa::path(
+    delete,
+    path = "/api/posts/{id}/likes",
+    tag = "posts",
+    security(("bearer_auth" = [])),
+    params(("id" = Uuid, Path, description = "UUID del post")),
+    responses(
+        (status = 204, description = "Like quitado"),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 404, description = "Post no encontrado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn unlike_post(
+    auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let user_id = user_id_from_auth(&state, &auth.0).await?;
+    let uc = UnlikePostUseCase::new(Arc::clone(&state.posts_repo));
+    uc.execute(id, user_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Comentarios de un post en hilos: comentarios de primer nivel con sus respuestas anidadas.
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/comments",
+    tag = "posts",
+    security(("bearer_auth" = [])),
+    params(("id" = Uuid, Path, description = "UUID del post")),
+    responses(
+        (status = 200, description = "Hilos de comentarios (más antiguos primero)", body = [PostCommentResponse]),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 404, description = "Post no encontrado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn list_post_comments(
+    _auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<PostCommentResponse>>, ApiError> {
+    let uc = GetPostCommentsUseCase::new(Arc::clone(&state.posts_repo));
+    let threads = uc.execute(id).await?;
+    Ok(Json(
+        threads.into_iter().map(PostCommentResponse::from).collect(),
+    ))
+}
+
+/// Comenta un post o responde a un comentario (parent_id) como el usuario autenticado.
+#[utoipa::path(
+    post,
+    path = "/api/posts/{id}/comments",
+    tag = "posts",
+    security(("bearer_auth" = [])),
+    params(("id" = Uuid, Path, description = "UUID del post")),
+    request_body = CreatePostCommentRequest,
+    responses(
+        (status = 200, description = "Comentario creado", body = PostCommentResponse),
+        (status = 400, description = "body vacío o demasiado largo, o parent_id de otro post", body = ErrorResponse),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 404, description = "Post no encontrado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn create_post_comment(
+    auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<CreatePostCommentRequest>,
+) -> Result<Json<PostCommentResponse>, ApiError> {
+    let user_id = user_id_from_auth(&state, &auth.0).await?;
+    let uc = CreatePostCommentUseCase::new(Arc::clone(&state.posts_repo));
+    let comment = uc.execute(id, user_id, body.parent_id, &body.body).await?;
+    Ok(Json(PostCommentResponse::from(comment)))
+}
+
+/// Edita un comentario propio (solo el autor).
+#[utoipa::path(
+    put,
+    path = "/api/posts/{id}/comments/{comment_id}",
+    tag = "posts",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "UUID del post"),
+        ("comment_id" = Uuid, Path, description = "UUID del comentario"),
+    ),
+    request_body = UpdatePostCommentRequest,
+    responses(
+        (status = 200, description = "Comentario actualizado", body = PostCommentResponse),
+        (status = 400, description = "body vacío o demasiado largo", body = ErrorResponse),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 403, description = "El comentario es de otro usuario", body = ErrorResponse),
+        (status = 404, description = "Comentario no encontrado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn update_post_comment(
+    auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Path((id, comment_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<UpdatePostCommentRequest>,
+) -> Result<Json<PostCommentResponse>, ApiError> {
+    let user_id = user_id_from_auth(&state, &auth.0).await?;
+    let uc = UpdatePostCommentUseCase::new(Arc::clone(&state.posts_repo));
+    let comment = uc.execute(id, comment_id, user_id, &body.body).await?;
+    Ok(Json(PostCommentResponse::from(comment)))
+}
+
+/// Elimina un comentario (el autor o un admin). Si tiene respuestas se conserva vacío (deleted) para no romper el hilo.
+#[utoipa::path(
+    delete,
+    path = "/api/posts/{id}/comments/{comment_id}",
+    tag = "posts",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "UUID del post"),
+        ("comment_id" = Uuid, Path, description = "UUID del comentario"),
+    ),
+    responses(
+        (status = 204, description = "Comentario eliminado"),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 403, description = "No es el autor del comentario ni administrador", body = ErrorResponse),
+        (status = 404, description = "Comentario no encontrado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn delete_post_comment(
+    auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Path((id, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    let actor = actor_from_auth(&state, &auth.0).await?;
+    let uc = DeletePostCommentUseCase::new(Arc::clone(&state.posts_repo));
+    uc.execute(id, comment_id, &actor).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Añade una imagen al final del carrusel de un post (autor o admin).
+#[utoipa::path(
+    post,
+    path = "/api/posts/{id}/images",
+    tag = "posts",
+    security(("bearer_auth" = [])),
+    params(("id" = Uuid, Path, description = "UUID del post")),
+    request_body = AddPostImageRequest,
+    responses(
+        (status = 200, description = "Post con la imagen añadida", body = PostResponse),
+        (status = 400, description = "Base64 inválido o el post ya tiene el máximo de imágenes", body = ErrorResponse),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 403, description = "No es el autor del post ni administrador", body = ErrorResponse),
+        (status = 404, description = "Post no encontrado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn add_post_image(
+    auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<AddPostImageRequest>,
+) -> Result<Json<PostResponse>, ApiError> {
+    let actor = actor_from_auth(&state, &auth.0).await?;
+    let (bytes, ext) = decode_post_image_base64(&body.image_base64)?;
+    let uc = AddPostImageUseCase::new(Arc::clone(&state.posts_repo));
+    uc.authorize(id, &actor).await?;
+    let image_id = Uuid::new_v4();
+    write_post_image(&state.posts_images_dir, &image_id, &bytes, ext)?;
+    let item = match uc.execute(id, &actor, image_id).await {
+        Ok(item) => item,
+        Err(e) => {
+            remove_post_image_file(&state.posts_images_dir, &image_id);
+            return Err(ApiError(e));
+        }
+    };
+    let mut items = mark_liked_by_me(&state, &auth.0, vec![item]).await?;
+    Ok(Json(PostResponse::from(items.remove(0))))
+}
+
+/// Reordena el carrusel de un post: `image_ids` con todas sus imágenes en el orden deseado (autor o admin).
+#[utoipa::path(
+    put,
+    path = "/api/posts/{id}/images/order",
+    tag = "posts",
+    security(("bearer_auth" = [])),
+    params(("id" = Uuid, Path, description = "UUID del post")),
+    request_body = ReorderPostImagesRequest,
+    responses(
+        (status = 200, description = "Post con las imágenes reordenadas", body = PostResponse),
+        (status = 400, description = "image_ids no contiene exactamente las imágenes del post", body = ErrorResponse),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 403, description = "No es el autor del post ni administrador", body = ErrorResponse),
+        (status = 404, description = "Post no encontrado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn reorder_post_images(
+    auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<ReorderPostImagesRequest>,
+) -> Result<Json<PostResponse>, ApiError> {
+    let actor = actor_from_auth(&state, &auth.0).await?;
+    let uc = ReorderPostImagesUseCase::new(Arc::clone(&state.posts_repo));
+    let item = uc.execute(id, &actor, &body.image_ids).await?;
+    let mut items = mark_liked_by_me(&state, &auth.0, vec![item]).await?;
+    Ok(Json(PostResponse::from(items.remove(0))))
+}
+
+/// Quita una imagen del carrusel (autor o admin). Un post conserva siempre al menos una imagen.
+#[utoipa::path(
+    delete,
+    path = "/api/posts/{id}/images/{image_id}",
+    tag = "posts",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "UUID del post"),
+        ("image_id" = Uuid, Path, description = "UUID de la imagen"),
+    ),
+    responses(
+        (status = 204, description = "Imagen quitada"),
+        (status = 400, description = "Es la única imagen del post", body = ErrorResponse),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 403, description = "No es el autor del post ni administrador", body = ErrorResponse),
+        (status = 404, description = "Post o imagen no encontrados", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn remove_post_image(
+    auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Path((id, image_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    let actor = actor_from_auth(&state, &auth.0).await?;
+    let uc = RemovePostImageUseCase::new(Arc::clone(&state.posts_repo));
+    uc.execute(id, &actor, image_id).await?;
+    remove_post_image_file(&state.posts_images_dir, &image_id);
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,7 +1,7 @@
 // Handlers de Sesiones. user_id desde JWT para AddFavoritesToSesion y CreateSesionFromFavorites.
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     Json,
 };
 use std::sync::Arc;
@@ -10,26 +10,68 @@ use uuid::Uuid;
 use crate::api::{
     dto::{
         AddPosesToSesionRequest, CreateSesionFromFavoritesRequest, CreateSesionRequest,
-        ErrorResponse, PoseResponse, SesionResponse, UpdateSesionCoverRequest,
+        ErrorResponse, ListResponse, Paginated, PoseResponse, SesionResponse,
+        UpdateSesionCoverRequest,
     },
+    fields::{FieldSet, FieldsParams, Sparse},
+    handlers::poses::PoseView,
+    list_query::{ListParams, PaginationQuery},
     state::AppState,
     ApiError,
 };
 use crate::application::{
     AddFavoritesToSesionUseCase, AddPosesToSesionUseCase, CreateSesionFromFavoritesUseCase,
-    CreateSesionUseCase, DeleteSesionUseCase, GetPosesBySesionUseCase, GetSesionByIdUseCase,
-    GetSesionesUseCase, RemovePoseFromSesionUseCase, UpdateSesionCoverUseCase,
+    CreateSesionUseCase, DeleteSesionUseCase, GetPosesBySesionPaginatedUseCase,
+    GetSesionByIdUseCase, GetSesionRelationsUseCase, GetSesionesPaginatedUseCase, RemovePoseFromSesionUseCase,
+    UpdateSesionCoverUseCase,
 };
 use crate::api::auth::{user_id_from_auth, BearerAuth};
+use crate::domain::{Sesion, SesionInclude};
 
-/// Lista todas las sesiones.
+/// `?fields=` e `?include=` de las respuestas de sesiones (validados antes de consultar).
+struct SesionView {
+    fields: FieldSet,
+    include: SesionInclude,
+}
+
+impl SesionView {
+    fn parse(params: &FieldsParams) -> Result<Self, ApiError> {
+        Ok(SesionView {
+            fields: params.fields(SesionResponse::FIELDS)?,
+            include: SesionInclude::parse(params.include.as_deref())?,
+        })
+    }
+
+    /// Convierte el lote en respuesta; `include=poses` se resuelve con una sola consulta.
+    async fn render(
+        &self,
+        state: &AppState,
+        sesiones: Vec<Sesion>,
+    ) -> Result<Vec<Sparse<SesionResponse>>, ApiError> {
+        let ids: Vec<Uuid> = sesiones.iter().map(|s| s.id).collect();
+        let uc = GetSesionRelationsUseCase::new(Arc::clone(&state.sesiones_repo));
+        let mut relations = uc.execute(&ids, self.include).await?;
+        Ok(sesiones
+            .into_iter()
+            .map(|s| self.fields.apply(SesionResponse::with_relations(s, &mut relations)))
+            .collect())
+    }
+}
+
+/// Lista las sesiones. Con `page` o `limit` devuelve la respuesta paginada (items, count, page, limit,
+/// total_pages); si no, una lista con las primeras 100.
+///
+/// `sort`: created_at (desc por defecto), name. `filter`: name.
+/// `fields`: id, name, created_at, cover_url, poses. `include`: poses.
 #[utoipa::path(
     get,
     path = "/api/sesiones",
     tag = "sesiones",
     security(("bearer_auth" = [])),
+    params(PaginationQuery, ListParams, FieldsParams),
     responses(
-        (status = 200, description = "Lista de sesiones", body = [SesionResponse]),
+        (status = 200, description = "Lista de sesiones (paginada con page/limit)", body = ListResponse<SesionResponse>),
+        (status = 400, description = "sort, filter, fields o include inválido", body = ErrorResponse),
         (status = 401, description = "No autorizado", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
@@ -37,21 +79,36 @@ use crate::api::auth::{user_id_from_auth, BearerAuth};
 pub async fn list_sesiones(
     _auth: BearerAuth,
     State(state): State<AppState>,
-) -> Result<Json<Vec<SesionResponse>>, ApiError> {
-    let uc = GetSesionesUseCase::new(Arc::clone(&state.sesiones_repo));
-    let items = uc.execute().await?;
-    Ok(Json(items.into_iter().map(SesionResponse::from).collect()))
+    Query(p): Query<PaginationQuery>,
+    Query(l): Query<ListParams>,
+    Query(v): Query<FieldsParams>,
+) -> Result<Json<ListResponse<Sparse<SesionResponse>>>, ApiError> {
+    let view = SesionView::parse(&v)?;
+    let list = l.to_query(&Sesion::LIST_SPEC)?;
+    let (page, limit) = p.page_and_limit();
+    let uc = GetSesionesPaginatedUseCase::new(Arc::clone(&state.sesiones_repo));
+    let (items, count) = uc.execute(&list, page, limit).await?;
+    let items = view.render(&state, items).await?;
+    if !p.is_requested() {
+        return Ok(Json(ListResponse::List(items)));
+    }
+    Ok(Json(ListResponse::Paginated(Paginated::page(
+        items, count, page, limit,
+    ))))
 }
 
-/// Obtiene una sesión por ID.
+/// Obtiene una sesión por ID. Con `?include=poses` devuelve también sus poses en el orden en que se añadieron.
+///
+/// `fields`: id, name, created_at, cover_url, poses.
 #[utoipa::path(
     get,
     path = "/api/sesiones/{id}",
     tag = "sesiones",
     security(("bearer_auth" = [])),
-    params(("id" = Uuid, Path, description = "UUID de la sesión")),
+    params(("id" = Uuid, Path, description = "UUID de la sesión"), FieldsParams),
     responses(
         (status = 200, description = "Sesión encontrada", body = SesionResponse),
+        (status = 400, description = "fields o include inválido", body = ErrorResponse),
         (status = 401, description = "No autorizado", body = ErrorResponse),
         (status = 404, description = "Sesión no encontrada", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
@@ -61,7 +118,9 @@ pub async fn get_sesion(
     _auth: BearerAuth,
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-) -> Result<Json<SesionResponse>, ApiError> {
+    Query(v): Query<FieldsParams>,
+) -> Result<Json<Sparse<SesionResponse>>, ApiError> {
+    let view = SesionView::parse(&v)?;
     let uc = GetSesionByIdUseCase::new(Arc::clone(&state.sesiones_repo));
     let sesion = uc.execute(id).await?;
     let sesion = sesion.ok_or_else(|| {
@@ -69,30 +128,46 @@ pub async fn get_sesion(
             "Sesión no encontrada".to_string(),
         ))
     })?;
-    Ok(Json(SesionResponse::from(sesion)))
+    let mut items = view.render(&state, vec![sesion]).await?;
+    Ok(Json(items.remove(0)))
 }
 
-/// Lista las poses de una sesión.
+/// Lista las poses de una sesión en el orden en que se añadieron. Con `page` o `limit` devuelve la
+/// respuesta paginada; si no, una lista con las primeras 100.
+///
+/// `fields`: id, url, title, description, people_count, difficulty, orientation, source_credit, created_at,
+/// hashtags, favorite, sesiones. `include`: hashtags, favorite, sesiones.
 #[utoipa::path(
     get,
     path = "/api/sesiones/{id}/poses",
     tag = "sesiones",
     security(("bearer_auth" = [])),
-    params(("id" = Uuid, Path, description = "UUID de la sesión")),
+    params(("id" = Uuid, Path, description = "UUID de la sesión"), PaginationQuery, FieldsParams),
     responses(
-        (status = 200, description = "Poses de la sesión", body = [PoseResponse]),
+        (status = 200, description = "Poses de la sesión (paginada con page/limit)", body = ListResponse<PoseResponse>),
+        (status = 400, description = "fields o include inválido", body = ErrorResponse),
         (status = 401, description = "No autorizado", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
 )]
 pub async fn get_poses_by_sesion(
-    _auth: BearerAuth,
+    auth: BearerAuth,
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-) -> Result<Json<Vec<PoseResponse>>, ApiError> {
-    let uc = GetPosesBySesionUseCase::new(Arc::clone(&state.sesiones_repo));
-    let poses = uc.execute(id).await?;
-    Ok(Json(poses.into_iter().map(PoseResponse::from).collect()))
+    Query(p): Query<PaginationQuery>,
+    Query(v): Query<FieldsParams>,
+) -> Result<Json<ListResponse<Sparse<PoseResponse>>>, ApiError> {
+    let view = PoseView::parse(&v)?;
+    let (page, limit) = p.page_and_limit();
+    let uc = GetPosesBySesionPaginatedUseCase::new(Arc::clone(&state.sesiones_repo));
+    let (poses, count) = uc.execute(id, page, limit).await?;
+    let items = view.render(&state, &auth, poses).await?;
+    if !p.is_requested() {
+        return Ok(Json(ListResponse::List(items)));
+    }
+    Ok(Json(ListResponse::Paginated(Paginated::page(
+        items, count, page, limit,
+    ))))
 }
 
 /// Crea una sesión nueva (vacía).
@@ -140,6 +215,7 @@ pub async fn create_sesion_from_favorites(
     let uc = CreateSesionFromFavoritesUseCase::new(
         Arc::clone(&state.sesiones_repo),
         Arc::clone(&state.favorites_repo),
+        Arc::clone(&state.unit_of_work),
     );
     let sesion = uc.execute(user_id, &body.name).await?;
     Ok(Json(SesionResponse::from(sesion)))
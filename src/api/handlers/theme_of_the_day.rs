@@ -1,6 +1,6 @@
 use axum::{
     body::Body,
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::{header, StatusCode},
     response::IntoResponse,
     Json,
@@ -8,20 +8,40 @@ use axum::{
 use base64::Engine;
 use std::path::Path as StdPath;
 use std::sync::Arc;
+use uuid::Uuid;
 
+use crate::api::auth::{user_id_from_auth, CalendarFeedAuth, CalendarTokenQuery};
 use crate::api::{
     dto::{
-        CreateThemeOfTheDayRequest, ErrorResponse, ThemeOfTheDayResponse,
+        AddThemeOfTheDayToPoolRequest, CreateThemeOfTheDayRequest, ErrorResponse,
+        ThemeOfTheDayPoolEntryResponse, ThemeOfTheDayResponse, ThemeOfTheDayTodayResponse,
         UpdateThemeOfTheDayRequest,
     },
+    ical::{self, IcsEvent},
     state::AppState,
     ApiError,
 };
+use crate::domain::ThemeOfTheDayKey;
 use crate::application::{
-    CreateThemeOfTheDayUseCase, DeleteThemeOfTheDayUseCase, GetThemeOfTheDayAllUseCase,
-    GetThemeOfTheDayByIdUseCase, GetThemeOfTheDayTodayUseCase, UpdateThemeOfTheDayUseCase,
+    AddThemeOfTheDayToPoolUseCase, CreateThemeOfTheDayUseCase, DeleteThemeOfTheDayUseCase,
+    GetThemeOfTheDayAllUseCase, GetThemeOfTheDayArchiveUseCase, GetThemeOfTheDayByIdUseCase,
+    GetThemeOfTheDayPoolUseCase, GetThemeOfTheDayTodayUseCase, RemoveThemeOfTheDayFromPoolUseCase,
+    ResolveTimeZoneUseCase, UpdateThemeOfTheDayUseCase,
 };
 
+#[derive(Debug, serde::Deserialize, utoipa::IntoParams)]
+pub struct TimeZoneQuery {
+    /// Zona horaria del cliente: nombre IANA (ej. `America/Bogota`) o desfase UTC (ej. `-05:00`).
+    /// Si se omite se usa la del perfil del usuario y, si no tiene, la del servidor.
+    pub tz: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize, utoipa::IntoParams)]
+pub struct ArchiveQuery {
+    /// Año del archivo (por defecto, el año actual).
+    pub year: Option<i32>,
+}
+
 /// Decodifica imagen base64 (acepta prefijo data:image/xxx;base64,) y la guarda en dir/{id}.{ext}.
 /// Devuelve la URL que debe guardarse en BD: /api/theme-of-the-day/{id}/image.
 fn save_theme_image_base64(
@@ -29,6 +49,22 @@ fn save_theme_image_base64(
     id: &str,
     image_base64: &str,
 ) -> Result<String, ApiError> {
+    write_theme_image_base64(dir, id, image_base64)?;
+    Ok(format!("/api/theme-of-the-day/{}/image", id))
+}
+
+/// Decodifica imagen base64 y la guarda en dir/{file_stem}.{ext}.
+fn write_theme_image_base64(
+    dir: &str,
+    file_stem: &str,
+    image_base64: &str,
+) -> Result<(), ApiError> {
+    let (bytes, ext) = decode_theme_image_base64(image_base64)?;
+    write_theme_image(dir, file_stem, &bytes, ext)
+}
+
+/// Decodifica imagen base64 (acepta prefijo data:image/xxx;base64,). Devuelve los bytes y la extensión.
+fn decode_theme_image_base64(image_base64: &str) -> Result<(Vec<u8>, &'static str), ApiError> {
     let (payload, ext) = if let Some(rest) = image_base64.strip_prefix("data:") {
         let (mime, b64) = rest
             .split_once(";base64,")
@@ -49,47 +85,66 @@ fn save_theme_image_base64(
     if bytes.is_empty() {
         return Err(ApiError(crate::domain::DomainError::Validation("imagen vacía".to_string())));
     }
+    Ok((bytes, ext))
+}
 
+/// Guarda la imagen en dir/{file_stem}.{ext}.
+fn write_theme_image(dir: &str, file_stem: &str, bytes: &[u8], ext: &str) -> Result<(), ApiError> {
     std::fs::create_dir_all(dir).map_err(|e| ApiError(crate::domain::DomainError::Repository(anyhow::Error::from(e))))?;
-    let filename = format!("{}.{}", id, ext);
+    let filename = format!("{}.{}", file_stem, ext);
     let path = StdPath::new(dir).join(&filename);
-    std::fs::write(&path, &bytes).map_err(|e| ApiError(crate::domain::DomainError::Repository(anyhow::Error::from(e))))?;
-
-    Ok(format!("/api/theme-of-the-day/{}/image", id))
+    std::fs::write(&path, bytes).map_err(|e| ApiError(crate::domain::DomainError::Repository(anyhow::Error::from(e))))?;
+    Ok(())
 }
 
-/// Obtiene el tema del día de hoy (id = MMdd de la fecha actual). Equivalente a Kotlin getThemeOfTheDay().
+/// Obtiene el tema del día de hoy (id = fecha local `YYYY-MM-DD`). Equivalente a Kotlin getThemeOfTheDay().
+/// La fecha local se calcula con `?tz=`, la zona del perfil o la del servidor, en ese orden.
+/// Los días sin tema programado reciben el fallback configurado (THEME_FALLBACK) desde una tarea periódica;
+/// ese tema se devuelve con auto_selected.
 #[utoipa::path(
     get,
     path = "/api/theme-of-the-day/today",
     tag = "theme_of_the_day",
     security(("bearer_auth" = [])),
+    params(TimeZoneQuery),
     responses(
-        (status = 200, description = "Tema del día de hoy", body = ThemeOfTheDayResponse),
+        (status = 200, description = "Tema del día de hoy (incluye local_date, time_zone y auto_selected)", body = ThemeOfTheDayTodayResponse),
+        (status = 400, description = "Zona horaria inválida", body = ErrorResponse),
         (status = 401, description = "No autorizado", body = ErrorResponse),
-        (status = 404, description = "No hay tema definido para hoy", body = ErrorResponse),
+        (status = 404, description = "No hay tema para hoy (ni programado ni elegido por el fallback)", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
 )]
 pub async fn get_theme_of_the_day_today(
-    _auth: crate::api::auth::BearerAuth,
+    auth: crate::api::auth::BearerAuth,
     State(state): State<AppState>,
-) -> Result<Json<ThemeOfTheDayResponse>, ApiError> {
+    Query(q): Query<TimeZoneQuery>,
+) -> Result<Json<ThemeOfTheDayTodayResponse>, ApiError> {
+    let user_id = user_id_from_auth(&state, &auth.0).await?;
+    let time_zone = ResolveTimeZoneUseCase::new(Arc::clone(&state.usuarios_repo))
+        .execute(user_id, q.tz.as_deref(), state.default_time_zone)
+        .await?;
     let uc = GetThemeOfTheDayTodayUseCase::new(Arc::clone(&state.theme_of_the_day_repo));
-    let item = uc.execute().await?;
-    Ok(Json(ThemeOfTheDayResponse::from(item)))
+    let (item, local_date) = uc.execute(time_zone).await?;
+    Ok(Json(ThemeOfTheDayTodayResponse {
+        theme: ThemeOfTheDayResponse::from(item),
+        local_date,
+        time_zone: time_zone.name(),
+    }))
 }
 
-/// Obtiene el tema del día para una fecha dada (MMdd). Misma respuesta que /today pero con el parámetro MesDía.
+/// Obtiene el tema del día para una fecha dada: `YYYY-MM-DD` (instancia exacta) o MMdd (instancia más
+/// reciente de ese día hasta hoy, o la próxima programada).
 #[utoipa::path(
     get,
     path = "/api/theme-of-the-day/date/{mmdd}",
     tag = "theme_of_the_day",
     security(("bearer_auth" = [])),
-    params(("mmdd" = String, Path, description = "Fecha en formato MMdd (mes y día, 4 caracteres)")),
+    params(("mmdd" = String, Path, description = "Fecha YYYY-MM-DD o MMdd (mes y día)")),
     responses(
         (status = 200, description = "Tema del día para la fecha indicada", body = ThemeOfTheDayResponse),
         (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 400, description = "Id inválido (no es una fecha YYYY-MM-DD ni MMdd)", body = ErrorResponse),
         (status = 404, description = "No hay tema definido para esa fecha", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
@@ -125,16 +180,85 @@ pub async fn list_theme_of_the_day(
     Ok(Json(items.into_iter().map(ThemeOfTheDayResponse::from).collect()))
 }
 
-/// Obtiene un tema del día por id (MMdd) (requiere Bearer token).
+/// Archivo de temas de un año (?year=2025), por fecha y con el número de posts de cada uno.
+#[utoipa::path(
+    get,
+    path = "/api/theme-of-the-day/archive",
+    tag = "theme_of_the_day",
+    security(("bearer_auth" = [])),
+    params(ArchiveQuery),
+    responses(
+        (status = 200, description = "Temas del año indicado", body = [ThemeOfTheDayResponse]),
+        (status = 400, description = "Año inválido", body = ErrorResponse),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn get_theme_of_the_day_archive(
+    _auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Query(q): Query<ArchiveQuery>,
+) -> Result<Json<Vec<ThemeOfTheDayResponse>>, ApiError> {
+    let year = q
+        .year
+        .unwrap_or_else(|| chrono::Datelike::year(&chrono::Utc::now().date_naive()));
+    let uc = GetThemeOfTheDayArchiveUseCase::new(Arc::clone(&state.theme_of_the_day_repo));
+    let items = uc.execute(year).await?;
+    Ok(Json(items.into_iter().map(ThemeOfTheDayResponse::from).collect()))
+}
+
+/// Feed iCalendar (RFC 5545) de los temas del día, cada instancia como evento de día completo en su fecha.
+/// Acepta Bearer token o `?token=` (feed personal) para que las apps de calendario puedan suscribirse.
+#[utoipa::path(
+    get,
+    path = "/api/theme-of-the-day/calendar.ics",
+    tag = "theme_of_the_day",
+    security(("bearer_auth" = [])),
+    params(CalendarTokenQuery),
+    responses(
+        (status = 200, description = "Feed iCalendar de temas del día", content_type = "text/calendar"),
+        (status = 401, description = "No autorizado / token de feed inválido", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn get_theme_of_the_day_calendar(
+    _auth: CalendarFeedAuth,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ApiError> {
+    let uc = GetThemeOfTheDayAllUseCase::new(Arc::clone(&state.theme_of_the_day_repo));
+    let items = uc.execute().await?;
+    let now = chrono::Utc::now();
+    let events: Vec<IcsEvent> = items
+        .into_iter()
+        .map(|t| IcsEvent {
+            uid: format!("theme-of-the-day-{}@danphoto", t.id),
+            summary: t.name,
+            date: t.date,
+            rrule: None,
+            location: None,
+            geo: None,
+            attach: state
+                .public_base_url
+                .as_deref()
+                .map(|base| ical::absolute_url(base, &t.url)),
+            dtstamp: now,
+        })
+        .collect();
+    let body = ical::render_calendar("DanPhoto - Tema del día", &events);
+    Ok(ical::calendar_response("theme-of-the-day.ics", body))
+}
+
+/// Obtiene un tema del día por id: fecha `YYYY-MM-DD` o MMdd (requiere Bearer token).
 #[utoipa::path(
     get,
     path = "/api/theme-of-the-day/{id}",
     tag = "theme_of_the_day",
     security(("bearer_auth" = [])),
-    params(("id" = String, Path, description = "Id del tema (MMdd, 4 caracteres)")),
+    params(("id" = String, Path, description = "Id del tema (YYYY-MM-DD, o MMdd para la instancia más reciente)")),
     responses(
         (status = 200, description = "Tema del día encontrado", body = ThemeOfTheDayResponse),
         (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 400, description = "Id inválido (no es una fecha YYYY-MM-DD ni MMdd)", body = ErrorResponse),
         (status = 404, description = "Tema no encontrado", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
@@ -149,7 +273,7 @@ pub async fn get_theme_of_the_day(
     Ok(Json(ThemeOfTheDayResponse::from(item)))
 }
 
-/// Crea un nuevo tema del día con imagen en base64 (requiere Bearer token).
+/// Programa un tema del día para una fecha (`YYYY-MM-DD`, o MMdd = próxima ocurrencia) con imagen en base64 (requiere Bearer token).
 #[utoipa::path(
     post,
     path = "/api/theme-of-the-day",
@@ -159,7 +283,7 @@ pub async fn get_theme_of_the_day(
     responses(
         (status = 200, description = "Tema del día creado", body = ThemeOfTheDayResponse),
         (status = 401, description = "No autorizado", body = ErrorResponse),
-        (status = 400, description = "Validación fallida (id debe ser 4 caracteres MMdd, base64 inválido)", body = ErrorResponse),
+        (status = 400, description = "Validación fallida (id no es una fecha válida, ya hay tema en esa fecha, base64 inválido)", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
 )]
@@ -168,13 +292,20 @@ pub async fn create_theme_of_the_day(
     State(state): State<AppState>,
     Json(body): Json<CreateThemeOfTheDayRequest>,
 ) -> Result<Json<ThemeOfTheDayResponse>, ApiError> {
-    let url = save_theme_image_base64(
-        &state.theme_of_the_day_images_dir,
-        &body.id,
-        &body.image_base64,
-    )?;
+    // Validar antes de usar la fecha como nombre de archivo.
+    let date = ThemeOfTheDayKey::parse(&body.id)?.schedule_date(chrono::Utc::now().date_naive());
+    let id = date.format("%Y-%m-%d").to_string();
+    let (bytes, ext) = decode_theme_image_base64(&body.image_base64)?;
+    // Primero el tema: si la fecha ya está ocupada falla aquí y no se sobrescribe la imagen existente.
+    let url = format!("/api/theme-of-the-day/{}/image", id);
     let uc = CreateThemeOfTheDayUseCase::new(Arc::clone(&state.theme_of_the_day_repo));
-    let item = uc.execute(&body.id, &body.name, &url).await?;
+    let item = uc.execute(date, &body.name, &url).await?;
+    if let Err(e) = write_theme_image(&state.theme_of_the_day_images_dir, &id, &bytes, ext) {
+        DeleteThemeOfTheDayUseCase::new(Arc::clone(&state.theme_of_the_day_repo))
+            .execute(date)
+            .await?;
+        return Err(e);
+    }
     Ok(Json(ThemeOfTheDayResponse::from(item)))
 }
 
@@ -184,11 +315,12 @@ pub async fn create_theme_of_the_day(
     path = "/api/theme-of-the-day/{id}",
     tag = "theme_of_the_day",
     security(("bearer_auth" = [])),
-    params(("id" = String, Path, description = "Id del tema (MMdd)")),
+    params(("id" = String, Path, description = "Id del tema (YYYY-MM-DD, o MMdd para la instancia más reciente)")),
     request_body = UpdateThemeOfTheDayRequest,
     responses(
         (status = 200, description = "Tema del día actualizado", body = ThemeOfTheDayResponse),
         (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 400, description = "Id inválido (no es una fecha YYYY-MM-DD ni MMdd)", body = ErrorResponse),
         (status = 404, description = "Tema no encontrado", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
@@ -199,6 +331,10 @@ pub async fn update_theme_of_the_day(
     Path(id): Path<String>,
     Json(body): Json<UpdateThemeOfTheDayRequest>,
 ) -> Result<Json<ThemeOfTheDayResponse>, ApiError> {
+    let theme = GetThemeOfTheDayByIdUseCase::new(Arc::clone(&state.theme_of_the_day_repo))
+        .execute(&id)
+        .await?;
+    let id = theme.id;
     let url = if let Some(ref img) = body.image_base64 {
         Some(save_theme_image_base64(
             &state.theme_of_the_day_images_dir,
@@ -210,7 +346,7 @@ pub async fn update_theme_of_the_day(
     };
     let uc = UpdateThemeOfTheDayUseCase::new(Arc::clone(&state.theme_of_the_day_repo));
     let item = uc
-        .execute(&id, body.name.as_deref(), url.as_deref())
+        .execute(theme.date, body.name.as_deref(), url.as_deref())
         .await?;
     Ok(Json(ThemeOfTheDayResponse::from(item)))
 }
@@ -220,9 +356,10 @@ pub async fn update_theme_of_the_day(
     get,
     path = "/api/theme-of-the-day/{id}/image",
     tag = "theme_of_the_day",
-    params(("id" = String, Path, description = "Id del tema (MMdd)")),
+    params(("id" = String, Path, description = "Id del tema (YYYY-MM-DD, o MMdd para la instancia más reciente)")),
     responses(
         (status = 200, description = "Imagen del tema", content_type = "image/*"),
+        (status = 400, description = "Id inválido (no es una fecha YYYY-MM-DD ni MMdd)", body = ErrorResponse),
         (status = 404, description = "Imagen no encontrada"),
     ),
 )]
@@ -230,6 +367,11 @@ pub async fn get_theme_of_the_day_image(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> Result<impl IntoResponse, ApiError> {
+    // Fecha (YYYY-MM-DD) o MMdd (imágenes anteriores a los temas por fecha).
+    let id = match ThemeOfTheDayKey::parse(&id)? {
+        ThemeOfTheDayKey::Date(date) => date.format("%Y-%m-%d").to_string(),
+        ThemeOfTheDayKey::MonthDay(mmdd) => mmdd.to_string(),
+    };
     let dir = StdPath::new(&state.theme_of_the_day_images_dir);
     for ext in ["png", "jpg", "jpeg"] {
         let path = dir.join(format!("{}.{}", id, ext));
@@ -260,10 +402,11 @@ pub async fn get_theme_of_the_day_image(
     path = "/api/theme-of-the-day/{id}",
     tag = "theme_of_the_day",
     security(("bearer_auth" = [])),
-    params(("id" = String, Path, description = "Id del tema (MMdd)")),
+    params(("id" = String, Path, description = "Id del tema (YYYY-MM-DD, o MMdd para la instancia más reciente)")),
     responses(
         (status = 204, description = "Tema del día eliminado"),
         (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 400, description = "Id inválido (no es una fecha YYYY-MM-DD ni MMdd)", body = ErrorResponse),
         (status = 404, description = "Tema no encontrado", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
     ),
@@ -273,7 +416,136 @@ pub async fn delete_theme_of_the_day(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> Result<axum::http::StatusCode, ApiError> {
+    let theme = GetThemeOfTheDayByIdUseCase::new(Arc::clone(&state.theme_of_the_day_repo))
+        .execute(&id)
+        .await?;
     let uc = DeleteThemeOfTheDayUseCase::new(Arc::clone(&state.theme_of_the_day_repo));
-    uc.execute(&id).await?;
+    uc.execute(theme.date).await?;
+    Ok(axum::http::StatusCode::NO_CONTENT)
+}
+
+/// Lista la bolsa de temas sin fecha usada por la rotación automática (requiere Bearer token).
+#[utoipa::path(
+    get,
+    path = "/api/theme-of-the-day/pool",
+    tag = "theme_of_the_day",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Temas de la bolsa, en orden de rotación", body = [ThemeOfTheDayPoolEntryResponse]),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn list_theme_of_the_day_pool(
+    _auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ThemeOfTheDayPoolEntryResponse>>, ApiError> {
+    let uc = GetThemeOfTheDayPoolUseCase::new(Arc::clone(&state.theme_of_the_day_repo));
+    let items = uc.execute().await?;
+    Ok(Json(
+        items
+            .into_iter()
+            .map(ThemeOfTheDayPoolEntryResponse::from)
+            .collect(),
+    ))
+}
+
+/// Añade un tema sin fecha (con imagen en base64) a la bolsa de rotación (requiere Bearer token).
+#[utoipa::path(
+    post,
+    path = "/api/theme-of-the-day/pool",
+    tag = "theme_of_the_day",
+    security(("bearer_auth" = [])),
+    request_body = AddThemeOfTheDayToPoolRequest,
+    responses(
+        (status = 200, description = "Tema añadido a la bolsa", body = ThemeOfTheDayPoolEntryResponse),
+        (status = 400, description = "Validación fallida (name vacío, base64 inválido)", body = ErrorResponse),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn add_theme_of_the_day_to_pool(
+    _auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Json(body): Json<AddThemeOfTheDayToPoolRequest>,
+) -> Result<Json<ThemeOfTheDayPoolEntryResponse>, ApiError> {
+    let id = Uuid::new_v4();
+    write_theme_image_base64(
+        &state.theme_of_the_day_images_dir,
+        &format!("pool-{}", id),
+        &body.image_base64,
+    )?;
+    let url = format!("/api/theme-of-the-day/pool/{}/image", id);
+    let uc = AddThemeOfTheDayToPoolUseCase::new(Arc::clone(&state.theme_of_the_day_repo));
+    let item = uc.execute_with_id(id, &body.name, &url).await?;
+    Ok(Json(ThemeOfTheDayPoolEntryResponse::from(item)))
+}
+
+/// Sirve la imagen de un tema de la bolsa (público).
+#[utoipa::path(
+    get,
+    path = "/api/theme-of-the-day/pool/{id}/image",
+    tag = "theme_of_the_day",
+    params(("id" = Uuid, Path, description = "UUID del tema en la bolsa")),
+    responses(
+        (status = 200, description = "Imagen del tema", content_type = "image/*"),
+        (status = 404, description = "Imagen no encontrada"),
+    ),
+)]
+pub async fn get_theme_of_the_day_pool_image(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let dir = StdPath::new(&state.theme_of_the_day_images_dir);
+    for ext in ["png", "jpg", "jpeg"] {
+        let path = dir.join(format!("pool-{}.{}", id, ext));
+        if path.exists() {
+            let bytes = std::fs::read(&path)
+                .map_err(|e| ApiError(crate::domain::DomainError::Repository(anyhow::Error::from(e))))?;
+            let content_type = if ext == "png" {
+                "image/png"
+            } else {
+                "image/jpeg"
+            };
+            return Ok((
+                StatusCode::OK,
+                [(header::CONTENT_TYPE, content_type)],
+                Body::from(bytes),
+            ));
+        }
+    }
+    Err(ApiError(crate::domain::DomainError::NotFound(format!(
+        "Imagen no encontrada para el tema de la bolsa {}",
+        id
+    ))))
+}
+
+/// Quita un tema de la bolsa de rotación (requiere Bearer token). Su imagen se borra del disco salvo que
+/// la use algún tema ya servido con el fallback `pool`.
+#[utoipa::path(
+    delete,
+    path = "/api/theme-of-the-day/pool/{id}",
+    tag = "theme_of_the_day",
+    security(("bearer_auth" = [])),
+    params(("id" = Uuid, Path, description = "UUID del tema en la bolsa")),
+    responses(
+        (status = 204, description = "Tema quitado de la bolsa"),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 404, description = "Tema no encontrado en la bolsa", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn delete_theme_of_the_day_from_pool(
+    _auth: crate::api::auth::BearerAuth,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<axum::http::StatusCode, ApiError> {
+    let uc = RemoveThemeOfTheDayFromPoolUseCase::new(Arc::clone(&state.theme_of_the_day_repo));
+    if uc.execute(id).await? {
+        let dir = StdPath::new(&state.theme_of_the_day_images_dir);
+        for ext in ["png", "jpg", "jpeg"] {
+            let _ = std::fs::remove_file(dir.join(format!("pool-{}.{}", id, ext)));
+        }
+    }
     Ok(axum::http::StatusCode::NO_CONTENT)
 }
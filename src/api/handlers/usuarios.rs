@@ -2,7 +2,7 @@
 
 use axum::{
     body::Body,
-    extract::State,
+    extract::{Query, State},
     http::{header, StatusCode},
     response::IntoResponse,
     Json,
@@ -12,13 +12,20 @@ use std::path::Path as StdPath;
 use std::sync::Arc;
 
 use crate::api::{
-    dto::{ErrorResponse, UpdateUsuarioAvatarRequest, UpdateUsuarioRequest, UsuarioResponse},
+    dto::{
+        CalendarFeedResponse, ErrorResponse, PostingStreakResponse, UpdateUsuarioAvatarRequest,
+        UpdateUsuarioRequest, UsuarioResponse,
+    },
+    handlers::theme_of_the_day::TimeZoneQuery,
+    ical,
     state::AppState,
     ApiError,
 };
 use crate::api::auth::{user_id_from_auth, BearerAuth};
 use crate::application::{
-    GetProfileUseCase, UpdateUsuarioAvatarUseCase, UpdateUsuarioUseCase,
+    GetCalendarTokenUseCase, GetPostingStreakUseCase, GetProfileUseCase,
+    RegenerateCalendarTokenUseCase, ResolveTimeZoneUseCase, UpdateUsuarioAvatarUseCase,
+    UpdateUsuarioUseCase,
 };
 
 /// Obtiene el perfil del usuario autenticado (datos sin password).
@@ -49,7 +56,7 @@ pub async fn get_profile(
     Ok(Json(UsuarioResponse::from(user)))
 }
 
-/// Actualiza el nombre del usuario autenticado.
+/// Actualiza el nombre y la zona horaria del usuario autenticado.
 #[utoipa::path(
     put,
     path = "/api/profile",
@@ -58,6 +65,7 @@ pub async fn get_profile(
     request_body = UpdateUsuarioRequest,
     responses(
         (status = 200, description = "Perfil actualizado", body = UsuarioResponse),
+        (status = 400, description = "Zona horaria inválida", body = ErrorResponse),
         (status = 401, description = "No autorizado", body = ErrorResponse),
         (status = 404, description = "Usuario no encontrado", body = ErrorResponse),
         (status = 500, description = "Error interno", body = ErrorResponse),
@@ -70,7 +78,9 @@ pub async fn update_profile(
 ) -> Result<Json<UsuarioResponse>, ApiError> {
     let user_id = user_id_from_auth(&state, &auth.0).await?;
     let uc = UpdateUsuarioUseCase::new(Arc::clone(&state.usuarios_repo));
-    let user = uc.execute(user_id, body.name.as_deref()).await?;
+    let user = uc
+        .execute(user_id, body.name.as_deref(), body.time_zone.as_deref())
+        .await?;
     let user = user.ok_or_else(|| {
         ApiError(crate::domain::DomainError::NotFound(
             "Usuario no encontrado".to_string(),
@@ -189,3 +199,112 @@ pub async fn get_profile_avatar(
         "Avatar no encontrado".to_string(),
     )))
 }
+
+/// `PUBLIC_BASE_URL`, necesaria para dar las URLs de los feeds (llevan el token de suscripción).
+fn calendar_feed_base_url(state: &AppState) -> Result<&str, ApiError> {
+    state.public_base_url.as_deref().ok_or_else(|| {
+        ApiError(crate::domain::DomainError::Repository(anyhow::anyhow!(
+            "PUBLIC_BASE_URL no está configurada: no se pueden generar las URLs de los feeds"
+        )))
+    })
+}
+
+/// Construye las URLs absolutas de los feeds iCalendar para un token.
+fn calendar_feed_response(base: &str, token: String) -> CalendarFeedResponse {
+    CalendarFeedResponse {
+        eventos_url: ical::absolute_url(
+            base,
+            &format!("/api/eventos/calendar.ics?token={}", token),
+        ),
+        theme_of_the_day_url: ical::absolute_url(
+            base,
+            &format!("/api/theme-of-the-day/calendar.ics?token={}", token),
+        ),
+        token,
+    }
+}
+
+/// URLs personales (con token) de los feeds iCalendar de eventos y tema del día, para suscribirse desde apps de calendario.
+#[utoipa::path(
+    get,
+    path = "/api/profile/calendar",
+    tag = "usuario",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "URLs de los feeds iCalendar", body = CalendarFeedResponse),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 404, description = "Usuario no encontrado", body = ErrorResponse),
+        (status = 500, description = "Error interno o PUBLIC_BASE_URL sin configurar", body = ErrorResponse),
+    ),
+)]
+pub async fn get_calendar_feed(
+    auth: BearerAuth,
+    State(state): State<AppState>,
+) -> Result<Json<CalendarFeedResponse>, ApiError> {
+    let user_id = user_id_from_auth(&state, &auth.0).await?;
+    let base = calendar_feed_base_url(&state)?;
+    let uc = GetCalendarTokenUseCase::new(Arc::clone(&state.usuarios_repo));
+    let token = uc.execute(user_id).await?;
+    Ok(Json(calendar_feed_response(base, token)))
+}
+
+/// Regenera el token de los feeds iCalendar (las suscripciones con el token anterior dejan de funcionar).
+#[utoipa::path(
+    post,
+    path = "/api/profile/calendar/regenerate",
+    tag = "usuario",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Nuevas URLs de los feeds iCalendar", body = CalendarFeedResponse),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 404, description = "Usuario no encontrado", body = ErrorResponse),
+        (status = 500, description = "Error interno o PUBLIC_BASE_URL sin configurar", body = ErrorResponse),
+    ),
+)]
+pub async fn regenerate_calendar_feed(
+    auth: BearerAuth,
+    State(state): State<AppState>,
+) -> Result<Json<CalendarFeedResponse>, ApiError> {
+    let user_id = user_id_from_auth(&state, &auth.0).await?;
+    let base = calendar_feed_base_url(&state)?;
+    let uc = RegenerateCalendarTokenUseCase::new(Arc::clone(&state.usuarios_repo));
+    let token = uc.execute(user_id).await?;
+    Ok(Json(calendar_feed_response(base, token)))
+}
+
+/// Racha del usuario autenticado en el reto diario: días consecutivos con post (por fecha del tema).
+/// "Hoy" se calcula con `?tz=`, la zona del perfil o la del servidor, en ese orden.
+#[utoipa::path(
+    get,
+    path = "/api/profile/streak",
+    tag = "usuario",
+    security(("bearer_auth" = [])),
+    params(TimeZoneQuery),
+    responses(
+        (status = 200, description = "Racha actual y mejor racha", body = PostingStreakResponse),
+        (status = 400, description = "Zona horaria inválida", body = ErrorResponse),
+        (status = 401, description = "No autorizado", body = ErrorResponse),
+        (status = 404, description = "Usuario no encontrado", body = ErrorResponse),
+        (status = 500, description = "Error interno", body = ErrorResponse),
+    ),
+)]
+pub async fn get_profile_streak(
+    auth: BearerAuth,
+    State(state): State<AppState>,
+    Query(q): Query<TimeZoneQuery>,
+) -> Result<Json<PostingStreakResponse>, ApiError> {
+    let user_id = user_id_from_auth(&state, &auth.0).await?;
+    let time_zone = ResolveTimeZoneUseCase::new(Arc::clone(&state.usuarios_repo))
+        .execute(user_id, q.tz.as_deref(), state.default_time_zone)
+        .await?;
+    let local_date = time_zone.local_date(chrono::Utc::now());
+    let uc = GetPostingStreakUseCase::new(Arc::clone(&state.posts_repo));
+    let streak = uc.execute(user_id, time_zone, local_date).await?;
+    Ok(Json(PostingStreakResponse {
+        current: streak.current,
+        longest: streak.longest,
+        last_posted_date: streak.last_posted_date,
+        local_date,
+        time_zone: time_zone.name(),
+    }))
+}
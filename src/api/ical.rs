@@ -0,0 +1,178 @@
+// Feeds iCalendar (RFC 5545): serialización de VCALENDAR/VEVENT y URLs absolutas para suscripciones.
+
+use axum::http::header;
+use axum::response::IntoResponse;
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// Evento de día completo dentro del feed.
+pub struct IcsEvent {
+    /// UID estable (derivado del id de la entidad) para que los calendarios no dupliquen eventos.
+    pub uid: String,
+    pub summary: String,
+    pub date: NaiveDate,
+    /// Regla de recurrencia sin el prefijo `RRULE:` (ej. `FREQ=YEARLY`).
+    pub rrule: Option<String>,
+    pub location: Option<String>,
+    /// Latitud y longitud (propiedad GEO).
+    pub geo: Option<(f64, f64)>,
+    /// URL absoluta de la imagen (propiedad ATTACH).
+    pub attach: Option<String>,
+    pub dtstamp: DateTime<Utc>,
+}
+
+/// Serializa un VCALENDAR completo (líneas CRLF, plegadas a 75 octetos).
+pub fn render_calendar(name: &str, events: &[IcsEvent]) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//DanPhoto//DanPhoto API//ES".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(name)),
+    ];
+    for e in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", e.uid));
+        lines.push(format!("DTSTAMP:{}", e.dtstamp.format("%Y%m%dT%H%M%SZ")));
+        lines.push(format!("DTSTART;VALUE=DATE:{}", e.date.format("%Y%m%d")));
+        if let Some(end) = e.date.succ_opt() {
+            lines.push(format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")));
+        }
+        if let Some(rrule) = &e.rrule {
+            lines.push(format!("RRULE:{}", rrule));
+        }
+        lines.push(format!("SUMMARY:{}", escape_text(&e.summary)));
+        if let Some(location) = &e.location {
+            lines.push(format!("LOCATION:{}", escape_text(location)));
+        }
+        if let Some((lat, lon)) = e.geo {
+            lines.push(format!("GEO:{};{}", lat, lon));
+        }
+        if let Some(attach) = &e.attach {
+            lines.push(format!("ATTACH:{}", attach));
+        }
+        lines.push("TRANSP:TRANSPARENT".to_string());
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut out = String::new();
+    for line in lines {
+        fold_line(&line, &mut out);
+    }
+    out
+}
+
+/// Respuesta HTTP `text/calendar` con el feed.
+pub fn calendar_response(filename: &str, body: String) -> impl IntoResponse {
+    (
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("inline; filename=\"{}\"", filename),
+            ),
+            (header::CACHE_CONTROL, "private, max-age=900".to_string()),
+        ],
+        body,
+    )
+}
+
+/// Convierte una URL relativa de la API (ej. `/api/eventos/{id}/image`) en absoluta sobre
+/// `PUBLIC_BASE_URL`. No se deriva de los headers Host o X-Forwarded-Proto: los controla el cliente
+/// y las URLs de los feeds llevan el token de suscripción.
+pub fn absolute_url(public_base_url: &str, path: &str) -> String {
+    if path.starts_with("http://") || path.starts_with("https://") {
+        return path.to_string();
+    }
+    format!("{}{}", public_base_url, path)
+}
+
+/// Escapa un valor TEXT según RFC 5545 §3.3.11.
+fn escape_text(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Pliega una línea de contenido a 75 octetos (RFC 5545 §3.1) sin partir caracteres UTF-8.
+fn fold_line(line: &str, out: &mut String) {
+    let mut width = 0;
+    for c in line.chars() {
+        let len = c.len_utf8();
+        if width + len > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += len;
+    }
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn folded(line: &str) -> String {
+        let mut out = String::new();
+        fold_line(line, &mut out);
+        out
+    }
+
+    #[test]
+    fn absolute_url_joins_the_public_base_url() {
+        let base = "https://api.danphoto.com";
+        assert_eq!(
+            absolute_url(base, "/api/eventos/calendar.ics?token=abc"),
+            "https://api.danphoto.com/api/eventos/calendar.ics?token=abc"
+        );
+        assert_eq!(
+            absolute_url(base, "https://cdn.danphoto.com/e.jpg"),
+            "https://cdn.danphoto.com/e.jpg"
+        );
+    }
+
+    #[test]
+    fn escape_text_escapes_special_characters() {
+        assert_eq!(escape_text("a,b;c\\d"), "a\\,b\\;c\\\\d");
+        assert_eq!(escape_text("línea 1\nlínea 2\r\nlínea 3"), "línea 1\\nlínea 2\\nlínea 3");
+        assert_eq!(escape_text("sin cambios"), "sin cambios");
+    }
+
+    #[test]
+    fn fold_line_keeps_short_lines() {
+        assert_eq!(folded("SUMMARY:corto"), "SUMMARY:corto\r\n");
+        let exact = "a".repeat(75);
+        assert_eq!(folded(&exact), format!("{}\r\n", exact));
+    }
+
+    #[test]
+    fn fold_line_splits_at_75_octets() {
+        let line = "a".repeat(80);
+        assert_eq!(folded(&line), format!("{}\r\n {}\r\n", "a".repeat(75), "a".repeat(5)));
+    }
+
+    #[test]
+    fn fold_line_does_not_split_multibyte_characters() {
+        // "é" ocupa 2 octetos: con 74 ya escritos no cabe y pasa entero a la línea siguiente.
+        let line = format!("{}é", "a".repeat(74));
+        assert_eq!(folded(&line), format!("{}\r\n é\r\n", "a".repeat(74)));
+
+        let line = "ñ€😀".repeat(40);
+        let out = folded(&line);
+        for physical in out.split("\r\n").filter(|l| !l.is_empty()) {
+            assert!(physical.len() <= 75, "línea de {} octetos", physical.len());
+        }
+        assert_eq!(out.trim_end_matches("\r\n").replace("\r\n ", ""), line);
+    }
+}
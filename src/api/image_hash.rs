@@ -0,0 +1,77 @@
+// Hash perceptual de imágenes (dHash de 64 bits) para la similitud visual entre poses.
+
+use std::path::Path;
+
+use image::imageops::FilterType;
+use uuid::Uuid;
+
+/// dHash: la imagen en grises reducida a 9×8; cada bit indica si un píxel es más claro que su vecino
+/// de la derecha. Imágenes parecidas (aunque cambie el tamaño o la compresión) difieren en pocos bits.
+/// None si los bytes no son una imagen PNG o JPEG válida.
+pub fn dhash(bytes: &[u8]) -> Option<i64> {
+    let small = image::load_from_memory(bytes)
+        .ok()?
+        .resize_exact(9, 8, FilterType::Triangle)
+        .to_luma8();
+    let mut hash: u64 = 0;
+    for y in 0..8 {
+        for x in 0..8 {
+            hash <<= 1;
+            if small.get_pixel(x, y)[0] > small.get_pixel(x + 1, y)[0] {
+                hash |= 1;
+            }
+        }
+    }
+    Some(hash as i64)
+}
+
+/// Hash de la imagen guardada de una pose (`{dir}/{id}.{png,jpg,jpeg}`). None si no hay archivo o no
+/// se puede decodificar. Lee y decodifica en el hilo actual: desde async, usar `spawn_blocking`.
+pub fn pose_image_hash(dir: &str, id: Uuid) -> Option<i64> {
+    let path = ["png", "jpg", "jpeg"]
+        .iter()
+        .map(|ext| Path::new(dir).join(format!("{}.{}", id, ext)))
+        .find(|path| path.exists())?;
+    dhash(&std::fs::read(path).ok()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{DynamicImage, ImageFormat, RgbImage};
+    use std::io::Cursor;
+
+    /// PNG con un degradado horizontal (oscuro → claro si `increasing`).
+    fn gradient_png(width: u32, height: u32, increasing: bool) -> Vec<u8> {
+        let img = RgbImage::from_fn(width, height, |x, _| {
+            let v = (x * 255 / (width - 1)) as u8;
+            let v = if increasing { v } else { 255 - v };
+            image::Rgb([v, v, v])
+        });
+        let mut bytes = Vec::new();
+        DynamicImage::ImageRgb8(img)
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn dhash_compares_neighbouring_pixels() {
+        assert_eq!(dhash(&gradient_png(90, 80, true)), Some(0));
+        assert_eq!(dhash(&gradient_png(90, 80, false)), Some(-1));
+    }
+
+    #[test]
+    fn dhash_ignores_size() {
+        assert_eq!(
+            dhash(&gradient_png(90, 80, false)),
+            dhash(&gradient_png(400, 300, false))
+        );
+    }
+
+    #[test]
+    fn dhash_rejects_invalid_images() {
+        assert_eq!(dhash(b"no es una imagen"), None);
+        assert_eq!(dhash(&[]), None);
+    }
+}
@@ -0,0 +1,100 @@
+// Parámetros comunes de orden, filtro y paginación de los listados (GET /api/poses, /api/posts, ...)
+
+use chrono::{DateTime, Utc};
+
+use crate::api::ApiError;
+use crate::domain::{ListQuery, ListSpec};
+
+/// Tamaño de página si no se indica `limit`.
+pub const DEFAULT_PAGE_SIZE: u32 = 20;
+/// Tamaño de página máximo: un `limit` mayor se recorta.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Tamaño de página pedido, entre 1 y `MAX_PAGE_SIZE` (por defecto `DEFAULT_PAGE_SIZE`).
+pub fn page_limit(limit: Option<u32>) -> u32 {
+    limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE)
+}
+
+#[derive(Debug, serde::Deserialize, utoipa::IntoParams)]
+pub struct PaginationQuery {
+    /// Página (0-based). Por defecto 0.
+    pub page: Option<u32>,
+    /// Tamaño de página (entre 1 y 100). Por defecto 20.
+    pub limit: Option<u32>,
+}
+
+impl PaginationQuery {
+    pub fn page(&self) -> u32 {
+        self.page.unwrap_or(0)
+    }
+
+    pub fn limit(&self) -> u32 {
+        page_limit(self.limit)
+    }
+
+    /// Si se pidió página (en los listados donde la paginación es opcional).
+    pub fn is_requested(&self) -> bool {
+        self.page.is_some() || self.limit.is_some()
+    }
+
+    /// Página y tamaño pedidos; sin paginación, la primera página de `MAX_PAGE_SIZE` elementos
+    /// (los listados sin paginar tampoco devuelven la tabla entera).
+    pub fn page_and_limit(&self) -> (u32, u32) {
+        if self.is_requested() {
+            (self.page(), self.limit())
+        } else {
+            (0, MAX_PAGE_SIZE)
+        }
+    }
+}
+
+/// Paginación por cursor: con `cursor` (vacío para la primera página) se ignora `page` y se
+/// devuelve `next_cursor` sin contar el total.
+#[derive(Debug, serde::Deserialize, utoipa::IntoParams)]
+pub struct CursorQuery {
+    /// Cursor opaco (`next_cursor` de la respuesta anterior).
+    pub cursor: Option<String>,
+}
+
+/// Orden y filtros genéricos. Los campos admitidos dependen del recurso (ver la descripción de cada endpoint);
+/// un campo fuera de la lista devuelve 400.
+#[derive(Debug, serde::Deserialize, utoipa::IntoParams)]
+pub struct ListParams {
+    /// `campo`, `campo:asc` o `campo:desc` (sin dirección, la por defecto del campo).
+    pub sort: Option<String>,
+    /// Solo elementos creados en o después de esta fecha (RFC 3339).
+    pub created_after: Option<DateTime<Utc>>,
+    /// Solo elementos creados antes de esta fecha (RFC 3339).
+    pub created_before: Option<DateTime<Utc>>,
+    /// Filtros de igualdad `campo:valor` separados por comas (ej. `difficulty:easy,people_count:2`).
+    pub filter: Option<String>,
+}
+
+impl ListParams {
+    /// Valida los parámetros contra la lista blanca del recurso.
+    pub fn to_query(&self, spec: &ListSpec) -> Result<ListQuery, ApiError> {
+        Ok(spec.query(
+            self.sort.as_deref(),
+            self.filter.as_deref(),
+            self.created_after,
+            self.created_before,
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pagination(page: Option<u32>, limit: Option<u32>) -> PaginationQuery {
+        PaginationQuery { page, limit }
+    }
+
+    #[test]
+    fn unpaginated_lists_are_capped_at_the_max_page_size() {
+        assert_eq!(pagination(None, None).page_and_limit(), (0, MAX_PAGE_SIZE));
+        assert_eq!(pagination(Some(2), None).page_and_limit(), (2, DEFAULT_PAGE_SIZE));
+        assert_eq!(pagination(None, Some(0)).page_and_limit(), (0, 1));
+        assert_eq!(pagination(None, Some(5000)).page_and_limit(), (0, MAX_PAGE_SIZE));
+    }
+}
@@ -3,7 +3,11 @@
 pub mod auth;
 pub mod dto;
 pub mod error;
+pub mod fields;
 pub mod handlers;
+pub mod ical;
+pub mod image_hash;
+pub mod list_query;
 pub mod routes;
 pub mod state;
 pub mod swagger;
@@ -10,14 +10,17 @@ use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 
 use super::auth::login;
 use super::handlers::eventos::{
-    create_evento, delete_evento, get_evento, get_evento_image, list_eventos, update_evento,
+    attend_evento, cancel_evento_attendance, create_evento, delete_evento, get_evento,
+    get_evento_attendees, get_evento_image, get_eventos_calendar, list_eventos,
+    list_my_upcoming_eventos, update_evento,
 };
 use super::handlers::favorites::{
     add_pose_to_favorites, get_favorite_poses, is_pose_favorite, remove_pose_from_favorites,
 };
 use super::handlers::hashtags::{
     add_hashtags_to_post, create_hashtag, delete_hashtag, get_hashtag, get_hashtags_by_pose,
-    list_hashtags,
+    get_hashtag_tree, get_hashtags_by_post, get_related_hashtags, list_hashtags, merge_hashtag,
+    suggest_hashtags, update_hashtag, update_post_hashtags,
 };
 use super::handlers::portfolio::{
     add_portfolio_image, create_portfolio_category, delete_portfolio_category,
@@ -28,12 +31,17 @@ use super::handlers::places::{
     create_place, delete_place, get_place, get_place_image, list_places, update_place,
 };
 use super::handlers::poses::{
-    create_pose, delete_pose, get_pose, get_pose_image, get_poses_by_hashtag,
-    get_poses_by_hashtag_paginated, list_poses, list_poses_paginated, update_pose_hashtags,
+    batch_poses, create_pose, delete_pose, get_pose, get_pose_image, get_poses_by_hashtag,
+    get_poses_by_hashtag_paginated, get_recommended_poses, get_similar_poses, list_poses,
+    list_poses_paginated, update_pose, update_pose_hashtags,
 };
 use super::handlers::posts::{
-    create_post, delete_post, get_post, get_post_image, get_posts_by_theme_of_the_day,
-    list_posts, list_posts_paginated,
+    add_post_image, create_post, create_post_comment, delete_post, delete_post_comment, get_post,
+    get_post_image, get_post_image_by_id, get_posts_by_evento, get_posts_by_hashtag,
+    get_posts_by_theme_of_the_day,
+    get_theme_of_the_day_leaderboard, like_post, list_post_comments, list_posts,
+    list_posts_paginated, remove_post_image, remove_post_vote, reorder_post_images, unlike_post,
+    update_post, update_post_comment, vote_post,
 };
 use super::handlers::sesiones::{
     add_favorites_to_sesion, add_poses_to_sesion, create_sesion, create_sesion_from_favorites,
@@ -41,12 +49,15 @@ use super::handlers::sesiones::{
     update_sesion_cover,
 };
 use super::handlers::theme_of_the_day::{
-    create_theme_of_the_day, delete_theme_of_the_day, get_theme_of_the_day,
-    get_theme_of_the_day_by_date, get_theme_of_the_day_image, get_theme_of_the_day_today,
-    list_theme_of_the_day, update_theme_of_the_day,
+    add_theme_of_the_day_to_pool, create_theme_of_the_day, delete_theme_of_the_day,
+    delete_theme_of_the_day_from_pool, get_theme_of_the_day, get_theme_of_the_day_archive,
+    get_theme_of_the_day_by_date, get_theme_of_the_day_calendar, get_theme_of_the_day_image,
+    get_theme_of_the_day_pool_image, get_theme_of_the_day_today, list_theme_of_the_day,
+    list_theme_of_the_day_pool, update_theme_of_the_day,
 };
 use super::handlers::usuarios::{
-    get_profile, get_profile_avatar, update_profile, update_profile_avatar,
+    get_calendar_feed, get_profile, get_profile_avatar, get_profile_streak,
+    regenerate_calendar_feed, update_profile, update_profile_avatar,
 };
 use super::state::AppState;
 use super::swagger::{
@@ -77,7 +88,15 @@ pub fn create_router(state: AppState, config: &crate::config::Config) -> Router
     let cors = cors_layer_from_config(config);
     let rest_routes = Router::new()
         .route("/api/eventos", get(list_eventos).post(create_evento))
+        .route("/api/eventos/calendar.ics", get(get_eventos_calendar))
+        .route("/api/eventos/attending", get(list_my_upcoming_eventos))
         .route("/api/eventos/{id}/image", get(get_evento_image))
+        .route(
+            "/api/eventos/{id}/attendance",
+            post(attend_evento).delete(cancel_evento_attendance),
+        )
+        .route("/api/eventos/{id}/attendees", get(get_evento_attendees))
+        .route("/api/eventos/{id}/posts", get(get_posts_by_evento))
         .route(
             "/api/eventos/{id}",
             get(get_evento).put(update_evento).delete(delete_evento),
@@ -90,11 +109,35 @@ pub fn create_router(state: AppState, config: &crate::config::Config) -> Router
             "/api/theme-of-the-day/today",
             get(get_theme_of_the_day_today),
         )
+        .route(
+            "/api/theme-of-the-day/archive",
+            get(get_theme_of_the_day_archive),
+        )
+        .route(
+            "/api/theme-of-the-day/pool",
+            get(list_theme_of_the_day_pool).post(add_theme_of_the_day_to_pool),
+        )
+        .route(
+            "/api/theme-of-the-day/pool/{id}",
+            delete(delete_theme_of_the_day_from_pool),
+        )
+        .route(
+            "/api/theme-of-the-day/pool/{id}/image",
+            get(get_theme_of_the_day_pool_image),
+        )
+        .route(
+            "/api/theme-of-the-day/calendar.ics",
+            get(get_theme_of_the_day_calendar),
+        )
         .route(
             "/api/theme-of-the-day/date/{mmdd}",
             get(get_theme_of_the_day_by_date),
         )
         .route("/api/theme-of-the-day/{id}/image", get(get_theme_of_the_day_image))
+        .route(
+            "/api/theme-of-the-day/{id}/leaderboard",
+            get(get_theme_of_the_day_leaderboard),
+        )
         .route(
             "/api/theme-of-the-day/{id}",
             get(get_theme_of_the_day)
@@ -102,20 +145,52 @@ pub fn create_router(state: AppState, config: &crate::config::Config) -> Router
                 .delete(delete_theme_of_the_day),
         )
         .route("/api/hashtags", get(list_hashtags).post(create_hashtag))
-        .route("/api/hashtags/{id}", get(get_hashtag).delete(delete_hashtag))
+        .route(
+            "/api/hashtags/{id}",
+            get(get_hashtag).put(update_hashtag).delete(delete_hashtag),
+        )
+        .route("/api/hashtags/suggest", get(suggest_hashtags))
+        .route("/api/hashtags/tree", get(get_hashtag_tree))
+        .route("/api/hashtags/{id}/merge", post(merge_hashtag))
+        .route("/api/hashtags/{id}/related", get(get_related_hashtags))
         .route("/api/poses/{pose_id}/hashtags", get(get_hashtags_by_pose).put(update_pose_hashtags))
-        .route("/api/posts/{post_id}/hashtags", post(add_hashtags_to_post))
+        .route(
+            "/api/posts/{post_id}/hashtags",
+            get(get_hashtags_by_post)
+                .post(add_hashtags_to_post)
+                .put(update_post_hashtags),
+        )
         .route("/api/poses", get(list_poses).post(create_pose))
         .route("/api/poses/paginated", get(list_poses_paginated))
+        .route("/api/poses/batch", post(batch_poses))
+        .route("/api/poses/recommended", get(get_recommended_poses))
+        .route("/api/poses/{id}/similar", get(get_similar_poses))
         .route("/api/poses/{id}/image", get(get_pose_image))
-        .route("/api/poses/{id}", get(get_pose).delete(delete_pose))
+        .route(
+            "/api/poses/{id}",
+            get(get_pose).put(update_pose).delete(delete_pose),
+        )
         .route("/api/hashtags/{hashtag_id}/poses", get(get_poses_by_hashtag))
         .route("/api/hashtags/{hashtag_id}/poses/paginated", get(get_poses_by_hashtag_paginated))
+        .route("/api/hashtags/{hashtag_id}/posts", get(get_posts_by_hashtag))
         .route("/api/posts", get(list_posts).post(create_post))
         .route("/api/posts/paginated", get(list_posts_paginated))
         .route("/api/posts/theme-of-the-day/{theme_of_the_day_id}", get(get_posts_by_theme_of_the_day))
         .route("/api/posts/{id}/image", get(get_post_image))
-        .route("/api/posts/{id}", get(get_post).delete(delete_post))
+        .route("/api/posts/{id}/images", post(add_post_image))
+        .route("/api/posts/{id}/images/order", put(reorder_post_images))
+        .route(
+            "/api/posts/{id}/images/{image_id}",
+            get(get_post_image_by_id).delete(remove_post_image),
+        )
+        .route("/api/posts/{id}/votes", post(vote_post).delete(remove_post_vote))
+        .route("/api/posts/{id}/likes", post(like_post).delete(unlike_post))
+        .route("/api/posts/{id}/comments", get(list_post_comments).post(create_post_comment))
+        .route(
+            "/api/posts/{id}/comments/{comment_id}",
+            put(update_post_comment).delete(delete_post_comment),
+        )
+        .route("/api/posts/{id}", get(get_post).put(update_post).delete(delete_post))
         .route("/api/portfolio/categories", get(list_portfolio_categories).post(create_portfolio_category))
         .route("/api/portfolio/{id}/cover", put(update_portfolio_cover))
         .route("/api/portfolio/categories/{id}", put(update_portfolio_category).delete(delete_portfolio_category))
@@ -136,6 +211,9 @@ pub fn create_router(state: AppState, config: &crate::config::Config) -> Router
         .route("/api/sesiones/{id}/cover", put(update_sesion_cover))
         .route("/api/profile", get(get_profile).put(update_profile))
         .route("/api/profile/avatar", get(get_profile_avatar).put(update_profile_avatar))
+        .route("/api/profile/calendar", get(get_calendar_feed))
+        .route("/api/profile/streak", get(get_profile_streak))
+        .route("/api/profile/calendar/regenerate", post(regenerate_calendar_feed))
         .route("/api/health", get(|| async { "ok" }))
         .route("/api-docs/openapi.json", get(serve_openapi_json))
         .route("/swagger-ui", get(serve_swagger_ui_root))
@@ -147,8 +225,8 @@ pub fn create_router(state: AppState, config: &crate::config::Config) -> Router
         .route("/swagger-ui-standalone-preset.js", get(serve_swagger_ui_standalone_preset_js))
         .route("/swagger-initializer.js", get(serve_swagger_initializer_js));
 
-    let app = if config.rate_limit_login_per_minute > 0 {
-        let period_secs = (60 / config.rate_limit_login_per_minute).max(1) as u64;
+    let app = if let Some(period_secs) = 60u32.checked_div(config.rate_limit_login_per_minute) {
+        let period_secs = period_secs.max(1) as u64;
         let governor_conf = GovernorConfigBuilder::default()
             .per_second(period_secs)
             .burst_size(config.rate_limit_login_per_minute)
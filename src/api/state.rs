@@ -3,9 +3,9 @@
 use std::sync::Arc;
 
 use crate::domain::{
-    AuthRepository, EventosRepository, FavoritesRepository, HashtagsRepository,
+    AuthRepository, EventosRepository, FavoritesRepository, HashtagsRepository, LocalTimeZone,
     PlacesRepository, PortfolioRepository, PosesRepository, PostsRepository,
-    SesionesRepository, ThemeOfTheDayRepository, UsuariosRepository,
+    SesionesRepository, ThemeOfTheDayRepository, UnitOfWorkFactory, UsuariosRepository,
 };
 
 #[derive(Clone)]
@@ -20,7 +20,11 @@ pub struct AppState {
     pub places_repo: Arc<dyn PlacesRepository>,
     pub sesiones_repo: Arc<dyn SesionesRepository>,
     pub usuarios_repo: Arc<dyn UsuariosRepository>,
+    /// Abre transacciones para los use cases que escriben en varios repositorios a la vez.
+    pub unit_of_work: Arc<dyn UnitOfWorkFactory>,
     pub jwt_secret: String,
+    /// Emails de administradores en minúsculas (desde config).
+    pub admin_emails: Vec<String>,
     pub auth_repository: Arc<dyn AuthRepository>,
     /// Carpeta donde se guardan las imágenes de theme-of-the-day (desde config).
     pub theme_of_the_day_images_dir: String,
@@ -36,4 +40,8 @@ pub struct AppState {
     pub places_images_dir: String,
     /// Carpeta donde se guardan los avatares de perfil (desde config).
     pub profile_avatars_dir: String,
+    /// URL pública base de la API (desde config; None = sin URLs de feeds ni adjuntos absolutos).
+    pub public_base_url: Option<String>,
+    /// Zona horaria por defecto del servidor (desde config) para calcular la fecha local.
+    pub default_time_zone: LocalTimeZone,
 }
@@ -1,6 +1,9 @@
 // Casos de uso de Eventos (orquestan el repositorio)
 
-use crate::domain::{DomainError, Evento, EventosRepository};
+use crate::domain::{
+    AttendanceStatus, DomainError, Evento, EventoAsistente, EventosRepository, ListQuery, MonthDay,
+};
+use chrono::NaiveDate;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -14,8 +17,30 @@ impl GetEventosUseCase {
         Self { repo }
     }
 
-    pub async fn execute(&self) -> Result<Vec<Evento>, DomainError> {
-        self.repo.get_all().await
+    pub async fn execute(&self, list: &ListQuery) -> Result<Vec<Evento>, DomainError> {
+        self.repo.get_all(list).await
+    }
+}
+
+#[derive(Clone)]
+pub struct GetEventosPaginatedUseCase {
+    repo: Arc<dyn EventosRepository>,
+}
+
+impl GetEventosPaginatedUseCase {
+    pub fn new(repo: Arc<dyn EventosRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(
+        &self,
+        list: &ListQuery,
+        page: u32,
+        limit: u32,
+    ) -> Result<(Vec<Evento>, u64), DomainError> {
+        let items = self.repo.get_paginated(list, page, limit).await?;
+        let total = self.repo.count(list).await?;
+        Ok((items, total))
     }
 }
 
@@ -55,13 +80,20 @@ impl CreateEventoUseCase {
         place: &str,
         url: &str,
         mmdd: &str,
+        capacity: Option<i32>,
     ) -> Result<Evento, DomainError> {
         if mmdd.trim().is_empty() {
             return Err(DomainError::Validation(
                 "El campo fecha (MMdd) es requerido".to_string(),
             ));
         }
-        self.repo.create_with_id(id, name, place, url, mmdd).await
+        let mmdd = MonthDay::parse(mmdd)?.to_string();
+        validate_capacity(capacity)?;
+        // 0 en la creación equivale a no enviar cupo.
+        let capacity = capacity.filter(|c| *c > 0);
+        self.repo
+            .create_with_id(id, name, place, url, &mmdd, capacity)
+            .await
     }
 }
 
@@ -82,9 +114,12 @@ impl UpdateEventoUseCase {
         place: Option<&str>,
         url: Option<&str>,
         mmdd: Option<&str>,
+        capacity: Option<i32>,
     ) -> Result<Evento, DomainError> {
+        let mmdd = mmdd.map(MonthDay::parse).transpose()?.map(|m| m.to_string());
+        validate_capacity(capacity)?;
         self.repo
-            .update(id, name, place, url, mmdd)
+            .update(id, name, place, url, mmdd.as_deref(), capacity)
             .await?
             .ok_or_else(|| DomainError::NotFound(format!("Evento no encontrado: {}", id)))
     }
@@ -104,3 +139,114 @@ impl DeleteEventoUseCase {
         self.repo.delete(id).await
     }
 }
+
+fn validate_capacity(capacity: Option<i32>) -> Result<(), DomainError> {
+    if capacity.is_some_and(|c| c < 0) {
+        return Err(DomainError::Validation(
+            "El cupo (capacity) no puede ser negativo".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Apunta al usuario al evento: confirmado si hay cupo, en lista de espera si no.
+/// Si ya estaba apuntado devuelve su asistencia actual.
+#[derive(Clone)]
+pub struct AttendEventoUseCase {
+    repo: Arc<dyn EventosRepository>,
+}
+
+impl AttendEventoUseCase {
+    pub fn new(repo: Arc<dyn EventosRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(
+        &self,
+        evento_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<EventoAsistente, DomainError> {
+        self.repo
+            .add_attendee(evento_id, user_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Evento no encontrado: {}", evento_id)))
+    }
+}
+
+/// Cancela la asistencia; el primero de la lista de espera ocupa el lugar liberado.
+#[derive(Clone)]
+pub struct CancelEventoAttendanceUseCase {
+    repo: Arc<dyn EventosRepository>,
+}
+
+impl CancelEventoAttendanceUseCase {
+    pub fn new(repo: Arc<dyn EventosRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(&self, evento_id: Uuid, user_id: Uuid) -> Result<(), DomainError> {
+        if self.repo.get_by_id(evento_id).await?.is_none() {
+            return Err(DomainError::NotFound(format!(
+                "Evento no encontrado: {}",
+                evento_id
+            )));
+        }
+        self.repo.remove_attendee(evento_id, user_id).await
+    }
+}
+
+#[derive(Clone)]
+pub struct GetEventoAttendeesUseCase {
+    repo: Arc<dyn EventosRepository>,
+}
+
+impl GetEventoAttendeesUseCase {
+    pub fn new(repo: Arc<dyn EventosRepository>) -> Self {
+        Self { repo }
+    }
+
+    /// Devuelve el evento y sus asistentes (confirmados primero, luego la lista de espera en orden).
+    pub async fn execute(
+        &self,
+        evento_id: Uuid,
+    ) -> Result<(Evento, Vec<EventoAsistente>), DomainError> {
+        let evento = self
+            .repo
+            .get_by_id(evento_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Evento no encontrado: {}", evento_id)))?;
+        let attendees = self.repo.get_attendees(evento_id).await?;
+        Ok((evento, attendees))
+    }
+}
+
+/// Próximos eventos a los que el usuario está apuntado, ordenados por fecha.
+#[derive(Clone)]
+pub struct GetMyUpcomingEventosUseCase {
+    repo: Arc<dyn EventosRepository>,
+}
+
+impl GetMyUpcomingEventosUseCase {
+    pub fn new(repo: Arc<dyn EventosRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        today: NaiveDate,
+    ) -> Result<Vec<(Evento, NaiveDate, AttendanceStatus)>, DomainError> {
+        let mut upcoming: Vec<_> = self
+            .repo
+            .get_by_attendee(user_id)
+            .await?
+            .into_iter()
+            .filter_map(|(evento, status)| {
+                let date = evento.date(today)?;
+                (date >= today).then_some((evento, date, status))
+            })
+            .collect();
+        upcoming.sort_by_key(|(_, date, _)| *date);
+        Ok(upcoming)
+    }
+}
@@ -0,0 +1,1177 @@
+// Repositorios y unidad de trabajo en memoria para los tests de casos de uso.
+// Guardan lo justo para comprobar permisos, validaciones y compensaciones; las consultas de listado
+// (filtros, orden, similares) devuelven vacío, salvo la galería de un evento, paginada por id.
+
+use crate::domain::{
+    AttendanceStatus, Compensation, DailyWinner, DomainError, Evento, EventoAsistente,
+    EventosRepository, FavoritesRepository, Hashtag, HashtagUsage, HashtagsRepository, ListQuery,
+    PageCursor, Pose, PoseFilter, PoseMetadata, PoseTagFilter, PosesRepository, Post, PostComment,
+    PostImage, PostsRepository, RelatedHashtag, ScoredPose, Sesion, SesionesRepository, UnitOfWork,
+    UnitOfWorkFactory,
+};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Como `PgUnitOfWork`: si se suelta sin commit, ejecuta las compensaciones en orden inverso.
+struct FakeUnitOfWork {
+    commits: Arc<AtomicUsize>,
+    compensations: Vec<Compensation>,
+}
+
+#[async_trait]
+impl UnitOfWork for FakeUnitOfWork {
+    fn on_rollback(&mut self, compensation: Compensation) {
+        self.compensations.push(compensation);
+    }
+
+    async fn commit(mut self: Box<Self>) -> Result<(), DomainError> {
+        self.compensations.clear();
+        self.commits.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl Drop for FakeUnitOfWork {
+    fn drop(&mut self) {
+        while let Some(compensation) = self.compensations.pop() {
+            compensation();
+        }
+    }
+}
+
+/// Abre `FakeUnitOfWork`s; con `fail_begin` falla como una base de datos sin conexión.
+#[derive(Default)]
+pub struct FakeUnitOfWorkFactory {
+    pub fail_begin: AtomicBool,
+    commits: Arc<AtomicUsize>,
+}
+
+impl FakeUnitOfWorkFactory {
+    pub fn commits(&self) -> usize {
+        self.commits.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl UnitOfWorkFactory for FakeUnitOfWorkFactory {
+    async fn begin(&self) -> Result<Box<dyn UnitOfWork>, DomainError> {
+        if self.fail_begin.load(Ordering::SeqCst) {
+            return Err(DomainError::Repository(anyhow::anyhow!("sin conexión")));
+        }
+        Ok(Box::new(FakeUnitOfWork {
+            commits: Arc::clone(&self.commits),
+            compensations: Vec::new(),
+        }))
+    }
+}
+
+/// Compensación que marca `flag` al ejecutarse (en lugar de borrar un archivo).
+pub fn flag_on_rollback(flag: &Arc<AtomicBool>) -> Compensation {
+    let flag = Arc::clone(flag);
+    Box::new(move || flag.store(true, Ordering::SeqCst))
+}
+
+/// Hace de transacción para las escrituras `_in` de los repositorios en memoria: si `uow` no se
+/// confirma, `state` vuelve a como estaba antes de la escritura.
+fn undo_on_rollback<T: Clone + Send + 'static>(uow: &mut dyn UnitOfWork, state: &Arc<Mutex<T>>) {
+    let before = state.lock().unwrap().clone();
+    let state = Arc::clone(state);
+    uow.on_rollback(Box::new(move || *state.lock().unwrap() = before));
+}
+
+/// Posts y comentarios en memoria.
+#[derive(Default)]
+pub struct FakePostsRepository {
+    posts: Arc<Mutex<HashMap<Uuid, Post>>>,
+    comments: Mutex<HashMap<Uuid, PostComment>>,
+}
+
+impl FakePostsRepository {
+    /// Guarda un post de `user_id` con una imagen.
+    pub fn insert_post(&self, user_id: Option<Uuid>) -> Post {
+        let id = Uuid::new_v4();
+        let post = Post {
+            id,
+            description: None,
+            url: Some(format!("/api/posts/{}/image", id)),
+            images: vec![PostImage::new(id, id)],
+            user_id,
+            theme_of_the_day_id: Some("0101".to_string()),
+            theme_date: None,
+            evento_id: None,
+            hashtags: Vec::new(),
+            votes_count: 0,
+            likes_count: 0,
+            comments_count: 0,
+            liked_by_me: false,
+            created_at: None,
+        };
+        self.posts.lock().unwrap().insert(id, post.clone());
+        post
+    }
+
+    /// Guarda un comentario de `user_id` en el post.
+    pub fn insert_comment(&self, post_id: Uuid, user_id: Uuid) -> PostComment {
+        let comment = PostComment {
+            id: Uuid::new_v4(),
+            post_id,
+            user_id,
+            user_name: None,
+            user_url: None,
+            parent_id: None,
+            body: "Qué buena luz".to_string(),
+            deleted: false,
+            created_at: None,
+            updated_at: None,
+        };
+        self.comments.lock().unwrap().insert(comment.id, comment.clone());
+        comment
+    }
+
+    /// Guarda un post en la galería del evento.
+    pub fn insert_evento_post(&self, evento_id: Uuid) -> Post {
+        let mut post = self.insert_post(None);
+        post.evento_id = Some(evento_id);
+        self.posts.lock().unwrap().insert(post.id, post.clone());
+        post
+    }
+
+    pub fn post(&self, id: Uuid) -> Option<Post> {
+        self.posts.lock().unwrap().get(&id).cloned()
+    }
+
+    fn evento_posts(&self, evento_id: Uuid) -> Vec<Post> {
+        let mut posts: Vec<Post> = self
+            .posts
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|p| p.evento_id == Some(evento_id))
+            .cloned()
+            .collect();
+        posts.sort_by_key(|p| p.id);
+        posts
+    }
+
+    pub fn comment(&self, id: Uuid) -> Option<PostComment> {
+        self.comments.lock().unwrap().get(&id).cloned()
+    }
+}
+
+#[async_trait]
+impl PostsRepository for FakePostsRepository {
+    async fn get_paginated(&self, _: &ListQuery, _: u32, _: u32) -> Result<Vec<Post>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn get_page_after(
+        &self,
+        _: &ListQuery,
+        _: Option<&PageCursor>,
+        _: u32,
+    ) -> Result<Vec<Post>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn count(&self, _: &ListQuery) -> Result<u64, DomainError> {
+        Ok(0)
+    }
+
+    async fn get_by_theme_of_the_day_id(&self, _: &str) -> Result<Vec<Post>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn get_by_theme_date(&self, _: NaiveDate) -> Result<Vec<Post>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn get_by_evento_paginated(
+        &self,
+        evento_id: Uuid,
+        page: u32,
+        limit: u32,
+    ) -> Result<Vec<Post>, DomainError> {
+        let posts = self.evento_posts(evento_id).into_iter();
+        Ok(posts.skip((page * limit) as usize).take(limit as usize).collect())
+    }
+
+    async fn count_by_evento(&self, evento_id: Uuid) -> Result<u64, DomainError> {
+        Ok(self.evento_posts(evento_id).len() as u64)
+    }
+
+    async fn get_by_hashtag_paginated(&self, _: Uuid, _: u32, _: u32) -> Result<Vec<Post>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn count_by_hashtag(&self, _: Uuid) -> Result<u64, DomainError> {
+        Ok(0)
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<Post>, DomainError> {
+        Ok(self.post(id))
+    }
+
+    async fn create_with_id_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        id: Uuid,
+        description: Option<&str>,
+        url: Option<&str>,
+        user_id: Option<Uuid>,
+        theme_of_the_day_id: &str,
+        theme_date: Option<NaiveDate>,
+        evento_id: Option<Uuid>,
+        image_ids: &[Uuid],
+    ) -> Result<Post, DomainError> {
+        let post = Post {
+            id,
+            description: description.map(str::to_string),
+            url: url.map(str::to_string),
+            images: image_ids.iter().map(|i| PostImage::new(id, *i)).collect(),
+            user_id,
+            theme_of_the_day_id: Some(theme_of_the_day_id.to_string()),
+            theme_date,
+            evento_id,
+            hashtags: Vec::new(),
+            votes_count: 0,
+            likes_count: 0,
+            comments_count: 0,
+            liked_by_me: false,
+            created_at: None,
+        };
+        undo_on_rollback(uow, &self.posts);
+        self.posts.lock().unwrap().insert(id, post.clone());
+        Ok(post)
+    }
+
+    async fn add_image(&self, post_id: Uuid, image_id: Uuid, max_images: usize) -> Result<bool, DomainError> {
+        let mut posts = self.posts.lock().unwrap();
+        let Some(post) = posts.get_mut(&post_id) else {
+            return Ok(false);
+        };
+        if post.images.len() >= max_images {
+            return Ok(false);
+        }
+        post.images.push(PostImage::new(post_id, image_id));
+        Ok(true)
+    }
+
+    async fn reorder_images(&self, post_id: Uuid, image_ids: &[Uuid]) -> Result<(), DomainError> {
+        if let Some(post) = self.posts.lock().unwrap().get_mut(&post_id) {
+            post.images = image_ids.iter().map(|i| PostImage::new(post_id, *i)).collect();
+        }
+        Ok(())
+    }
+
+    async fn remove_image(&self, post_id: Uuid, image_id: Uuid) -> Result<bool, DomainError> {
+        let mut posts = self.posts.lock().unwrap();
+        let Some(post) = posts.get_mut(&post_id) else {
+            return Ok(false);
+        };
+        let before = post.images.len();
+        post.images.retain(|i| i.id != image_id);
+        Ok(post.images.len() < before)
+    }
+
+    async fn update_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        id: Uuid,
+        description: Option<&str>,
+        url: Option<&str>,
+        theme: Option<(&str, Option<NaiveDate>)>,
+    ) -> Result<Option<Post>, DomainError> {
+        undo_on_rollback(uow, &self.posts);
+        let mut posts = self.posts.lock().unwrap();
+        let Some(post) = posts.get_mut(&id) else {
+            return Ok(None);
+        };
+        if let Some(description) = description {
+            post.description = Some(description.to_string()).filter(|d| !d.is_empty());
+        }
+        if let Some(url) = url {
+            post.url = Some(url.to_string());
+        }
+        if let Some((mmdd, date)) = theme {
+            post.theme_of_the_day_id = Some(mmdd.to_string());
+            post.theme_date = date;
+        }
+        Ok(Some(post.clone()))
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.posts.lock().unwrap().remove(&id);
+        Ok(())
+    }
+
+    async fn add_vote(&self, post_id: Uuid, _: Uuid) -> Result<bool, DomainError> {
+        let mut posts = self.posts.lock().unwrap();
+        let post = posts.get_mut(&post_id).expect("post existente");
+        post.votes_count += 1;
+        Ok(true)
+    }
+
+    async fn remove_vote(&self, _: Uuid, _: Uuid) -> Result<bool, DomainError> {
+        Ok(false)
+    }
+
+    async fn get_leaderboard(&self, _: NaiveDate, _: u32) -> Result<Vec<Post>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn decide_daily_winner(&self, _: NaiveDate) -> Result<Option<DailyWinner>, DomainError> {
+        Ok(None)
+    }
+
+    async fn decide_daily_winners_until(&self, _: NaiveDate) -> Result<u64, DomainError> {
+        Ok(0)
+    }
+
+    async fn get_challenge_post_times(
+        &self,
+        _: Uuid,
+    ) -> Result<Vec<chrono::DateTime<chrono::Utc>>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn add_like(&self, _: Uuid, _: Uuid) -> Result<bool, DomainError> {
+        Ok(true)
+    }
+
+    async fn remove_like(&self, _: Uuid, _: Uuid) -> Result<bool, DomainError> {
+        Ok(false)
+    }
+
+    async fn get_liked_post_ids(&self, _: Uuid, _: &[Uuid]) -> Result<Vec<Uuid>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn get_comments(&self, post_id: Uuid) -> Result<Vec<PostComment>, DomainError> {
+        let comments = self.comments.lock().unwrap();
+        Ok(comments.values().filter(|c| c.post_id == post_id).cloned().collect())
+    }
+
+    async fn get_comment_by_id(&self, id: Uuid) -> Result<Option<PostComment>, DomainError> {
+        Ok(self.comment(id))
+    }
+
+    async fn get_comment_depth(&self, id: Uuid) -> Result<u32, DomainError> {
+        let comments = self.comments.lock().unwrap();
+        let mut depth = 0;
+        let mut parent = comments.get(&id).and_then(|c| c.parent_id);
+        while let Some(parent_id) = parent {
+            depth += 1;
+            parent = comments.get(&parent_id).and_then(|c| c.parent_id);
+        }
+        Ok(depth)
+    }
+
+    async fn create_comment(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        parent_id: Option<Uuid>,
+        body: &str,
+    ) -> Result<PostComment, DomainError> {
+        let mut comment = self.insert_comment(post_id, user_id);
+        comment.parent_id = parent_id;
+        comment.body = body.to_string();
+        self.comments.lock().unwrap().insert(comment.id, comment.clone());
+        Ok(comment)
+    }
+
+    async fn update_comment(&self, id: Uuid, body: &str) -> Result<Option<PostComment>, DomainError> {
+        let mut comments = self.comments.lock().unwrap();
+        Ok(comments.get_mut(&id).map(|c| {
+            c.body = body.to_string();
+            c.clone()
+        }))
+    }
+
+    async fn delete_comment(&self, id: Uuid) -> Result<(), DomainError> {
+        self.comments.lock().unwrap().remove(&id);
+        Ok(())
+    }
+}
+
+/// Hashtags y sus asociaciones con poses y posts, en memoria. Asociar un hashtag que no existe falla
+/// con NotFound, como la clave foránea en la base de datos.
+#[derive(Default)]
+pub struct FakeHashtagsRepository {
+    hashtags: Arc<Mutex<Vec<Hashtag>>>,
+    /// (post_id, hashtag_id)
+    post_links: Arc<Mutex<Vec<(Uuid, Uuid)>>>,
+    /// (pose_id, hashtag_id)
+    pose_links: Arc<Mutex<Vec<(Uuid, Uuid)>>>,
+}
+
+impl FakeHashtagsRepository {
+    pub fn insert(&self, name: &str) -> Hashtag {
+        let hashtag = Hashtag {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            parent_id: None,
+        };
+        self.hashtags.lock().unwrap().push(hashtag.clone());
+        hashtag
+    }
+
+    /// Hashtags de un post, ordenados por id.
+    pub fn post_hashtag_ids(&self, post_id: Uuid) -> Vec<Uuid> {
+        let mut ids: Vec<Uuid> = self
+            .post_links
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(p, _)| *p == post_id)
+            .map(|(_, h)| *h)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// Pares (pose_id, hashtag_id) en el orden en que se asociaron.
+    pub fn pose_links(&self) -> Vec<(Uuid, Uuid)> {
+        self.pose_links.lock().unwrap().clone()
+    }
+
+    fn get(&self, id: Uuid) -> Option<Hashtag> {
+        self.hashtags.lock().unwrap().iter().find(|h| h.id == id).cloned()
+    }
+
+    fn require(&self, id: Uuid) -> Result<(), DomainError> {
+        match self.get(id) {
+            Some(_) => Ok(()),
+            None => Err(DomainError::NotFound(format!("Hashtag no encontrado: {}", id))),
+        }
+    }
+
+    fn by_post(&self, post_id: Uuid) -> Vec<Hashtag> {
+        let mut hashtags: Vec<Hashtag> = self
+            .post_hashtag_ids(post_id)
+            .into_iter()
+            .filter_map(|id| self.get(id))
+            .collect();
+        hashtags.sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id)));
+        hashtags
+    }
+
+    fn link_post(&self, post_id: Uuid, hashtag_ids: &[Uuid]) -> Result<(), DomainError> {
+        for id in hashtag_ids {
+            self.require(*id)?;
+        }
+        let mut links = self.post_links.lock().unwrap();
+        for id in hashtag_ids {
+            if !links.contains(&(post_id, *id)) {
+                links.push((post_id, *id));
+            }
+        }
+        Ok(())
+    }
+
+    fn unlink_post(&self, post_id: Uuid, hashtag_ids: &[Uuid]) {
+        self.post_links
+            .lock()
+            .unwrap()
+            .retain(|(p, h)| *p != post_id || !hashtag_ids.contains(h));
+    }
+
+    fn link_pose(&self, pose_id: Uuid, hashtag_id: Uuid) -> Result<bool, DomainError> {
+        self.require(hashtag_id)?;
+        let mut links = self.pose_links.lock().unwrap();
+        if links.contains(&(pose_id, hashtag_id)) {
+            return Ok(false);
+        }
+        links.push((pose_id, hashtag_id));
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl HashtagsRepository for FakeHashtagsRepository {
+    async fn get_all(&self, _: &ListQuery) -> Result<Vec<HashtagUsage>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn get_paginated(&self, _: &ListQuery, _: u32, _: u32) -> Result<Vec<HashtagUsage>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn count(&self, _: &ListQuery) -> Result<u64, DomainError> {
+        Ok(0)
+    }
+
+    async fn suggest(&self, _: &str, _: u32) -> Result<Vec<HashtagUsage>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn get_related(&self, _: Uuid, _: u32) -> Result<Vec<RelatedHashtag>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<Hashtag>, DomainError> {
+        Ok(self.get(id))
+    }
+
+    async fn get_by_name(&self, name: &str) -> Result<Option<Hashtag>, DomainError> {
+        let hashtags = self.hashtags.lock().unwrap();
+        Ok(hashtags
+            .iter()
+            .find(|h| Hashtag::normalize_name(&h.name) == name)
+            .cloned())
+    }
+
+    async fn create(&self, name: &str, parent_id: Option<Uuid>) -> Result<Hashtag, DomainError> {
+        let hashtag = Hashtag {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            parent_id,
+        };
+        self.hashtags.lock().unwrap().push(hashtag.clone());
+        Ok(hashtag)
+    }
+
+    async fn update(
+        &self,
+        _: Uuid,
+        _: Option<&str>,
+        _: Option<Option<Uuid>>,
+    ) -> Result<Option<Hashtag>, DomainError> {
+        Ok(None)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.hashtags.lock().unwrap().retain(|h| h.id != id);
+        Ok(())
+    }
+
+    async fn merge(&self, _: Uuid, _: Uuid) -> Result<(), DomainError> {
+        Ok(())
+    }
+
+    async fn get_or_create_by_names_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        names: &[String],
+    ) -> Result<Vec<Hashtag>, DomainError> {
+        undo_on_rollback(uow, &self.hashtags);
+        let mut out = Vec::with_capacity(names.len());
+        for name in names {
+            match self.get_by_name(name).await? {
+                Some(hashtag) => out.push(hashtag),
+                None => out.push(self.insert(name)),
+            }
+        }
+        Ok(out)
+    }
+
+    async fn get_hashtags_by_pose(&self, pose_id: Uuid) -> Result<Vec<Hashtag>, DomainError> {
+        Ok(self
+            .get_hashtags_by_poses(&[pose_id])
+            .await?
+            .into_iter()
+            .map(|(_, h)| h)
+            .collect())
+    }
+
+    async fn get_hashtags_by_poses(&self, pose_ids: &[Uuid]) -> Result<Vec<(Uuid, Hashtag)>, DomainError> {
+        Ok(self
+            .pose_links()
+            .into_iter()
+            .filter(|(p, _)| pose_ids.contains(p))
+            .filter_map(|(p, h)| self.get(h).map(|h| (p, h)))
+            .collect())
+    }
+
+    async fn get_hashtags_by_post(&self, post_id: Uuid) -> Result<Vec<Hashtag>, DomainError> {
+        Ok(self.by_post(post_id))
+    }
+
+    async fn get_hashtags_by_post_in(
+        &self,
+        _: &mut dyn UnitOfWork,
+        post_id: Uuid,
+    ) -> Result<Vec<Hashtag>, DomainError> {
+        Ok(self.by_post(post_id))
+    }
+
+    async fn add_hashtags_to_post(&self, post_id: Uuid, hashtag_ids: &[Uuid]) -> Result<(), DomainError> {
+        self.link_post(post_id, hashtag_ids)
+    }
+
+    async fn add_hashtags_to_post_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        post_id: Uuid,
+        hashtag_ids: &[Uuid],
+    ) -> Result<(), DomainError> {
+        undo_on_rollback(uow, &self.post_links);
+        self.link_post(post_id, hashtag_ids)
+    }
+
+    async fn remove_hashtags_from_post_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        post_id: Uuid,
+        hashtag_ids: &[Uuid],
+    ) -> Result<(), DomainError> {
+        undo_on_rollback(uow, &self.post_links);
+        self.unlink_post(post_id, hashtag_ids);
+        Ok(())
+    }
+
+    async fn add_hashtag_to_pose(&self, pose_id: Uuid, hashtag_id: Uuid) -> Result<(), DomainError> {
+        self.link_pose(pose_id, hashtag_id).map(|_| ())
+    }
+
+    async fn add_hashtag_to_pose_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        pose_id: Uuid,
+        hashtag_id: Uuid,
+    ) -> Result<(), DomainError> {
+        undo_on_rollback(uow, &self.pose_links);
+        self.link_pose(pose_id, hashtag_id).map(|_| ())
+    }
+
+    async fn remove_hashtag_from_pose(&self, pose_id: Uuid, hashtag_id: Uuid) -> Result<(), DomainError> {
+        self.pose_links
+            .lock()
+            .unwrap()
+            .retain(|link| *link != (pose_id, hashtag_id));
+        Ok(())
+    }
+
+    async fn remove_all_hashtags_from_pose_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        pose_id: Uuid,
+    ) -> Result<(), DomainError> {
+        self.remove_all_hashtags_from_poses_in(uow, &[pose_id]).await
+    }
+
+    async fn lock_existing_ids_in(
+        &self,
+        _: &mut dyn UnitOfWork,
+        ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, DomainError> {
+        Ok(ids.iter().copied().filter(|id| self.get(*id).is_some()).collect())
+    }
+
+    async fn add_hashtags_to_poses_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        pose_ids: &[Uuid],
+        hashtag_ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, DomainError> {
+        undo_on_rollback(uow, &self.pose_links);
+        let mut added = Vec::new();
+        for pose_id in pose_ids {
+            for hashtag_id in hashtag_ids {
+                if self.link_pose(*pose_id, *hashtag_id)? {
+                    added.push(*pose_id);
+                }
+            }
+        }
+        Ok(added)
+    }
+
+    async fn remove_hashtags_from_poses_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        pose_ids: &[Uuid],
+        hashtag_ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, DomainError> {
+        undo_on_rollback(uow, &self.pose_links);
+        let mut links = self.pose_links.lock().unwrap();
+        let removed: Vec<Uuid> = links
+            .iter()
+            .filter(|(p, h)| pose_ids.contains(p) && hashtag_ids.contains(h))
+            .map(|(p, _)| *p)
+            .collect();
+        links.retain(|(p, h)| !(pose_ids.contains(p) && hashtag_ids.contains(h)));
+        Ok(removed)
+    }
+
+    async fn remove_all_hashtags_from_poses_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        pose_ids: &[Uuid],
+    ) -> Result<(), DomainError> {
+        undo_on_rollback(uow, &self.pose_links);
+        self.pose_links.lock().unwrap().retain(|(p, _)| !pose_ids.contains(p));
+        Ok(())
+    }
+
+    async fn get_poses_by_hashtag(&self, _: Uuid, _: bool) -> Result<Vec<Pose>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn get_poses_by_hashtag_paginated(
+        &self,
+        _: Uuid,
+        _: bool,
+        _: u32,
+        _: u32,
+    ) -> Result<Vec<Pose>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn count_poses_by_hashtag(&self, _: Uuid, _: bool) -> Result<u64, DomainError> {
+        Ok(0)
+    }
+
+    async fn get_poses_by_tag_filter(
+        &self,
+        _: &PoseTagFilter,
+        _: &PoseFilter,
+        _: &ListQuery,
+        _: u32,
+        _: u32,
+    ) -> Result<Vec<Pose>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn count_poses_by_tag_filter(
+        &self,
+        _: &PoseTagFilter,
+        _: &PoseFilter,
+        _: &ListQuery,
+    ) -> Result<u64, DomainError> {
+        Ok(0)
+    }
+}
+
+/// Poses en memoria. Con `fail_delete`, borrar falla como una base de datos caída.
+#[derive(Default)]
+pub struct FakePosesRepository {
+    poses: Arc<Mutex<HashMap<Uuid, Pose>>>,
+    pub fail_delete: AtomicBool,
+}
+
+impl FakePosesRepository {
+    pub fn insert(&self) -> Pose {
+        let id = Uuid::new_v4();
+        let pose = Pose {
+            id,
+            url: format!("/api/poses/{}/image", id),
+            metadata: PoseMetadata::default(),
+            created_at: None,
+        };
+        self.poses.lock().unwrap().insert(id, pose.clone());
+        pose
+    }
+
+    pub fn pose(&self, id: Uuid) -> Option<Pose> {
+        self.poses.lock().unwrap().get(&id).cloned()
+    }
+
+    fn delete_existing(&self, ids: &[Uuid]) -> Result<Vec<Uuid>, DomainError> {
+        if self.fail_delete.load(Ordering::SeqCst) {
+            return Err(DomainError::Repository(anyhow::anyhow!("sin conexión")));
+        }
+        let mut poses = self.poses.lock().unwrap();
+        Ok(ids.iter().copied().filter(|id| poses.remove(id).is_some()).collect())
+    }
+}
+
+#[async_trait]
+impl PosesRepository for FakePosesRepository {
+    async fn get_paginated(&self, _: &PoseFilter, _: &ListQuery, _: u32, _: u32) -> Result<Vec<Pose>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn get_page_after(
+        &self,
+        _: &PoseFilter,
+        _: &ListQuery,
+        _: Option<&PageCursor>,
+        _: u32,
+    ) -> Result<Vec<Pose>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn count(&self, _: &PoseFilter, _: &ListQuery) -> Result<u64, DomainError> {
+        Ok(0)
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<Pose>, DomainError> {
+        Ok(self.pose(id))
+    }
+
+    async fn create_with_id_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        id: Uuid,
+        url: &str,
+        metadata: &PoseMetadata,
+        _: Option<i64>,
+    ) -> Result<Pose, DomainError> {
+        let pose = Pose {
+            id,
+            url: url.to_string(),
+            metadata: metadata.clone(),
+            created_at: None,
+        };
+        undo_on_rollback(uow, &self.poses);
+        self.poses.lock().unwrap().insert(id, pose.clone());
+        Ok(pose)
+    }
+
+    async fn get_ids_without_image_hash(&self) -> Result<Vec<Uuid>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn set_image_hash(&self, _: Uuid, _: i64) -> Result<(), DomainError> {
+        Ok(())
+    }
+
+    async fn refresh_hashtag_idf(&self) -> Result<(), DomainError> {
+        Ok(())
+    }
+
+    async fn update_metadata(&self, id: Uuid, metadata: &PoseMetadata) -> Result<Option<Pose>, DomainError> {
+        let mut poses = self.poses.lock().unwrap();
+        Ok(poses.get_mut(&id).map(|pose| {
+            pose.metadata = metadata.clone();
+            pose.clone()
+        }))
+    }
+
+    async fn delete_in(&self, uow: &mut dyn UnitOfWork, id: Uuid) -> Result<(), DomainError> {
+        undo_on_rollback(uow, &self.poses);
+        self.delete_existing(&[id]).map(|_| ())
+    }
+
+    async fn delete_many_in(&self, uow: &mut dyn UnitOfWork, ids: &[Uuid]) -> Result<Vec<Uuid>, DomainError> {
+        undo_on_rollback(uow, &self.poses);
+        self.delete_existing(ids)
+    }
+
+    async fn lock_existing_ids_in(&self, _: &mut dyn UnitOfWork, ids: &[Uuid]) -> Result<Vec<Uuid>, DomainError> {
+        Ok(ids.iter().copied().filter(|id| self.pose(*id).is_some()).collect())
+    }
+
+    async fn get_similar(&self, _: Uuid, _: u32) -> Result<Vec<ScoredPose>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn get_recommended(&self, _: Uuid, _: u32) -> Result<Vec<ScoredPose>, DomainError> {
+        Ok(Vec::new())
+    }
+}
+
+/// Sesiones y sus poses en memoria.
+#[derive(Default)]
+pub struct FakeSesionesRepository {
+    sesiones: Arc<Mutex<HashMap<Uuid, Sesion>>>,
+    /// (sesion_id, pose_id), en el orden en que se añadieron.
+    pose_links: Arc<Mutex<Vec<(Uuid, Uuid)>>>,
+}
+
+impl FakeSesionesRepository {
+    pub fn sesiones(&self) -> Vec<Sesion> {
+        self.sesiones.lock().unwrap().values().cloned().collect()
+    }
+
+    pub fn pose_ids(&self, sesion_id: Uuid) -> Vec<Uuid> {
+        self.pose_links
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(s, _)| *s == sesion_id)
+            .map(|(_, p)| *p)
+            .collect()
+    }
+
+    fn insert(&self, name: &str) -> Sesion {
+        let sesion = Sesion {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            created_at: None,
+            cover_url: String::new(),
+        };
+        self.sesiones.lock().unwrap().insert(sesion.id, sesion.clone());
+        sesion
+    }
+
+    fn link(&self, sesion_id: Uuid, pose_ids: &[Uuid]) -> Vec<Uuid> {
+        let mut links = self.pose_links.lock().unwrap();
+        let mut added = Vec::new();
+        for pose_id in pose_ids {
+            if !links.contains(&(sesion_id, *pose_id)) {
+                links.push((sesion_id, *pose_id));
+                added.push(*pose_id);
+            }
+        }
+        added
+    }
+}
+
+#[async_trait]
+impl SesionesRepository for FakeSesionesRepository {
+    async fn get_paginated(&self, _: &ListQuery, _: u32, _: u32) -> Result<Vec<Sesion>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn count(&self, _: &ListQuery) -> Result<u64, DomainError> {
+        Ok(0)
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<Sesion>, DomainError> {
+        Ok(self.sesiones.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn get_poses_by_sesion_paginated(&self, _: Uuid, _: u32, _: u32) -> Result<Vec<Pose>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn count_poses_by_sesion(&self, sesion_id: Uuid) -> Result<u64, DomainError> {
+        Ok(self.pose_ids(sesion_id).len() as u64)
+    }
+
+    async fn get_poses_by_sesiones(&self, _: &[Uuid]) -> Result<Vec<(Uuid, Pose)>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn get_sesiones_by_poses(&self, _: &[Uuid]) -> Result<Vec<(Uuid, Sesion)>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn create(&self, name: &str) -> Result<Sesion, DomainError> {
+        Ok(self.insert(name))
+    }
+
+    async fn create_in(&self, uow: &mut dyn UnitOfWork, name: &str) -> Result<Sesion, DomainError> {
+        undo_on_rollback(uow, &self.sesiones);
+        Ok(self.insert(name))
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.sesiones.lock().unwrap().remove(&id);
+        self.pose_links.lock().unwrap().retain(|(s, _)| *s != id);
+        Ok(())
+    }
+
+    async fn add_poses_to_sesion(&self, sesion_id: Uuid, pose_ids: &[Uuid]) -> Result<(), DomainError> {
+        self.link(sesion_id, pose_ids);
+        Ok(())
+    }
+
+    async fn add_poses_to_sesion_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        sesion_id: Uuid,
+        pose_ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, DomainError> {
+        undo_on_rollback(uow, &self.pose_links);
+        Ok(self.link(sesion_id, pose_ids))
+    }
+
+    async fn lock_existing_ids_in(&self, _: &mut dyn UnitOfWork, ids: &[Uuid]) -> Result<Vec<Uuid>, DomainError> {
+        let sesiones = self.sesiones.lock().unwrap();
+        Ok(ids.iter().copied().filter(|id| sesiones.contains_key(id)).collect())
+    }
+
+    async fn remove_pose_from_sesion(&self, sesion_id: Uuid, pose_id: Uuid) -> Result<(), DomainError> {
+        self.pose_links
+            .lock()
+            .unwrap()
+            .retain(|link| *link != (sesion_id, pose_id));
+        Ok(())
+    }
+
+    async fn update_cover(&self, sesion_id: Uuid, cover_url: &str) -> Result<Option<Sesion>, DomainError> {
+        let mut sesiones = self.sesiones.lock().unwrap();
+        Ok(sesiones.get_mut(&sesion_id).map(|sesion| {
+            sesion.cover_url = cover_url.to_string();
+            sesion.clone()
+        }))
+    }
+}
+
+/// Favoritos en memoria, del más reciente al más antiguo. Con `fail_remove`, quitar favoritos falla
+/// como una base de datos caída.
+#[derive(Default)]
+pub struct FakeFavoritesRepository {
+    /// (user_id, pose)
+    favorites: Arc<Mutex<Vec<(Uuid, Pose)>>>,
+    pub fail_remove: AtomicBool,
+}
+
+impl FakeFavoritesRepository {
+    pub fn insert(&self, user_id: Uuid, pose: Pose) {
+        self.favorites.lock().unwrap().insert(0, (user_id, pose));
+    }
+
+    fn of(&self, user_id: Uuid) -> Vec<Pose> {
+        self.favorites
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(u, _)| *u == user_id)
+            .map(|(_, p)| p.clone())
+            .collect()
+    }
+
+    fn remove(&self, user_id: Uuid, pose_ids: &[Uuid]) -> Result<(), DomainError> {
+        if self.fail_remove.load(Ordering::SeqCst) {
+            return Err(DomainError::Repository(anyhow::anyhow!("sin conexión")));
+        }
+        self.favorites
+            .lock()
+            .unwrap()
+            .retain(|(u, p)| *u != user_id || !pose_ids.contains(&p.id));
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl FavoritesRepository for FakeFavoritesRepository {
+    async fn is_pose_favorite(&self, user_id: Uuid, pose_id: Uuid) -> Result<bool, DomainError> {
+        Ok(self.of(user_id).iter().any(|p| p.id == pose_id))
+    }
+
+    async fn get_favorite_pose_ids(&self, user_id: Uuid, pose_ids: &[Uuid]) -> Result<Vec<Uuid>, DomainError> {
+        Ok(self
+            .of(user_id)
+            .into_iter()
+            .map(|p| p.id)
+            .filter(|id| pose_ids.contains(id))
+            .collect())
+    }
+
+    async fn add_pose_to_favorites(&self, _: Uuid, _: Uuid) -> Result<(), DomainError> {
+        Ok(())
+    }
+
+    async fn remove_pose_from_favorites(&self, user_id: Uuid, pose_id: Uuid) -> Result<(), DomainError> {
+        self.remove(user_id, &[pose_id])
+    }
+
+    async fn remove_poses_from_favorites(&self, user_id: Uuid, pose_ids: &[Uuid]) -> Result<(), DomainError> {
+        self.remove(user_id, pose_ids)
+    }
+
+    async fn remove_poses_from_favorites_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        user_id: Uuid,
+        pose_ids: &[Uuid],
+    ) -> Result<(), DomainError> {
+        undo_on_rollback(uow, &self.favorites);
+        self.remove(user_id, pose_ids)
+    }
+
+    async fn get_favorite_poses(&self, user_id: Uuid) -> Result<Vec<Pose>, DomainError> {
+        Ok(self.of(user_id))
+    }
+
+    async fn get_favorite_poses_in(&self, _: &mut dyn UnitOfWork, user_id: Uuid) -> Result<Vec<Pose>, DomainError> {
+        Ok(self.of(user_id))
+    }
+
+    async fn get_favorite_poses_paginated(&self, _: Uuid, _: u32, _: u32) -> Result<Vec<Pose>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn count_favorite_poses(&self, user_id: Uuid) -> Result<u64, DomainError> {
+        Ok(self.of(user_id).len() as u64)
+    }
+}
+
+/// Eventos en memoria (sin asistentes).
+#[derive(Default)]
+pub struct FakeEventosRepository {
+    eventos: Mutex<HashMap<Uuid, Evento>>,
+}
+
+impl FakeEventosRepository {
+    pub fn insert(&self, name: &str) -> Evento {
+        let evento = Evento {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            place: "Parque".to_string(),
+            mmdd: "0615".to_string(),
+            url: String::new(),
+            created_at: None,
+            capacity: None,
+            attendees_count: 0,
+            posts_count: 0,
+        };
+        self.eventos.lock().unwrap().insert(evento.id, evento.clone());
+        evento
+    }
+}
+
+#[async_trait]
+impl EventosRepository for FakeEventosRepository {
+    async fn get_all(&self, _: &ListQuery) -> Result<Vec<Evento>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn get_paginated(&self, _: &ListQuery, _: u32, _: u32) -> Result<Vec<Evento>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn count(&self, _: &ListQuery) -> Result<u64, DomainError> {
+        Ok(0)
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<Evento>, DomainError> {
+        Ok(self.eventos.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn create_with_id(
+        &self,
+        _: Uuid,
+        name: &str,
+        _: &str,
+        _: &str,
+        _: &str,
+        _: Option<i32>,
+    ) -> Result<Evento, DomainError> {
+        Ok(self.insert(name))
+    }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        _: Option<&str>,
+        _: Option<&str>,
+        _: Option<&str>,
+        _: Option<&str>,
+        _: Option<i32>,
+    ) -> Result<Option<Evento>, DomainError> {
+        self.get_by_id(id).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
+        self.eventos.lock().unwrap().remove(&id);
+        Ok(())
+    }
+
+    async fn add_attendee(&self, _: Uuid, _: Uuid) -> Result<Option<EventoAsistente>, DomainError> {
+        Ok(None)
+    }
+
+    async fn remove_attendee(&self, _: Uuid, _: Uuid) -> Result<(), DomainError> {
+        Ok(())
+    }
+
+    async fn get_attendees(&self, _: Uuid) -> Result<Vec<EventoAsistente>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn get_by_attendee(&self, _: Uuid) -> Result<Vec<(Evento, AttendanceStatus)>, DomainError> {
+        Ok(Vec::new())
+    }
+}
@@ -5,17 +5,24 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 #[derive(Clone)]
-pub struct GetFavoritePosesUseCase {
+pub struct GetFavoritePosesPaginatedUseCase {
     repo: Arc<dyn FavoritesRepository>,
 }
 
-impl GetFavoritePosesUseCase {
+impl GetFavoritePosesPaginatedUseCase {
     pub fn new(repo: Arc<dyn FavoritesRepository>) -> Self {
         Self { repo }
     }
 
-    pub async fn execute(&self, user_id: Uuid) -> Result<Vec<Pose>, DomainError> {
-        self.repo.get_favorite_poses(user_id).await
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        page: u32,
+        limit: u32,
+    ) -> Result<(Vec<Pose>, u64), DomainError> {
+        let items = self.repo.get_favorite_poses_paginated(user_id, page, limit).await?;
+        let total = self.repo.count_favorite_poses(user_id).await?;
+        Ok((items, total))
     }
 }
 
@@ -1,21 +1,71 @@
 // Casos de uso de Hashtags (alineados con Kotlin domain/cases/hashtags)
 
-use crate::domain::{DomainError, Hashtag, HashtagsRepository};
+use crate::domain::{
+    DomainError, Hashtag, HashtagTreeNode, HashtagUsage, HashtagsRepository, ListQuery,
+    PostsRepository, RelatedHashtag, UnitOfWorkFactory,
+};
+use std::collections::HashSet;
 use std::sync::Arc;
 use uuid::Uuid;
 
 #[derive(Clone)]
-pub struct GetHashtagsUseCase {
+pub struct GetHashtagsPaginatedUseCase {
     repo: Arc<dyn HashtagsRepository>,
 }
 
-impl GetHashtagsUseCase {
+impl GetHashtagsPaginatedUseCase {
     pub fn new(repo: Arc<dyn HashtagsRepository>) -> Self {
         Self { repo }
     }
 
-    pub async fn execute(&self) -> Result<Vec<Hashtag>, DomainError> {
-        self.repo.get_all().await
+    pub async fn execute(
+        &self,
+        list: &ListQuery,
+        page: u32,
+        limit: u32,
+    ) -> Result<(Vec<HashtagUsage>, u64), DomainError> {
+        let items = self.repo.get_paginated(list, page, limit).await?;
+        let total = self.repo.count(list).await?;
+        Ok((items, total))
+    }
+}
+
+/// Autocompletado de hashtags por prefijo (se normaliza igual que los nombres: "#Retr" → "retr").
+#[derive(Clone)]
+pub struct SuggestHashtagsUseCase {
+    repo: Arc<dyn HashtagsRepository>,
+}
+
+impl SuggestHashtagsUseCase {
+    pub fn new(repo: Arc<dyn HashtagsRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(&self, prefix: &str, limit: u32) -> Result<Vec<HashtagUsage>, DomainError> {
+        self.repo.suggest(&Hashtag::normalize_name(prefix), limit).await
+    }
+}
+
+/// Hashtags relacionados: los que más se usan junto a uno dado en las mismas poses.
+#[derive(Clone)]
+pub struct GetRelatedHashtagsUseCase {
+    repo: Arc<dyn HashtagsRepository>,
+}
+
+impl GetRelatedHashtagsUseCase {
+    pub fn new(repo: Arc<dyn HashtagsRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(
+        &self,
+        hashtag_id: Uuid,
+        limit: u32,
+    ) -> Result<Vec<RelatedHashtag>, DomainError> {
+        if self.repo.get_by_id(hashtag_id).await?.is_none() {
+            return Err(DomainError::NotFound(format!("Hashtag no encontrado: {}", hashtag_id)));
+        }
+        self.repo.get_related(hashtag_id, limit).await
     }
 }
 
@@ -47,12 +97,130 @@ impl CreateHashtagUseCase {
         Self { repo }
     }
 
-    pub async fn execute(&self, name: &str) -> Result<Hashtag, DomainError> {
-        let name = name.trim();
-        if name.is_empty() {
-            return Err(DomainError::Validation("El nombre del hashtag es requerido".to_string()));
+    /// Guarda el nombre normalizado ("#Retrato" → "retrato"); falla si ya existe.
+    /// `parent_id`: hashtag padre en la taxonomía (opcional).
+    pub async fn execute(
+        &self,
+        name: &str,
+        parent_id: Option<Uuid>,
+    ) -> Result<Hashtag, DomainError> {
+        let name = Hashtag::canonical_name(name)?;
+        if self.repo.get_by_name(&name).await?.is_some() {
+            return Err(DomainError::Validation(format!("Ya existe el hashtag: {}", name)));
+        }
+        if let Some(parent_id) = parent_id {
+            ensure_parent_exists(self.repo.as_ref(), parent_id).await?;
+        }
+        self.repo.create(&name, parent_id).await
+    }
+}
+
+async fn ensure_parent_exists(
+    repo: &dyn HashtagsRepository,
+    parent_id: Uuid,
+) -> Result<(), DomainError> {
+    if repo.get_by_id(parent_id).await?.is_none() {
+        return Err(DomainError::Validation(format!(
+            "Hashtag padre no encontrado: {}",
+            parent_id
+        )));
+    }
+    Ok(())
+}
+
+/// Edita un hashtag: nombre (normalizado; si ya lo usa otro hashtag, hay que fusionarlos) y padre.
+/// Un hashtag no puede colgar de sí mismo ni de uno de sus descendientes.
+#[derive(Clone)]
+pub struct UpdateHashtagUseCase {
+    repo: Arc<dyn HashtagsRepository>,
+}
+
+impl UpdateHashtagUseCase {
+    pub fn new(repo: Arc<dyn HashtagsRepository>) -> Self {
+        Self { repo }
+    }
+
+    /// Los campos ausentes no cambian; `parent_id: Some(None)` lo convierte en raíz.
+    pub async fn execute(
+        &self,
+        id: Uuid,
+        name: Option<&str>,
+        parent_id: Option<Option<Uuid>>,
+    ) -> Result<Hashtag, DomainError> {
+        if self.repo.get_by_id(id).await?.is_none() {
+            return Err(DomainError::NotFound(format!("Hashtag no encontrado: {}", id)));
         }
-        self.repo.create(name).await
+        let name = match name {
+            Some(name) => {
+                let name = Hashtag::canonical_name(name)?;
+                if let Some(other) = self.repo.get_by_name(&name).await? {
+                    if other.id != id {
+                        return Err(DomainError::Validation(format!(
+                            "Ya existe el hashtag: {} (usa merge para unirlos)",
+                            name
+                        )));
+                    }
+                }
+                Some(name)
+            }
+            None => None,
+        };
+        // El ciclo lo comprueba el repositorio en la transacción del cambio.
+        if let Some(Some(parent_id)) = parent_id {
+            ensure_parent_exists(self.repo.as_ref(), parent_id).await?;
+        }
+        self.repo
+            .update(id, name.as_deref(), parent_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Hashtag no encontrado: {}", id)))
+    }
+}
+
+/// Árbol de hashtags (raíces y sus descendientes, por nombre) con su uso.
+#[derive(Clone)]
+pub struct GetHashtagTreeUseCase {
+    repo: Arc<dyn HashtagsRepository>,
+}
+
+impl GetHashtagTreeUseCase {
+    pub fn new(repo: Arc<dyn HashtagsRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(&self) -> Result<Vec<HashtagTreeNode>, DomainError> {
+        let hashtags = self.repo.get_all(&ListQuery::default()).await?;
+        Ok(HashtagTreeNode::build(hashtags))
+    }
+}
+
+/// Fusiona un hashtag en otro: sus poses y posts pasan al destino y el origen se elimina.
+#[derive(Clone)]
+pub struct MergeHashtagUseCase {
+    repo: Arc<dyn HashtagsRepository>,
+}
+
+impl MergeHashtagUseCase {
+    pub fn new(repo: Arc<dyn HashtagsRepository>) -> Self {
+        Self { repo }
+    }
+
+    /// Devuelve el hashtag destino.
+    pub async fn execute(&self, source_id: Uuid, target_id: Uuid) -> Result<Hashtag, DomainError> {
+        if source_id == target_id {
+            return Err(DomainError::Validation(
+                "No se puede fusionar un hashtag consigo mismo".to_string(),
+            ));
+        }
+        for id in [source_id, target_id] {
+            if self.repo.get_by_id(id).await?.is_none() {
+                return Err(DomainError::NotFound(format!("Hashtag no encontrado: {}", id)));
+            }
+        }
+        self.repo.merge(source_id, target_id).await?;
+        self.repo
+            .get_by_id(target_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Hashtag no encontrado: {}", target_id)))
     }
 }
 
@@ -102,3 +270,160 @@ impl AddHashtagsToPostUseCase {
         self.repo.add_hashtags_to_post(post_id, hashtag_ids).await
     }
 }
+
+/// Hashtags asociados a un post. NotFound si el post no existe.
+#[derive(Clone)]
+pub struct GetHashtagsByPostUseCase {
+    repo: Arc<dyn HashtagsRepository>,
+    posts_repo: Arc<dyn PostsRepository>,
+}
+
+impl GetHashtagsByPostUseCase {
+    pub fn new(repo: Arc<dyn HashtagsRepository>, posts_repo: Arc<dyn PostsRepository>) -> Self {
+        Self { repo, posts_repo }
+    }
+
+    pub async fn execute(&self, post_id: Uuid) -> Result<Vec<Hashtag>, DomainError> {
+        if self.posts_repo.get_by_id(post_id).await?.is_none() {
+            return Err(DomainError::NotFound(format!("Post no encontrado: {}", post_id)));
+        }
+        self.repo.get_hashtags_by_post(post_id).await
+    }
+}
+
+/// Reemplaza los hashtags de un post (añade los nuevos y quita los que ya no están) en una sola
+/// unidad de trabajo.
+#[derive(Clone)]
+pub struct UpdatePostHashtagsUseCase {
+    repo: Arc<dyn HashtagsRepository>,
+    unit_of_work: Arc<dyn UnitOfWorkFactory>,
+}
+
+impl UpdatePostHashtagsUseCase {
+    pub fn new(repo: Arc<dyn HashtagsRepository>, unit_of_work: Arc<dyn UnitOfWorkFactory>) -> Self {
+        Self { repo, unit_of_work }
+    }
+
+    /// Validation si algún hashtag no existe; en ese caso el post no cambia.
+    pub async fn execute(
+        &self,
+        post_id: Uuid,
+        hashtag_ids: &[Uuid],
+    ) -> Result<Vec<Hashtag>, DomainError> {
+        let new_ids: HashSet<Uuid> = hashtag_ids.iter().copied().collect();
+        let requested: Vec<Uuid> = new_ids.iter().copied().collect();
+        let mut uow = self.unit_of_work.begin().await?;
+        let existing: HashSet<Uuid> = self
+            .repo
+            .lock_existing_ids_in(&mut *uow, &requested)
+            .await?
+            .into_iter()
+            .collect();
+        if let Some(missing) = requested.iter().find(|id| !existing.contains(id)) {
+            return Err(DomainError::Validation(format!("Hashtag no encontrado: {}", missing)));
+        }
+        let current = self.repo.get_hashtags_by_post_in(&mut *uow, post_id).await?;
+        let current_ids: HashSet<Uuid> = current.into_iter().map(|h| h.id).collect();
+        let to_add: Vec<Uuid> = new_ids.difference(&current_ids).copied().collect();
+        let to_remove: Vec<Uuid> = current_ids.difference(&new_ids).copied().collect();
+        if !to_add.is_empty() {
+            self.repo
+                .add_hashtags_to_post_in(&mut *uow, post_id, &to_add)
+                .await?;
+        }
+        if !to_remove.is_empty() {
+            self.repo
+                .remove_hashtags_from_post_in(&mut *uow, post_id, &to_remove)
+                .await?;
+        }
+        let hashtags = self.repo.get_hashtags_by_post_in(&mut *uow, post_id).await?;
+        uow.commit().await?;
+        Ok(hashtags)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::fakes::{
+        FakeHashtagsRepository, FakePostsRepository, FakeUnitOfWorkFactory,
+    };
+
+    fn update_uc(
+        hashtags: &Arc<FakeHashtagsRepository>,
+        unit_of_work: &Arc<FakeUnitOfWorkFactory>,
+    ) -> UpdatePostHashtagsUseCase {
+        UpdatePostHashtagsUseCase::new(
+            Arc::clone(hashtags) as Arc<dyn HashtagsRepository>,
+            Arc::clone(unit_of_work) as Arc<dyn UnitOfWorkFactory>,
+        )
+    }
+
+    #[tokio::test]
+    async fn replaces_post_hashtags_in_one_unit_of_work() {
+        let hashtags = Arc::new(FakeHashtagsRepository::default());
+        let unit_of_work = Arc::new(FakeUnitOfWorkFactory::default());
+        let playa = hashtags.insert("playa");
+        let retrato = hashtags.insert("retrato");
+        let noche = hashtags.insert("noche");
+        let post_id = Uuid::new_v4();
+        hashtags.add_hashtags_to_post(post_id, &[playa.id, retrato.id]).await.unwrap();
+
+        let result = update_uc(&hashtags, &unit_of_work)
+            .execute(post_id, &[retrato.id, noche.id, noche.id])
+            .await
+            .unwrap();
+
+        let names: Vec<&str> = result.iter().map(|h| h.name.as_str()).collect();
+        assert_eq!(names, ["noche", "retrato"]);
+        let mut expected = vec![retrato.id, noche.id];
+        expected.sort();
+        assert_eq!(hashtags.post_hashtag_ids(post_id), expected);
+        assert_eq!(unit_of_work.commits(), 1);
+    }
+
+    #[tokio::test]
+    async fn unknown_hashtag_leaves_post_unchanged() {
+        let hashtags = Arc::new(FakeHashtagsRepository::default());
+        let unit_of_work = Arc::new(FakeUnitOfWorkFactory::default());
+        let playa = hashtags.insert("playa");
+        let retrato = hashtags.insert("retrato");
+        let post_id = Uuid::new_v4();
+        hashtags.add_hashtags_to_post(post_id, &[playa.id]).await.unwrap();
+
+        let result = update_uc(&hashtags, &unit_of_work)
+            .execute(post_id, &[retrato.id, Uuid::new_v4()])
+            .await;
+
+        assert!(matches!(result, Err(DomainError::Validation(_))));
+        assert_eq!(hashtags.post_hashtag_ids(post_id), vec![playa.id]);
+        assert_eq!(unit_of_work.commits(), 0);
+    }
+
+    #[tokio::test]
+    async fn hashtags_of_unknown_post_are_not_found() {
+        let hashtags = Arc::new(FakeHashtagsRepository::default());
+        let posts = Arc::new(FakePostsRepository::default());
+        let uc = GetHashtagsByPostUseCase::new(
+            Arc::clone(&hashtags) as Arc<dyn HashtagsRepository>,
+            Arc::clone(&posts) as Arc<dyn PostsRepository>,
+        );
+
+        let result = uc.execute(Uuid::new_v4()).await;
+        assert!(matches!(result, Err(DomainError::NotFound(_))));
+
+        let post = posts.insert_post(None);
+        assert!(uc.execute(post.id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn created_hashtag_is_canonical_and_unique() {
+        let hashtags = Arc::new(FakeHashtagsRepository::default());
+        let uc = CreateHashtagUseCase::new(Arc::clone(&hashtags) as Arc<dyn HashtagsRepository>);
+
+        let created = uc.execute("  #Atardecér", None).await.unwrap();
+        assert_eq!(created.name, "atardecer");
+        let result = uc.execute("ATARDECER", None).await;
+        assert!(matches!(result, Err(DomainError::Validation(_))));
+    }
+}
@@ -1,6 +1,8 @@
 // Capa de aplicación: casos de uso
 
 pub mod eventos;
+#[cfg(test)]
+mod fakes;
 pub mod favorites;
 pub mod hashtags;
 pub mod places;
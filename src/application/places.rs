@@ -1,6 +1,6 @@
 // Casos de uso de Places (Kotlin domain/cases/places)
 
-use crate::domain::{DomainError, Place, PlacesRepository};
+use crate::domain::{DomainError, ListQuery, Place, PlacesRepository};
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -14,8 +14,30 @@ impl GetPlacesUseCase {
         Self { repo }
     }
 
-    pub async fn execute(&self) -> Result<Vec<Place>, DomainError> {
-        self.repo.get_all().await
+    pub async fn execute(&self, list: &ListQuery) -> Result<Vec<Place>, DomainError> {
+        self.repo.get_all(list).await
+    }
+}
+
+#[derive(Clone)]
+pub struct GetPlacesPaginatedUseCase {
+    repo: Arc<dyn PlacesRepository>,
+}
+
+impl GetPlacesPaginatedUseCase {
+    pub fn new(repo: Arc<dyn PlacesRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(
+        &self,
+        list: &ListQuery,
+        page: u32,
+        limit: u32,
+    ) -> Result<(Vec<Place>, u64), DomainError> {
+        let items = self.repo.get_paginated(list, page, limit).await?;
+        let total = self.repo.count(list).await?;
+        Ok((items, total))
     }
 }
 
@@ -45,6 +67,7 @@ impl CreatePlaceUseCase {
     }
 
     /// Crea un lugar con id conocido (imagen guardada como {id}.{ext}).
+    #[allow(clippy::too_many_arguments)]
     pub async fn execute_with_id(
         &self,
         id: Uuid,
@@ -85,6 +108,7 @@ impl UpdatePlaceUseCase {
         Self { repo }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn execute(
         &self,
         id: Uuid,
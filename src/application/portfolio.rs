@@ -1,7 +1,7 @@
 // Casos de uso de Portfolio (Kotlin domain/cases/portfolio)
 
 use crate::domain::{
-    DomainError, PortfolioCategory, PortfolioImage, PortfolioRepository,
+    DomainError, PageCursor, PortfolioCategory, PortfolioImage, PortfolioRepository,
 };
 use std::sync::Arc;
 use uuid::Uuid;
@@ -43,6 +43,33 @@ impl GetPortfolioImagesByCategoryUseCase {
     }
 }
 
+/// Imágenes de una categoría por cursor (keyset): la página y el cursor de la siguiente.
+#[derive(Clone)]
+pub struct GetPortfolioImagesByCursorUseCase {
+    repo: Arc<dyn PortfolioRepository>,
+}
+
+impl GetPortfolioImagesByCursorUseCase {
+    pub fn new(repo: Arc<dyn PortfolioRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(
+        &self,
+        category_id: Uuid,
+        after: Option<PageCursor>,
+        limit: u32,
+    ) -> Result<(Vec<PortfolioImage>, Option<PageCursor>), DomainError> {
+        let items = self
+            .repo
+            .get_images_by_category_after(category_id, after.as_ref(), limit + 1)
+            .await?;
+        Ok(PageCursor::paginate(items, limit, |i| {
+            PageCursor::after(i.created_at, i.id)
+        }))
+    }
+}
+
 #[derive(Clone)]
 pub struct CreatePortfolioCategoryUseCase {
     repo: Arc<dyn PortfolioRepository>,
@@ -1,38 +1,65 @@
 // Casos de uso de Poses (Kotlin domain/cases/poses)
 
-use crate::domain::{DomainError, HashtagsRepository, Pose, PosesRepository};
+use crate::domain::{
+    Compensation, DomainError, FavoritesRepository, HashtagsRepository, ListQuery, PageCursor, Pose, PoseBatch,
+    PoseBatchItem, PoseBatchOperation, PoseBatchStatus, PoseFilter, PoseInclude, PoseMetadata, PoseMetadataPatch, PoseRelations,
+    PoseTagFilter, PosesRepository, ScoredPose, SesionesRepository, UnitOfWorkFactory,
+};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use uuid::Uuid;
 
 #[derive(Clone)]
-pub struct GetPosesUseCase {
+pub struct GetPosesPaginatedUseCase {
     repo: Arc<dyn PosesRepository>,
 }
 
-impl GetPosesUseCase {
+impl GetPosesPaginatedUseCase {
     pub fn new(repo: Arc<dyn PosesRepository>) -> Self {
         Self { repo }
     }
 
-    pub async fn execute(&self) -> Result<Vec<Pose>, DomainError> {
-        self.repo.get_all().await
+    pub async fn execute(
+        &self,
+        filter: &PoseFilter,
+        list: &ListQuery,
+        page: u32,
+        limit: u32,
+    ) -> Result<(Vec<Pose>, u64), DomainError> {
+        filter.validate()?;
+        let items = self.repo.get_paginated(filter, list, page, limit).await?;
+        let total = self.repo.count(filter, list).await?;
+        Ok((items, total))
     }
 }
 
+/// Poses por cursor (keyset): devuelve la página y el cursor de la siguiente (None si no hay más).
 #[derive(Clone)]
-pub struct GetPosesPaginatedUseCase {
+pub struct GetPosesByCursorUseCase {
     repo: Arc<dyn PosesRepository>,
 }
 
-impl GetPosesPaginatedUseCase {
+impl GetPosesByCursorUseCase {
     pub fn new(repo: Arc<dyn PosesRepository>) -> Self {
         Self { repo }
     }
 
-    pub async fn execute(&self, page: u32, limit: u32) -> Result<(Vec<Pose>, u64), DomainError> {
-        let items = self.repo.get_paginated(page, limit).await?;
-        let total = self.repo.count().await?;
-        Ok((items, total))
+    pub async fn execute(
+        &self,
+        filter: &PoseFilter,
+        list: &ListQuery,
+        after: Option<PageCursor>,
+        limit: u32,
+    ) -> Result<(Vec<Pose>, Option<PageCursor>), DomainError> {
+        filter.validate()?;
+        list.require_default_sort("cursor")?;
+        let items = self
+            .repo
+            .get_page_after(filter, list, after.as_ref(), limit + 1)
+            .await?;
+        Ok(PageCursor::paginate(items, limit, |p| {
+            PageCursor::after(p.created_at, p.id)
+        }))
     }
 }
 
@@ -56,43 +83,300 @@ impl GetPoseByIdUseCase {
 
 #[derive(Clone)]
 pub struct CreatePoseUseCase {
-    repo: Arc<dyn PosesRepository>,
+    poses_repo: Arc<dyn PosesRepository>,
+    hashtags_repo: Arc<dyn HashtagsRepository>,
+    unit_of_work: Arc<dyn UnitOfWorkFactory>,
 }
 
 impl CreatePoseUseCase {
-    pub fn new(repo: Arc<dyn PosesRepository>) -> Self {
-        Self { repo }
+    pub fn new(
+        poses_repo: Arc<dyn PosesRepository>,
+        hashtags_repo: Arc<dyn HashtagsRepository>,
+        unit_of_work: Arc<dyn UnitOfWorkFactory>,
+    ) -> Self {
+        Self {
+            poses_repo,
+            hashtags_repo,
+            unit_of_work,
+        }
     }
 
-    /// Crea una pose con id conocido (para imágenes guardadas como {id}.{ext}).
-    pub async fn execute_with_id(&self, id: Uuid, url: &str) -> Result<Pose, DomainError> {
+    /// Crea una pose con id conocido (para imágenes guardadas como {id}.{ext}) junto con sus hashtags,
+    /// todo o nada. `image_hash` es el hash perceptual de la imagen (None si no se pudo calcular).
+    /// `undo_image` borra la imagen ya escrita si la creación no se confirma.
+    pub async fn execute_with_id(
+        &self,
+        id: Uuid,
+        url: &str,
+        metadata: PoseMetadata,
+        image_hash: Option<i64>,
+        hashtag_ids: &[Uuid],
+        undo_image: Compensation,
+    ) -> Result<Pose, DomainError> {
+        let mut uow = match self.unit_of_work.begin().await {
+            Ok(uow) => uow,
+            Err(e) => {
+                undo_image();
+                return Err(e);
+            }
+        };
+        uow.on_rollback(undo_image);
         if url.trim().is_empty() {
             return Err(DomainError::Validation("La URL es requerida".to_string()));
         }
-        self.repo.create_with_id(id, url).await
+        let metadata = metadata.validated()?;
+        let pose = self
+            .poses_repo
+            .create_with_id_in(&mut *uow, id, url, &metadata, image_hash)
+            .await?;
+        for hashtag_id in hashtag_ids {
+            self.hashtags_repo
+                .add_hashtag_to_pose_in(&mut *uow, pose.id, *hashtag_id)
+                .await?;
+        }
+        uow.commit().await?;
+        Ok(pose)
+    }
+}
+
+/// Edita los metadatos de una pose (solo los campos enviados).
+#[derive(Clone)]
+pub struct UpdatePoseUseCase {
+    repo: Arc<dyn PosesRepository>,
+}
+
+impl UpdatePoseUseCase {
+    pub fn new(repo: Arc<dyn PosesRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(&self, id: Uuid, patch: PoseMetadataPatch) -> Result<Pose, DomainError> {
+        let not_found = || DomainError::NotFound(format!("Pose no encontrada: {}", id));
+        let pose = self.repo.get_by_id(id).await?.ok_or_else(not_found)?;
+        let metadata = patch.apply(pose.metadata).validated()?;
+        self.repo
+            .update_metadata(id, &metadata)
+            .await?
+            .ok_or_else(not_found)
+    }
+}
+
+/// "Más como esta": poses parecidas por hashtags compartidos y, si hay hash de imagen, visualmente.
+#[derive(Clone)]
+pub struct GetSimilarPosesUseCase {
+    repo: Arc<dyn PosesRepository>,
+}
+
+impl GetSimilarPosesUseCase {
+    pub fn new(repo: Arc<dyn PosesRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(&self, id: Uuid, limit: u32) -> Result<Vec<ScoredPose>, DomainError> {
+        if self.repo.get_by_id(id).await?.is_none() {
+            return Err(DomainError::NotFound(format!("Pose no encontrada: {}", id)));
+        }
+        self.repo.get_similar(id, limit).await
+    }
+}
+
+/// Calcula el hash de imagen de las poses que aún no lo tienen (creadas antes de calcularlo al subir).
+#[derive(Clone)]
+pub struct BackfillPoseImageHashesUseCase {
+    repo: Arc<dyn PosesRepository>,
+}
+
+impl BackfillPoseImageHashesUseCase {
+    pub fn new(repo: Arc<dyn PosesRepository>) -> Self {
+        Self { repo }
+    }
+
+    /// `hash_image` calcula el hash de la imagen de una pose (None si no hay archivo o no se puede leer;
+    /// esa pose se vuelve a intentar en el siguiente arranque). Devuelve cuántas poses se completaron.
+    pub async fn execute<F, Fut>(&self, hash_image: F) -> Result<u64, DomainError>
+    where
+        F: Fn(Uuid) -> Fut,
+        Fut: std::future::Future<Output = Option<i64>>,
+    {
+        let mut updated = 0;
+        for id in self.repo.get_ids_without_image_hash().await? {
+            if let Some(image_hash) = hash_image(id).await {
+                self.repo.set_image_hash(id, image_hash).await?;
+                updated += 1;
+            }
+        }
+        Ok(updated)
+    }
+}
+
+/// Recalcula el peso por rareza de los hashtags que usan la similitud y las recomendaciones (tarea periódica).
+#[derive(Clone)]
+pub struct RefreshHashtagWeightsUseCase {
+    repo: Arc<dyn PosesRepository>,
+}
+
+impl RefreshHashtagWeightsUseCase {
+    pub fn new(repo: Arc<dyn PosesRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(&self) -> Result<(), DomainError> {
+        self.repo.refresh_hashtag_idf().await
+    }
+}
+
+/// Poses recomendadas para el usuario a partir de sus favoritos y las sesiones donde aparecen.
+#[derive(Clone)]
+pub struct GetRecommendedPosesUseCase {
+    repo: Arc<dyn PosesRepository>,
+}
+
+impl GetRecommendedPosesUseCase {
+    pub fn new(repo: Arc<dyn PosesRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(&self, user_id: Uuid, limit: u32) -> Result<Vec<ScoredPose>, DomainError> {
+        self.repo.get_recommended(user_id, limit).await
     }
 }
 
 #[derive(Clone)]
 pub struct DeletePoseUseCase {
     poses_repo: Arc<dyn PosesRepository>,
-    hashtags_repo: Arc<dyn crate::domain::HashtagsRepository>,
+    hashtags_repo: Arc<dyn HashtagsRepository>,
+    unit_of_work: Arc<dyn UnitOfWorkFactory>,
 }
 
 impl DeletePoseUseCase {
     pub fn new(
         poses_repo: Arc<dyn PosesRepository>,
-        hashtags_repo: Arc<dyn crate::domain::HashtagsRepository>,
+        hashtags_repo: Arc<dyn HashtagsRepository>,
+        unit_of_work: Arc<dyn UnitOfWorkFactory>,
     ) -> Self {
         Self {
             poses_repo,
             hashtags_repo,
+            unit_of_work,
         }
     }
 
+    /// Quita los hashtags de la pose y la borra en la misma transacción.
     pub async fn execute(&self, id: Uuid) -> Result<(), DomainError> {
-        let _ = self.hashtags_repo.remove_all_hashtags_from_pose(id).await;
-        self.poses_repo.delete(id).await
+        let mut uow = self.unit_of_work.begin().await?;
+        self.hashtags_repo
+            .remove_all_hashtags_from_pose_in(&mut *uow, id)
+            .await?;
+        self.poses_repo.delete_in(&mut *uow, id).await?;
+        uow.commit().await
+    }
+}
+
+/// Operaciones en lote sobre poses (POST /api/poses/batch). Los hashtags y sesiones referenciados deben
+/// existir; las poses que no existen se informan como not_found sin abortar el lote.
+#[derive(Clone)]
+pub struct BatchPosesUseCase {
+    poses_repo: Arc<dyn PosesRepository>,
+    hashtags_repo: Arc<dyn HashtagsRepository>,
+    sesiones_repo: Arc<dyn SesionesRepository>,
+    unit_of_work: Arc<dyn UnitOfWorkFactory>,
+}
+
+impl BatchPosesUseCase {
+    pub fn new(
+        poses_repo: Arc<dyn PosesRepository>,
+        hashtags_repo: Arc<dyn HashtagsRepository>,
+        sesiones_repo: Arc<dyn SesionesRepository>,
+        unit_of_work: Arc<dyn UnitOfWorkFactory>,
+    ) -> Self {
+        Self {
+            poses_repo,
+            hashtags_repo,
+            sesiones_repo,
+            unit_of_work,
+        }
+    }
+
+    /// Aplica todo el lote en una unidad de trabajo (todo o nada). Poses, hashtags y sesiones quedan
+    /// bloqueados desde que se comprueban hasta el commit. Devuelve un resultado por pose, en el orden
+    /// de `batch.pose_ids`.
+    pub async fn execute(&self, batch: &PoseBatch) -> Result<Vec<PoseBatchItem>, DomainError> {
+        let mut uow = self.unit_of_work.begin().await?;
+        let hashtag_ids = batch.added_hashtag_ids();
+        if !hashtag_ids.is_empty() {
+            let found = self
+                .hashtags_repo
+                .lock_existing_ids_in(&mut *uow, &hashtag_ids)
+                .await?;
+            if let Some(id) = hashtag_ids.iter().find(|id| !found.contains(id)) {
+                return Err(DomainError::NotFound(format!("Hashtag no encontrado: {}", id)));
+            }
+        }
+        let sesion_ids = batch.sesion_ids();
+        if !sesion_ids.is_empty() {
+            let found = self
+                .sesiones_repo
+                .lock_existing_ids_in(&mut *uow, &sesion_ids)
+                .await?;
+            if let Some(id) = sesion_ids.iter().find(|id| !found.contains(id)) {
+                return Err(DomainError::NotFound(format!("Sesión no encontrada: {}", id)));
+            }
+        }
+        let ids = self
+            .poses_repo
+            .lock_existing_ids_in(&mut *uow, &batch.pose_ids)
+            .await?;
+        let mut items: HashMap<Uuid, PoseBatchItem> = ids
+            .iter()
+            .map(|id| (*id, PoseBatchItem::new(*id, PoseBatchStatus::Ok)))
+            .collect();
+        for op in &batch.operations {
+            // Un pose_id por cambio, que se suma al resultado de la pose.
+            let changed = match op {
+                PoseBatchOperation::AddTags(hashtag_ids) => {
+                    self.hashtags_repo
+                        .add_hashtags_to_poses_in(&mut *uow, &ids, hashtag_ids)
+                        .await?
+                }
+                PoseBatchOperation::RemoveTags(hashtag_ids) => {
+                    self.hashtags_repo
+                        .remove_hashtags_from_poses_in(&mut *uow, &ids, hashtag_ids)
+                        .await?
+                }
+                PoseBatchOperation::AddToSesion(sesion_id) => {
+                    self.sesiones_repo
+                        .add_poses_to_sesion_in(&mut *uow, *sesion_id, &ids)
+                        .await?
+                }
+                PoseBatchOperation::Delete => {
+                    self.hashtags_repo
+                        .remove_all_hashtags_from_poses_in(&mut *uow, &ids)
+                        .await?;
+                    self.poses_repo.delete_many_in(&mut *uow, &ids).await?
+                }
+            };
+            for pose_id in changed {
+                let Some(item) = items.get_mut(&pose_id) else {
+                    continue;
+                };
+                match op {
+                    PoseBatchOperation::AddTags(_) => item.tags_added += 1,
+                    PoseBatchOperation::RemoveTags(_) => item.tags_removed += 1,
+                    PoseBatchOperation::AddToSesion(_) => item.sesiones_added += 1,
+                    PoseBatchOperation::Delete => item.deleted = true,
+                }
+            }
+        }
+        uow.commit().await?;
+        Ok(batch
+            .pose_ids
+            .iter()
+            .map(|id| {
+                items
+                    .remove(id)
+                    .unwrap_or_else(|| PoseBatchItem::new(*id, PoseBatchStatus::NotFound))
+            })
+            .collect())
     }
 }
 
@@ -106,8 +390,13 @@ impl GetPosesByHashtagUseCase {
         Self { repo }
     }
 
-    pub async fn execute(&self, hashtag_id: Uuid) -> Result<Vec<Pose>, DomainError> {
-        self.repo.get_poses_by_hashtag(hashtag_id).await
+    /// `include_children`: incluye las poses de los hashtags descendientes.
+    pub async fn execute(
+        &self,
+        hashtag_id: Uuid,
+        include_children: bool,
+    ) -> Result<Vec<Pose>, DomainError> {
+        self.repo.get_poses_by_hashtag(hashtag_id, include_children).await
     }
 }
 
@@ -124,14 +413,64 @@ impl GetPosesByHashtagPaginatedUseCase {
     pub async fn execute(
         &self,
         hashtag_id: Uuid,
+        include_children: bool,
+        page: u32,
+        limit: u32,
+    ) -> Result<(Vec<Pose>, u64), DomainError> {
+        let items = self
+            .repo
+            .get_poses_by_hashtag_paginated(hashtag_id, include_children, page, limit)
+            .await?;
+        let total = self
+            .repo
+            .count_poses_by_hashtag(hashtag_id, include_children)
+            .await?;
+        Ok((items, total))
+    }
+}
+
+/// Poses filtradas por hashtags (todas / alguna / ninguna) y por metadatos, paginado.
+#[derive(Clone)]
+pub struct GetPosesByTagsUseCase {
+    repo: Arc<dyn HashtagsRepository>,
+}
+
+impl GetPosesByTagsUseCase {
+    pub fn new(repo: Arc<dyn HashtagsRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(
+        &self,
+        mut filter: PoseTagFilter,
+        metadata: &PoseFilter,
+        list: &ListQuery,
         page: u32,
         limit: u32,
     ) -> Result<(Vec<Pose>, u64), DomainError> {
+        for ids in [&mut filter.all, &mut filter.any, &mut filter.none] {
+            ids.sort();
+            ids.dedup();
+        }
+        if let Some(id) = filter
+            .none
+            .iter()
+            .find(|id| filter.all.contains(id) || filter.any.contains(id))
+        {
+            return Err(DomainError::Validation(format!(
+                "El hashtag {} no puede estar a la vez en tags_none y en tags_all/tags_any",
+                id
+            )));
+        }
+        metadata.validate()?;
         let items = self
             .repo
-            .get_poses_by_hashtag_paginated(hashtag_id, page, limit)
+            .get_poses_by_tag_filter(&filter, metadata, list, page, limit)
+            .await?;
+        let total = self
+            .repo
+            .count_poses_by_tag_filter(&filter, metadata, list)
             .await?;
-        let total = self.repo.count_poses_by_hashtag(hashtag_id).await?;
         Ok((items, total))
     }
 }
@@ -169,3 +508,207 @@ impl UpdatePoseHashtagsUseCase {
         Ok(())
     }
 }
+
+/// Relaciones de un lote de poses (`?include=`): una consulta por relación pedida, no una por pose.
+#[derive(Clone)]
+pub struct GetPoseRelationsUseCase {
+    hashtags_repo: Arc<dyn HashtagsRepository>,
+    favorites_repo: Arc<dyn FavoritesRepository>,
+    sesiones_repo: Arc<dyn SesionesRepository>,
+}
+
+impl GetPoseRelationsUseCase {
+    pub fn new(
+        hashtags_repo: Arc<dyn HashtagsRepository>,
+        favorites_repo: Arc<dyn FavoritesRepository>,
+        sesiones_repo: Arc<dyn SesionesRepository>,
+    ) -> Self {
+        Self {
+            hashtags_repo,
+            favorites_repo,
+            sesiones_repo,
+        }
+    }
+
+    /// `favorite` solo se resuelve con `user_id` (el usuario autenticado).
+    pub async fn execute(
+        &self,
+        pose_ids: &[Uuid],
+        include: PoseInclude,
+        user_id: Option<Uuid>,
+    ) -> Result<PoseRelations, DomainError> {
+        let mut relations = PoseRelations::default();
+        if include.hashtags {
+            let mut by_pose: HashMap<Uuid, Vec<_>> = HashMap::new();
+            for (pose_id, hashtag) in self.hashtags_repo.get_hashtags_by_poses(pose_ids).await? {
+                by_pose.entry(pose_id).or_default().push(hashtag);
+            }
+            relations.hashtags = Some(by_pose);
+        }
+        if let (true, Some(user_id)) = (include.favorite, user_id) {
+            let ids = self.favorites_repo.get_favorite_pose_ids(user_id, pose_ids).await?;
+            relations.favorites = Some(ids.into_iter().collect::<HashSet<_>>());
+        }
+        if include.sesiones {
+            let mut by_pose: HashMap<Uuid, Vec<_>> = HashMap::new();
+            for (pose_id, sesion) in self.sesiones_repo.get_sesiones_by_poses(pose_ids).await? {
+                by_pose.entry(pose_id).or_default().push(sesion);
+            }
+            relations.sesiones = Some(by_pose);
+        }
+        Ok(relations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::fakes::{
+        flag_on_rollback, FakeHashtagsRepository, FakePosesRepository, FakeUnitOfWorkFactory,
+    };
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    struct Setup {
+        poses: Arc<FakePosesRepository>,
+        hashtags: Arc<FakeHashtagsRepository>,
+        unit_of_work: Arc<FakeUnitOfWorkFactory>,
+    }
+
+    fn setup() -> Setup {
+        Setup {
+            poses: Arc::new(FakePosesRepository::default()),
+            hashtags: Arc::new(FakeHashtagsRepository::default()),
+            unit_of_work: Arc::new(FakeUnitOfWorkFactory::default()),
+        }
+    }
+
+    /// Crea una pose con la imagen ya escrita. Devuelve el id, el resultado y si se borró la imagen.
+    async fn create(setup: &Setup, url: &str, hashtag_ids: &[Uuid]) -> (Uuid, Result<Pose, DomainError>, bool) {
+        let uc = CreatePoseUseCase::new(
+            Arc::clone(&setup.poses) as Arc<dyn PosesRepository>,
+            Arc::clone(&setup.hashtags) as Arc<dyn HashtagsRepository>,
+            Arc::clone(&setup.unit_of_work) as Arc<dyn UnitOfWorkFactory>,
+        );
+        let id = Uuid::new_v4();
+        let undone = Arc::new(AtomicBool::new(false));
+        let result = uc
+            .execute_with_id(
+                id,
+                url,
+                PoseMetadata::default(),
+                Some(42),
+                hashtag_ids,
+                flag_on_rollback(&undone),
+            )
+            .await;
+        (id, result, undone.load(Ordering::SeqCst))
+    }
+
+    fn delete_uc(setup: &Setup) -> DeletePoseUseCase {
+        DeletePoseUseCase::new(
+            Arc::clone(&setup.poses) as Arc<dyn PosesRepository>,
+            Arc::clone(&setup.hashtags) as Arc<dyn HashtagsRepository>,
+            Arc::clone(&setup.unit_of_work) as Arc<dyn UnitOfWorkFactory>,
+        )
+    }
+
+    #[tokio::test]
+    async fn committed_pose_keeps_its_image() {
+        let setup = setup();
+        let hashtag = setup.hashtags.insert("retrato");
+        let (id, result, undone) = create(&setup, "/api/poses/x/image", &[hashtag.id]).await;
+        result.unwrap();
+        assert!(!undone);
+        assert_eq!(setup.unit_of_work.commits(), 1);
+        assert!(setup.poses.pose(id).is_some());
+        assert_eq!(setup.hashtags.pose_links(), vec![(id, hashtag.id)]);
+    }
+
+    #[tokio::test]
+    async fn failed_hashtag_link_removes_the_image_and_the_pose() {
+        let setup = setup();
+        let hashtag = setup.hashtags.insert("retrato");
+        let hashtag_ids = [hashtag.id, Uuid::new_v4()];
+        let (id, result, undone) = create(&setup, "/api/poses/x/image", &hashtag_ids).await;
+        assert!(matches!(result, Err(DomainError::NotFound(_))));
+        assert!(undone);
+        assert_eq!(setup.unit_of_work.commits(), 0);
+        assert!(setup.poses.pose(id).is_none());
+        assert!(setup.hashtags.pose_links().is_empty());
+    }
+
+    #[tokio::test]
+    async fn invalid_pose_removes_the_image() {
+        let setup = setup();
+        let (id, result, undone) = create(&setup, "  ", &[]).await;
+        assert!(matches!(result, Err(DomainError::Validation(_))));
+        assert!(undone);
+        assert_eq!(setup.unit_of_work.commits(), 0);
+        assert!(setup.poses.pose(id).is_none());
+    }
+
+    #[tokio::test]
+    async fn failed_begin_removes_the_image() {
+        let setup = setup();
+        setup.unit_of_work.fail_begin.store(true, Ordering::SeqCst);
+        let (_, result, undone) = create(&setup, "/api/poses/x/image", &[]).await;
+        assert!(matches!(result, Err(DomainError::Repository(_))));
+        assert!(undone);
+    }
+
+    #[tokio::test]
+    async fn deleted_pose_loses_its_hashtags() {
+        let setup = setup();
+        let pose = setup.poses.insert();
+        let other = setup.poses.insert();
+        let hashtag = setup.hashtags.insert("retrato");
+        setup.hashtags.add_hashtag_to_pose(pose.id, hashtag.id).await.unwrap();
+        setup.hashtags.add_hashtag_to_pose(other.id, hashtag.id).await.unwrap();
+
+        delete_uc(&setup).execute(pose.id).await.unwrap();
+
+        assert!(setup.poses.pose(pose.id).is_none());
+        assert_eq!(setup.hashtags.pose_links(), vec![(other.id, hashtag.id)]);
+        assert_eq!(setup.unit_of_work.commits(), 1);
+    }
+
+    #[tokio::test]
+    async fn failed_delete_keeps_the_pose_hashtags() {
+        let setup = setup();
+        let pose = setup.poses.insert();
+        let hashtag = setup.hashtags.insert("retrato");
+        setup.hashtags.add_hashtag_to_pose(pose.id, hashtag.id).await.unwrap();
+        setup.poses.fail_delete.store(true, Ordering::SeqCst);
+
+        let result = delete_uc(&setup).execute(pose.id).await;
+
+        assert!(matches!(result, Err(DomainError::Repository(_))));
+        assert!(setup.poses.pose(pose.id).is_some());
+        assert_eq!(setup.hashtags.pose_links(), vec![(pose.id, hashtag.id)]);
+        assert_eq!(setup.unit_of_work.commits(), 0);
+    }
+
+    #[tokio::test]
+    async fn metadata_update_patches_only_the_given_fields() {
+        let poses = Arc::new(FakePosesRepository::default());
+        let pose = poses.insert();
+        let uc = UpdatePoseUseCase::new(Arc::clone(&poses) as Arc<dyn PosesRepository>);
+
+        let patch = |title: Option<&str>, people_count: Option<i32>| PoseMetadataPatch {
+            title: Some(title.map(str::to_string)),
+            people_count: people_count.map(Some),
+            ..PoseMetadataPatch::default()
+        };
+        uc.execute(pose.id, patch(Some(" Silueta "), Some(2))).await.unwrap();
+        let updated = uc.execute(pose.id, patch(Some("Contraluz"), None)).await.unwrap();
+        assert_eq!(updated.metadata.title.as_deref(), Some("Contraluz"));
+        assert_eq!(updated.metadata.people_count, Some(2));
+
+        let result = uc.execute(pose.id, patch(None, Some(0))).await;
+        assert!(matches!(result, Err(DomainError::Validation(_))));
+        assert_eq!(poses.pose(pose.id).unwrap().metadata, updated.metadata);
+
+        let result = uc.execute(Uuid::new_v4(), PoseMetadataPatch::default()).await;
+        assert!(matches!(result, Err(DomainError::NotFound(_))));
+    }
+}
@@ -1,37 +1,85 @@
 // Casos de uso de Posts (Kotlin domain/cases/posts)
 
-use crate::domain::{DomainError, Post, PostsRepository};
+use crate::domain::{
+    Actor, Compensation, DailyWinner, DomainError, EventosRepository, Hashtag, HashtagsRepository,
+    LeaderboardEntry, ListQuery, LocalTimeZone, MonthDay, PageCursor, Post, PostComment,
+    PostCommentThread, PostingStreak, PostsRepository, ThemeOfTheDay, ThemeOfTheDayKey, UnitOfWork,
+    UnitOfWorkFactory, MAX_COMMENT_DEPTH, MAX_POST_IMAGES,
+};
+use chrono::NaiveDate;
+use std::collections::HashSet;
 use std::sync::Arc;
 use uuid::Uuid;
 
 #[derive(Clone)]
-pub struct GetPostsUseCase {
+pub struct GetPostsPaginatedUseCase {
     repo: Arc<dyn PostsRepository>,
 }
 
-impl GetPostsUseCase {
+impl GetPostsPaginatedUseCase {
     pub fn new(repo: Arc<dyn PostsRepository>) -> Self {
         Self { repo }
     }
 
-    pub async fn execute(&self) -> Result<Vec<Post>, DomainError> {
-        self.repo.get_all().await
+    pub async fn execute(
+        &self,
+        list: &ListQuery,
+        page: u32,
+        limit: u32,
+    ) -> Result<(Vec<Post>, u64), DomainError> {
+        let items = self.repo.get_paginated(list, page, limit).await?;
+        let total = self.repo.count(list).await?;
+        Ok((items, total))
     }
 }
 
+/// Posts por cursor (keyset): devuelve la página y el cursor de la siguiente (None si no hay más).
 #[derive(Clone)]
-pub struct GetPostsPaginatedUseCase {
+pub struct GetPostsByCursorUseCase {
     repo: Arc<dyn PostsRepository>,
 }
 
-impl GetPostsPaginatedUseCase {
+impl GetPostsByCursorUseCase {
     pub fn new(repo: Arc<dyn PostsRepository>) -> Self {
         Self { repo }
     }
 
-    pub async fn execute(&self, page: u32, limit: u32) -> Result<(Vec<Post>, u64), DomainError> {
-        let items = self.repo.get_paginated(page, limit).await?;
-        let total = self.repo.count().await?;
+    pub async fn execute(
+        &self,
+        list: &ListQuery,
+        after: Option<PageCursor>,
+        limit: u32,
+    ) -> Result<(Vec<Post>, Option<PageCursor>), DomainError> {
+        list.require_default_sort("cursor")?;
+        let items = self
+            .repo
+            .get_page_after(list, after.as_ref(), limit + 1)
+            .await?;
+        Ok(PageCursor::paginate(items, limit, |p| {
+            PageCursor::after(p.created_at, p.id)
+        }))
+    }
+}
+
+/// Posts etiquetados con un hashtag (paginado).
+#[derive(Clone)]
+pub struct GetPostsByHashtagPaginatedUseCase {
+    repo: Arc<dyn PostsRepository>,
+}
+
+impl GetPostsByHashtagPaginatedUseCase {
+    pub fn new(repo: Arc<dyn PostsRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(
+        &self,
+        hashtag_id: Uuid,
+        page: u32,
+        limit: u32,
+    ) -> Result<(Vec<Post>, u64), DomainError> {
+        let items = self.repo.get_by_hashtag_paginated(hashtag_id, page, limit).await?;
+        let total = self.repo.count_by_hashtag(hashtag_id).await?;
         Ok((items, total))
     }
 }
@@ -46,8 +94,49 @@ impl GetPostsByThemeOfTheDayIdUseCase {
         Self { repo }
     }
 
+    /// `YYYY-MM-DD`: galería de esa instancia del tema; MMdd: posts de ese día de todos los años.
     pub async fn execute(&self, theme_of_the_day_id: &str) -> Result<Vec<Post>, DomainError> {
-        self.repo.get_by_theme_of_the_day_id(theme_of_the_day_id).await
+        match ThemeOfTheDayKey::parse(theme_of_the_day_id)? {
+            ThemeOfTheDayKey::Date(date) => self.repo.get_by_theme_date(date).await,
+            ThemeOfTheDayKey::MonthDay(mmdd) => {
+                self.repo
+                    .get_by_theme_of_the_day_id(&mmdd.to_string())
+                    .await
+            }
+        }
+    }
+}
+
+/// Galería de un evento: posts paginados y total. NotFound si el evento no existe.
+#[derive(Clone)]
+pub struct GetPostsByEventoPaginatedUseCase {
+    repo: Arc<dyn PostsRepository>,
+    eventos_repo: Arc<dyn EventosRepository>,
+}
+
+impl GetPostsByEventoPaginatedUseCase {
+    pub fn new(repo: Arc<dyn PostsRepository>, eventos_repo: Arc<dyn EventosRepository>) -> Self {
+        Self { repo, eventos_repo }
+    }
+
+    pub async fn execute(
+        &self,
+        evento_id: Uuid,
+        page: u32,
+        limit: u32,
+    ) -> Result<(Vec<Post>, u64), DomainError> {
+        if self.eventos_repo.get_by_id(evento_id).await?.is_none() {
+            return Err(DomainError::NotFound(format!(
+                "Evento no encontrado: {}",
+                evento_id
+            )));
+        }
+        let items = self
+            .repo
+            .get_by_evento_paginated(evento_id, page, limit)
+            .await?;
+        let total = self.repo.count_by_evento(evento_id).await?;
+        Ok((items, total))
     }
 }
 
@@ -71,15 +160,28 @@ impl GetPostByIdUseCase {
 
 #[derive(Clone)]
 pub struct CreatePostUseCase {
-    repo: Arc<dyn PostsRepository>,
+    posts_repo: Arc<dyn PostsRepository>,
+    hashtags_repo: Arc<dyn HashtagsRepository>,
+    unit_of_work: Arc<dyn UnitOfWorkFactory>,
 }
 
 impl CreatePostUseCase {
-    pub fn new(repo: Arc<dyn PostsRepository>) -> Self {
-        Self { repo }
+    pub fn new(
+        posts_repo: Arc<dyn PostsRepository>,
+        hashtags_repo: Arc<dyn HashtagsRepository>,
+        unit_of_work: Arc<dyn UnitOfWorkFactory>,
+    ) -> Self {
+        Self {
+            posts_repo,
+            hashtags_repo,
+            unit_of_work,
+        }
     }
 
-    /// Crea un post con id conocido (para imágenes guardadas como {id}.{ext}).
+    /// Crea un post con id conocido e imágenes guardadas como {image_id}.{ext} (en orden), junto con los
+    /// #hashtags de la descripción, todo o nada. `theme_date` es la instancia del tema a la que se enlaza
+    /// (si existe). `undo_images` borra las imágenes ya escritas si la creación no se confirma.
+    #[allow(clippy::too_many_arguments)]
     pub async fn execute_with_id(
         &self,
         id: Uuid,
@@ -87,13 +189,311 @@ impl CreatePostUseCase {
         url: Option<&str>,
         user_id: Option<Uuid>,
         theme_of_the_day_id: &str,
+        theme_date: Option<NaiveDate>,
+        evento_id: Option<Uuid>,
+        image_ids: &[Uuid],
+        undo_images: Compensation,
     ) -> Result<Post, DomainError> {
+        let mut uow = match self.unit_of_work.begin().await {
+            Ok(uow) => uow,
+            Err(e) => {
+                undo_images();
+                return Err(e);
+            }
+        };
+        uow.on_rollback(undo_images);
+        if image_ids.is_empty() || image_ids.len() > MAX_POST_IMAGES {
+            return Err(DomainError::Validation(format!(
+                "Un post debe tener entre 1 y {} imágenes",
+                MAX_POST_IMAGES
+            )));
+        }
+        let theme_of_the_day_id = MonthDay::parse(theme_of_the_day_id)?.to_string();
+        let mut post = self
+            .posts_repo
+            .create_with_id_in(
+                &mut *uow,
+                id,
+                description,
+                url,
+                user_id,
+                &theme_of_the_day_id,
+                theme_date,
+                evento_id,
+                image_ids,
+            )
+            .await?;
+        let names = description.map(Hashtag::extract_names).unwrap_or_default();
+        if !names.is_empty() {
+            let mut hashtags = self
+                .hashtags_repo
+                .get_or_create_by_names_in(&mut *uow, &names)
+                .await?;
+            let ids: Vec<Uuid> = hashtags.iter().map(|h| h.id).collect();
+            self.hashtags_repo
+                .add_hashtags_to_post_in(&mut *uow, post.id, &ids)
+                .await?;
+            // Mismo orden que los hashtags leídos de la base de datos.
+            hashtags.sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id)));
+            post.hashtags = hashtags;
+        }
+        uow.commit().await?;
+        Ok(post)
+    }
+}
+
+/// Obtiene el post y comprueba que el usuario pueda modificarlo (autor o admin).
+async fn get_modifiable_post(
+    repo: &dyn PostsRepository,
+    id: Uuid,
+    actor: &Actor,
+) -> Result<Post, DomainError> {
+    let post = repo
+        .get_by_id(id)
+        .await?
+        .ok_or_else(|| DomainError::NotFound(format!("Post no encontrado: {}", id)))?;
+    if !actor.can_modify(post.user_id) {
+        return Err(DomainError::Forbidden(
+            "Solo el autor del post o un administrador pueden modificarlo".to_string(),
+        ));
+    }
+    Ok(post)
+}
+
+/// Edita un post (solo el autor o un admin). Si cambia la descripción, re-sincroniza sus #hashtags
+/// (crea los que no existen, enlaza los nuevos y quita los que ya no aparecen) en la misma transacción.
+/// Los hashtags añadidos a mano (por id) que no aparecen en ninguna de las dos descripciones se mantienen.
+#[derive(Clone)]
+pub struct UpdatePostUseCase {
+    posts_repo: Arc<dyn PostsRepository>,
+    hashtags_repo: Arc<dyn HashtagsRepository>,
+    unit_of_work: Arc<dyn UnitOfWorkFactory>,
+}
+
+impl UpdatePostUseCase {
+    pub fn new(
+        posts_repo: Arc<dyn PostsRepository>,
+        hashtags_repo: Arc<dyn HashtagsRepository>,
+        unit_of_work: Arc<dyn UnitOfWorkFactory>,
+    ) -> Self {
+        Self {
+            posts_repo,
+            hashtags_repo,
+            unit_of_work,
+        }
+    }
+
+    /// Comprueba permisos sin modificar nada (para validar antes de guardar una imagen nueva).
+    pub async fn authorize(&self, id: Uuid, actor: &Actor) -> Result<Post, DomainError> {
+        get_modifiable_post(self.posts_repo.as_ref(), id, actor).await
+    }
+
+    /// `theme`: (MMdd o fecha del tema, instancia resuelta). Los campos ausentes no cambian.
+    /// `undo_image` descarta la imagen nueva ya escrita si la edición no se confirma.
+    pub async fn execute(
+        &self,
+        id: Uuid,
+        actor: &Actor,
+        description: Option<&str>,
+        url: Option<&str>,
+        theme: Option<(&str, Option<NaiveDate>)>,
+        undo_image: Option<Compensation>,
+    ) -> Result<Post, DomainError> {
+        let mut uow = match self.unit_of_work.begin().await {
+            Ok(uow) => uow,
+            Err(e) => {
+                if let Some(undo_image) = undo_image {
+                    undo_image();
+                }
+                return Err(e);
+            }
+        };
+        if let Some(undo_image) = undo_image {
+            uow.on_rollback(undo_image);
+        }
+        let post = get_modifiable_post(self.posts_repo.as_ref(), id, actor).await?;
+        let mmdd = match theme {
+            Some((key, _)) => Some(ThemeOfTheDayKey::parse(key)?.month_day().to_string()),
+            None => None,
+        };
+        let theme = mmdd.as_deref().zip(theme.map(|(_, date)| date));
+        let description = description.map(str::trim);
+        // Una descripción vacía la elimina.
+        let new_description = description.map(|d| Some(d).filter(|d| !d.is_empty()));
+        if let Some(new_description) = new_description.filter(|d| *d != post.description.as_deref()) {
+            self.sync_hashtags_in(&mut *uow, id, post.description.as_deref(), new_description)
+                .await?;
+        }
+        // Después de sincronizar, para que el post devuelto ya tenga los hashtags nuevos.
+        let post = self
+            .posts_repo
+            .update_in(&mut *uow, id, description, url, theme)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Post no encontrado: {}", id)))?;
+        uow.commit().await?;
+        Ok(post)
+    }
+
+    async fn sync_hashtags_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        post_id: Uuid,
+        old_description: Option<&str>,
+        new_description: Option<&str>,
+    ) -> Result<(), DomainError> {
+        let old_names = old_description.map(Hashtag::extract_names).unwrap_or_default();
+        let new_names = new_description.map(Hashtag::extract_names).unwrap_or_default();
+        let stale: HashSet<String> = old_names
+            .into_iter()
+            .filter(|name| !new_names.contains(name))
+            .collect();
+        if !stale.is_empty() {
+            let stale_ids: Vec<Uuid> = self
+                .hashtags_repo
+                .get_hashtags_by_post_in(uow, post_id)
+                .await?
+                .into_iter()
+                .filter(|h| stale.contains(&Hashtag::normalize_name(&h.name)))
+                .map(|h| h.id)
+                .collect();
+            if !stale_ids.is_empty() {
+                self.hashtags_repo
+                    .remove_hashtags_from_post_in(uow, post_id, &stale_ids)
+                    .await?;
+            }
+        }
+        if !new_names.is_empty() {
+            let ids: Vec<Uuid> = self
+                .hashtags_repo
+                .get_or_create_by_names_in(uow, &new_names)
+                .await?
+                .into_iter()
+                .map(|h| h.id)
+                .collect();
+            self.hashtags_repo
+                .add_hashtags_to_post_in(uow, post_id, &ids)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+/// Añade una imagen al carrusel de un post (autor o admin).
+#[derive(Clone)]
+pub struct AddPostImageUseCase {
+    repo: Arc<dyn PostsRepository>,
+}
+
+impl AddPostImageUseCase {
+    pub fn new(repo: Arc<dyn PostsRepository>) -> Self {
+        Self { repo }
+    }
+
+    /// Comprueba permisos y que quede sitio en el carrusel (antes de guardar la imagen en disco).
+    pub async fn authorize(&self, post_id: Uuid, actor: &Actor) -> Result<Post, DomainError> {
+        let post = get_modifiable_post(self.repo.as_ref(), post_id, actor).await?;
+        if post.images.len() >= MAX_POST_IMAGES {
+            return Err(too_many_post_images());
+        }
+        Ok(post)
+    }
+
+    pub async fn execute(
+        &self,
+        post_id: Uuid,
+        actor: &Actor,
+        image_id: Uuid,
+    ) -> Result<Post, DomainError> {
+        self.authorize(post_id, actor).await?;
+        // El repositorio vuelve a comprobar el máximo con el post bloqueado (altas concurrentes).
+        if !self.repo.add_image(post_id, image_id, MAX_POST_IMAGES).await? {
+            return Err(too_many_post_images());
+        }
         self.repo
-            .create_with_id(id, description, url, user_id, theme_of_the_day_id)
-            .await
+            .get_by_id(post_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Post no encontrado: {}", post_id)))
     }
 }
 
+fn too_many_post_images() -> DomainError {
+    DomainError::Validation(format!(
+        "Un post admite como máximo {} imágenes",
+        MAX_POST_IMAGES
+    ))
+}
+
+/// Reordena el carrusel de un post (autor o admin).
+#[derive(Clone)]
+pub struct ReorderPostImagesUseCase {
+    repo: Arc<dyn PostsRepository>,
+}
+
+impl ReorderPostImagesUseCase {
+    pub fn new(repo: Arc<dyn PostsRepository>) -> Self {
+        Self { repo }
+    }
+
+    /// `image_ids` debe contener exactamente las imágenes del post, sin repetir.
+    pub async fn execute(
+        &self,
+        post_id: Uuid,
+        actor: &Actor,
+        image_ids: &[Uuid],
+    ) -> Result<Post, DomainError> {
+        let post = get_modifiable_post(self.repo.as_ref(), post_id, actor).await?;
+        let mut current: Vec<Uuid> = post.images.iter().map(|i| i.id).collect();
+        let mut requested = image_ids.to_vec();
+        current.sort();
+        requested.sort();
+        if current != requested {
+            return Err(DomainError::Validation(
+                "image_ids debe contener todas las imágenes del post, una sola vez".to_string(),
+            ));
+        }
+        self.repo.reorder_images(post_id, image_ids).await?;
+        self.repo
+            .get_by_id(post_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Post no encontrado: {}", post_id)))
+    }
+}
+
+/// Quita una imagen del carrusel (autor o admin). No se puede quitar la única imagen.
+#[derive(Clone)]
+pub struct RemovePostImageUseCase {
+    repo: Arc<dyn PostsRepository>,
+}
+
+impl RemovePostImageUseCase {
+    pub fn new(repo: Arc<dyn PostsRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(
+        &self,
+        post_id: Uuid,
+        actor: &Actor,
+        image_id: Uuid,
+    ) -> Result<(), DomainError> {
+        let post = get_modifiable_post(self.repo.as_ref(), post_id, actor).await?;
+        if !post.images.iter().any(|i| i.id == image_id) {
+            return Err(DomainError::NotFound(format!(
+                "Imagen no encontrada: {}",
+                image_id
+            )));
+        }
+        if post.images.len() == 1 {
+            return Err(DomainError::Validation(
+                "No se puede quitar la única imagen del post".to_string(),
+            ));
+        }
+        self.repo.remove_image(post_id, image_id).await?;
+        Ok(())
+    }
+}
+
+/// Elimina un post (solo el autor o un admin).
 #[derive(Clone)]
 pub struct DeletePostUseCase {
     repo: Arc<dyn PostsRepository>,
@@ -104,7 +504,678 @@ impl DeletePostUseCase {
         Self { repo }
     }
 
-    pub async fn execute(&self, id: Uuid) -> Result<(), DomainError> {
-        self.repo.delete(id).await
+    /// Devuelve el post eliminado (para borrar sus imágenes del disco).
+    pub async fn execute(&self, id: Uuid, actor: &Actor) -> Result<Post, DomainError> {
+        let post = get_modifiable_post(self.repo.as_ref(), id, actor).await?;
+        self.repo.delete(id).await?;
+        Ok(post)
+    }
+}
+
+/// Comprueba que la votación del día del post siga abierta (posts sin tema programado: siempre abierta).
+fn ensure_voting_open(post: &Post) -> Result<(), DomainError> {
+    if let Some(theme_date) = post.theme_date {
+        if chrono::Utc::now() >= ThemeOfTheDay::closes_at(theme_date) {
+            return Err(DomainError::Validation(format!(
+                "La votación del tema del {} está cerrada",
+                theme_date
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Vota un post del reto diario (un voto por usuario; repetir el voto no suma). No se votan los propios.
+#[derive(Clone)]
+pub struct VotePostUseCase {
+    repo: Arc<dyn PostsRepository>,
+}
+
+impl VotePostUseCase {
+    pub fn new(repo: Arc<dyn PostsRepository>) -> Self {
+        Self { repo }
+    }
+
+    /// Devuelve el post con el número de votos actualizado.
+    pub async fn execute(&self, post_id: Uuid, user_id: Uuid) -> Result<Post, DomainError> {
+        let post = self
+            .repo
+            .get_by_id(post_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Post no encontrado: {}", post_id)))?;
+        if post.user_id == Some(user_id) {
+            return Err(DomainError::Validation("No puedes votar tu propio post".to_string()));
+        }
+        ensure_voting_open(&post)?;
+        self.repo.add_vote(post_id, user_id).await?;
+        self.repo
+            .get_by_id(post_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Post no encontrado: {}", post_id)))
+    }
+}
+
+#[derive(Clone)]
+pub struct RemovePostVoteUseCase {
+    repo: Arc<dyn PostsRepository>,
+}
+
+impl RemovePostVoteUseCase {
+    pub fn new(repo: Arc<dyn PostsRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(&self, post_id: Uuid, user_id: Uuid) -> Result<(), DomainError> {
+        let post = self
+            .repo
+            .get_by_id(post_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Post no encontrado: {}", post_id)))?;
+        ensure_voting_open(&post)?;
+        if !self.repo.remove_vote(post_id, user_id).await? {
+            return Err(DomainError::NotFound(format!(
+                "No has votado el post {}",
+                post_id
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Ranking de votos de una instancia del tema. Si el día ya cerró, fija (una sola vez) y devuelve el ganador.
+#[derive(Clone)]
+pub struct GetThemeLeaderboardUseCase {
+    repo: Arc<dyn PostsRepository>,
+}
+
+impl GetThemeLeaderboardUseCase {
+    pub fn new(repo: Arc<dyn PostsRepository>) -> Self {
+        Self { repo }
+    }
+
+    /// Devuelve (ranking, cerrado, ganador).
+    pub async fn execute(
+        &self,
+        theme_date: NaiveDate,
+        limit: u32,
+    ) -> Result<(Vec<LeaderboardEntry>, bool, Option<DailyWinner>), DomainError> {
+        let posts = self.repo.get_leaderboard(theme_date, limit).await?;
+        let mut entries: Vec<LeaderboardEntry> = Vec::with_capacity(posts.len());
+        for (i, post) in posts.into_iter().enumerate() {
+            let rank = match entries.last() {
+                Some(prev) if prev.post.votes_count == post.votes_count => prev.rank,
+                _ => i as u32 + 1,
+            };
+            entries.push(LeaderboardEntry { rank, post });
+        }
+        let closed = chrono::Utc::now() >= ThemeOfTheDay::closes_at(theme_date);
+        let winner = if closed {
+            self.repo.decide_daily_winner(theme_date).await?
+        } else {
+            None
+        };
+        Ok((entries, closed, winner))
+    }
+}
+
+/// Racha de días consecutivos con post del usuario: cuenta el día local (en `time_zone`) en que publicó
+/// cada post del reto, relativo a `today`.
+#[derive(Clone)]
+pub struct GetPostingStreakUseCase {
+    repo: Arc<dyn PostsRepository>,
+}
+
+impl GetPostingStreakUseCase {
+    pub fn new(repo: Arc<dyn PostsRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        time_zone: LocalTimeZone,
+        today: NaiveDate,
+    ) -> Result<PostingStreak, DomainError> {
+        let dates: Vec<NaiveDate> = self
+            .repo
+            .get_challenge_post_times(user_id)
+            .await?
+            .into_iter()
+            .map(|t| time_zone.local_date(t))
+            .collect();
+        Ok(PostingStreak::from_dates(&dates, today))
+    }
+}
+
+/// Fija los ganadores de los días cuya votación ya cerró y aún no lo tienen (tarea periódica).
+#[derive(Clone)]
+pub struct DecideDailyWinnersUseCase {
+    repo: Arc<dyn PostsRepository>,
+}
+
+impl DecideDailyWinnersUseCase {
+    pub fn new(repo: Arc<dyn PostsRepository>) -> Self {
+        Self { repo }
+    }
+
+    /// Devuelve cuántos ganadores se fijaron.
+    pub async fn execute(&self, now: chrono::DateTime<chrono::Utc>) -> Result<u64, DomainError> {
+        self.repo
+            .decide_daily_winners_until(ThemeOfTheDay::last_closed_date(now))
+            .await
+    }
+}
+
+/// Longitud máxima del texto de un comentario (en caracteres).
+const MAX_COMMENT_LENGTH: usize = 2000;
+
+fn validate_comment_body(body: &str) -> Result<&str, DomainError> {
+    let body = body.trim();
+    if body.is_empty() {
+        return Err(DomainError::Validation(
+            "El campo body es requerido".to_string(),
+        ));
+    }
+    if body.chars().count() > MAX_COMMENT_LENGTH {
+        return Err(DomainError::Validation(format!(
+            "El comentario supera los {} caracteres",
+            MAX_COMMENT_LENGTH
+        )));
+    }
+    Ok(body)
+}
+
+/// Da like a un post (uno por usuario; repetirlo no suma).
+#[derive(Clone)]
+pub struct LikePostUseCase {
+    repo: Arc<dyn PostsRepository>,
+}
+
+impl LikePostUseCase {
+    pub fn new(repo: Arc<dyn PostsRepository>) -> Self {
+        Self { repo }
+    }
+
+    /// Devuelve el post con el número de likes actualizado.
+    pub async fn execute(&self, post_id: Uuid, user_id: Uuid) -> Result<Post, DomainError> {
+        if self.repo.get_by_id(post_id).await?.is_none() {
+            return Err(DomainError::NotFound(format!("Post no encontrado: {}", post_id)));
+        }
+        self.repo.add_like(post_id, user_id).await?;
+        let mut post = self
+            .repo
+            .get_by_id(post_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Post no encontrado: {}", post_id)))?;
+        post.liked_by_me = true;
+        Ok(post)
+    }
+}
+
+#[derive(Clone)]
+pub struct UnlikePostUseCase {
+    repo: Arc<dyn PostsRepository>,
+}
+
+impl UnlikePostUseCase {
+    pub fn new(repo: Arc<dyn PostsRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(&self, post_id: Uuid, user_id: Uuid) -> Result<(), DomainError> {
+        if self.repo.get_by_id(post_id).await?.is_none() {
+            return Err(DomainError::NotFound(format!("Post no encontrado: {}", post_id)));
+        }
+        self.repo.remove_like(post_id, user_id).await?;
+        Ok(())
+    }
+}
+
+/// Marca `liked_by_me` en los posts a los que el usuario dio like (una sola consulta).
+#[derive(Clone)]
+pub struct MarkPostsLikedByUserUseCase {
+    repo: Arc<dyn PostsRepository>,
+}
+
+impl MarkPostsLikedByUserUseCase {
+    pub fn new(repo: Arc<dyn PostsRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(&self, user_id: Uuid, mut posts: Vec<Post>) -> Result<Vec<Post>, DomainError> {
+        let ids: Vec<Uuid> = posts.iter().map(|p| p.id).collect();
+        let liked = self.repo.get_liked_post_ids(user_id, &ids).await?;
+        for post in posts.iter_mut() {
+            post.liked_by_me = liked.contains(&post.id);
+        }
+        Ok(posts)
+    }
+}
+
+/// Comentarios de un post organizados en hilos.
+#[derive(Clone)]
+pub struct GetPostCommentsUseCase {
+    repo: Arc<dyn PostsRepository>,
+}
+
+impl GetPostCommentsUseCase {
+    pub fn new(repo: Arc<dyn PostsRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(&self, post_id: Uuid) -> Result<Vec<PostCommentThread>, DomainError> {
+        if self.repo.get_by_id(post_id).await?.is_none() {
+            return Err(DomainError::NotFound(format!("Post no encontrado: {}", post_id)));
+        }
+        let comments = self.repo.get_comments(post_id).await?;
+        Ok(PostCommentThread::build(comments))
+    }
+}
+
+/// Comenta un post o responde a un comentario (`parent_id`) del mismo post.
+#[derive(Clone)]
+pub struct CreatePostCommentUseCase {
+    repo: Arc<dyn PostsRepository>,
+}
+
+impl CreatePostCommentUseCase {
+    pub fn new(repo: Arc<dyn PostsRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        parent_id: Option<Uuid>,
+        body: &str,
+    ) -> Result<PostComment, DomainError> {
+        let body = validate_comment_body(body)?;
+        if self.repo.get_by_id(post_id).await?.is_none() {
+            return Err(DomainError::NotFound(format!("Post no encontrado: {}", post_id)));
+        }
+        if let Some(parent_id) = parent_id {
+            let parent = self.repo.get_comment_by_id(parent_id).await?;
+            if parent.is_none_or(|c| c.post_id != post_id) {
+                return Err(DomainError::Validation(format!(
+                    "parent_id {} no es un comentario de este post",
+                    parent_id
+                )));
+            }
+            if self.repo.get_comment_depth(parent_id).await? + 1 >= MAX_COMMENT_DEPTH {
+                return Err(DomainError::Validation(format!(
+                    "Los hilos admiten como máximo {} niveles de respuestas",
+                    MAX_COMMENT_DEPTH - 1
+                )));
+            }
+        }
+        self.repo
+            .create_comment(post_id, user_id, parent_id, body)
+            .await
+    }
+}
+
+/// Busca un comentario (no eliminado) del post.
+async fn get_post_comment(
+    repo: &dyn PostsRepository,
+    post_id: Uuid,
+    comment_id: Uuid,
+) -> Result<PostComment, DomainError> {
+    repo.get_comment_by_id(comment_id)
+        .await?
+        .filter(|c| c.post_id == post_id && !c.deleted)
+        .ok_or_else(|| DomainError::NotFound(format!("Comentario no encontrado: {}", comment_id)))
+}
+
+/// Edita un comentario propio (solo el autor).
+#[derive(Clone)]
+pub struct UpdatePostCommentUseCase {
+    repo: Arc<dyn PostsRepository>,
+}
+
+impl UpdatePostCommentUseCase {
+    pub fn new(repo: Arc<dyn PostsRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(
+        &self,
+        post_id: Uuid,
+        comment_id: Uuid,
+        user_id: Uuid,
+        body: &str,
+    ) -> Result<PostComment, DomainError> {
+        let body = validate_comment_body(body)?;
+        let comment = get_post_comment(self.repo.as_ref(), post_id, comment_id).await?;
+        if comment.user_id != user_id {
+            return Err(DomainError::Forbidden(
+                "Solo el autor puede editar el comentario".to_string(),
+            ));
+        }
+        self.repo
+            .update_comment(comment_id, body)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Comentario no encontrado: {}", comment_id)))
+    }
+}
+
+/// Elimina un comentario (el autor o un admin). Si tiene respuestas queda como "eliminado" en el hilo.
+#[derive(Clone)]
+pub struct DeletePostCommentUseCase {
+    repo: Arc<dyn PostsRepository>,
+}
+
+impl DeletePostCommentUseCase {
+    pub fn new(repo: Arc<dyn PostsRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(
+        &self,
+        post_id: Uuid,
+        comment_id: Uuid,
+        actor: &Actor,
+    ) -> Result<(), DomainError> {
+        let comment = get_post_comment(self.repo.as_ref(), post_id, comment_id).await?;
+        if !actor.can_modify(Some(comment.user_id)) {
+            return Err(DomainError::Forbidden(
+                "Solo el autor del comentario o un administrador pueden eliminarlo".to_string(),
+            ));
+        }
+        self.repo.delete_comment(comment_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::fakes::{
+        flag_on_rollback, FakeEventosRepository, FakeHashtagsRepository, FakePostsRepository,
+        FakeUnitOfWorkFactory,
+    };
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    fn actor(user_id: Uuid) -> Actor {
+        Actor {
+            user_id,
+            is_admin: false,
+        }
+    }
+
+    fn admin() -> Actor {
+        Actor {
+            user_id: Uuid::new_v4(),
+            is_admin: true,
+        }
+    }
+
+    /// Edita la descripción con una imagen nueva pendiente. Devuelve el resultado, si se descartó la
+    /// imagen y si la edición se confirmó.
+    async fn update_description(
+        posts: &Arc<FakePostsRepository>,
+        post_id: Uuid,
+        actor: &Actor,
+    ) -> (Result<Post, DomainError>, bool, bool) {
+        let unit_of_work = Arc::new(FakeUnitOfWorkFactory::default());
+        let uc = UpdatePostUseCase::new(
+            Arc::clone(posts) as Arc<dyn PostsRepository>,
+            Arc::new(FakeHashtagsRepository::default()),
+            Arc::clone(&unit_of_work) as Arc<dyn UnitOfWorkFactory>,
+        );
+        let undone = Arc::new(AtomicBool::new(false));
+        let result = uc
+            .execute(
+                post_id,
+                actor,
+                Some("Atardecer"),
+                None,
+                None,
+                Some(flag_on_rollback(&undone)),
+            )
+            .await;
+        (result, undone.load(Ordering::SeqCst), unit_of_work.commits() == 1)
+    }
+
+    #[tokio::test]
+    async fn author_and_admin_can_edit_a_post() {
+        let posts = Arc::new(FakePostsRepository::default());
+        let author = Uuid::new_v4();
+        let post = posts.insert_post(Some(author));
+
+        let (result, undone, committed) = update_description(&posts, post.id, &actor(author)).await;
+        assert_eq!(result.unwrap().description.as_deref(), Some("Atardecer"));
+        assert!(!undone);
+        assert!(committed);
+
+        let (result, undone, committed) = update_description(&posts, post.id, &admin()).await;
+        assert!(result.is_ok());
+        assert!(!undone);
+        assert!(committed);
+    }
+
+    #[tokio::test]
+    async fn stranger_cannot_edit_a_post_and_its_new_image_is_discarded() {
+        let posts = Arc::new(FakePostsRepository::default());
+        let post = posts.insert_post(Some(Uuid::new_v4()));
+
+        let (result, undone, committed) =
+            update_description(&posts, post.id, &actor(Uuid::new_v4())).await;
+        assert!(matches!(result, Err(DomainError::Forbidden(_))));
+        assert!(undone);
+        assert!(!committed);
+        assert_eq!(posts.post(post.id).unwrap().description, None);
+    }
+
+    #[tokio::test]
+    async fn ownerless_post_is_only_editable_by_an_admin() {
+        let posts = Arc::new(FakePostsRepository::default());
+        let post = posts.insert_post(None);
+
+        let (result, _, _) = update_description(&posts, post.id, &actor(Uuid::new_v4())).await;
+        assert!(matches!(result, Err(DomainError::Forbidden(_))));
+        let (result, _, _) = update_description(&posts, post.id, &admin()).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn editing_the_description_resyncs_its_hashtags() {
+        let posts = Arc::new(FakePostsRepository::default());
+        let hashtags = Arc::new(FakeHashtagsRepository::default());
+        let author = actor(Uuid::new_v4());
+        let post = posts.insert_post(Some(author.user_id));
+        let uc = UpdatePostUseCase::new(
+            Arc::clone(&posts) as Arc<dyn PostsRepository>,
+            Arc::clone(&hashtags) as Arc<dyn HashtagsRepository>,
+            Arc::new(FakeUnitOfWorkFactory::default()),
+        );
+
+        uc.execute(post.id, &author, Some("#Retrato al #atardecer"), None, None, None)
+            .await
+            .unwrap();
+        // Añadido a mano: no está en ninguna descripción, así que se mantiene.
+        let boda = hashtags.insert("boda");
+        hashtags.add_hashtags_to_post(post.id, &[boda.id]).await.unwrap();
+        uc.execute(post.id, &author, Some("Solo #retrato"), None, None, None)
+            .await
+            .unwrap();
+
+        let names: Vec<String> = hashtags
+            .get_hashtags_by_post(post.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|h| h.name)
+            .collect();
+        assert_eq!(names, vec!["boda", "retrato"]);
+    }
+
+    #[tokio::test]
+    async fn stranger_cannot_delete_a_post() {
+        let posts = Arc::new(FakePostsRepository::default());
+        let author = Uuid::new_v4();
+        let post = posts.insert_post(Some(author));
+        let uc = DeletePostUseCase::new(Arc::clone(&posts) as Arc<dyn PostsRepository>);
+
+        let result = uc.execute(post.id, &actor(Uuid::new_v4())).await;
+        assert!(matches!(result, Err(DomainError::Forbidden(_))));
+        assert!(posts.post(post.id).is_some());
+
+        uc.execute(post.id, &actor(author)).await.unwrap();
+        assert!(posts.post(post.id).is_none());
+    }
+
+    #[tokio::test]
+    async fn stranger_cannot_change_post_images() {
+        let posts = Arc::new(FakePostsRepository::default());
+        let author = Uuid::new_v4();
+        let post = posts.insert_post(Some(author));
+        let second = Uuid::new_v4();
+        AddPostImageUseCase::new(Arc::clone(&posts) as Arc<dyn PostsRepository>)
+            .execute(post.id, &actor(author), second)
+            .await
+            .unwrap();
+        let stranger = actor(Uuid::new_v4());
+
+        let add = AddPostImageUseCase::new(Arc::clone(&posts) as Arc<dyn PostsRepository>)
+            .authorize(post.id, &stranger)
+            .await;
+        assert!(matches!(add, Err(DomainError::Forbidden(_))));
+        let reorder = ReorderPostImagesUseCase::new(Arc::clone(&posts) as Arc<dyn PostsRepository>)
+            .execute(post.id, &stranger, &[second, post.id])
+            .await;
+        assert!(matches!(reorder, Err(DomainError::Forbidden(_))));
+        let remove = RemovePostImageUseCase::new(Arc::clone(&posts) as Arc<dyn PostsRepository>)
+            .execute(post.id, &stranger, second)
+            .await;
+        assert!(matches!(remove, Err(DomainError::Forbidden(_))));
+
+        let images: Vec<Uuid> = posts.post(post.id).unwrap().images.iter().map(|i| i.id).collect();
+        assert_eq!(images, vec![post.id, second]);
+
+        RemovePostImageUseCase::new(Arc::clone(&posts) as Arc<dyn PostsRepository>)
+            .execute(post.id, &admin(), second)
+            .await
+            .unwrap();
+        assert_eq!(posts.post(post.id).unwrap().images.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn carousel_stops_at_max_post_images() {
+        let posts = Arc::new(FakePostsRepository::default());
+        let author = Uuid::new_v4();
+        let post = posts.insert_post(Some(author));
+        let uc = AddPostImageUseCase::new(Arc::clone(&posts) as Arc<dyn PostsRepository>);
+
+        for _ in 1..MAX_POST_IMAGES {
+            uc.execute(post.id, &actor(author), Uuid::new_v4()).await.unwrap();
+        }
+        let result = uc.execute(post.id, &actor(author), Uuid::new_v4()).await;
+        assert!(matches!(result, Err(DomainError::Validation(_))));
+        assert_eq!(posts.post(post.id).unwrap().images.len(), MAX_POST_IMAGES);
+    }
+
+    #[tokio::test]
+    async fn evento_gallery_pages_only_that_evento_posts() {
+        let posts = Arc::new(FakePostsRepository::default());
+        let eventos = Arc::new(FakeEventosRepository::default());
+        let evento = eventos.insert("Taller de retrato");
+        let otro = eventos.insert("Paseo fotográfico");
+        let mut ids: Vec<Uuid> = (0..3).map(|_| posts.insert_evento_post(evento.id).id).collect();
+        ids.sort();
+        posts.insert_evento_post(otro.id);
+        posts.insert_post(None);
+        let uc = GetPostsByEventoPaginatedUseCase::new(
+            Arc::clone(&posts) as Arc<dyn PostsRepository>,
+            eventos,
+        );
+
+        let (first, total) = uc.execute(evento.id, 0, 2).await.unwrap();
+        let (second, _) = uc.execute(evento.id, 1, 2).await.unwrap();
+        assert_eq!(total, 3);
+        let paged: Vec<Uuid> = first.iter().chain(&second).map(|p| p.id).collect();
+        assert_eq!(paged, ids);
+        assert!(first.iter().chain(&second).all(|p| p.evento_id == Some(evento.id)));
+    }
+
+    #[tokio::test]
+    async fn gallery_of_unknown_evento_is_not_found() {
+        let posts = Arc::new(FakePostsRepository::default());
+        let uc = GetPostsByEventoPaginatedUseCase::new(
+            Arc::clone(&posts) as Arc<dyn PostsRepository>,
+            Arc::new(FakeEventosRepository::default()),
+        );
+        let orphan = posts.insert_evento_post(Uuid::new_v4());
+
+        let result = uc.execute(orphan.evento_id.unwrap(), 0, 20).await;
+        assert!(matches!(result, Err(DomainError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn author_cannot_vote_own_post() {
+        let posts = Arc::new(FakePostsRepository::default());
+        let author = Uuid::new_v4();
+        let post = posts.insert_post(Some(author));
+        let uc = VotePostUseCase::new(Arc::clone(&posts) as Arc<dyn PostsRepository>);
+
+        let result = uc.execute(post.id, author).await;
+        assert!(matches!(result, Err(DomainError::Validation(_))));
+        assert_eq!(posts.post(post.id).unwrap().votes_count, 0);
+
+        let voted = uc.execute(post.id, Uuid::new_v4()).await.unwrap();
+        assert_eq!(voted.votes_count, 1);
+    }
+
+    #[tokio::test]
+    async fn replies_stop_at_max_comment_depth() {
+        let posts = Arc::new(FakePostsRepository::default());
+        let post = posts.insert_post(Some(Uuid::new_v4()));
+        let uc = CreatePostCommentUseCase::new(Arc::clone(&posts) as Arc<dyn PostsRepository>);
+
+        let mut parent = uc.execute(post.id, Uuid::new_v4(), None, "Nivel 0").await.unwrap();
+        for _ in 1..MAX_COMMENT_DEPTH {
+            parent = uc.execute(post.id, Uuid::new_v4(), Some(parent.id), "Respuesta").await.unwrap();
+        }
+
+        let result = uc.execute(post.id, Uuid::new_v4(), Some(parent.id), "Demasiado hondo").await;
+        assert!(matches!(result, Err(DomainError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn only_the_author_can_edit_a_comment() {
+        let posts = Arc::new(FakePostsRepository::default());
+        let post = posts.insert_post(Some(Uuid::new_v4()));
+        let author = Uuid::new_v4();
+        let comment = posts.insert_comment(post.id, author);
+        let uc = UpdatePostCommentUseCase::new(Arc::clone(&posts) as Arc<dyn PostsRepository>);
+
+        // Ni siquiera un admin edita comentarios ajenos.
+        for other in [Uuid::new_v4(), admin().user_id] {
+            let result = uc.execute(post.id, comment.id, other, "Editado").await;
+            assert!(matches!(result, Err(DomainError::Forbidden(_))));
+        }
+        assert_eq!(posts.comment(comment.id).unwrap().body, comment.body);
+
+        let edited = uc.execute(post.id, comment.id, author, "Editado").await.unwrap();
+        assert_eq!(edited.body, "Editado");
+    }
+
+    #[tokio::test]
+    async fn comment_is_deleted_by_its_author_or_an_admin() {
+        let posts = Arc::new(FakePostsRepository::default());
+        let post = posts.insert_post(Some(Uuid::new_v4()));
+        let author = Uuid::new_v4();
+        let uc = DeletePostCommentUseCase::new(Arc::clone(&posts) as Arc<dyn PostsRepository>);
+
+        let comment = posts.insert_comment(post.id, author);
+        let result = uc.execute(post.id, comment.id, &actor(Uuid::new_v4())).await;
+        assert!(matches!(result, Err(DomainError::Forbidden(_))));
+        assert!(posts.comment(comment.id).is_some());
+
+        uc.execute(post.id, comment.id, &actor(author)).await.unwrap();
+        assert!(posts.comment(comment.id).is_none());
+
+        let comment = posts.insert_comment(post.id, author);
+        uc.execute(post.id, comment.id, &admin()).await.unwrap();
+        assert!(posts.comment(comment.id).is_none());
     }
 }
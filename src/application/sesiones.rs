@@ -1,21 +1,32 @@
 // Casos de uso de Sesiones (Kotlin domain/cases/sesiones)
 
-use crate::domain::{DomainError, FavoritesRepository, Pose, Sesion, SesionesRepository};
+use crate::domain::{
+    DomainError, FavoritesRepository, ListQuery, Pose, Sesion, SesionInclude, SesionRelations,
+    SesionesRepository, UnitOfWorkFactory,
+};
+use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
 #[derive(Clone)]
-pub struct GetSesionesUseCase {
+pub struct GetSesionesPaginatedUseCase {
     repo: Arc<dyn SesionesRepository>,
 }
 
-impl GetSesionesUseCase {
+impl GetSesionesPaginatedUseCase {
     pub fn new(repo: Arc<dyn SesionesRepository>) -> Self {
         Self { repo }
     }
 
-    pub async fn execute(&self) -> Result<Vec<Sesion>, DomainError> {
-        self.repo.get_all().await
+    pub async fn execute(
+        &self,
+        list: &ListQuery,
+        page: u32,
+        limit: u32,
+    ) -> Result<(Vec<Sesion>, u64), DomainError> {
+        let items = self.repo.get_paginated(list, page, limit).await?;
+        let total = self.repo.count(list).await?;
+        Ok((items, total))
     }
 }
 
@@ -35,17 +46,52 @@ impl GetSesionByIdUseCase {
 }
 
 #[derive(Clone)]
-pub struct GetPosesBySesionUseCase {
+pub struct GetPosesBySesionPaginatedUseCase {
+    repo: Arc<dyn SesionesRepository>,
+}
+
+impl GetPosesBySesionPaginatedUseCase {
+    pub fn new(repo: Arc<dyn SesionesRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(
+        &self,
+        sesion_id: Uuid,
+        page: u32,
+        limit: u32,
+    ) -> Result<(Vec<Pose>, u64), DomainError> {
+        let items = self.repo.get_poses_by_sesion_paginated(sesion_id, page, limit).await?;
+        let total = self.repo.count_poses_by_sesion(sesion_id).await?;
+        Ok((items, total))
+    }
+}
+
+/// Relaciones de un lote de sesiones (`?include=poses`) en una sola consulta.
+#[derive(Clone)]
+pub struct GetSesionRelationsUseCase {
     repo: Arc<dyn SesionesRepository>,
 }
 
-impl GetPosesBySesionUseCase {
+impl GetSesionRelationsUseCase {
     pub fn new(repo: Arc<dyn SesionesRepository>) -> Self {
         Self { repo }
     }
 
-    pub async fn execute(&self, sesion_id: Uuid) -> Result<Vec<Pose>, DomainError> {
-        self.repo.get_poses_by_sesion(sesion_id).await
+    pub async fn execute(
+        &self,
+        sesion_ids: &[Uuid],
+        include: SesionInclude,
+    ) -> Result<SesionRelations, DomainError> {
+        let mut relations = SesionRelations::default();
+        if include.poses {
+            let mut by_sesion: HashMap<Uuid, Vec<Pose>> = HashMap::new();
+            for (sesion_id, pose) in self.repo.get_poses_by_sesiones(sesion_ids).await? {
+                by_sesion.entry(sesion_id).or_default().push(pose);
+            }
+            relations.poses = Some(by_sesion);
+        }
+        Ok(relations)
     }
 }
 
@@ -162,31 +208,125 @@ impl AddFavoritesToSesionUseCase {
 }
 
 /// Crea una sesión nueva con el nombre dado y mueve las poses favoritas del usuario a ella (luego las quita de favoritos).
+/// Todo en una transacción: si algún paso falla no queda ni la sesión ni los favoritos a medio mover.
 #[derive(Clone)]
 pub struct CreateSesionFromFavoritesUseCase {
     sesiones_repo: Arc<dyn SesionesRepository>,
     favorites_repo: Arc<dyn FavoritesRepository>,
+    unit_of_work: Arc<dyn UnitOfWorkFactory>,
 }
 
 impl CreateSesionFromFavoritesUseCase {
     pub fn new(
         sesiones_repo: Arc<dyn SesionesRepository>,
         favorites_repo: Arc<dyn FavoritesRepository>,
+        unit_of_work: Arc<dyn UnitOfWorkFactory>,
     ) -> Self {
         Self {
             sesiones_repo,
             favorites_repo,
+            unit_of_work,
         }
     }
 
     pub async fn execute(&self, user_id: Uuid, name: &str) -> Result<Sesion, DomainError> {
-        let sesion = self.sesiones_repo.create(name).await?;
-        let poses = self.favorites_repo.get_favorite_poses(user_id).await?;
+        let mut uow = self.unit_of_work.begin().await?;
+        let sesion = self.sesiones_repo.create_in(&mut *uow, name).await?;
+        let poses = self
+            .favorites_repo
+            .get_favorite_poses_in(&mut *uow, user_id)
+            .await?;
         let pose_ids: Vec<Uuid> = poses.into_iter().map(|p| p.id).collect();
         if !pose_ids.is_empty() {
-            self.sesiones_repo.add_poses_to_sesion(sesion.id, &pose_ids).await?;
-            self.favorites_repo.remove_poses_from_favorites(user_id, &pose_ids).await?;
+            self.sesiones_repo
+                .add_poses_to_sesion_in(&mut *uow, sesion.id, &pose_ids)
+                .await?;
+            self.favorites_repo
+                .remove_poses_from_favorites_in(&mut *uow, user_id, &pose_ids)
+                .await?;
         }
+        uow.commit().await?;
         Ok(sesion)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::fakes::{
+        FakeFavoritesRepository, FakePosesRepository, FakeSesionesRepository, FakeUnitOfWorkFactory,
+    };
+    use std::sync::atomic::Ordering;
+
+    struct Setup {
+        sesiones: Arc<FakeSesionesRepository>,
+        favorites: Arc<FakeFavoritesRepository>,
+        unit_of_work: Arc<FakeUnitOfWorkFactory>,
+        user_id: Uuid,
+        favorite_ids: Vec<Uuid>,
+    }
+
+    /// Un usuario con dos poses favoritas (la más reciente primero).
+    fn setup() -> Setup {
+        let poses = FakePosesRepository::default();
+        let favorites = Arc::new(FakeFavoritesRepository::default());
+        let user_id = Uuid::new_v4();
+        let (older, newer) = (poses.insert(), poses.insert());
+        let favorite_ids = vec![newer.id, older.id];
+        favorites.insert(user_id, older);
+        favorites.insert(user_id, newer);
+        Setup {
+            sesiones: Arc::new(FakeSesionesRepository::default()),
+            favorites,
+            unit_of_work: Arc::new(FakeUnitOfWorkFactory::default()),
+            user_id,
+            favorite_ids,
+        }
+    }
+
+    async fn create(setup: &Setup) -> Result<Sesion, DomainError> {
+        CreateSesionFromFavoritesUseCase::new(
+            Arc::clone(&setup.sesiones) as Arc<dyn SesionesRepository>,
+            Arc::clone(&setup.favorites) as Arc<dyn FavoritesRepository>,
+            Arc::clone(&setup.unit_of_work) as Arc<dyn UnitOfWorkFactory>,
+        )
+        .execute(setup.user_id, "Favoritas")
+        .await
+    }
+
+    async fn favorite_ids(setup: &Setup) -> Vec<Uuid> {
+        setup
+            .favorites
+            .get_favorite_poses(setup.user_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|p| p.id)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn favorites_move_into_the_new_sesion() {
+        let setup = setup();
+
+        let sesion = create(&setup).await.unwrap();
+
+        assert_eq!(sesion.name, "Favoritas");
+        assert_eq!(setup.sesiones.pose_ids(sesion.id), setup.favorite_ids);
+        assert!(favorite_ids(&setup).await.is_empty());
+        assert_eq!(setup.unit_of_work.commits(), 1);
+    }
+
+    #[tokio::test]
+    async fn failed_favorites_removal_rolls_back_the_sesion() {
+        let setup = setup();
+        setup.favorites.fail_remove.store(true, Ordering::SeqCst);
+
+        let result = create(&setup).await;
+
+        assert!(matches!(result, Err(DomainError::Repository(_))));
+        assert!(setup.sesiones.sesiones().is_empty());
+        assert_eq!(favorite_ids(&setup).await, setup.favorite_ids);
+        assert_eq!(setup.unit_of_work.commits(), 0);
+    }
+}
@@ -1,7 +1,12 @@
 // Casos de uso de Theme of the Day (orquestan el repositorio)
 
-use crate::domain::{DomainError, ThemeOfTheDay, ThemeOfTheDayRepository};
+use crate::domain::{
+    DomainError, LocalTimeZone, MonthDay, ThemeFallback, ThemeOfTheDay, ThemeOfTheDayKey,
+    ThemeOfTheDayPoolEntry, ThemeOfTheDayRepository,
+};
+use chrono::{Datelike, NaiveDate};
 use std::sync::Arc;
+use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct GetThemeOfTheDayAllUseCase {
@@ -18,7 +23,27 @@ impl GetThemeOfTheDayAllUseCase {
     }
 }
 
-/// Obtiene el tema del día de hoy (id = MMdd de la fecha actual). Equivalente a Kotlin getThemeOfTheDay().
+/// Archivo de temas de un año (con el número de posts de cada uno).
+#[derive(Clone)]
+pub struct GetThemeOfTheDayArchiveUseCase {
+    repo: Arc<dyn ThemeOfTheDayRepository>,
+}
+
+impl GetThemeOfTheDayArchiveUseCase {
+    pub fn new(repo: Arc<dyn ThemeOfTheDayRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(&self, year: i32) -> Result<Vec<ThemeOfTheDay>, DomainError> {
+        if !(1900..=9999).contains(&year) {
+            return Err(DomainError::Validation(format!("Año inválido: {}", year)));
+        }
+        self.repo.get_by_year(year).await
+    }
+}
+
+/// Obtiene el tema del día de hoy (fecha local en la zona indicada). Equivalente a Kotlin getThemeOfTheDay().
+/// Solo lee: el tema de los días sin programar lo elige y guarda `EnsureThemeOfTheDayFallbacksUseCase`.
 #[derive(Clone)]
 pub struct GetThemeOfTheDayTodayUseCase {
     repo: Arc<dyn ThemeOfTheDayRepository>,
@@ -29,15 +54,94 @@ impl GetThemeOfTheDayTodayUseCase {
         Self { repo }
     }
 
-    pub async fn execute(&self) -> Result<ThemeOfTheDay, DomainError> {
-        let mmdd = chrono::Utc::now().format("%m%d").to_string();
-        self.repo
-            .get_by_id(&mmdd)
-            .await?
-            .ok_or_else(|| DomainError::NotFound(format!("No hay tema del día para hoy ({}).", mmdd)))
+    /// Devuelve el tema y la fecha local con la que se resolvió. `fallback` del tema indica si se eligió
+    /// automáticamente.
+    pub async fn execute(
+        &self,
+        time_zone: LocalTimeZone,
+    ) -> Result<(ThemeOfTheDay, NaiveDate), DomainError> {
+        let local_date = time_zone.local_date(chrono::Utc::now());
+        match self.repo.get_by_date(local_date).await? {
+            Some(theme) => Ok((theme, local_date)),
+            None => Err(DomainError::NotFound(format!(
+                "No hay tema del día para hoy ({}).",
+                MonthDay::from_date(local_date)
+            ))),
+        }
+    }
+}
+
+/// Elige y guarda el tema de los días sin programar (tarea periódica). Cubre todas las fechas que son "hoy"
+/// en alguna zona horaria (de UTC-12 a UTC+14). Prueba las estrategias de fallback en orden y guarda el
+/// tema elegido como instancia de ese día (id = fecha, marcada con su fallback). Devuelve cuántas creó.
+#[derive(Clone)]
+pub struct EnsureThemeOfTheDayFallbacksUseCase {
+    repo: Arc<dyn ThemeOfTheDayRepository>,
+}
+
+impl EnsureThemeOfTheDayFallbacksUseCase {
+    pub fn new(repo: Arc<dyn ThemeOfTheDayRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        fallbacks: &[ThemeFallback],
+    ) -> Result<usize, DomainError> {
+        let first = (now - chrono::Duration::hours(12)).date_naive();
+        let last = (now + chrono::Duration::hours(14)).date_naive();
+        let mut created = 0;
+        for date in first.iter_days().take_while(|d| *d <= last) {
+            if self.repo.get_by_date(date).await?.is_some() {
+                continue;
+            }
+            if self.pick(date, fallbacks).await?.is_some() {
+                created += 1;
+            }
+        }
+        Ok(created)
+    }
+
+    async fn pick(
+        &self,
+        date: NaiveDate,
+        fallbacks: &[ThemeFallback],
+    ) -> Result<Option<ThemeOfTheDay>, DomainError> {
+        let mmdd = MonthDay::from_date(date);
+        for fallback in fallbacks {
+            // Nombre, imagen y entrada de la bolsa del tema elegido.
+            let picked = match fallback {
+                ThemeFallback::SameDay => self
+                    .repo
+                    .get_latest_by_mmdd(mmdd, date)
+                    .await?
+                    .filter(|t| t.date < date)
+                    .map(|t| (t.name, t.url, None)),
+                ThemeFallback::Pool => self
+                    .repo
+                    .next_pool_entry()
+                    .await?
+                    .map(|entry| (entry.name, entry.url, Some(entry.id))),
+                ThemeFallback::Popular => self
+                    .repo
+                    .get_most_popular_before(date)
+                    .await?
+                    .map(|t| (t.name, t.url, None)),
+            };
+            if let Some((name, url, pool_entry_id)) = picked {
+                let theme = self
+                    .repo
+                    .create_if_missing(date, &name, &url, *fallback, pool_entry_id)
+                    .await?;
+                return Ok(Some(theme));
+            }
+        }
+        Ok(None)
     }
 }
 
+/// Obtiene un tema por fecha (`YYYY-MM-DD`) o por MMdd (instancia más reciente hasta hoy, o la próxima).
 #[derive(Clone)]
 pub struct GetThemeOfTheDayByIdUseCase {
     repo: Arc<dyn ThemeOfTheDayRepository>,
@@ -49,13 +153,59 @@ impl GetThemeOfTheDayByIdUseCase {
     }
 
     pub async fn execute(&self, id: &str) -> Result<ThemeOfTheDay, DomainError> {
-        self.repo
-            .get_by_id(id)
-            .await?
-            .ok_or_else(|| DomainError::NotFound(format!("Tema del día no encontrado: {}", id)))
+        let theme = match ThemeOfTheDayKey::parse(id)? {
+            ThemeOfTheDayKey::Date(date) => self.repo.get_by_date(date).await?,
+            ThemeOfTheDayKey::MonthDay(mmdd) => {
+                let today = chrono::Utc::now().date_naive();
+                self.repo.get_latest_by_mmdd(mmdd, today).await?
+            }
+        };
+        theme.ok_or_else(|| DomainError::NotFound(format!("Tema del día no encontrado: {}", id.trim())))
     }
 }
 
+/// Instancia del tema a la que se enlaza un post. Con fecha completa, esa fecha; con MMdd, ese día en el
+/// año de la fecha local del post (`local_date`, en la zona horaria del autor). Solo enlaza instancias que
+/// ya existen (las crean el admin o la tarea de fallback): NotFound si ese día no tiene tema.
+#[derive(Clone)]
+pub struct ResolvePostThemeUseCase {
+    repo: Arc<dyn ThemeOfTheDayRepository>,
+}
+
+impl ResolvePostThemeUseCase {
+    pub fn new(repo: Arc<dyn ThemeOfTheDayRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(
+        &self,
+        key: ThemeOfTheDayKey,
+        local_date: NaiveDate,
+    ) -> Result<NaiveDate, DomainError> {
+        let date = match key {
+            ThemeOfTheDayKey::Date(date) => Some(date),
+            // 0229 en un año no bisiesto no tiene instancia.
+            ThemeOfTheDayKey::MonthDay(mmdd) => {
+                NaiveDate::from_ymd_opt(local_date.year(), mmdd.month(), mmdd.day())
+            }
+        };
+        match date {
+            Some(date) if self.repo.get_by_date(date).await?.is_some() => Ok(date),
+            Some(date) => Err(DomainError::NotFound(format!(
+                "No hay tema del día para {}",
+                date
+            ))),
+            None => Err(DomainError::NotFound(format!(
+                "No hay tema del día para {} en {}",
+                key.month_day(),
+                local_date.year()
+            ))),
+        }
+    }
+}
+
+/// Programa un tema para una fecha (se pueden programar con un año o más de antelación).
+/// Un tema ya programado en esa fecha devuelve Validation; uno elegido por fallback se sustituye.
 #[derive(Clone)]
 pub struct CreateThemeOfTheDayUseCase {
     repo: Arc<dyn ThemeOfTheDayRepository>,
@@ -66,18 +216,18 @@ impl CreateThemeOfTheDayUseCase {
         Self { repo }
     }
 
-    pub async fn execute(&self, id: &str, name: &str, url: &str) -> Result<ThemeOfTheDay, DomainError> {
-        if id.trim().is_empty() {
-            return Err(DomainError::Validation(
-                "El campo id (MMdd) es requerido".to_string(),
-            ));
-        }
-        if id.len() != 4 {
+    pub async fn execute(
+        &self,
+        date: NaiveDate,
+        name: &str,
+        url: &str,
+    ) -> Result<ThemeOfTheDay, DomainError> {
+        if name.trim().is_empty() {
             return Err(DomainError::Validation(
-                "El id debe tener exactamente 4 caracteres (formato MMdd)".to_string(),
+                "El campo name es requerido".to_string(),
             ));
         }
-        self.repo.create(id, name, url).await
+        self.repo.create(date, name, url).await
     }
 }
 
@@ -93,14 +243,14 @@ impl UpdateThemeOfTheDayUseCase {
 
     pub async fn execute(
         &self,
-        id: &str,
+        date: NaiveDate,
         name: Option<&str>,
         url: Option<&str>,
     ) -> Result<ThemeOfTheDay, DomainError> {
         self.repo
-            .update(id, name, url)
+            .update(date, name, url)
             .await?
-            .ok_or_else(|| DomainError::NotFound(format!("Tema del día no encontrado: {}", id)))
+            .ok_or_else(|| DomainError::NotFound(format!("Tema del día no encontrado: {}", date)))
     }
 }
 
@@ -114,7 +264,71 @@ impl DeleteThemeOfTheDayUseCase {
         Self { repo }
     }
 
-    pub async fn execute(&self, id: &str) -> Result<(), DomainError> {
-        self.repo.delete(id).await
+    pub async fn execute(&self, date: NaiveDate) -> Result<(), DomainError> {
+        self.repo.delete(date).await
+    }
+}
+
+#[derive(Clone)]
+pub struct GetThemeOfTheDayPoolUseCase {
+    repo: Arc<dyn ThemeOfTheDayRepository>,
+}
+
+impl GetThemeOfTheDayPoolUseCase {
+    pub fn new(repo: Arc<dyn ThemeOfTheDayRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(&self) -> Result<Vec<ThemeOfTheDayPoolEntry>, DomainError> {
+        self.repo.get_pool().await
+    }
+}
+
+/// Añade un tema sin fecha a la bolsa de rotación (id conocido: imagen guardada como pool-{id}.{ext}).
+#[derive(Clone)]
+pub struct AddThemeOfTheDayToPoolUseCase {
+    repo: Arc<dyn ThemeOfTheDayRepository>,
+}
+
+impl AddThemeOfTheDayToPoolUseCase {
+    pub fn new(repo: Arc<dyn ThemeOfTheDayRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute_with_id(
+        &self,
+        id: Uuid,
+        name: &str,
+        url: &str,
+    ) -> Result<ThemeOfTheDayPoolEntry, DomainError> {
+        if name.trim().is_empty() {
+            return Err(DomainError::Validation(
+                "El campo name es requerido".to_string(),
+            ));
+        }
+        self.repo.add_to_pool(id, name.trim(), url).await
+    }
+}
+
+#[derive(Clone)]
+pub struct RemoveThemeOfTheDayFromPoolUseCase {
+    repo: Arc<dyn ThemeOfTheDayRepository>,
+}
+
+impl RemoveThemeOfTheDayFromPoolUseCase {
+    pub fn new(repo: Arc<dyn ThemeOfTheDayRepository>) -> Self {
+        Self { repo }
+    }
+
+    /// Devuelve true si ningún tema servido usa la imagen de la entrada (se puede borrar del disco).
+    pub async fn execute(&self, id: Uuid) -> Result<bool, DomainError> {
+        let used = self.repo.is_pool_entry_used(id).await?;
+        if !self.repo.remove_from_pool(id).await? {
+            return Err(DomainError::NotFound(format!(
+                "Tema no encontrado en la bolsa: {}",
+                id
+            )));
+        }
+        Ok(!used)
     }
 }
@@ -1,6 +1,6 @@
 // Casos de uso de Usuario/Perfil (Kotlin domain/cases/usuario). user_id viene del JWT.
 
-use crate::domain::{DomainError, Usuario, UsuariosRepository};
+use crate::domain::{DomainError, LocalTimeZone, Usuario, UsuariosRepository};
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -30,8 +30,34 @@ impl UpdateUsuarioUseCase {
         Self { repo }
     }
 
-    pub async fn execute(&self, id: Uuid, name: Option<&str>) -> Result<Option<Usuario>, DomainError> {
-        self.repo.update_name(id, name).await
+    /// Actualiza solo los campos enviados: el nombre y la zona horaria (`time_zone` vacío la elimina).
+    pub async fn execute(
+        &self,
+        id: Uuid,
+        name: Option<&str>,
+        time_zone: Option<&str>,
+    ) -> Result<Option<Usuario>, DomainError> {
+        if let Some(tz) = time_zone {
+            let canonical = if tz.trim().is_empty() {
+                None
+            } else {
+                let parsed = LocalTimeZone::parse(tz).ok_or_else(|| {
+                    DomainError::Validation(format!(
+                        "Zona horaria inválida: {} (usa un nombre IANA como America/Bogota o un desfase como -05:00)",
+                        tz
+                    ))
+                })?;
+                Some(parsed.name())
+            };
+            let user = self.repo.update_time_zone(id, canonical.as_deref()).await?;
+            if user.is_none() || name.is_none() {
+                return Ok(user);
+            }
+        }
+        match name {
+            Some(name) => self.repo.update_name(id, Some(name)).await,
+            None => self.repo.get_by_id(id).await,
+        }
     }
 }
 
@@ -49,3 +75,206 @@ impl UpdateUsuarioAvatarUseCase {
         self.repo.update_avatar(id, url).await
     }
 }
+
+/// Zona horaria efectiva de una petición: la indicada por el cliente, si no la del perfil del usuario,
+/// y si no la del servidor.
+#[derive(Clone)]
+pub struct ResolveTimeZoneUseCase {
+    repo: Arc<dyn UsuariosRepository>,
+}
+
+impl ResolveTimeZoneUseCase {
+    pub fn new(repo: Arc<dyn UsuariosRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        requested: Option<&str>,
+        default: LocalTimeZone,
+    ) -> Result<LocalTimeZone, DomainError> {
+        if let Some(tz) = requested.filter(|tz| !tz.trim().is_empty()) {
+            return LocalTimeZone::parse(tz).ok_or_else(|| {
+                DomainError::Validation(format!(
+                    "Zona horaria inválida: {} (usa un nombre IANA como America/Bogota o un desfase como -05:00)",
+                    tz
+                ))
+            });
+        }
+        let from_profile = self
+            .repo
+            .get_by_id(user_id)
+            .await?
+            .and_then(|u| u.time_zone)
+            .and_then(|tz| LocalTimeZone::parse(&tz));
+        Ok(from_profile.unwrap_or(default))
+    }
+}
+
+/// Token del feed iCalendar del usuario (se genera la primera vez que se pide).
+#[derive(Clone)]
+pub struct GetCalendarTokenUseCase {
+    repo: Arc<dyn UsuariosRepository>,
+}
+
+impl GetCalendarTokenUseCase {
+    pub fn new(repo: Arc<dyn UsuariosRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(&self, user_id: Uuid) -> Result<String, DomainError> {
+        let new_token = Uuid::new_v4().simple().to_string();
+        self.repo
+            .get_or_create_calendar_token(user_id, &new_token)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Usuario no encontrado".to_string()))
+    }
+}
+
+/// Genera un token nuevo para el feed iCalendar; las URLs anteriores dejan de funcionar.
+#[derive(Clone)]
+pub struct RegenerateCalendarTokenUseCase {
+    repo: Arc<dyn UsuariosRepository>,
+}
+
+impl RegenerateCalendarTokenUseCase {
+    pub fn new(repo: Arc<dyn UsuariosRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn execute(&self, user_id: Uuid) -> Result<String, DomainError> {
+        let token = Uuid::new_v4().simple().to_string();
+        self.repo
+            .set_calendar_token(user_id, &token)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Usuario no encontrado".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    /// Repositorio en memoria con un único usuario.
+    struct FakeUsuariosRepository {
+        user: Mutex<Usuario>,
+    }
+
+    impl FakeUsuariosRepository {
+        fn new(name: &str) -> Self {
+            Self {
+                user: Mutex::new(Usuario {
+                    id: Uuid::new_v4(),
+                    name: Some(name.to_string()),
+                    email: None,
+                    url: None,
+                    time_zone: None,
+                    created_at: None,
+                }),
+            }
+        }
+
+        fn id(&self) -> Uuid {
+            self.user.lock().unwrap().id
+        }
+
+        fn update(&self, id: Uuid, f: impl FnOnce(&mut Usuario)) -> Option<Usuario> {
+            let mut user = self.user.lock().unwrap();
+            if user.id != id {
+                return None;
+            }
+            f(&mut user);
+            Some(user.clone())
+        }
+    }
+
+    #[async_trait]
+    impl UsuariosRepository for FakeUsuariosRepository {
+        async fn get_by_id(&self, id: Uuid) -> Result<Option<Usuario>, DomainError> {
+            Ok(self.update(id, |_| {}))
+        }
+
+        async fn update_name(&self, id: Uuid, name: Option<&str>) -> Result<Option<Usuario>, DomainError> {
+            Ok(self.update(id, |u| u.name = name.map(str::to_string)))
+        }
+
+        async fn update_time_zone(
+            &self,
+            id: Uuid,
+            time_zone: Option<&str>,
+        ) -> Result<Option<Usuario>, DomainError> {
+            Ok(self.update(id, |u| u.time_zone = time_zone.map(str::to_string)))
+        }
+
+        async fn update_avatar(&self, id: Uuid, url: &str) -> Result<Option<Usuario>, DomainError> {
+            Ok(self.update(id, |u| u.url = Some(url.to_string())))
+        }
+
+        async fn get_or_create_calendar_token(
+            &self,
+            _id: Uuid,
+            new_token: &str,
+        ) -> Result<Option<String>, DomainError> {
+            Ok(Some(new_token.to_string()))
+        }
+
+        async fn set_calendar_token(&self, _id: Uuid, token: &str) -> Result<Option<String>, DomainError> {
+            Ok(Some(token.to_string()))
+        }
+
+        async fn get_by_calendar_token(&self, _token: &str) -> Result<Option<Usuario>, DomainError> {
+            Ok(None)
+        }
+    }
+
+    #[tokio::test]
+    async fn time_zone_only_update_keeps_name() {
+        let repo = Arc::new(FakeUsuariosRepository::new("Dan"));
+        let id = repo.id();
+        let uc = UpdateUsuarioUseCase::new(repo.clone());
+
+        let user = uc.execute(id, None, Some("America/Bogota")).await.unwrap().unwrap();
+
+        assert_eq!(user.name.as_deref(), Some("Dan"));
+        assert_eq!(user.time_zone.as_deref(), Some("America/Bogota"));
+    }
+
+    #[tokio::test]
+    async fn name_and_time_zone_update_both() {
+        let repo = Arc::new(FakeUsuariosRepository::new("Dan"));
+        let id = repo.id();
+        let uc = UpdateUsuarioUseCase::new(repo.clone());
+
+        let user = uc.execute(id, Some("Daniel"), Some("-05:00")).await.unwrap().unwrap();
+
+        assert_eq!(user.name.as_deref(), Some("Daniel"));
+        assert_eq!(user.time_zone.as_deref(), Some("-05:00"));
+    }
+
+    #[tokio::test]
+    async fn empty_update_returns_current_profile() {
+        let repo = Arc::new(FakeUsuariosRepository::new("Dan"));
+        let id = repo.id();
+        let uc = UpdateUsuarioUseCase::new(repo.clone());
+
+        let user = uc.execute(id, None, None).await.unwrap().unwrap();
+
+        assert_eq!(user.name.as_deref(), Some("Dan"));
+        assert!(uc.execute(Uuid::new_v4(), None, None).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn invalid_time_zone_is_rejected_without_changes() {
+        let repo = Arc::new(FakeUsuariosRepository::new("Dan"));
+        let id = repo.id();
+        let uc = UpdateUsuarioUseCase::new(repo.clone());
+
+        let err = uc.execute(id, Some("Otro"), Some("Mars/Base")).await.unwrap_err();
+
+        assert!(matches!(err, DomainError::Validation(_)));
+        assert_eq!(repo.user.lock().unwrap().name.as_deref(), Some("Dan"));
+    }
+}
@@ -25,6 +25,9 @@ pub struct Config {
     /// Orígenes CORS permitidos (vacío = permitir cualquier origen, adecuado para desarrollo).
     /// En producción conviene definir `CORS_ALLOWED_ORIGINS` con orígenes separados por coma (ej. `https://app.ejemplo.com,https://admin.ejemplo.com`).
     pub cors_allowed_origins: Vec<String>,
+    /// Emails de administradores (`ADMIN_EMAILS`, separados por coma): pueden editar y borrar posts
+    /// de otros usuarios. Vacío = sin administradores.
+    pub admin_emails: Vec<String>,
     /// Carpeta donde se guardan las imágenes de theme-of-the-day (POST con imagen base64).
     pub theme_of_the_day_images_dir: String,
     /// Carpeta donde se guardan las imágenes de poses (POST con imagen base64).
@@ -39,6 +42,16 @@ pub struct Config {
     pub places_images_dir: String,
     /// Carpeta donde se guardan los avatares de perfil (PUT /api/profile/avatar con base64).
     pub profile_avatars_dir: String,
+    /// URL pública base de la API (ej. `https://api.danphoto.com`), usada para generar URLs absolutas
+    /// (feeds iCalendar, adjuntos). Sin ella no se dan las URLs de los feeds y se omiten los adjuntos.
+    pub public_base_url: Option<String>,
+    /// Zona horaria por defecto para calcular "hoy" (tema del día) cuando ni la petición ni el perfil
+    /// del usuario indican una (`DEFAULT_TIME_ZONE`, nombre IANA o desfase; por defecto UTC).
+    pub default_time_zone: String,
+    /// Estrategias (en orden) cuando no hay tema programado para hoy (`THEME_FALLBACK`, separadas por coma):
+    /// `same_day` (mismo MMdd de otro año), `pool` (rotación por la bolsa de temas sin fecha),
+    /// `popular` (tema pasado con más posts). `none` desactiva el fallback. Por defecto `same_day,pool,popular`.
+    pub theme_fallback: String,
 }
 
 impl Config {
@@ -80,6 +93,15 @@ impl Config {
                         .collect()
                 })
                 .unwrap_or_default(),
+            admin_emails: std::env::var("ADMIN_EMAILS")
+                .ok()
+                .map(|s| {
+                    s.split(',')
+                        .map(|e| e.trim().to_lowercase())
+                        .filter(|e| !e.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
             theme_of_the_day_images_dir: std::env::var("THEME_OF_THE_DAY_IMAGES_DIR")
                 .unwrap_or_else(|_| "./uploads/theme-of-the-day".to_string()),
             poses_images_dir: std::env::var("POSES_IMAGES_DIR")
@@ -94,6 +116,17 @@ impl Config {
                 .unwrap_or_else(|_| "./uploads/places".to_string()),
             profile_avatars_dir: std::env::var("PROFILE_AVATARS_DIR")
                 .unwrap_or_else(|_| "./uploads/profile-avatars".to_string()),
+            public_base_url: std::env::var("PUBLIC_BASE_URL")
+                .ok()
+                .map(|s| s.trim().trim_end_matches('/').to_string())
+                .filter(|s| !s.is_empty()),
+            default_time_zone: std::env::var("DEFAULT_TIME_ZONE")
+                .ok()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .unwrap_or_else(|| "UTC".to_string()),
+            theme_fallback: std::env::var("THEME_FALLBACK")
+                .unwrap_or_else(|_| "same_day,pool,popular".to_string()),
         }
     }
 
@@ -117,6 +150,20 @@ impl Config {
             }
         }
 
+        if crate::domain::LocalTimeZone::parse(&self.default_time_zone).is_none() {
+            return Err(format!(
+                "DEFAULT_TIME_ZONE inválida: {} (usa un nombre IANA como America/Bogota o un desfase como -05:00)",
+                self.default_time_zone
+            ));
+        }
+
+        if crate::domain::ThemeFallback::parse_list(&self.theme_fallback).is_none() {
+            return Err(format!(
+                "THEME_FALLBACK inválido: {} (valores: same_day, pool, popular o none)",
+                self.theme_fallback
+            ));
+        }
+
         if self.database_url.is_empty() {
             return Err("DATABASE_URL no puede estar vacío".to_string());
         }
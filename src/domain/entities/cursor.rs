@@ -0,0 +1,121 @@
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::DomainError;
+
+/// Posición en un listado ordenado por (created_at, id) descendente (paginación keyset).
+/// Se expone al cliente como token opaco (`next_cursor`): base64 de `{created_at en µs}:{id}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl PageCursor {
+    /// Cursor que apunta detrás de un elemento (None si no tiene fecha de creación).
+    pub fn after(created_at: Option<DateTime<Utc>>, id: Uuid) -> Option<Self> {
+        created_at.map(|created_at| PageCursor { created_at, id })
+    }
+
+    pub fn encode(&self) -> String {
+        base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .encode(format!("{}:{}", self.created_at.timestamp_micros(), self.id))
+    }
+
+    pub fn decode(token: &str) -> Result<Self, DomainError> {
+        let invalid = || DomainError::Validation(format!("cursor inválido: {}", token.trim()));
+        let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(token.trim())
+            .map_err(|_| invalid())?;
+        let text = String::from_utf8(bytes).map_err(|_| invalid())?;
+        let (micros, id) = text.split_once(':').ok_or_else(invalid)?;
+        let created_at = micros
+            .parse::<i64>()
+            .ok()
+            .and_then(DateTime::from_timestamp_micros)
+            .ok_or_else(invalid)?;
+        let id = Uuid::parse_str(id).map_err(|_| invalid())?;
+        Ok(PageCursor { created_at, id })
+    }
+
+    /// Cursor recibido en la query: vacío = primera página en modo cursor.
+    pub fn from_query(token: &str) -> Result<Option<Self>, DomainError> {
+        if token.trim().is_empty() {
+            return Ok(None);
+        }
+        PageCursor::decode(token).map(Some)
+    }
+
+    /// Recorta una página pedida con `limit + 1` filas: devuelve `limit` elementos y, si sobraba
+    /// alguno, el cursor para pedir la siguiente.
+    pub fn paginate<T>(
+        mut items: Vec<T>,
+        limit: u32,
+        key: impl Fn(&T) -> Option<PageCursor>,
+    ) -> (Vec<T>, Option<PageCursor>) {
+        if items.len() <= limit as usize {
+            return (items, None);
+        }
+        items.truncate(limit as usize);
+        let next = items.last().and_then(key);
+        (items, next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_text(text: &str) -> String {
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(text)
+    }
+
+    fn cursor(micros: i64) -> PageCursor {
+        PageCursor {
+            created_at: DateTime::from_timestamp_micros(micros).unwrap(),
+            id: Uuid::from_u128(micros as u128),
+        }
+    }
+
+    #[test]
+    fn encode_decode_round_trip() {
+        let original = cursor(1_700_000_000_123_456);
+        assert_eq!(PageCursor::decode(&original.encode()).unwrap(), original);
+        assert_eq!(
+            PageCursor::from_query(&format!(" {} ", original.encode())).unwrap(),
+            Some(original)
+        );
+        assert_eq!(PageCursor::from_query("  ").unwrap(), None);
+    }
+
+    #[test]
+    fn decode_rejects_malformed_tokens() {
+        let id = Uuid::new_v4();
+        for token in [
+            "***no-base64***".to_string(),
+            encode_text(&format!("1700000000000000{}", id)),
+            encode_text("1700000000000000:no-es-uuid"),
+            encode_text(&format!("ayer:{}", id)),
+            encode_text(&format!("{}:{}", i64::MAX, id)),
+        ] {
+            assert!(
+                matches!(PageCursor::decode(&token), Err(DomainError::Validation(_))),
+                "{}",
+                token
+            );
+        }
+    }
+
+    #[test]
+    fn paginate_has_more_only_beyond_limit() {
+        let items: Vec<i64> = (1..=3).collect();
+        let key = |micros: &i64| Some(cursor(*micros));
+
+        let (page, next) = PageCursor::paginate(items.clone(), 3, key);
+        assert_eq!((page, next), (vec![1, 2, 3], None));
+
+        let (page, next) = PageCursor::paginate(items, 2, key);
+        assert_eq!((page, next), (vec![1, 2], Some(cursor(2))));
+    }
+}
@@ -1,6 +1,9 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::{FilterKind, ListSpec, MonthDay, SortDirection};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Evento {
     pub id: Uuid,
@@ -9,4 +12,108 @@ pub struct Evento {
     pub mmdd: String,
     pub url: String,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Cupo máximo de asistentes confirmados (None = sin límite).
+    pub capacity: Option<i32>,
+    /// Asistentes confirmados (sin contar la lista de espera).
+    pub attendees_count: i64,
+    /// Posts de la galería del evento.
+    pub posts_count: i64,
+}
+
+impl Evento {
+    /// Orden y filtros admitidos en el listado de eventos (por defecto, por día del año y nombre).
+    pub const LIST_SPEC: ListSpec = ListSpec {
+        sort_fields: &[
+            ("mmdd", SortDirection::Asc),
+            ("name", SortDirection::Asc),
+            ("created_at", SortDirection::Desc),
+            ("capacity", SortDirection::Asc),
+            ("attendees_count", SortDirection::Desc),
+        ],
+        filter_fields: &[("place", FilterKind::Text), ("mmdd", FilterKind::Text)],
+        has_created_at: true,
+    };
+
+    /// Fecha concreta del evento: primera ocurrencia de `mmdd` en o después de la fecha de creación
+    /// (o de `today` si no hay created_at); 0229 cae el 28 de febrero en años no bisiestos.
+    /// None si `mmdd` no es una fecha válida.
+    pub fn date(&self, today: NaiveDate) -> Option<NaiveDate> {
+        let from = self.created_at.map(|c| c.date_naive()).unwrap_or(today);
+        let month_day = MonthDay::parse(&self.mmdd).ok()?;
+        Some(month_day.next_on_or_after(from))
+    }
+}
+
+/// Estado de la asistencia de un usuario a un evento.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttendanceStatus {
+    Confirmed,
+    /// El cupo estaba lleno; se confirma automáticamente cuando se libera un lugar.
+    Waitlisted,
+}
+
+impl AttendanceStatus {
+    /// Estado de quien se apunta a un evento con `capacity` (None = sin límite) y `confirmed`
+    /// asistentes ya confirmados: en lista de espera solo si el cupo está lleno.
+    pub fn for_new_attendee(capacity: Option<i32>, confirmed: i64) -> Self {
+        match capacity {
+            Some(cap) if confirmed >= i64::from(cap) => AttendanceStatus::Waitlisted,
+            _ => AttendanceStatus::Confirmed,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AttendanceStatus::Confirmed => "confirmed",
+            AttendanceStatus::Waitlisted => "waitlisted",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "confirmed" => Some(AttendanceStatus::Confirmed),
+            "waitlisted" => Some(AttendanceStatus::Waitlisted),
+            _ => None,
+        }
+    }
+}
+
+/// Usuario apuntado a un evento (tabla evento_asistentes), con datos públicos del perfil.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventoAsistente {
+    pub evento_id: Uuid,
+    pub user_id: Uuid,
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub status: AttendanceStatus,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_attendee_is_waitlisted_only_when_capacity_is_full() {
+        assert_eq!(AttendanceStatus::for_new_attendee(Some(3), 0), AttendanceStatus::Confirmed);
+        assert_eq!(AttendanceStatus::for_new_attendee(Some(3), 2), AttendanceStatus::Confirmed);
+        assert_eq!(AttendanceStatus::for_new_attendee(Some(3), 3), AttendanceStatus::Waitlisted);
+        // Un cupo reducido por debajo de los ya confirmados también deja en espera.
+        assert_eq!(AttendanceStatus::for_new_attendee(Some(1), 4), AttendanceStatus::Waitlisted);
+    }
+
+    #[test]
+    fn new_attendee_is_confirmed_without_capacity() {
+        assert_eq!(AttendanceStatus::for_new_attendee(None, 0), AttendanceStatus::Confirmed);
+        assert_eq!(AttendanceStatus::for_new_attendee(None, 10_000), AttendanceStatus::Confirmed);
+    }
+
+    #[test]
+    fn status_round_trips_through_its_column_value() {
+        for status in [AttendanceStatus::Confirmed, AttendanceStatus::Waitlisted] {
+            assert_eq!(AttendanceStatus::parse(status.as_str()), Some(status));
+        }
+        assert_eq!(AttendanceStatus::parse("cancelled"), None);
+    }
 }
@@ -1,8 +1,274 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::{FilterKind, ListSpec, SortDirection};
+use crate::domain::DomainError;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Hashtag {
     pub id: Uuid,
     pub name: String,
+    /// Hashtag padre en la taxonomía (ej. "sentados" dentro de "parejas"). None = raíz.
+    pub parent_id: Option<Uuid>,
+}
+
+/// Hashtag con su uso: poses (hashtag_image) y posts (hashtag_pose) etiquetados.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HashtagUsage {
+    pub hashtag: Hashtag,
+    pub poses_count: i64,
+    pub posts_count: i64,
+}
+
+/// Nodo del árbol de hashtags (menú de navegación): hashtag con su uso y sus hijos por nombre.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HashtagTreeNode {
+    pub hashtag: HashtagUsage,
+    pub children: Vec<HashtagTreeNode>,
+}
+
+impl HashtagTreeNode {
+    /// Arma el árbol a partir de todos los hashtags (en el orden en que deben quedar los hermanos).
+    pub fn build(hashtags: Vec<HashtagUsage>) -> Vec<HashtagTreeNode> {
+        fn children(
+            parent_id: Option<Uuid>,
+            by_parent: &mut std::collections::HashMap<Option<Uuid>, Vec<HashtagUsage>>,
+        ) -> Vec<HashtagTreeNode> {
+            by_parent
+                .remove(&parent_id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|hashtag| {
+                    let children = children(Some(hashtag.hashtag.id), by_parent);
+                    HashtagTreeNode { hashtag, children }
+                })
+                .collect()
+        }
+        let mut by_parent: std::collections::HashMap<Option<Uuid>, Vec<HashtagUsage>> =
+            std::collections::HashMap::new();
+        for hashtag in hashtags {
+            by_parent.entry(hashtag.hashtag.parent_id).or_default().push(hashtag);
+        }
+        children(None, &mut by_parent)
+    }
+}
+
+/// Hashtag que aparece junto a otro en las mismas poses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelatedHashtag {
+    pub hashtag: Hashtag,
+    /// Poses etiquetadas con ambos hashtags.
+    pub shared_poses: i64,
+}
+
+impl HashtagUsage {
+    /// Orden y filtros admitidos en el listado de hashtags (por defecto, alfabético). `popular` ordena
+    /// por poses + posts. Los hashtags no tienen fecha de creación.
+    pub const LIST_SPEC: ListSpec = ListSpec {
+        sort_fields: &[
+            ("name", SortDirection::Asc),
+            ("popular", SortDirection::Desc),
+            ("poses_count", SortDirection::Desc),
+            ("posts_count", SortDirection::Desc),
+        ],
+        filter_fields: &[("parent_id", FilterKind::Uuid)],
+        has_created_at: false,
+    };
+}
+
+/// Caracteres con tilde y su equivalente sin tilde (misma posición). La ñ se conserva.
+/// Incluye mayúsculas porque la función SQL `hashtag_key` (migración 0010) usa las mismas listas con
+/// `translate(lower(name), ...)`, y `lower` no pasa a minúsculas los no ASCII con cualquier collation.
+const HASHTAG_ACCENTS: &str = "áàäâãéèëêíìïîóòöôõúùüûçÁÀÄÂÃÉÈËÊÍÌÏÎÓÒÖÔÕÚÙÜÛÇÑ";
+const HASHTAG_ACCENTS_FOLDED: &str = "aaaaaeeeeiiiiooooouuuucaaaaaeeeeiiiiooooouuuucñ";
+
+impl Hashtag {
+    /// Forma canónica de un nombre: sin `#` inicial, en minúsculas y sin tildes ("#Atardecér" → "atardecer").
+    pub fn normalize_name(name: &str) -> String {
+        name.trim()
+            .trim_start_matches('#')
+            .chars()
+            .flat_map(char::to_lowercase)
+            .map(fold_accent)
+            .collect()
+    }
+
+    /// Nombre canónico para crear o renombrar un hashtag: normalizado y escribible en una descripción
+    /// (solo letras, números y `_`, con al menos una letra).
+    pub fn canonical_name(name: &str) -> Result<String, DomainError> {
+        let name = Hashtag::normalize_name(name);
+        if name.is_empty() {
+            return Err(DomainError::Validation("El nombre del hashtag es requerido".to_string()));
+        }
+        if !name.chars().all(is_hashtag_char) || !name.chars().any(char::is_alphabetic) {
+            return Err(DomainError::Validation(format!(
+                "Nombre de hashtag inválido: {} (solo letras, números y _)",
+                name
+            )));
+        }
+        Ok(name)
+    }
+
+    /// Hashtags escritos en un texto (`#golden_hour`), normalizados y sin repetir, en orden de aparición.
+    /// Se ignoran los `#` pegados a una palabra (`foto#1`) o tras `/` (`ejemplo.com/#seccion`), los de
+    /// enlaces (`https://…#ancla`) y los que solo llevan números (`#1`).
+    pub fn extract_names(text: &str) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for word in text.split_whitespace() {
+            if word.contains("://") || word.to_lowercase().starts_with("www.") {
+                continue;
+            }
+            let mut prev: Option<char> = None;
+            let mut chars = word.chars().peekable();
+            while let Some(c) = chars.next() {
+                if c != '#' || prev.is_some_and(|p| is_hashtag_char(p) || p == '/') {
+                    prev = Some(c);
+                    continue;
+                }
+                let mut tag = String::new();
+                while let Some(&next) = chars.peek().filter(|n| is_hashtag_char(**n)) {
+                    tag.push(next);
+                    chars.next();
+                }
+                prev = tag.chars().last().or(Some(c));
+                if !tag.chars().any(char::is_alphabetic) {
+                    continue;
+                }
+                let name = Hashtag::normalize_name(&tag);
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        names
+    }
+}
+
+fn is_hashtag_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn fold_accent(c: char) -> char {
+    HASHTAG_ACCENTS
+        .chars()
+        .position(|a| a == c)
+        .and_then(|i| HASHTAG_ACCENTS_FOLDED.chars().nth(i))
+        .unwrap_or(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_names_normalizes_and_dedupes_in_order() {
+        assert_eq!(
+            Hashtag::extract_names("#Retrato al #atardecer, otro #retrato y #RETRATO"),
+            vec!["retrato", "atardecer"]
+        );
+    }
+
+    #[test]
+    fn extract_names_stops_at_punctuation() {
+        assert_eq!(
+            Hashtag::extract_names("(#playa). ¡#sol! #golden_hour; \"#luz\"?"),
+            vec!["playa", "sol", "golden_hour", "luz"]
+        );
+    }
+
+    #[test]
+    fn extract_names_folds_accents_and_keeps_unicode_letters() {
+        assert_eq!(
+            Hashtag::extract_names("#Atardecér #Niño #東京 #Ελλάδα"),
+            vec!["atardecer", "niño", "東京", "ελλάδα"]
+        );
+    }
+
+    #[test]
+    fn extract_names_ignores_urls_and_attached_hashes() {
+        assert!(Hashtag::extract_names("http://x/#section").is_empty());
+        assert!(Hashtag::extract_names("https://ejemplo.com/galeria#fotos").is_empty());
+        assert!(Hashtag::extract_names("ejemplo.com/#seccion www.ejemplo.com#inicio").is_empty());
+        assert!(Hashtag::extract_names("foto#1 correo#tag").is_empty());
+        assert_eq!(
+            Hashtag::extract_names("mira http://x/#section #boda"),
+            vec!["boda"]
+        );
+    }
+
+    #[test]
+    fn extract_names_ignores_numbers_only_and_empty_tags() {
+        assert!(Hashtag::extract_names("#1 #2024 # ## #🔥").is_empty());
+        assert_eq!(Hashtag::extract_names("#2024boda ##doble"), vec!["2024boda", "doble"]);
+    }
+
+    #[test]
+    fn sql_hashtag_key_uses_the_same_accent_lists() {
+        let migration = include_str!("../../../migrations/0010_hashtags_unique_name.sql");
+        assert!(migration.contains(&format!("'{}'", HASHTAG_ACCENTS)));
+        assert!(migration.contains(&format!("'{}'", HASHTAG_ACCENTS_FOLDED)));
+    }
+
+    #[test]
+    fn canonical_name_normalizes_valid_names() {
+        assert_eq!(Hashtag::canonical_name("  #Atardecér ").unwrap(), "atardecer");
+        assert_eq!(Hashtag::canonical_name("Golden_Hour_2024").unwrap(), "golden_hour_2024");
+        assert_eq!(Hashtag::canonical_name("2024boda").unwrap(), "2024boda");
+    }
+
+    #[test]
+    fn canonical_name_rejects_empty_numeric_and_punctuated_names() {
+        for name in ["", "   ", "#", "##", "2024", "_1", "golden hour", "sol!", "a-b", "#tag#otro"] {
+            assert!(
+                matches!(Hashtag::canonical_name(name), Err(DomainError::Validation(_))),
+                "{:?}",
+                name
+            );
+        }
+    }
+
+    fn usage(name: &str, parent: Option<&HashtagUsage>) -> HashtagUsage {
+        HashtagUsage {
+            hashtag: Hashtag {
+                id: Uuid::new_v4(),
+                name: name.to_string(),
+                parent_id: parent.map(|p| p.hashtag.id),
+            },
+            poses_count: 0,
+            posts_count: 0,
+        }
+    }
+
+    fn names(nodes: &[HashtagTreeNode]) -> Vec<&str> {
+        nodes.iter().map(|n| n.hashtag.hashtag.name.as_str()).collect()
+    }
+
+    #[test]
+    fn tree_nests_children_under_their_parent() {
+        let parejas = usage("parejas", None);
+        let sentados = usage("sentados", Some(&parejas));
+        let banco = usage("banco", Some(&sentados));
+        let exterior = usage("exterior", None);
+        // Los hijos pueden llegar antes que su padre.
+        let tree = HashtagTreeNode::build(vec![banco, sentados, exterior, parejas]);
+
+        assert_eq!(names(&tree), vec!["exterior", "parejas"]);
+        assert!(tree[0].children.is_empty());
+        assert_eq!(names(&tree[1].children), vec!["sentados"]);
+        assert_eq!(names(&tree[1].children[0].children), vec!["banco"]);
+    }
+
+    #[test]
+    fn tree_keeps_the_given_sibling_order() {
+        let parejas = usage("parejas", None);
+        let children = ["sentados", "abrazo", "mirada"].map(|name| usage(name, Some(&parejas)));
+        let playa = usage("playa", None);
+        let mut all = vec![playa, parejas];
+        all.extend(children);
+
+        let tree = HashtagTreeNode::build(all);
+
+        assert_eq!(names(&tree), vec!["playa", "parejas"]);
+        assert_eq!(names(&tree[1].children), vec!["sentados", "abrazo", "mirada"]);
+    }
 }
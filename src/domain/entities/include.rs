@@ -0,0 +1,114 @@
+use std::collections::{HashMap, HashSet};
+
+use uuid::Uuid;
+
+use crate::domain::{DomainError, Hashtag, Pose, Sesion};
+
+/// Nombres de `?include=` separados por comas, validados contra los admitidos por el recurso.
+fn parse_include(raw: Option<&str>, allowed: &[&'static str]) -> Result<Vec<&'static str>, DomainError> {
+    raw.unwrap_or("")
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            allowed
+                .iter()
+                .copied()
+                .find(|a| a.eq_ignore_ascii_case(name))
+                .ok_or_else(|| {
+                    DomainError::Validation(format!(
+                        "include inválido: {} (admitidos: {})",
+                        name,
+                        allowed.join(", ")
+                    ))
+                })
+        })
+        .collect()
+}
+
+/// Relaciones que se pueden incluir en una pose (`?include=hashtags,favorite,sesiones`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PoseInclude {
+    pub hashtags: bool,
+    /// Si la pose está en favoritos del usuario autenticado.
+    pub favorite: bool,
+    /// Sesiones que contienen la pose.
+    pub sesiones: bool,
+}
+
+impl PoseInclude {
+    pub const NAMES: &'static [&'static str] = &["hashtags", "favorite", "sesiones"];
+
+    pub fn parse(raw: Option<&str>) -> Result<Self, DomainError> {
+        let names = parse_include(raw, Self::NAMES)?;
+        Ok(PoseInclude {
+            hashtags: names.contains(&"hashtags"),
+            favorite: names.contains(&"favorite"),
+            sesiones: names.contains(&"sesiones"),
+        })
+    }
+}
+
+/// Relaciones que se pueden incluir en una sesión (`?include=poses`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SesionInclude {
+    pub poses: bool,
+}
+
+impl SesionInclude {
+    pub const NAMES: &'static [&'static str] = &["poses"];
+
+    pub fn parse(raw: Option<&str>) -> Result<Self, DomainError> {
+        let names = parse_include(raw, Self::NAMES)?;
+        Ok(SesionInclude {
+            poses: names.contains(&"poses"),
+        })
+    }
+}
+
+/// Relaciones de un lote de poses, resueltas con una consulta por relación. None = no se pidió.
+#[derive(Debug, Default)]
+pub struct PoseRelations {
+    pub hashtags: Option<HashMap<Uuid, Vec<Hashtag>>>,
+    /// Poses del lote que están en favoritos del usuario.
+    pub favorites: Option<HashSet<Uuid>>,
+    pub sesiones: Option<HashMap<Uuid, Vec<Sesion>>>,
+}
+
+/// Poses de un lote de sesiones (en el orden en que se añadieron). None = no se pidieron.
+#[derive(Debug, Default)]
+pub struct SesionRelations {
+    pub poses: Option<HashMap<Uuid, Vec<Pose>>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pose_include_reads_each_known_name() {
+        assert_eq!(PoseInclude::parse(None).unwrap(), PoseInclude::default());
+        assert_eq!(
+            PoseInclude::parse(Some(" Hashtags, ,favorite")).unwrap(),
+            PoseInclude {
+                hashtags: true,
+                favorite: true,
+                sesiones: false,
+            }
+        );
+        assert!(PoseInclude::parse(Some("sesiones")).unwrap().sesiones);
+    }
+
+    #[test]
+    fn unknown_include_is_rejected() {
+        for raw in ["comments", "hashtags,poses"] {
+            assert!(
+                matches!(PoseInclude::parse(Some(raw)), Err(DomainError::Validation(_))),
+                "{}",
+                raw
+            );
+        }
+        assert!(matches!(SesionInclude::parse(Some("hashtags")), Err(DomainError::Validation(_))));
+        assert!(SesionInclude::parse(Some("POSES")).unwrap().poses);
+    }
+}
@@ -0,0 +1,302 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use uuid::Uuid;
+
+use crate::domain::DomainError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => "asc",
+            SortDirection::Desc => "desc",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "asc" => Some(SortDirection::Asc),
+            "desc" => Some(SortDirection::Desc),
+            _ => None,
+        }
+    }
+}
+
+/// Tipo del valor de un campo filtrable (se valida y normaliza antes de llegar al repositorio).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterKind {
+    /// Texto; se compara sin distinguir mayúsculas.
+    Text,
+    Uuid,
+    Int,
+    /// Fecha `YYYY-MM-DD`.
+    Date,
+}
+
+/// Lista blanca de un listado: campos ordenables (con su dirección por defecto) y campos filtrables.
+#[derive(Debug, Clone, Copy)]
+pub struct ListSpec {
+    pub sort_fields: &'static [(&'static str, SortDirection)],
+    pub filter_fields: &'static [(&'static str, FilterKind)],
+    /// Si el recurso tiene created_at (admite created_after / created_before).
+    pub has_created_at: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortSpec {
+    pub field: &'static str,
+    pub direction: SortDirection,
+}
+
+/// Filtro de igualdad sobre un campo de la lista blanca (valor ya normalizado).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldFilter {
+    pub field: &'static str,
+    pub kind: FilterKind,
+    pub value: String,
+}
+
+/// Orden y filtros comunes de un listado, validados contra el `ListSpec` del recurso.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListQuery {
+    /// None = orden por defecto del listado.
+    pub sort: Option<SortSpec>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    pub filters: Vec<FieldFilter>,
+}
+
+impl ListQuery {
+    /// Error si se pidió un orden propio en un modo que fija el suyo (p. ej. paginación por cursor).
+    pub fn require_default_sort(&self, mode: &str) -> Result<(), DomainError> {
+        match self.sort {
+            Some(_) => Err(DomainError::Validation(format!(
+                "sort no se puede combinar con {} (el orden es el del listado por defecto)",
+                mode
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+impl ListSpec {
+    /// Construye el `ListQuery` a partir de `sort=campo[:asc|desc]`, `filter=campo:valor[,campo:valor]`
+    /// y el rango de fechas de creación.
+    pub fn query(
+        &self,
+        sort: Option<&str>,
+        filter: Option<&str>,
+        created_after: Option<DateTime<Utc>>,
+        created_before: Option<DateTime<Utc>>,
+    ) -> Result<ListQuery, DomainError> {
+        if !self.has_created_at && (created_after.is_some() || created_before.is_some()) {
+            return Err(DomainError::Validation(
+                "Este listado no admite created_after ni created_before".to_string(),
+            ));
+        }
+        if let (Some(after), Some(before)) = (created_after, created_before) {
+            if after >= before {
+                return Err(DomainError::Validation(
+                    "created_after debe ser anterior a created_before".to_string(),
+                ));
+            }
+        }
+        let sort = match sort.map(str::trim).filter(|s| !s.is_empty()) {
+            Some(s) => Some(self.parse_sort(s)?),
+            None => None,
+        };
+        let filters = match filter {
+            Some(f) => self.parse_filters(f)?,
+            None => Vec::new(),
+        };
+        Ok(ListQuery {
+            sort,
+            created_after,
+            created_before,
+            filters,
+        })
+    }
+
+    fn parse_sort(&self, s: &str) -> Result<SortSpec, DomainError> {
+        let (name, direction) = match s.split_once(':') {
+            Some((name, direction)) => (name.trim(), Some(direction)),
+            None => (s, None),
+        };
+        let &(field, default_direction) = self
+            .sort_fields
+            .iter()
+            .find(|(f, _)| f.eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                DomainError::Validation(format!(
+                    "sort inválido: {} (campos: {})",
+                    name,
+                    self.sort_field_names()
+                ))
+            })?;
+        let direction = match direction {
+            Some(d) => SortDirection::parse(d).ok_or_else(|| {
+                DomainError::Validation(format!("Dirección de orden inválida: {} (usa asc o desc)", d))
+            })?,
+            None => default_direction,
+        };
+        Ok(SortSpec { field, direction })
+    }
+
+    fn parse_filters(&self, s: &str) -> Result<Vec<FieldFilter>, DomainError> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .map(|part| {
+                let (name, value) = part.split_once(':').ok_or_else(|| {
+                    DomainError::Validation(format!("filter inválido: {} (usa campo:valor)", part))
+                })?;
+                let &(field, kind) = self
+                    .filter_fields
+                    .iter()
+                    .find(|(f, _)| f.eq_ignore_ascii_case(name.trim()))
+                    .ok_or_else(|| {
+                        DomainError::Validation(format!(
+                            "Campo de filtro no permitido: {} (campos: {})",
+                            name.trim(),
+                            self.filter_field_names()
+                        ))
+                    })?;
+                let value = normalize_filter_value(field, kind, value.trim())?;
+                Ok(FieldFilter { field, kind, value })
+            })
+            .collect()
+    }
+
+    pub fn sort_field_names(&self) -> String {
+        let names: Vec<&str> = self.sort_fields.iter().map(|(f, _)| *f).collect();
+        names.join(", ")
+    }
+
+    pub fn filter_field_names(&self) -> String {
+        let names: Vec<&str> = self.filter_fields.iter().map(|(f, _)| *f).collect();
+        if names.is_empty() {
+            return "ninguno".to_string();
+        }
+        names.join(", ")
+    }
+}
+
+fn normalize_filter_value(field: &str, kind: FilterKind, value: &str) -> Result<String, DomainError> {
+    let invalid = |expected: &str| {
+        DomainError::Validation(format!("{}: valor inválido: {} (se esperaba {})", field, value, expected))
+    };
+    match kind {
+        FilterKind::Text if value.is_empty() => Err(invalid("texto")),
+        FilterKind::Text => Ok(value.to_string()),
+        FilterKind::Uuid => Uuid::parse_str(value)
+            .map(|id| id.to_string())
+            .map_err(|_| invalid("UUID")),
+        FilterKind::Int => value
+            .parse::<i64>()
+            .map(|n| n.to_string())
+            .map_err(|_| invalid("número entero")),
+        FilterKind::Date => NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map(|d| d.to_string())
+            .map_err(|_| invalid("fecha YYYY-MM-DD")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const SPEC: ListSpec = ListSpec {
+        sort_fields: &[("created_at", SortDirection::Desc), ("name", SortDirection::Asc)],
+        filter_fields: &[
+            ("name", FilterKind::Text),
+            ("parent_id", FilterKind::Uuid),
+            ("people_count", FilterKind::Int),
+            ("date", FilterKind::Date),
+        ],
+        has_created_at: true,
+    };
+
+    fn at(day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, day, 0, 0, 0).unwrap()
+    }
+
+    fn is_validation<T: std::fmt::Debug>(result: Result<T, DomainError>) -> bool {
+        matches!(result, Err(DomainError::Validation(_)))
+    }
+
+    #[test]
+    fn sort_uses_the_field_default_direction_unless_given() {
+        let sort = |s| SPEC.query(Some(s), None, None, None).unwrap().sort.unwrap();
+        assert_eq!(sort("name").direction, SortDirection::Asc);
+        assert_eq!(sort("Created_At").field, "created_at");
+        assert_eq!(sort("created_at").direction, SortDirection::Desc);
+        assert_eq!(sort("created_at: ASC").direction, SortDirection::Asc);
+        assert_eq!(SPEC.query(Some("  "), None, None, None).unwrap().sort, None);
+    }
+
+    #[test]
+    fn unknown_sort_field_or_direction_is_rejected() {
+        assert!(is_validation(SPEC.query(Some("password"), None, None, None)));
+        assert!(is_validation(SPEC.query(Some("name:up"), None, None, None)));
+        assert!(is_validation(SPEC.query(Some("name:"), None, None, None)));
+    }
+
+    #[test]
+    fn filters_are_validated_and_normalized() {
+        let id = Uuid::new_v4();
+        let filter = format!("name: Boda , PARENT_ID:{},people_count:+2,date:2024-05-01", id.simple());
+        let query = SPEC.query(None, Some(&filter), None, None).unwrap();
+        let values: Vec<(&str, &str)> = query
+            .filters
+            .iter()
+            .map(|f| (f.field, f.value.as_str()))
+            .collect();
+        let id = id.to_string();
+        assert_eq!(
+            values,
+            [
+                ("name", "Boda"),
+                ("parent_id", id.as_str()),
+                ("people_count", "2"),
+                ("date", "2024-05-01"),
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_or_unknown_filters_are_rejected() {
+        for filter in [
+            "name",
+            "email:a@b.c",
+            "name:",
+            "parent_id:abc",
+            "people_count:dos",
+            "date:01/05/2024",
+        ] {
+            assert!(is_validation(SPEC.query(None, Some(filter), None, None)), "{}", filter);
+        }
+    }
+
+    #[test]
+    fn created_range_must_be_increasing() {
+        assert!(SPEC.query(None, None, Some(at(1)), Some(at(2))).is_ok());
+        assert!(is_validation(SPEC.query(None, None, Some(at(2)), Some(at(2)))));
+        assert!(is_validation(SPEC.query(None, None, Some(at(3)), Some(at(2)))));
+    }
+
+    #[test]
+    fn created_range_needs_created_at() {
+        let spec = ListSpec {
+            has_created_at: false,
+            ..SPEC
+        };
+        assert!(spec.query(None, None, None, None).is_ok());
+        assert!(is_validation(spec.query(None, None, Some(at(1)), None)));
+        assert!(is_validation(spec.query(None, None, None, Some(at(1)))));
+    }
+}
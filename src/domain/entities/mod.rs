@@ -2,24 +2,42 @@
 
 #![allow(dead_code, unused_imports)]
 
+mod cursor;
 mod evento;
 mod favorito;
 mod hashtag;
+mod include;
+mod list_query;
+mod month_day;
 mod place;
 mod portfolio;
 mod pose;
 mod post;
 mod sesion;
 mod theme_of_the_day;
+mod time_zone;
 mod usuario;
 
-pub use evento::Evento;
+pub use cursor::PageCursor;
+pub use evento::{AttendanceStatus, Evento, EventoAsistente};
 pub use favorito::Favorito;
-pub use hashtag::Hashtag;
+pub use hashtag::{Hashtag, HashtagTreeNode, HashtagUsage, RelatedHashtag};
+pub use include::{PoseInclude, PoseRelations, SesionInclude, SesionRelations};
+pub use list_query::{FieldFilter, FilterKind, ListQuery, ListSpec, SortDirection, SortSpec};
+pub use month_day::MonthDay;
 pub use place::Place;
 pub use portfolio::{PortfolioCategory, PortfolioImage};
-pub use pose::Pose;
-pub use post::Post;
+pub use pose::{
+    Pose, PoseBatch, PoseBatchItem, PoseBatchOperation, PoseBatchStatus, PoseDifficulty, PoseFilter,
+    PoseMetadata, PoseMetadataPatch, PoseOrientation, PoseTagFilter, ScoredPose, MAX_POSE_BATCH,
+};
+pub use post::{
+    DailyWinner, LeaderboardEntry, Post, PostComment, PostCommentThread, PostImage, PostingStreak,
+    MAX_COMMENT_DEPTH, MAX_POST_IMAGES,
+};
 pub use sesion::Sesion;
-pub use theme_of_the_day::ThemeOfTheDay;
-pub use usuario::Usuario;
+pub use theme_of_the_day::{
+    ThemeFallback, ThemeOfTheDay, ThemeOfTheDayKey, ThemeOfTheDayPoolEntry,
+};
+pub use time_zone::LocalTimeZone;
+pub use usuario::{Actor, Usuario};
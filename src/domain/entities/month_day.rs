@@ -0,0 +1,139 @@
+use std::fmt;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::domain::DomainError;
+
+/// Día del año sin año (formato MMdd), usado como id del tema del día y fecha de los eventos.
+///
+/// Política del 29 de febrero: `0229` es válido. El tema del día `0229` solo aparece en años
+/// bisiestos (ese día no existe en los demás); para fechas concretas (`in_year`, eventos) en
+/// años no bisiestos se toma el 28 de febrero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MonthDay {
+    month: u32,
+    day: u32,
+}
+
+impl MonthDay {
+    /// Valida una cadena MMdd (4 dígitos, mes 01-12, día existente en ese mes en algún año).
+    pub fn parse(s: &str) -> Result<Self, DomainError> {
+        let s = s.trim();
+        if s.len() != 4 || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(DomainError::Validation(format!(
+                "Fecha inválida: \"{}\" (se esperaba MMdd, ej. 1024)",
+                s
+            )));
+        }
+        let month: u32 = s[0..2].parse().unwrap_or(0);
+        let day: u32 = s[2..4].parse().unwrap_or(0);
+        // 2000 es bisiesto: acepta 0229.
+        if NaiveDate::from_ymd_opt(2000, month, day).is_none() {
+            return Err(DomainError::Validation(format!(
+                "Fecha inválida: \"{}\" no es un día del calendario (MMdd)",
+                s
+            )));
+        }
+        Ok(MonthDay { month, day })
+    }
+
+    pub fn from_date(date: NaiveDate) -> Self {
+        MonthDay {
+            month: date.month(),
+            day: date.day(),
+        }
+    }
+
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    pub fn is_leap_day(&self) -> bool {
+        self.month == 2 && self.day == 29
+    }
+
+    /// Fecha concreta en `year` (0229 en año no bisiesto → 28 de febrero).
+    pub fn in_year(&self, year: i32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, self.month, self.day)
+            .or_else(|| NaiveDate::from_ymd_opt(year, self.month, self.day - 1))
+            .unwrap_or(NaiveDate::MIN)
+    }
+
+    /// Primera ocurrencia en o después de `from`.
+    pub fn next_on_or_after(&self, from: NaiveDate) -> NaiveDate {
+        let this_year = self.in_year(from.year());
+        if this_year >= from {
+            this_year
+        } else {
+            self.in_year(from.year() + 1)
+        }
+    }
+}
+
+impl fmt::Display for MonthDay {
+    /// Formato MMdd (ej. "0229").
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}{:02}", self.month, self.day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn parses_valid_dates() {
+        let md = MonthDay::parse("1024").unwrap();
+        assert_eq!((md.month(), md.day()), (10, 24));
+        assert_eq!(md.to_string(), "1024");
+        assert_eq!(MonthDay::parse(" 0101 ").unwrap().to_string(), "0101");
+        assert_eq!(MonthDay::parse("1231").unwrap().to_string(), "1231");
+    }
+
+    #[test]
+    fn accepts_leap_day() {
+        let md = MonthDay::parse("0229").unwrap();
+        assert!(md.is_leap_day());
+        assert_eq!(md.in_year(2028), date(2028, 2, 29));
+        assert_eq!(md.in_year(2027), date(2027, 2, 28));
+        assert_eq!(md.next_on_or_after(date(2027, 3, 1)), date(2028, 2, 29));
+    }
+
+    #[test]
+    fn rejects_days_not_in_calendar() {
+        for s in ["1301", "0000", "0100", "0001", "0230", "0431", "9999"] {
+            assert!(
+                matches!(MonthDay::parse(s), Err(DomainError::Validation(_))),
+                "{} debería ser inválido",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_wrong_length_or_non_digits() {
+        for s in ["", "124", "01024", "10-24", "1o24", "+124", "１０２４"] {
+            assert!(
+                matches!(MonthDay::parse(s), Err(DomainError::Validation(_))),
+                "{:?} debería ser inválido",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn next_on_or_after_wraps_to_next_year() {
+        let md = MonthDay::parse("0115").unwrap();
+        assert_eq!(md.next_on_or_after(date(2026, 1, 15)), date(2026, 1, 15));
+        assert_eq!(md.next_on_or_after(date(2026, 1, 16)), date(2027, 1, 15));
+        assert_eq!(MonthDay::from_date(date(2026, 10, 18)).to_string(), "1018");
+    }
+}
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::{FilterKind, ListSpec, SortDirection};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Place {
     pub id: Uuid,
@@ -15,3 +17,16 @@ pub struct Place {
     pub url: String,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
 }
+
+impl Place {
+    /// Orden y filtros admitidos en el listado de lugares (por defecto, los más recientes primero).
+    pub const LIST_SPEC: ListSpec = ListSpec {
+        sort_fields: &[
+            ("created_at", SortDirection::Desc),
+            ("name", SortDirection::Asc),
+            ("location", SortDirection::Asc),
+        ],
+        filter_fields: &[("name", FilterKind::Text), ("location", FilterKind::Text)],
+        has_created_at: true,
+    };
+}
@@ -1,9 +1,523 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::{FilterKind, ListSpec, SortDirection};
+use crate::domain::DomainError;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Pose {
     pub id: Uuid,
     pub url: String,
+    #[serde(flatten)]
+    pub metadata: PoseMetadata,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
 }
+
+impl Pose {
+    /// Orden y filtros admitidos en los listados de poses (por defecto, las más recientes primero).
+    pub const LIST_SPEC: ListSpec = ListSpec {
+        sort_fields: &[
+            ("created_at", SortDirection::Desc),
+            ("title", SortDirection::Asc),
+            ("people_count", SortDirection::Asc),
+            ("difficulty", SortDirection::Asc),
+        ],
+        filter_fields: &[
+            ("difficulty", FilterKind::Text),
+            ("orientation", FilterKind::Text),
+            ("people_count", FilterKind::Int),
+            ("source_credit", FilterKind::Text),
+        ],
+        has_created_at: true,
+    };
+}
+
+/// Pose con la puntuación de una recomendación (mayor = más relevante).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoredPose {
+    pub pose: Pose,
+    pub score: f64,
+}
+
+/// Datos descriptivos de una pose (todos opcionales).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoseMetadata {
+    pub title: Option<String>,
+    /// Descripción o instrucciones para dirigir la pose.
+    pub description: Option<String>,
+    /// Número de personas en la pose.
+    pub people_count: Option<i32>,
+    pub difficulty: Option<PoseDifficulty>,
+    pub orientation: Option<PoseOrientation>,
+    /// Crédito de la fuente (autor o enlace de la referencia original).
+    pub source_credit: Option<String>,
+}
+
+const POSE_TITLE_MAX_LEN: usize = 120;
+const POSE_DESCRIPTION_MAX_LEN: usize = 2000;
+const POSE_SOURCE_CREDIT_MAX_LEN: usize = 200;
+const POSE_PEOPLE_COUNT_MAX: i32 = 50;
+
+impl PoseMetadata {
+    /// Recorta los textos (vacío = sin valor) y valida longitudes y número de personas.
+    pub fn validated(self) -> Result<Self, DomainError> {
+        let people_count = match self.people_count {
+            Some(n) if !(1..=POSE_PEOPLE_COUNT_MAX).contains(&n) => {
+                return Err(DomainError::Validation(format!(
+                    "people_count debe estar entre 1 y {}",
+                    POSE_PEOPLE_COUNT_MAX
+                )));
+            }
+            n => n,
+        };
+        Ok(PoseMetadata {
+            title: clean_text("title", self.title, POSE_TITLE_MAX_LEN)?,
+            description: clean_text("description", self.description, POSE_DESCRIPTION_MAX_LEN)?,
+            people_count,
+            difficulty: self.difficulty,
+            orientation: self.orientation,
+            source_credit: clean_text(
+                "source_credit",
+                self.source_credit,
+                POSE_SOURCE_CREDIT_MAX_LEN,
+            )?,
+        })
+    }
+}
+
+fn clean_text(
+    field: &str,
+    value: Option<String>,
+    max_len: usize,
+) -> Result<Option<String>, DomainError> {
+    let Some(value) = value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty()) else {
+        return Ok(None);
+    };
+    if value.chars().count() > max_len {
+        return Err(DomainError::Validation(format!(
+            "{} no puede superar {} caracteres",
+            field, max_len
+        )));
+    }
+    Ok(Some(value))
+}
+
+/// Cambios parciales de metadatos (PUT /api/poses/{id}): None = sin cambios, Some(None) = borrar.
+#[derive(Debug, Clone, Default)]
+pub struct PoseMetadataPatch {
+    pub title: Option<Option<String>>,
+    pub description: Option<Option<String>>,
+    pub people_count: Option<Option<i32>>,
+    pub difficulty: Option<Option<PoseDifficulty>>,
+    pub orientation: Option<Option<PoseOrientation>>,
+    pub source_credit: Option<Option<String>>,
+}
+
+impl PoseMetadataPatch {
+    pub fn apply(self, metadata: PoseMetadata) -> PoseMetadata {
+        PoseMetadata {
+            title: self.title.unwrap_or(metadata.title),
+            description: self.description.unwrap_or(metadata.description),
+            people_count: self.people_count.unwrap_or(metadata.people_count),
+            difficulty: self.difficulty.unwrap_or(metadata.difficulty),
+            orientation: self.orientation.unwrap_or(metadata.orientation),
+            source_credit: self.source_credit.unwrap_or(metadata.source_credit),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PoseDifficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl PoseDifficulty {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PoseDifficulty::Easy => "easy",
+            PoseDifficulty::Medium => "medium",
+            PoseDifficulty::Hard => "hard",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "easy" => Some(PoseDifficulty::Easy),
+            "medium" => Some(PoseDifficulty::Medium),
+            "hard" => Some(PoseDifficulty::Hard),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PoseOrientation {
+    Portrait,
+    Landscape,
+}
+
+impl PoseOrientation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PoseOrientation::Portrait => "portrait",
+            PoseOrientation::Landscape => "landscape",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "portrait" => Some(PoseOrientation::Portrait),
+            "landscape" => Some(PoseOrientation::Landscape),
+            _ => None,
+        }
+    }
+}
+
+/// Filtro de poses por metadatos; los campos None no filtran.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PoseFilter {
+    pub difficulty: Option<PoseDifficulty>,
+    pub orientation: Option<PoseOrientation>,
+    /// Mínimo de personas (inclusive).
+    pub min_people: Option<i32>,
+    /// Máximo de personas (inclusive).
+    pub max_people: Option<i32>,
+    /// Texto a buscar en el título o la descripción (sin distinguir mayúsculas).
+    pub q: Option<String>,
+}
+
+impl PoseFilter {
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if let (Some(min), Some(max)) = (self.min_people, self.max_people) {
+            if min > max {
+                return Err(DomainError::Validation(
+                    "min_people no puede ser mayor que max_people".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Filtro booleano de poses por hashtags: todas las de `all`, al menos una de `any` y ninguna de `none`.
+/// Las listas vacías no filtran.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PoseTagFilter {
+    pub all: Vec<Uuid>,
+    pub any: Vec<Uuid>,
+    pub none: Vec<Uuid>,
+}
+
+impl PoseTagFilter {
+    pub fn is_empty(&self) -> bool {
+        self.all.is_empty() && self.any.is_empty() && self.none.is_empty()
+    }
+}
+
+/// Máximo de poses por lote en POST /api/poses/batch.
+pub const MAX_POSE_BATCH: usize = 200;
+
+/// Operación de un lote de poses; se aplica a todas las poses del lote, en orden.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PoseBatchOperation {
+    AddTags(Vec<Uuid>),
+    RemoveTags(Vec<Uuid>),
+    AddToSesion(Uuid),
+    /// Borra las poses y sus hashtags; solo puede ser la última operación.
+    Delete,
+}
+
+impl PoseBatchOperation {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PoseBatchOperation::AddTags(_) => "add_tags",
+            PoseBatchOperation::RemoveTags(_) => "remove_tags",
+            PoseBatchOperation::AddToSesion(_) => "add_to_sesion",
+            PoseBatchOperation::Delete => "delete",
+        }
+    }
+}
+
+/// Lote validado: poses sin repetir (en el orden pedido) y operaciones con sus ids sin repetir.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoseBatch {
+    pub pose_ids: Vec<Uuid>,
+    pub operations: Vec<PoseBatchOperation>,
+}
+
+impl PoseBatch {
+    pub fn new(pose_ids: Vec<Uuid>, operations: Vec<PoseBatchOperation>) -> Result<Self, DomainError> {
+        let pose_ids = dedup(pose_ids);
+        if pose_ids.is_empty() {
+            return Err(DomainError::Validation("pose_ids no puede estar vacío".to_string()));
+        }
+        if pose_ids.len() > MAX_POSE_BATCH {
+            return Err(DomainError::Validation(format!(
+                "Máximo {} poses por lote",
+                MAX_POSE_BATCH
+            )));
+        }
+        if operations.is_empty() {
+            return Err(DomainError::Validation("operations no puede estar vacío".to_string()));
+        }
+        let last = operations.len() - 1;
+        let operations = operations
+            .into_iter()
+            .enumerate()
+            .map(|(i, op)| {
+                let name = op.name();
+                match op {
+                    PoseBatchOperation::Delete if i != last => Err(DomainError::Validation(
+                        "delete debe ser la última operación".to_string(),
+                    )),
+                    PoseBatchOperation::AddTags(ids) | PoseBatchOperation::RemoveTags(ids)
+                        if ids.is_empty() =>
+                    {
+                        Err(DomainError::Validation(format!(
+                            "{}: hashtag_ids no puede estar vacío",
+                            name
+                        )))
+                    }
+                    PoseBatchOperation::AddTags(ids) => Ok(PoseBatchOperation::AddTags(dedup(ids))),
+                    PoseBatchOperation::RemoveTags(ids) => {
+                        Ok(PoseBatchOperation::RemoveTags(dedup(ids)))
+                    }
+                    op => Ok(op),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(PoseBatch {
+            pose_ids,
+            operations,
+        })
+    }
+
+    /// Hashtags de add_tags (deben existir).
+    pub fn added_hashtag_ids(&self) -> Vec<Uuid> {
+        let ids = self.operations.iter().flat_map(|op| match op {
+            PoseBatchOperation::AddTags(ids) => ids.clone(),
+            _ => Vec::new(),
+        });
+        dedup(ids.collect())
+    }
+
+    /// Sesiones de add_to_sesion (deben existir).
+    pub fn sesion_ids(&self) -> Vec<Uuid> {
+        let ids = self.operations.iter().filter_map(|op| match op {
+            PoseBatchOperation::AddToSesion(id) => Some(*id),
+            _ => None,
+        });
+        dedup(ids.collect())
+    }
+}
+
+fn dedup(ids: Vec<Uuid>) -> Vec<Uuid> {
+    let mut seen = std::collections::HashSet::new();
+    ids.into_iter().filter(|id| seen.insert(*id)).collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PoseBatchStatus {
+    Ok,
+    /// La pose no existe; no se le aplicó nada.
+    NotFound,
+}
+
+impl PoseBatchStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PoseBatchStatus::Ok => "ok",
+            PoseBatchStatus::NotFound => "not_found",
+        }
+    }
+}
+
+/// Resultado de un lote para una pose: cuántas filas cambió cada tipo de operación.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoseBatchItem {
+    pub pose_id: Uuid,
+    pub status: PoseBatchStatus,
+    pub tags_added: u32,
+    pub tags_removed: u32,
+    pub sesiones_added: u32,
+    pub deleted: bool,
+}
+
+impl PoseBatchItem {
+    pub fn new(pose_id: Uuid, status: PoseBatchStatus) -> Self {
+        PoseBatchItem {
+            pose_id,
+            status,
+            tags_added: 0,
+            tags_removed: 0,
+            sesiones_added: 0,
+            deleted: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(n: usize) -> Vec<Uuid> {
+        (0..n).map(|_| Uuid::new_v4()).collect()
+    }
+
+    fn is_validation<T>(result: Result<T, DomainError>) -> bool {
+        matches!(result, Err(DomainError::Validation(_)))
+    }
+
+    #[test]
+    fn metadata_trims_texts_and_drops_empty_ones() {
+        let metadata = PoseMetadata {
+            title: Some("  Contraluz ".to_string()),
+            description: Some("   ".to_string()),
+            source_credit: Some(String::new()),
+            ..PoseMetadata::default()
+        }
+        .validated()
+        .unwrap();
+        assert_eq!(metadata.title.as_deref(), Some("Contraluz"));
+        assert_eq!(metadata.description, None);
+        assert_eq!(metadata.source_credit, None);
+    }
+
+    #[test]
+    fn metadata_limits_text_length_in_characters() {
+        let title = |len: usize| PoseMetadata {
+            title: Some("ñ".repeat(len)),
+            ..PoseMetadata::default()
+        };
+        assert!(title(POSE_TITLE_MAX_LEN).validated().is_ok());
+        assert!(is_validation(title(POSE_TITLE_MAX_LEN + 1).validated()));
+        let credit = PoseMetadata {
+            source_credit: Some("a".repeat(POSE_SOURCE_CREDIT_MAX_LEN + 1)),
+            ..PoseMetadata::default()
+        };
+        assert!(is_validation(credit.validated()));
+    }
+
+    #[test]
+    fn metadata_people_count_must_be_in_range() {
+        let people = |n: i32| PoseMetadata {
+            people_count: Some(n),
+            ..PoseMetadata::default()
+        };
+        assert!(people(1).validated().is_ok());
+        assert!(people(POSE_PEOPLE_COUNT_MAX).validated().is_ok());
+        assert!(is_validation(people(0).validated()));
+        assert!(is_validation(people(-2).validated()));
+        assert!(is_validation(people(POSE_PEOPLE_COUNT_MAX + 1).validated()));
+    }
+
+    #[test]
+    fn patch_keeps_missing_fields_and_clears_explicit_nulls() {
+        let current = PoseMetadata {
+            title: Some("Contraluz".to_string()),
+            description: Some("De espaldas al sol".to_string()),
+            people_count: Some(2),
+            difficulty: Some(PoseDifficulty::Hard),
+            orientation: Some(PoseOrientation::Portrait),
+            source_credit: None,
+        };
+        let patch = PoseMetadataPatch {
+            title: Some(Some("Silueta".to_string())),
+            description: Some(None),
+            difficulty: Some(Some(PoseDifficulty::Easy)),
+            ..PoseMetadataPatch::default()
+        };
+        assert_eq!(
+            patch.apply(current),
+            PoseMetadata {
+                title: Some("Silueta".to_string()),
+                description: None,
+                people_count: Some(2),
+                difficulty: Some(PoseDifficulty::Easy),
+                orientation: Some(PoseOrientation::Portrait),
+                source_credit: None,
+            }
+        );
+    }
+
+    #[test]
+    fn difficulty_and_orientation_parse_case_insensitively() {
+        assert_eq!(PoseDifficulty::parse(" Medium "), Some(PoseDifficulty::Medium));
+        assert_eq!(PoseDifficulty::parse("extreme"), None);
+        assert_eq!(PoseOrientation::parse("LANDSCAPE"), Some(PoseOrientation::Landscape));
+        assert_eq!(PoseOrientation::parse("square"), None);
+        for difficulty in [PoseDifficulty::Easy, PoseDifficulty::Medium, PoseDifficulty::Hard] {
+            assert_eq!(PoseDifficulty::parse(difficulty.as_str()), Some(difficulty));
+        }
+    }
+
+    #[test]
+    fn filter_people_range_must_be_ordered() {
+        let range = |min, max| PoseFilter {
+            min_people: min,
+            max_people: max,
+            ..PoseFilter::default()
+        };
+        assert!(range(Some(2), Some(2)).validate().is_ok());
+        assert!(range(Some(3), None).validate().is_ok());
+        assert!(is_validation(range(Some(3), Some(2)).validate()));
+    }
+
+    #[test]
+    fn batch_rejects_empty_poses_and_operations() {
+        assert!(is_validation(PoseBatch::new(Vec::new(), vec![PoseBatchOperation::Delete])));
+        assert!(is_validation(PoseBatch::new(ids(1), Vec::new())));
+        assert!(is_validation(PoseBatch::new(
+            ids(1),
+            vec![PoseBatchOperation::AddTags(Vec::new())]
+        )));
+    }
+
+    #[test]
+    fn batch_rejects_more_than_max_poses() {
+        let ops = || vec![PoseBatchOperation::Delete];
+        assert!(PoseBatch::new(ids(MAX_POSE_BATCH), ops()).is_ok());
+        assert!(is_validation(PoseBatch::new(ids(MAX_POSE_BATCH + 1), ops())));
+    }
+
+    #[test]
+    fn batch_counts_duplicate_poses_once() {
+        let [a, b] = [Uuid::new_v4(), Uuid::new_v4()];
+        let mut pose_ids = vec![b, a, b];
+        pose_ids.extend(std::iter::repeat_n(a, MAX_POSE_BATCH));
+        let batch = PoseBatch::new(pose_ids, vec![PoseBatchOperation::Delete]).unwrap();
+        assert_eq!(batch.pose_ids, vec![b, a]);
+    }
+
+    #[test]
+    fn batch_dedups_operation_ids() {
+        let [h1, h2, s] = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        let batch = PoseBatch::new(
+            ids(1),
+            vec![
+                PoseBatchOperation::AddTags(vec![h1, h2, h1]),
+                PoseBatchOperation::RemoveTags(vec![h2, h2]),
+                PoseBatchOperation::AddTags(vec![h2]),
+                PoseBatchOperation::AddToSesion(s),
+                PoseBatchOperation::AddToSesion(s),
+            ],
+        )
+        .unwrap();
+        assert_eq!(batch.operations[0], PoseBatchOperation::AddTags(vec![h1, h2]));
+        assert_eq!(batch.operations[1], PoseBatchOperation::RemoveTags(vec![h2]));
+        assert_eq!(batch.added_hashtag_ids(), vec![h1, h2]);
+        assert_eq!(batch.sesion_ids(), vec![s]);
+    }
+
+    #[test]
+    fn batch_requires_delete_last() {
+        let ops = vec![
+            PoseBatchOperation::Delete,
+            PoseBatchOperation::AddToSesion(Uuid::new_v4()),
+        ];
+        assert!(is_validation(PoseBatch::new(ids(1), ops)));
+    }
+}
@@ -1,12 +1,305 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::{FilterKind, Hashtag, ListSpec, SortDirection};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Post {
     pub id: Uuid,
     pub description: Option<String>,
+    /// Primera imagen (compatibilidad con clientes de una sola imagen).
     pub url: Option<String>,
+    /// Imágenes del post en orden (carrusel); la primera es la que sirve `url`.
+    pub images: Vec<PostImage>,
     pub user_id: Option<Uuid>,
+    /// MMdd del tema del día (búsqueda por día del año).
     pub theme_of_the_day_id: Option<String>,
+    /// Fecha de la instancia del tema a la que pertenece el post (su galería).
+    pub theme_date: Option<chrono::NaiveDate>,
+    /// Evento a cuya galería pertenece el post (opcional).
+    pub evento_id: Option<Uuid>,
+    /// Hashtags del post (tabla hashtag_pose), por nombre.
+    pub hashtags: Vec<Hashtag>,
+    /// Votos del reto diario (uno por usuario).
+    pub votes_count: i64,
+    pub likes_count: i64,
+    /// Comentarios visibles (sin contar los eliminados).
+    pub comments_count: i64,
+    /// Si el usuario que consulta ha dado like (se rellena por petición; false por defecto).
+    pub liked_by_me: bool,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl Post {
+    /// Orden y filtros admitidos en los listados de posts (por defecto, los más recientes primero).
+    pub const LIST_SPEC: ListSpec = ListSpec {
+        sort_fields: &[
+            ("created_at", SortDirection::Desc),
+            ("votes_count", SortDirection::Desc),
+            ("likes_count", SortDirection::Desc),
+            ("comments_count", SortDirection::Desc),
+            ("theme_date", SortDirection::Desc),
+        ],
+        filter_fields: &[
+            ("user_id", FilterKind::Uuid),
+            ("evento_id", FilterKind::Uuid),
+            ("theme_of_the_day_id", FilterKind::Text),
+            ("theme_date", FilterKind::Date),
+        ],
+        has_created_at: true,
+    };
+}
+
+/// Comentario de un post. `parent_id` apunta al comentario al que responde (hilos).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostComment {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub user_id: Uuid,
+    /// Nombre y avatar del autor.
+    pub user_name: Option<String>,
+    pub user_url: Option<String>,
+    pub parent_id: Option<Uuid>,
+    /// Vacío si el comentario se eliminó pero se conserva por tener respuestas.
+    pub body: String,
+    pub deleted: bool,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Comentario con sus respuestas anidadas (en orden cronológico).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostCommentThread {
+    pub comment: PostComment,
+    pub replies: Vec<PostCommentThread>,
+}
+
+/// Niveles de anidación de un hilo (el comentario directo al post es el nivel 0).
+pub const MAX_COMMENT_DEPTH: u32 = 8;
+
+impl PostCommentThread {
+    /// Arma los hilos a partir de los comentarios de un post ordenados por fecha. La profundidad queda
+    /// acotada a `MAX_COMMENT_DEPTH`: las respuestas más profundas (anteriores al límite) se aplanan en
+    /// el último nivel, en orden cronológico.
+    pub fn build(comments: Vec<PostComment>) -> Vec<PostCommentThread> {
+        type ByParent = std::collections::HashMap<Option<Uuid>, Vec<PostComment>>;
+
+        fn descendants(id: Uuid, by_parent: &mut ByParent) -> Vec<PostCommentThread> {
+            let mut out = Vec::new();
+            let mut pending = vec![id];
+            while let Some(parent_id) = pending.pop() {
+                for comment in by_parent.remove(&Some(parent_id)).unwrap_or_default() {
+                    pending.push(comment.id);
+                    out.push(comment);
+                }
+            }
+            out.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+            out.into_iter()
+                .map(|comment| PostCommentThread { comment, replies: Vec::new() })
+                .collect()
+        }
+
+        fn children(parent_id: Option<Uuid>, depth: u32, by_parent: &mut ByParent) -> Vec<PostCommentThread> {
+            by_parent
+                .remove(&parent_id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|comment| {
+                    let replies = if depth + 2 < MAX_COMMENT_DEPTH {
+                        children(Some(comment.id), depth + 1, by_parent)
+                    } else {
+                        descendants(comment.id, by_parent)
+                    };
+                    PostCommentThread { comment, replies }
+                })
+                .collect()
+        }
+
+        let mut by_parent = ByParent::new();
+        for comment in comments {
+            by_parent.entry(comment.parent_id).or_default().push(comment);
+        }
+        children(None, 0, &mut by_parent)
+    }
+}
+
+/// Imagen de un post. La primera imagen de un post creado con una sola imagen tiene el id del post.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostImage {
+    pub id: Uuid,
+    pub url: String,
+}
+
+impl PostImage {
+    pub fn new(post_id: Uuid, id: Uuid) -> Self {
+        PostImage {
+            id,
+            url: format!("/api/posts/{}/images/{}", post_id, id),
+        }
+    }
+}
+
+/// Máximo de imágenes por post.
+pub const MAX_POST_IMAGES: usize = 10;
+
+/// Post en el ranking de votos de un tema del día (posición 1 = más votado; empates comparten posición).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub rank: u32,
+    pub post: Post,
+}
+
+/// Ganador del reto de un día, fijado al cerrarse la votación.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyWinner {
+    pub theme_date: NaiveDate,
+    pub post_id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub votes: i64,
+    pub decided_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Racha de días consecutivos con post en el reto diario. Cuenta el día (local) en que se publicó, no la
+/// fecha del tema: publicar hoy para temas pasados no alarga la racha.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PostingStreak {
+    /// Días seguidos hasta hoy (o hasta ayer, si hoy aún no ha publicado).
+    pub current: u32,
+    pub longest: u32,
+    pub last_posted_date: Option<NaiveDate>,
+}
+
+impl PostingStreak {
+    /// Calcula la racha a partir de las fechas locales con post (en cualquier orden; las repetidas
+    /// cuentan una vez).
+    pub fn from_dates(dates: &[NaiveDate], today: NaiveDate) -> Self {
+        let mut dates = dates.to_vec();
+        dates.sort_unstable();
+        dates.dedup();
+        let mut longest = 0;
+        let mut run = 0;
+        let mut previous: Option<NaiveDate> = None;
+        for date in dates {
+            run = match previous {
+                Some(p) if p.succ_opt() == Some(date) => run + 1,
+                _ => 1,
+            };
+            longest = longest.max(run);
+            previous = Some(date);
+        }
+        let current = match previous {
+            Some(last) if last == today || last.succ_opt() == Some(today) => run,
+            _ => 0,
+        };
+        PostingStreak {
+            current,
+            longest,
+            last_posted_date: previous,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn comment(id: u128, parent: Option<u128>, minute: u32) -> PostComment {
+        PostComment {
+            id: Uuid::from_u128(id),
+            post_id: Uuid::nil(),
+            user_id: Uuid::nil(),
+            user_name: None,
+            user_url: None,
+            parent_id: parent.map(Uuid::from_u128),
+            body: format!("c{}", id),
+            deleted: false,
+            created_at: chrono::DateTime::from_timestamp(i64::from(minute) * 60, 0),
+            updated_at: None,
+        }
+    }
+
+    fn depth(threads: &[PostCommentThread]) -> u32 {
+        threads.iter().map(|t| 1 + depth(&t.replies)).max().unwrap_or(0)
+    }
+
+    #[test]
+    fn comment_threads_nest_replies_in_order() {
+        let threads = PostCommentThread::build(vec![
+            comment(1, None, 0),
+            comment(2, Some(1), 1),
+            comment(3, None, 2),
+            comment(4, Some(1), 3),
+            comment(5, Some(2), 4),
+        ]);
+        let ids = |t: &[PostCommentThread]| t.iter().map(|t| t.comment.id.as_u128()).collect::<Vec<_>>();
+        assert_eq!(ids(&threads), vec![1, 3]);
+        assert_eq!(ids(&threads[0].replies), vec![2, 4]);
+        assert_eq!(ids(&threads[0].replies[0].replies), vec![5]);
+    }
+
+    #[test]
+    fn comment_threads_flatten_beyond_max_depth() {
+        // Cadena de 20 respuestas (anterior al límite de inserción): se corta en MAX_COMMENT_DEPTH.
+        let mut comments = vec![comment(1, None, 0)];
+        for id in 2..=20 {
+            comments.push(comment(id, Some(id - 1), id as u32));
+        }
+        let threads = PostCommentThread::build(comments);
+        assert_eq!(depth(&threads), MAX_COMMENT_DEPTH);
+
+        let mut last = &threads[0];
+        while let Some(next) = last.replies.first().filter(|t| !t.replies.is_empty()) {
+            last = next;
+        }
+        let flattened: Vec<u128> = last.replies.iter().map(|t| t.comment.id.as_u128()).collect();
+        assert_eq!(flattened, (MAX_COMMENT_DEPTH as u128..=20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn from_dates_empty() {
+        assert_eq!(PostingStreak::from_dates(&[], d("2024-05-10")), PostingStreak::default());
+    }
+
+    #[test]
+    fn from_dates_counts_today_and_yesterday() {
+        let dates = [d("2024-05-08"), d("2024-05-09"), d("2024-05-10")];
+        let today = PostingStreak::from_dates(&dates, d("2024-05-10"));
+        assert_eq!(today.current, 3);
+        assert_eq!(today.last_posted_date, Some(d("2024-05-10")));
+        // Aún no publica hoy: la racha de ayer sigue viva.
+        let yesterday = PostingStreak::from_dates(&dates, d("2024-05-11"));
+        assert_eq!(yesterday.current, 3);
+        // Saltó un día completo: se rompe.
+        let broken = PostingStreak::from_dates(&dates, d("2024-05-12"));
+        assert_eq!(broken.current, 0);
+        assert_eq!(broken.longest, 3);
+    }
+
+    #[test]
+    fn from_dates_gaps_reset_the_run() {
+        let dates = [
+            d("2024-05-01"),
+            d("2024-05-02"),
+            d("2024-05-03"),
+            d("2024-05-05"),
+            d("2024-05-06"),
+        ];
+        let streak = PostingStreak::from_dates(&dates, d("2024-05-06"));
+        assert_eq!(streak.current, 2);
+        assert_eq!(streak.longest, 3);
+    }
+
+    #[test]
+    fn from_dates_ignores_duplicates_and_order() {
+        let dates = [d("2024-05-03"), d("2024-05-02"), d("2024-05-03"), d("2024-05-02")];
+        let streak = PostingStreak::from_dates(&dates, d("2024-05-03"));
+        assert_eq!(streak.current, 2);
+        assert_eq!(streak.longest, 2);
+        assert_eq!(streak.last_posted_date, Some(d("2024-05-03")));
+    }
 }
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::{FilterKind, ListSpec, SortDirection};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Sesion {
     pub id: Uuid,
@@ -8,3 +10,12 @@ pub struct Sesion {
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
     pub cover_url: String,
 }
+
+impl Sesion {
+    /// Orden y filtros admitidos en el listado de sesiones (por defecto, las más recientes primero).
+    pub const LIST_SPEC: ListSpec = ListSpec {
+        sort_fields: &[("created_at", SortDirection::Desc), ("name", SortDirection::Asc)],
+        filter_fields: &[("name", FilterKind::Text)],
+        has_created_at: true,
+    };
+}
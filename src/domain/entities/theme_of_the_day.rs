@@ -1,8 +1,192 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
+use super::MonthDay;
+use crate::domain::DomainError;
+
+/// Tema del día programado para una fecha concreta. Cada año tiene sus propias instancias,
+/// de modo que los temas (y sus galerías de posts) de años anteriores se conservan.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThemeOfTheDay {
-    pub id: String, // MMdd
+    pub id: String, // YYYY-MM-DD (fecha de la instancia)
+    pub date: NaiveDate,
+    /// MMdd de la fecha, para búsquedas por día del año.
+    pub mmdd: String,
+    pub name: String,
+    pub url: String,
+    /// Posts enlazados a esta instancia del tema.
+    pub posts_count: i64,
+    /// Estrategia con la que se eligió el tema al no haber uno programado (None = programado).
+    pub fallback: Option<ThemeFallback>,
+    /// Entrada de la bolsa de la que salen el nombre y la imagen (fallback `pool`).
+    pub pool_entry_id: Option<Uuid>,
+}
+
+impl ThemeOfTheDay {
+    /// Momento en que termina el día `date` en todas las zonas horarias (la última es UTC-12):
+    /// a partir de ahí se cierra la votación y se fija el ganador.
+    pub fn closes_at(date: NaiveDate) -> chrono::DateTime<chrono::Utc> {
+        let next_day = date.succ_opt().unwrap_or(date);
+        next_day.and_hms_opt(12, 0, 0).unwrap_or_default().and_utc()
+    }
+
+    /// Último día cuya votación ya cerró en `now` (ver `closes_at`).
+    pub fn last_closed_date(now: chrono::DateTime<chrono::Utc>) -> NaiveDate {
+        let date = (now - chrono::Duration::hours(12)).date_naive();
+        date.pred_opt().unwrap_or(date)
+    }
+}
+
+/// Clave con la que los clientes identifican un tema: fecha completa (`YYYY-MM-DD`, una instancia)
+/// o MMdd (búsqueda por día del año: la instancia más reciente hasta hoy, o la próxima programada).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeOfTheDayKey {
+    Date(NaiveDate),
+    MonthDay(MonthDay),
+}
+
+impl ThemeOfTheDayKey {
+    pub fn parse(s: &str) -> Result<Self, DomainError> {
+        let s = s.trim();
+        if s.len() == 10 {
+            return NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .map(ThemeOfTheDayKey::Date)
+                .map_err(|_| {
+                    DomainError::Validation(format!(
+                        "Fecha inválida: \"{}\" (se esperaba YYYY-MM-DD o MMdd)",
+                        s
+                    ))
+                });
+        }
+        MonthDay::parse(s).map(ThemeOfTheDayKey::MonthDay)
+    }
+
+    pub fn month_day(&self) -> MonthDay {
+        match self {
+            ThemeOfTheDayKey::Date(date) => MonthDay::from_date(*date),
+            ThemeOfTheDayKey::MonthDay(month_day) => *month_day,
+        }
+    }
+
+    /// Fecha en la que se programa un tema nuevo: la indicada, o la próxima ocurrencia del MMdd.
+    pub fn schedule_date(&self, today: NaiveDate) -> NaiveDate {
+        match self {
+            ThemeOfTheDayKey::Date(date) => *date,
+            ThemeOfTheDayKey::MonthDay(month_day) => month_day.next_on_or_after(today),
+        }
+    }
+}
+
+/// Tema sin fecha en la bolsa de rotación: se usa cuando no hay tema programado para un día.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThemeOfTheDayPoolEntry {
+    pub id: Uuid,
     pub name: String,
     pub url: String,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Estrategia para elegir un tema cuando no hay ninguno programado para hoy (se prueban en orden).
+/// El tema elegido se guarda como instancia de hoy la primera vez que se sirve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeFallback {
+    /// Repite el nombre y la imagen de la instancia más reciente del mismo MMdd (ej. el año pasado).
+    SameDay,
+    /// Rota por la bolsa de temas sin fecha: la entrada que lleva más tiempo sin usarse.
+    Pool,
+    /// Reutiliza el tema pasado con más posts.
+    Popular,
+}
+
+impl ThemeFallback {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ThemeFallback::SameDay => "same_day",
+            ThemeFallback::Pool => "pool",
+            ThemeFallback::Popular => "popular",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "same_day" => Some(ThemeFallback::SameDay),
+            "pool" => Some(ThemeFallback::Pool),
+            "popular" => Some(ThemeFallback::Popular),
+            _ => None,
+        }
+    }
+
+    /// Lista separada por comas (ej. "same_day,pool,popular"); "none" o vacío = sin fallback.
+    pub fn parse_list(s: &str) -> Option<Vec<Self>> {
+        let s = s.trim();
+        if s.is_empty() || s.eq_ignore_ascii_case("none") {
+            return Some(Vec::new());
+        }
+        s.split(',').map(ThemeFallback::parse).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn key_parses_full_dates_and_month_days() {
+        assert_eq!(
+            ThemeOfTheDayKey::parse("2024-02-29").unwrap(),
+            ThemeOfTheDayKey::Date(date(2024, 2, 29))
+        );
+        let key = ThemeOfTheDayKey::parse(" 0229 ").unwrap();
+        assert_eq!(key, ThemeOfTheDayKey::MonthDay(MonthDay::parse("0229").unwrap()));
+        assert_eq!(ThemeOfTheDayKey::Date(date(2023, 7, 4)).month_day().to_string(), "0704");
+    }
+
+    #[test]
+    fn key_rejects_invalid_dates() {
+        for s in ["2023-02-29", "2024-13-01", "24-01-01", "1301", ""] {
+            assert!(
+                matches!(ThemeOfTheDayKey::parse(s), Err(DomainError::Validation(_))),
+                "{}",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn schedule_date_is_the_next_occurrence_of_a_month_day() {
+        let today = date(2024, 6, 15);
+        let schedule = |s: &str| ThemeOfTheDayKey::parse(s).unwrap().schedule_date(today);
+        assert_eq!(schedule("0615"), today);
+        assert_eq!(schedule("0101"), date(2025, 1, 1));
+        assert_eq!(schedule("2023-01-01"), date(2023, 1, 1));
+    }
+
+    #[test]
+    fn fallback_list_keeps_order_and_rejects_unknown_strategies() {
+        assert_eq!(
+            ThemeFallback::parse_list(" Pool, same_day ").unwrap(),
+            vec![ThemeFallback::Pool, ThemeFallback::SameDay]
+        );
+        assert_eq!(ThemeFallback::parse_list("none").unwrap(), Vec::new());
+        assert_eq!(ThemeFallback::parse_list("").unwrap(), Vec::new());
+        assert!(ThemeFallback::parse_list("pool,random").is_none());
+    }
+
+    #[test]
+    fn last_closed_date_matches_closes_at() {
+        let day = NaiveDate::from_ymd_opt(2024, 5, 10).unwrap();
+        let closes = ThemeOfTheDay::closes_at(day);
+        assert_eq!(ThemeOfTheDay::last_closed_date(closes), day);
+        let before = closes - chrono::Duration::seconds(1);
+        assert_eq!(ThemeOfTheDay::last_closed_date(before), day.pred_opt().unwrap());
+        let late = chrono::Utc.with_ymd_and_hms(2024, 5, 11, 23, 59, 0).unwrap();
+        assert_eq!(ThemeOfTheDay::last_closed_date(late), day);
+    }
 }
@@ -0,0 +1,141 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+use chrono_tz::Tz;
+
+/// Zona horaria con la que se calcula la fecha local de un usuario:
+/// un nombre IANA (ej. "America/Bogota") o un desfase fijo respecto a UTC (ej. "-05:00").
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalTimeZone {
+    Iana(Tz),
+    Offset(FixedOffset),
+}
+
+impl Default for LocalTimeZone {
+    fn default() -> Self {
+        LocalTimeZone::Iana(Tz::UTC)
+    }
+}
+
+impl LocalTimeZone {
+    /// Acepta un nombre IANA o un desfase `±HH:MM`, `±HHMM` o `±H` (opcionalmente con prefijo UTC/GMT).
+    /// Un desfase sin signo se toma como positivo (en query strings `+` llega como espacio).
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        if s.is_empty() {
+            return None;
+        }
+        if let Ok(tz) = s.parse::<Tz>() {
+            return Some(LocalTimeZone::Iana(tz));
+        }
+        parse_offset(s).map(LocalTimeZone::Offset)
+    }
+
+    /// Fecha local en esta zona para el instante `now`.
+    pub fn local_date(&self, now: DateTime<Utc>) -> NaiveDate {
+        match self {
+            LocalTimeZone::Iana(tz) => now.with_timezone(tz).date_naive(),
+            LocalTimeZone::Offset(offset) => now.with_timezone(offset).date_naive(),
+        }
+    }
+
+    /// Representación canónica (nombre IANA o `±HH:MM`), la que se guarda en el perfil.
+    pub fn name(&self) -> String {
+        match self {
+            LocalTimeZone::Iana(tz) => tz.name().to_string(),
+            LocalTimeZone::Offset(offset) => offset.to_string(),
+        }
+    }
+}
+
+fn parse_offset(s: &str) -> Option<FixedOffset> {
+    let upper = s.to_ascii_uppercase();
+    let rest = upper
+        .strip_prefix("UTC")
+        .or_else(|| upper.strip_prefix("GMT"))
+        .unwrap_or(&upper)
+        .trim();
+    let (sign, digits) = match rest.as_bytes().first()? {
+        b'+' => (1, &rest[1..]),
+        b'-' => (-1, &rest[1..]),
+        _ => (1, rest),
+    };
+    let (hours, minutes) = match digits.split_once(':') {
+        Some((h, m)) => (h, m),
+        None if digits.len() > 2 => digits.split_at(digits.len() - 2),
+        None => (digits, "0"),
+    };
+    if hours.is_empty() || !hours.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    if !minutes.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let hours: i32 = hours.parse().ok()?;
+    let minutes: i32 = minutes.parse().ok()?;
+    if hours > 14 || minutes > 59 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn offset_secs(s: &str) -> Option<i32> {
+        match LocalTimeZone::parse(s)? {
+            LocalTimeZone::Offset(offset) => Some(offset.local_minus_utc()),
+            LocalTimeZone::Iana(tz) => panic!("{} se interpretó como IANA ({})", s, tz),
+        }
+    }
+
+    #[test]
+    fn parses_iana_names() {
+        assert_eq!(
+            LocalTimeZone::parse("America/Bogota"),
+            Some(LocalTimeZone::Iana(chrono_tz::America::Bogota))
+        );
+        assert_eq!(
+            LocalTimeZone::parse("  Europe/Madrid "),
+            Some(LocalTimeZone::Iana(chrono_tz::Europe::Madrid))
+        );
+        assert_eq!(LocalTimeZone::parse("UTC"), Some(LocalTimeZone::Iana(Tz::UTC)));
+        assert_eq!(LocalTimeZone::parse("America/Bogota").unwrap().name(), "America/Bogota");
+    }
+
+    #[test]
+    fn parses_offsets() {
+        assert_eq!(offset_secs("-05:00"), Some(-5 * 3600));
+        assert_eq!(offset_secs("+05:30"), Some(5 * 3600 + 30 * 60));
+        assert_eq!(offset_secs("-0500"), Some(-5 * 3600));
+        assert_eq!(offset_secs("-5"), Some(-5 * 3600));
+        assert_eq!(offset_secs("UTC-03:00"), Some(-3 * 3600));
+        assert_eq!(offset_secs("GMT+1"), Some(3600));
+        // `+` en query string llega como espacio.
+        assert_eq!(offset_secs(" 09:00"), Some(9 * 3600));
+        assert_eq!(offset_secs("+14:00"), Some(14 * 3600));
+        assert_eq!(LocalTimeZone::parse("-0500").unwrap().name(), "-05:00");
+    }
+
+    #[test]
+    fn rejects_out_of_range_offsets() {
+        assert_eq!(LocalTimeZone::parse("+15:00"), None);
+        assert_eq!(LocalTimeZone::parse("-05:60"), None);
+        assert_eq!(LocalTimeZone::parse("+99"), None);
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        for s in ["", "   ", "Mars/Base", "abc", "+", "-:30", "+5:x", "05:00:00", "UTC+"] {
+            assert_eq!(LocalTimeZone::parse(s), None, "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn local_date_uses_the_zone() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 1, 3, 0, 0).unwrap();
+        let bogota = LocalTimeZone::parse("America/Bogota").unwrap();
+        assert_eq!(bogota.local_date(now), NaiveDate::from_ymd_opt(2026, 2, 28).unwrap());
+        assert_eq!(LocalTimeZone::default().local_date(now), NaiveDate::from_ymd_opt(2026, 3, 1).unwrap());
+    }
+}
@@ -7,5 +7,50 @@ pub struct Usuario {
     pub name: Option<String>,
     pub email: Option<String>,
     pub url: Option<String>,
+    /// Zona horaria preferida (nombre IANA o desfase `±HH:MM`); None = la del servidor.
+    pub time_zone: Option<String>,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
 }
+
+/// Usuario que realiza una acción, para comprobar permisos sobre recursos con autor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Actor {
+    pub user_id: Uuid,
+    /// Administrador (ADMIN_EMAILS): puede modificar recursos de otros usuarios.
+    pub is_admin: bool,
+}
+
+impl Actor {
+    /// El autor o un admin (los recursos sin autor solo los modifica un admin).
+    pub fn can_modify(&self, owner: Option<Uuid>) -> bool {
+        self.is_admin || owner == Some(self.user_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn author_can_modify_own_resources_only() {
+        let author = Uuid::new_v4();
+        let actor = Actor {
+            user_id: author,
+            is_admin: false,
+        };
+        assert!(actor.can_modify(Some(author)));
+        assert!(!actor.can_modify(Some(Uuid::new_v4())));
+        assert!(!actor.can_modify(None));
+    }
+
+    #[test]
+    fn admin_can_modify_any_resource() {
+        let admin = Actor {
+            user_id: Uuid::new_v4(),
+            is_admin: true,
+        };
+        assert!(admin.can_modify(Some(admin.user_id)));
+        assert!(admin.can_modify(Some(Uuid::new_v4())));
+        assert!(admin.can_modify(None));
+    }
+}
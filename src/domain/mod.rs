@@ -5,7 +5,7 @@ pub mod repositories;
 
 pub use entities::*;
 pub use repositories::{
-    AuthRepository, AuthUser, DomainError, EventosRepository, FavoritesRepository,
+    AuthRepository, AuthUser, Compensation, DomainError, EventosRepository, FavoritesRepository,
     HashtagsRepository, PlacesRepository, PortfolioRepository, PosesRepository, PostsRepository,
-    SesionesRepository, ThemeOfTheDayRepository, UsuariosRepository,
+    SesionesRepository, ThemeOfTheDayRepository, UnitOfWork, UnitOfWorkFactory, UsuariosRepository,
 };
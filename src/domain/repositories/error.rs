@@ -6,6 +6,9 @@ pub enum DomainError {
     NotFound(String),
     #[error("validation: {0}")]
     Validation(String),
+    /// El usuario está autenticado pero no puede modificar el recurso (no es el autor ni admin).
+    #[error("forbidden: {0}")]
+    Forbidden(String),
     #[error("repository: {0}")]
     Repository(#[from] anyhow::Error),
 }
@@ -3,13 +3,21 @@
 use async_trait::async_trait;
 use uuid::Uuid;
 
-use crate::domain::Evento;
+use crate::domain::{AttendanceStatus, Evento, EventoAsistente, ListQuery};
 
 use super::error::DomainError;
 
 #[async_trait]
 pub trait EventosRepository: Send + Sync {
-    async fn get_all(&self) -> Result<Vec<Evento>, DomainError>;
+    async fn get_all(&self, list: &ListQuery) -> Result<Vec<Evento>, DomainError>;
+    async fn get_paginated(
+        &self,
+        list: &ListQuery,
+        page: u32,
+        limit: u32,
+    ) -> Result<Vec<Evento>, DomainError>;
+    /// Total de eventos que cumplen el listado (para paginación).
+    async fn count(&self, list: &ListQuery) -> Result<u64, DomainError>;
     async fn get_by_id(&self, id: Uuid) -> Result<Option<Evento>, DomainError>;
     /// Crea un evento con id conocido (para guardar la imagen como {id}.{ext}).
     async fn create_with_id(
@@ -19,7 +27,9 @@ pub trait EventosRepository: Send + Sync {
         place: &str,
         url: &str,
         mmdd: &str,
+        capacity: Option<i32>,
     ) -> Result<Evento, DomainError>;
+    /// `capacity`: None = no cambia, Some(0) = quita el límite. Si el cupo crece, promueve la lista de espera.
     async fn update(
         &self,
         id: Uuid,
@@ -27,6 +37,23 @@ pub trait EventosRepository: Send + Sync {
         place: Option<&str>,
         url: Option<&str>,
         mmdd: Option<&str>,
+        capacity: Option<i32>,
     ) -> Result<Option<Evento>, DomainError>;
     async fn delete(&self, id: Uuid) -> Result<(), DomainError>;
+    /// Apunta al usuario (confirmado si hay cupo, si no en lista de espera). Idempotente:
+    /// si ya estaba apuntado devuelve su asistencia actual. None si el evento no existe.
+    async fn add_attendee(
+        &self,
+        evento_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<EventoAsistente>, DomainError>;
+    /// Quita al usuario del evento y, si liberó un lugar confirmado, promueve al primero en espera.
+    async fn remove_attendee(&self, evento_id: Uuid, user_id: Uuid) -> Result<(), DomainError>;
+    /// Asistentes del evento: confirmados primero y luego la lista de espera, por orden de llegada.
+    async fn get_attendees(&self, evento_id: Uuid) -> Result<Vec<EventoAsistente>, DomainError>;
+    /// Eventos a los que está apuntado el usuario, con su estado de asistencia.
+    async fn get_by_attendee(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<(Evento, AttendanceStatus)>, DomainError>;
 }
@@ -6,10 +6,17 @@ use uuid::Uuid;
 use crate::domain::Pose;
 
 use super::error::DomainError;
+use super::unit_of_work::UnitOfWork;
 
 #[async_trait]
 pub trait FavoritesRepository: Send + Sync {
     async fn is_pose_favorite(&self, user_id: Uuid, pose_id: Uuid) -> Result<bool, DomainError>;
+    /// De `pose_ids`, las que están en favoritos del usuario (una sola consulta).
+    async fn get_favorite_pose_ids(
+        &self,
+        user_id: Uuid,
+        pose_ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, DomainError>;
     async fn add_pose_to_favorites(
         &self,
         user_id: Uuid,
@@ -26,5 +33,26 @@ pub trait FavoritesRepository: Send + Sync {
         user_id: Uuid,
         pose_ids: &[Uuid],
     ) -> Result<(), DomainError>;
+    /// `remove_poses_from_favorites` dentro de la unidad de trabajo `uow`.
+    async fn remove_poses_from_favorites_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        user_id: Uuid,
+        pose_ids: &[Uuid],
+    ) -> Result<(), DomainError>;
     async fn get_favorite_poses(&self, user_id: Uuid) -> Result<Vec<Pose>, DomainError>;
+    /// `get_favorite_poses` dentro de la unidad de trabajo `uow`.
+    async fn get_favorite_poses_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        user_id: Uuid,
+    ) -> Result<Vec<Pose>, DomainError>;
+    /// Favoritos del usuario, los más recientes primero (paginado).
+    async fn get_favorite_poses_paginated(
+        &self,
+        user_id: Uuid,
+        page: u32,
+        limit: u32,
+    ) -> Result<Vec<Pose>, DomainError>;
+    async fn count_favorite_poses(&self, user_id: Uuid) -> Result<u64, DomainError>;
 }
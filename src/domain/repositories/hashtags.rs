@@ -3,43 +3,174 @@
 use async_trait::async_trait;
 use uuid::Uuid;
 
-use crate::domain::{Hashtag, Pose};
+use crate::domain::{
+    Hashtag, HashtagUsage, ListQuery, Pose, PoseFilter, PoseTagFilter, RelatedHashtag,
+};
 
 use super::error::DomainError;
+use super::unit_of_work::UnitOfWork;
 
 #[async_trait]
 pub trait HashtagsRepository: Send + Sync {
-    async fn get_all(&self) -> Result<Vec<Hashtag>, DomainError>;
+    /// Todos los hashtags con su número de poses y posts.
+    async fn get_all(&self, list: &ListQuery) -> Result<Vec<HashtagUsage>, DomainError>;
+    async fn get_paginated(
+        &self,
+        list: &ListQuery,
+        page: u32,
+        limit: u32,
+    ) -> Result<Vec<HashtagUsage>, DomainError>;
+    /// Total de hashtags que cumplen el listado (para paginación).
+    async fn count(&self, list: &ListQuery) -> Result<u64, DomainError>;
+    /// Autocompletado: primero los que empiezan por `prefix` (normalizado), luego los parecidos
+    /// (trigramas); dentro de cada grupo, los más usados. Con `prefix` vacío, los más usados.
+    async fn suggest(&self, prefix: &str, limit: u32) -> Result<Vec<HashtagUsage>, DomainError>;
+    /// Hashtags que más aparecen junto a `hashtag_id` en las mismas poses.
+    async fn get_related(
+        &self,
+        hashtag_id: Uuid,
+        limit: u32,
+    ) -> Result<Vec<RelatedHashtag>, DomainError>;
     async fn get_by_id(&self, id: Uuid) -> Result<Option<Hashtag>, DomainError>;
-    async fn create(&self, name: &str) -> Result<Hashtag, DomainError>;
+    /// Hashtag cuyo nombre normalizado coincide con `name` (ya normalizado).
+    async fn get_by_name(&self, name: &str) -> Result<Option<Hashtag>, DomainError>;
+    async fn create(&self, name: &str, parent_id: Option<Uuid>) -> Result<Hashtag, DomainError>;
+    /// Actualiza los campos presentes: nombre y padre (`Some(None)` lo deja como raíz). None si no existe.
+    /// Rechaza (Validation) un padre que crearía un ciclo, comprobado en la misma transacción.
+    async fn update(
+        &self,
+        id: Uuid,
+        name: Option<&str>,
+        parent_id: Option<Option<Uuid>>,
+    ) -> Result<Option<Hashtag>, DomainError>;
     async fn delete(&self, id: Uuid) -> Result<(), DomainError>;
+    /// Pasa todas las poses y posts de `source_id` a `target_id` y elimina `source_id`.
+    async fn merge(&self, source_id: Uuid, target_id: Uuid) -> Result<(), DomainError>;
+    /// Hashtags con esos nombres normalizados (ver `Hashtag::normalize_name`); crea los que falten.
+    /// Devuelve uno por nombre, en el mismo orden, dentro de la unidad de trabajo `uow`.
+    async fn get_or_create_by_names_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        names: &[String],
+    ) -> Result<Vec<Hashtag>, DomainError>;
     /// Hashtags asociados a una pose (tabla hashtag_image).
     async fn get_hashtags_by_pose(&self, pose_id: Uuid) -> Result<Vec<Hashtag>, DomainError>;
+    /// Hashtags de varias poses en una sola consulta, como pares (pose_id, hashtag).
+    async fn get_hashtags_by_poses(
+        &self,
+        pose_ids: &[Uuid],
+    ) -> Result<Vec<(Uuid, Hashtag)>, DomainError>;
+    /// Hashtags asociados a un post (tabla hashtag_pose), por nombre.
+    async fn get_hashtags_by_post(&self, post_id: Uuid) -> Result<Vec<Hashtag>, DomainError>;
+    /// `get_hashtags_by_post` dentro de la unidad de trabajo `uow`.
+    async fn get_hashtags_by_post_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        post_id: Uuid,
+    ) -> Result<Vec<Hashtag>, DomainError>;
     /// Añade hashtags a un post (tabla hashtag_pose). Idempotente por (post_id, hashtag_id).
     async fn add_hashtags_to_post(
         &self,
         post_id: Uuid,
         hashtag_ids: &[Uuid],
     ) -> Result<(), DomainError>;
+    /// `add_hashtags_to_post` dentro de la unidad de trabajo `uow`.
+    async fn add_hashtags_to_post_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        post_id: Uuid,
+        hashtag_ids: &[Uuid],
+    ) -> Result<(), DomainError>;
+    /// Quita hashtags de un post dentro de la unidad de trabajo `uow`.
+    async fn remove_hashtags_from_post_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        post_id: Uuid,
+        hashtag_ids: &[Uuid],
+    ) -> Result<(), DomainError>;
+    /// Ids de `ids` que existen, bloqueados (FOR SHARE) hasta el fin de `uow` para que no se borren
+    /// mientras se usan.
+    async fn lock_existing_ids_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, DomainError>;
     /// Añade un hashtag a una pose (hashtag_image). Idempotente.
     async fn add_hashtag_to_pose(&self, pose_id: Uuid, hashtag_id: Uuid) -> Result<(), DomainError>;
+    /// `add_hashtag_to_pose` dentro de la unidad de trabajo `uow`.
+    async fn add_hashtag_to_pose_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        pose_id: Uuid,
+        hashtag_id: Uuid,
+    ) -> Result<(), DomainError>;
     /// Quita un hashtag de una pose.
     async fn remove_hashtag_from_pose(
         &self,
         pose_id: Uuid,
         hashtag_id: Uuid,
     ) -> Result<(), DomainError>;
-    /// Quita todos los hashtags de una pose.
-    async fn remove_all_hashtags_from_pose(&self, pose_id: Uuid) -> Result<(), DomainError>;
-    /// Poses etiquetadas con un hashtag.
-    async fn get_poses_by_hashtag(&self, hashtag_id: Uuid) -> Result<Vec<Pose>, DomainError>;
+    /// Quita todos los hashtags de una pose dentro de la unidad de trabajo `uow`.
+    async fn remove_all_hashtags_from_pose_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        pose_id: Uuid,
+    ) -> Result<(), DomainError>;
+    /// Añade cada hashtag a cada pose dentro de `uow`. Idempotente; devuelve el pose_id de cada
+    /// asociación nueva.
+    async fn add_hashtags_to_poses_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        pose_ids: &[Uuid],
+        hashtag_ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, DomainError>;
+    /// Quita los hashtags de las poses dentro de `uow`; devuelve el pose_id de cada asociación quitada.
+    async fn remove_hashtags_from_poses_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        pose_ids: &[Uuid],
+        hashtag_ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, DomainError>;
+    /// Quita todos los hashtags de varias poses dentro de `uow`.
+    async fn remove_all_hashtags_from_poses_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        pose_ids: &[Uuid],
+    ) -> Result<(), DomainError>;
+    /// Poses etiquetadas con un hashtag (o con alguno de sus descendientes si `include_children`).
+    async fn get_poses_by_hashtag(
+        &self,
+        hashtag_id: Uuid,
+        include_children: bool,
+    ) -> Result<Vec<Pose>, DomainError>;
     /// Poses etiquetadas con un hashtag (paginado).
     async fn get_poses_by_hashtag_paginated(
         &self,
         hashtag_id: Uuid,
+        include_children: bool,
         page: u32,
         limit: u32,
     ) -> Result<Vec<Pose>, DomainError>;
     /// Total de poses etiquetadas con el hashtag (para paginación).
-    async fn count_poses_by_hashtag(&self, hashtag_id: Uuid) -> Result<u64, DomainError>;
+    async fn count_poses_by_hashtag(
+        &self,
+        hashtag_id: Uuid,
+        include_children: bool,
+    ) -> Result<u64, DomainError>;
+    /// Poses que cumplen el filtro de hashtags y el de metadatos (más recientes primero, paginado).
+    async fn get_poses_by_tag_filter(
+        &self,
+        filter: &PoseTagFilter,
+        metadata: &PoseFilter,
+        list: &ListQuery,
+        page: u32,
+        limit: u32,
+    ) -> Result<Vec<Pose>, DomainError>;
+    /// Total de poses que cumplen el filtro (para paginación).
+    async fn count_poses_by_tag_filter(
+        &self,
+        filter: &PoseTagFilter,
+        metadata: &PoseFilter,
+        list: &ListQuery,
+    ) -> Result<u64, DomainError>;
 }
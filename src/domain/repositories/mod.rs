@@ -11,6 +11,7 @@ mod poses;
 mod posts;
 mod sesiones;
 mod theme_of_the_day;
+mod unit_of_work;
 mod usuarios;
 
 pub use auth::{AuthRepository, AuthUser};
@@ -24,4 +25,5 @@ pub use poses::PosesRepository;
 pub use posts::PostsRepository;
 pub use sesiones::SesionesRepository;
 pub use theme_of_the_day::ThemeOfTheDayRepository;
+pub use unit_of_work::{Compensation, UnitOfWork, UnitOfWorkFactory};
 pub use usuarios::UsuariosRepository;
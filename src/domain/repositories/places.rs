@@ -3,13 +3,22 @@
 use async_trait::async_trait;
 use uuid::Uuid;
 
-use crate::domain::Place;
+use crate::domain::{ListQuery, Place};
 
 use super::error::DomainError;
 
 #[async_trait]
+#[allow(clippy::too_many_arguments)]
 pub trait PlacesRepository: Send + Sync {
-    async fn get_all(&self) -> Result<Vec<Place>, DomainError>;
+    async fn get_all(&self, list: &ListQuery) -> Result<Vec<Place>, DomainError>;
+    async fn get_paginated(
+        &self,
+        list: &ListQuery,
+        page: u32,
+        limit: u32,
+    ) -> Result<Vec<Place>, DomainError>;
+    /// Total de lugares que cumplen el listado (para paginación).
+    async fn count(&self, list: &ListQuery) -> Result<u64, DomainError>;
     async fn get_by_id(&self, id: Uuid) -> Result<Option<Place>, DomainError>;
     /// Crea un lugar con id conocido (para guardar la imagen como {id}.{ext}).
     async fn create_with_id(
@@ -3,7 +3,7 @@
 use async_trait::async_trait;
 use uuid::Uuid;
 
-use crate::domain::{PortfolioCategory, PortfolioImage};
+use crate::domain::{PageCursor, PortfolioCategory, PortfolioImage};
 
 use super::error::DomainError;
 
@@ -16,6 +16,14 @@ pub trait PortfolioRepository: Send + Sync {
         page: u32,
         limit: u32,
     ) -> Result<Vec<PortfolioImage>, DomainError>;
+    /// Hasta `limit` imágenes de la categoría posteriores a `after` en el orden (created_at, id)
+    /// descendente (keyset).
+    async fn get_images_by_category_after(
+        &self,
+        category_id: Uuid,
+        after: Option<&PageCursor>,
+        limit: u32,
+    ) -> Result<Vec<PortfolioImage>, DomainError>;
     /// Total de imágenes en la categoría (para paginación).
     async fn count_images_by_category(&self, category_id: Uuid) -> Result<u64, DomainError>;
     async fn create_category(&self, name: &str) -> Result<PortfolioCategory, DomainError>;
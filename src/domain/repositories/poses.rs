@@ -3,18 +3,77 @@
 use async_trait::async_trait;
 use uuid::Uuid;
 
-use crate::domain::Pose;
+use crate::domain::{ListQuery, PageCursor, Pose, PoseFilter, PoseMetadata, ScoredPose};
 
 use super::error::DomainError;
+use super::unit_of_work::UnitOfWork;
 
 #[async_trait]
 pub trait PosesRepository: Send + Sync {
-    async fn get_all(&self) -> Result<Vec<Pose>, DomainError>;
-    async fn get_paginated(&self, page: u32, limit: u32) -> Result<Vec<Pose>, DomainError>;
-    /// Total de poses (para paginación).
-    async fn count(&self) -> Result<u64, DomainError>;
+    async fn get_paginated(
+        &self,
+        filter: &PoseFilter,
+        list: &ListQuery,
+        page: u32,
+        limit: u32,
+    ) -> Result<Vec<Pose>, DomainError>;
+    /// Hasta `limit` poses posteriores a `after` en el orden (created_at, id) descendente (keyset).
+    /// Se ignora `list.sort`: el orden lo fija el cursor.
+    async fn get_page_after(
+        &self,
+        filter: &PoseFilter,
+        list: &ListQuery,
+        after: Option<&PageCursor>,
+        limit: u32,
+    ) -> Result<Vec<Pose>, DomainError>;
+    /// Total de poses que cumplen el filtro (para paginación).
+    async fn count(&self, filter: &PoseFilter, list: &ListQuery) -> Result<u64, DomainError>;
     async fn get_by_id(&self, id: Uuid) -> Result<Option<Pose>, DomainError>;
-    /// Crea una pose con id conocido (para guardar la imagen con ese id como nombre de archivo).
-    async fn create_with_id(&self, id: Uuid, url: &str) -> Result<Pose, DomainError>;
-    async fn delete(&self, id: Uuid) -> Result<(), DomainError>;
+    /// Crea una pose con id conocido (para guardar la imagen con ese id como nombre de archivo), dentro de
+    /// la unidad de trabajo `uow`. `image_hash` es el hash perceptual de la imagen, si se pudo calcular.
+    async fn create_with_id_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        id: Uuid,
+        url: &str,
+        metadata: &PoseMetadata,
+        image_hash: Option<i64>,
+    ) -> Result<Pose, DomainError>;
+    /// Ids de las poses sin hash de imagen (creadas antes de calcularlo al subir).
+    async fn get_ids_without_image_hash(&self) -> Result<Vec<Uuid>, DomainError>;
+    /// Guarda el hash perceptual de la imagen de una pose.
+    async fn set_image_hash(&self, id: Uuid, image_hash: i64) -> Result<(), DomainError>;
+    /// Recalcula los pesos por rareza de los hashtags (vista `hashtag_idf`) que usa `get_similar`.
+    async fn refresh_hashtag_idf(&self) -> Result<(), DomainError>;
+    /// Reemplaza los metadatos de una pose; None si no existe.
+    async fn update_metadata(
+        &self,
+        id: Uuid,
+        metadata: &PoseMetadata,
+    ) -> Result<Option<Pose>, DomainError>;
+    /// Borra una pose dentro de la unidad de trabajo `uow`.
+    async fn delete_in(&self, uow: &mut dyn UnitOfWork, id: Uuid) -> Result<(), DomainError>;
+    /// Borra varias poses dentro de `uow`; devuelve los ids borrados.
+    async fn delete_many_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, DomainError>;
+    /// Ids de `ids` que existen, bloqueados (FOR UPDATE) hasta el fin de `uow` para que nadie los
+    /// modifique ni los borre mientras tanto.
+    async fn lock_existing_ids_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, DomainError>;
+    /// Poses parecidas a `id`: hashtags compartidos ponderados por rareza y, si ambas tienen
+    /// hash de imagen, similitud visual. Excluye la propia pose.
+    async fn get_similar(&self, id: Uuid, limit: u32) -> Result<Vec<ScoredPose>, DomainError>;
+    /// Poses recomendadas para un usuario según los hashtags de sus favoritos y las sesiones en las
+    /// que aparecen; excluye sus favoritos. Sin historial, las más guardadas en favoritos.
+    async fn get_recommended(
+        &self,
+        user_id: Uuid,
+        limit: u32,
+    ) -> Result<Vec<ScoredPose>, DomainError>;
 }
@@ -1,31 +1,142 @@
 // Contrato del repositorio de posts
 
 use async_trait::async_trait;
+use chrono::NaiveDate;
 use uuid::Uuid;
 
-use crate::domain::Post;
+use crate::domain::{DailyWinner, ListQuery, PageCursor, Post, PostComment};
 
 use super::error::DomainError;
+use super::unit_of_work::UnitOfWork;
 
 #[async_trait]
 pub trait PostsRepository: Send + Sync {
-    async fn get_all(&self) -> Result<Vec<Post>, DomainError>;
-    async fn get_paginated(&self, page: u32, limit: u32) -> Result<Vec<Post>, DomainError>;
-    /// Total de posts (para paginación).
-    async fn count(&self) -> Result<u64, DomainError>;
+    async fn get_paginated(
+        &self,
+        list: &ListQuery,
+        page: u32,
+        limit: u32,
+    ) -> Result<Vec<Post>, DomainError>;
+    /// Hasta `limit` posts posteriores a `after` en el orden (created_at, id) descendente (keyset).
+    /// Se ignora `list.sort`: el orden lo fija el cursor.
+    async fn get_page_after(
+        &self,
+        list: &ListQuery,
+        after: Option<&PageCursor>,
+        limit: u32,
+    ) -> Result<Vec<Post>, DomainError>;
+    /// Total de posts que cumplen el listado (para paginación).
+    async fn count(&self, list: &ListQuery) -> Result<u64, DomainError>;
+    /// Posts de un día del año (MMdd), de todos los años.
     async fn get_by_theme_of_the_day_id(
         &self,
         theme_of_the_day_id: &str,
     ) -> Result<Vec<Post>, DomainError>;
+    /// Posts de una instancia concreta del tema (galería de ese día).
+    async fn get_by_theme_date(&self, theme_date: NaiveDate) -> Result<Vec<Post>, DomainError>;
+    /// Posts de la galería de un evento (más recientes primero).
+    async fn get_by_evento_paginated(
+        &self,
+        evento_id: Uuid,
+        page: u32,
+        limit: u32,
+    ) -> Result<Vec<Post>, DomainError>;
+    /// Total de posts de un evento (para paginación).
+    async fn count_by_evento(&self, evento_id: Uuid) -> Result<u64, DomainError>;
+    /// Posts etiquetados con un hashtag (más recientes primero).
+    async fn get_by_hashtag_paginated(
+        &self,
+        hashtag_id: Uuid,
+        page: u32,
+        limit: u32,
+    ) -> Result<Vec<Post>, DomainError>;
+    /// Total de posts etiquetados con el hashtag (para paginación).
+    async fn count_by_hashtag(&self, hashtag_id: Uuid) -> Result<u64, DomainError>;
     async fn get_by_id(&self, id: Uuid) -> Result<Option<Post>, DomainError>;
-    /// Crea un post con id conocido (para guardar la imagen con ese id como nombre de archivo).
-    async fn create_with_id(
+    /// Crea un post con id conocido y sus imágenes en orden (cada imagen se guarda como {image_id}.{ext}),
+    /// dentro de la unidad de trabajo `uow`.
+    #[allow(clippy::too_many_arguments)]
+    async fn create_with_id_in(
         &self,
+        uow: &mut dyn UnitOfWork,
         id: Uuid,
         description: Option<&str>,
         url: Option<&str>,
         user_id: Option<Uuid>,
         theme_of_the_day_id: &str,
+        theme_date: Option<NaiveDate>,
+        evento_id: Option<Uuid>,
+        image_ids: &[Uuid],
     ) -> Result<Post, DomainError>;
+    /// Añade una imagen al final del carrusel. Devuelve false si el post ya tiene `max_images`.
+    async fn add_image(
+        &self,
+        post_id: Uuid,
+        image_id: Uuid,
+        max_images: usize,
+    ) -> Result<bool, DomainError>;
+    /// Reordena las imágenes (`image_ids` debe contener todas las imágenes del post).
+    async fn reorder_images(&self, post_id: Uuid, image_ids: &[Uuid]) -> Result<(), DomainError>;
+    /// Quita una imagen. Devuelve false si no era del post.
+    async fn remove_image(&self, post_id: Uuid, image_id: Uuid) -> Result<bool, DomainError>;
+    /// Actualiza los campos presentes. `description` vacía la elimina; `theme` cambia el MMdd y la
+    /// instancia del tema a la vez (la instancia puede quedar en None si ese día no tiene tema). Dentro de
+    /// la unidad de trabajo `uow`.
+    async fn update_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        id: Uuid,
+        description: Option<&str>,
+        url: Option<&str>,
+        theme: Option<(&str, Option<NaiveDate>)>,
+    ) -> Result<Option<Post>, DomainError>;
     async fn delete(&self, id: Uuid) -> Result<(), DomainError>;
+    /// Registra el voto del usuario (uno por post). Devuelve false si ya había votado.
+    async fn add_vote(&self, post_id: Uuid, user_id: Uuid) -> Result<bool, DomainError>;
+    /// Quita el voto del usuario. Devuelve false si no había votado.
+    async fn remove_vote(&self, post_id: Uuid, user_id: Uuid) -> Result<bool, DomainError>;
+    /// Posts de una instancia del tema ordenados por votos (desempate: el más antiguo primero).
+    async fn get_leaderboard(
+        &self,
+        theme_date: NaiveDate,
+        limit: u32,
+    ) -> Result<Vec<Post>, DomainError>;
+    /// Fija el ganador del día (si aún no estaba fijado y hay posts) y lo devuelve.
+    async fn decide_daily_winner(
+        &self,
+        theme_date: NaiveDate,
+    ) -> Result<Option<DailyWinner>, DomainError>;
+    /// Fija el ganador de todos los días hasta `last_closed` (incluido) que tienen posts y aún no lo
+    /// tienen. Devuelve cuántos ganadores se fijaron.
+    async fn decide_daily_winners_until(&self, last_closed: NaiveDate) -> Result<u64, DomainError>;
+    /// Momentos (ascendentes) en que el usuario publicó posts del reto diario (enlazados a un tema).
+    async fn get_challenge_post_times(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<chrono::DateTime<chrono::Utc>>, DomainError>;
+    /// Registra el like del usuario. Devuelve false si ya lo había dado.
+    async fn add_like(&self, post_id: Uuid, user_id: Uuid) -> Result<bool, DomainError>;
+    /// Quita el like del usuario. Devuelve false si no lo había dado.
+    async fn remove_like(&self, post_id: Uuid, user_id: Uuid) -> Result<bool, DomainError>;
+    /// De los posts indicados, los que tienen like del usuario.
+    async fn get_liked_post_ids(
+        &self,
+        user_id: Uuid,
+        post_ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, DomainError>;
+    /// Comentarios de un post (incluye respuestas), más antiguos primero.
+    async fn get_comments(&self, post_id: Uuid) -> Result<Vec<PostComment>, DomainError>;
+    async fn get_comment_by_id(&self, id: Uuid) -> Result<Option<PostComment>, DomainError>;
+    /// Nivel de un comentario en su hilo (0 = comentario directo al post).
+    async fn get_comment_depth(&self, id: Uuid) -> Result<u32, DomainError>;
+    async fn create_comment(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        parent_id: Option<Uuid>,
+        body: &str,
+    ) -> Result<PostComment, DomainError>;
+    async fn update_comment(&self, id: Uuid, body: &str) -> Result<Option<PostComment>, DomainError>;
+    /// Elimina un comentario. Si tiene respuestas se conserva vacío (deleted) para no romper el hilo.
+    async fn delete_comment(&self, id: Uuid) -> Result<(), DomainError>;
 }
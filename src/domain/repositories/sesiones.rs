@@ -3,16 +3,43 @@
 use async_trait::async_trait;
 use uuid::Uuid;
 
-use crate::domain::{Pose, Sesion};
+use crate::domain::{ListQuery, Pose, Sesion};
 
 use super::error::DomainError;
+use super::unit_of_work::UnitOfWork;
 
 #[async_trait]
 pub trait SesionesRepository: Send + Sync {
-    async fn get_all(&self) -> Result<Vec<Sesion>, DomainError>;
+    async fn get_paginated(
+        &self,
+        list: &ListQuery,
+        page: u32,
+        limit: u32,
+    ) -> Result<Vec<Sesion>, DomainError>;
+    /// Total de sesiones que cumplen el listado (para paginación).
+    async fn count(&self, list: &ListQuery) -> Result<u64, DomainError>;
     async fn get_by_id(&self, id: Uuid) -> Result<Option<Sesion>, DomainError>;
-    async fn get_poses_by_sesion(&self, sesion_id: Uuid) -> Result<Vec<Pose>, DomainError>;
+    /// Poses de una sesión en el orden en que se añadieron (paginado).
+    async fn get_poses_by_sesion_paginated(
+        &self,
+        sesion_id: Uuid,
+        page: u32,
+        limit: u32,
+    ) -> Result<Vec<Pose>, DomainError>;
+    async fn count_poses_by_sesion(&self, sesion_id: Uuid) -> Result<u64, DomainError>;
+    /// Poses de varias sesiones en una sola consulta, como pares (sesion_id, pose).
+    async fn get_poses_by_sesiones(
+        &self,
+        sesion_ids: &[Uuid],
+    ) -> Result<Vec<(Uuid, Pose)>, DomainError>;
+    /// Sesiones que contienen cada una de las poses, como pares (pose_id, sesion).
+    async fn get_sesiones_by_poses(
+        &self,
+        pose_ids: &[Uuid],
+    ) -> Result<Vec<(Uuid, Sesion)>, DomainError>;
     async fn create(&self, name: &str) -> Result<Sesion, DomainError>;
+    /// `create` dentro de la unidad de trabajo `uow`.
+    async fn create_in(&self, uow: &mut dyn UnitOfWork, name: &str) -> Result<Sesion, DomainError>;
     async fn delete(&self, id: Uuid) -> Result<(), DomainError>;
     /// Añade poses a una sesión (tabla sesion_image). Idempotente.
     async fn add_poses_to_sesion(
@@ -20,6 +47,21 @@ pub trait SesionesRepository: Send + Sync {
         sesion_id: Uuid,
         pose_ids: &[Uuid],
     ) -> Result<(), DomainError>;
+    /// `add_poses_to_sesion` dentro de la unidad de trabajo `uow`; devuelve las poses añadidas (no las
+    /// que ya estaban).
+    async fn add_poses_to_sesion_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        sesion_id: Uuid,
+        pose_ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, DomainError>;
+    /// Ids de `ids` que existen, bloqueados (FOR SHARE) hasta el fin de `uow` para que no se borren
+    /// mientras se usan.
+    async fn lock_existing_ids_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, DomainError>;
     async fn remove_pose_from_sesion(
         &self,
         sesion_id: Uuid,
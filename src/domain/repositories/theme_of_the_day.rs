@@ -1,21 +1,69 @@
-// Contrato del repositorio de tema del día
+// Contrato del repositorio de tema del día (instancias por fecha; MMdd como búsqueda)
 
 use async_trait::async_trait;
+use chrono::NaiveDate;
+use uuid::Uuid;
 
-use crate::domain::ThemeOfTheDay;
+use crate::domain::{MonthDay, ThemeFallback, ThemeOfTheDay, ThemeOfTheDayPoolEntry};
 
 use super::error::DomainError;
 
 #[async_trait]
 pub trait ThemeOfTheDayRepository: Send + Sync {
+    /// Todas las instancias, ordenadas por fecha.
     async fn get_all(&self) -> Result<Vec<ThemeOfTheDay>, DomainError>;
-    async fn get_by_id(&self, id: &str) -> Result<Option<ThemeOfTheDay>, DomainError>;
-    async fn create(&self, id: &str, name: &str, url: &str) -> Result<ThemeOfTheDay, DomainError>;
+    /// Instancias de un año (archivo), ordenadas por fecha.
+    async fn get_by_year(&self, year: i32) -> Result<Vec<ThemeOfTheDay>, DomainError>;
+    async fn get_by_date(&self, date: NaiveDate) -> Result<Option<ThemeOfTheDay>, DomainError>;
+    /// Instancia más reciente de un MMdd con fecha <= `on_or_before`; si no hay, la próxima programada.
+    async fn get_latest_by_mmdd(
+        &self,
+        mmdd: MonthDay,
+        on_or_before: NaiveDate,
+    ) -> Result<Option<ThemeOfTheDay>, DomainError>;
+    /// Sustituye el tema elegido por fallback en `date`; falla con Validation si ya hay uno programado.
+    async fn create(
+        &self,
+        date: NaiveDate,
+        name: &str,
+        url: &str,
+    ) -> Result<ThemeOfTheDay, DomainError>;
+    /// Crea la instancia de `date` con ese nombre e imagen, elegida con `fallback` (y `pool_entry_id` si
+    /// sale de la bolsa), si aún no existe, y devuelve la que quede guardada (seguro ante peticiones
+    /// concurrentes: todas reciben la misma).
+    async fn create_if_missing(
+        &self,
+        date: NaiveDate,
+        name: &str,
+        url: &str,
+        fallback: ThemeFallback,
+        pool_entry_id: Option<Uuid>,
+    ) -> Result<ThemeOfTheDay, DomainError>;
     async fn update(
         &self,
-        id: &str,
+        date: NaiveDate,
         name: Option<&str>,
         url: Option<&str>,
     ) -> Result<Option<ThemeOfTheDay>, DomainError>;
-    async fn delete(&self, id: &str) -> Result<(), DomainError>;
+    async fn delete(&self, date: NaiveDate) -> Result<(), DomainError>;
+    /// Instancia anterior a `before` con más posts (desempate: la más reciente).
+    async fn get_most_popular_before(
+        &self,
+        before: NaiveDate,
+    ) -> Result<Option<ThemeOfTheDay>, DomainError>;
+    /// Bolsa de temas sin fecha, en orden de alta.
+    async fn get_pool(&self) -> Result<Vec<ThemeOfTheDayPoolEntry>, DomainError>;
+    /// Entrada a la que le toca la rotación: la que lleva más tiempo sin usarse (las nunca usadas
+    /// primero, en orden de alta). None si la bolsa está vacía.
+    async fn next_pool_entry(&self) -> Result<Option<ThemeOfTheDayPoolEntry>, DomainError>;
+    /// Si algún tema ya servido salió de esta entrada (y sigue usando su imagen).
+    async fn is_pool_entry_used(&self, id: Uuid) -> Result<bool, DomainError>;
+    async fn add_to_pool(
+        &self,
+        id: Uuid,
+        name: &str,
+        url: &str,
+    ) -> Result<ThemeOfTheDayPoolEntry, DomainError>;
+    /// Devuelve false si la entrada no existía.
+    async fn remove_from_pool(&self, id: Uuid) -> Result<bool, DomainError>;
 }
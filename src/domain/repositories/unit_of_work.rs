@@ -0,0 +1,27 @@
+// Contrato de la unidad de trabajo: varias escrituras de repositorio que se confirman o deshacen juntas
+
+use std::any::Any;
+
+use async_trait::async_trait;
+
+use super::error::DomainError;
+
+/// Deshace un efecto fuera de la base de datos (p. ej. un archivo de imagen ya escrito).
+pub type Compensation = Box<dyn FnOnce() + Send>;
+
+/// Transacción de negocio. Los repositorios se unen a ella con sus métodos `*_in`; nada queda confirmado
+/// hasta `commit`. Si la unidad se suelta sin `commit` (error con `?`), se deshace y se ejecutan las
+/// compensaciones registradas, en orden inverso.
+#[async_trait]
+pub trait UnitOfWork: Send {
+    /// Registra una compensación para el caso de rollback.
+    fn on_rollback(&mut self, compensation: Compensation);
+    async fn commit(self: Box<Self>) -> Result<(), DomainError>;
+    /// Implementación concreta (la usan los repositorios de infraestructura para unirse a la transacción).
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+#[async_trait]
+pub trait UnitOfWorkFactory: Send + Sync {
+    async fn begin(&self) -> Result<Box<dyn UnitOfWork>, DomainError>;
+}
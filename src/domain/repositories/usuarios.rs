@@ -11,5 +11,21 @@ use super::error::DomainError;
 pub trait UsuariosRepository: Send + Sync {
     async fn get_by_id(&self, id: Uuid) -> Result<Option<Usuario>, DomainError>;
     async fn update_name(&self, id: Uuid, name: Option<&str>) -> Result<Option<Usuario>, DomainError>;
+    /// Guarda la zona horaria del usuario (None la elimina).
+    async fn update_time_zone(
+        &self,
+        id: Uuid,
+        time_zone: Option<&str>,
+    ) -> Result<Option<Usuario>, DomainError>;
     async fn update_avatar(&self, id: Uuid, url: &str) -> Result<Option<Usuario>, DomainError>;
+    /// Token del feed iCalendar del usuario; si aún no tiene uno, guarda `new_token` y lo devuelve.
+    async fn get_or_create_calendar_token(
+        &self,
+        id: Uuid,
+        new_token: &str,
+    ) -> Result<Option<String>, DomainError>;
+    /// Reemplaza el token del feed iCalendar (invalida las suscripciones anteriores).
+    async fn set_calendar_token(&self, id: Uuid, token: &str) -> Result<Option<String>, DomainError>;
+    /// Usuario dueño de un token de feed iCalendar.
+    async fn get_by_calendar_token(&self, token: &str) -> Result<Option<Usuario>, DomainError>;
 }
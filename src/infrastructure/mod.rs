@@ -14,4 +14,5 @@ pub use repositories::poses_repository::PosesRepositoryImpl;
 pub use repositories::posts_repository::PostsRepositoryImpl;
 pub use repositories::sesiones_repository::SesionesRepositoryImpl;
 pub use repositories::theme_of_the_day_repository::ThemeOfTheDayRepositoryImpl;
+pub use repositories::unit_of_work::PgUnitOfWorkFactory;
 pub use repositories::usuarios_repository::UsuariosRepositoryImpl;
\ No newline at end of file
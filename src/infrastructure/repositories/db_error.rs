@@ -0,0 +1,10 @@
+use crate::domain::DomainError;
+
+/// Error de sqlx como DomainError. Una violación de unicidad se devuelve como Validation con `message`:
+/// la restricción de la base de datos decide los duplicados, sin una lectura previa que pueda quedar vieja.
+pub fn unique_violation_as_validation(e: sqlx::Error, message: impl FnOnce() -> String) -> DomainError {
+    match &e {
+        sqlx::Error::Database(db) if db.is_unique_violation() => DomainError::Validation(message()),
+        _ => DomainError::Repository(anyhow::Error::from(e)),
+    }
+}
@@ -1,8 +1,31 @@
-use crate::domain::{DomainError, Evento, EventosRepository};
+use crate::domain::{
+    AttendanceStatus, DomainError, Evento, EventoAsistente, EventosRepository, ListQuery,
+};
 use async_trait::async_trait;
 use sqlx::FromRow;
 use uuid::Uuid;
 
+use super::list_query::ListSql;
+
+/// Columnas de eventos (incluye asistentes confirmados y posts de la galería) para SELECT y RETURNING.
+const EVENTO_COLUMNS: &str = r#"
+    eventos.id, eventos.name, eventos.place, eventos.mmdd, eventos.url, eventos.created_at,
+    eventos.capacity,
+    (SELECT COUNT(*) FROM evento_asistentes a
+     WHERE a.evento_id = eventos.id AND a.status = 'confirmed') AS attendees_count,
+    (SELECT COUNT(*) FROM posts p WHERE p.evento_id = eventos.id) AS posts_count
+"#;
+
+/// Campos de `Evento::LIST_SPEC`: columnas de eventos o alias de `EVENTO_COLUMNS` (solo para ORDER BY).
+const EVENTO_LIST_COLUMNS: &[(&str, &str)] = &[
+    ("mmdd", "eventos.mmdd"),
+    ("name", "eventos.name"),
+    ("created_at", "eventos.created_at"),
+    ("capacity", "eventos.capacity"),
+    ("attendees_count", "attendees_count"),
+    ("place", "eventos.place"),
+];
+
 #[derive(FromRow)]
 pub struct EventoRow {
     pub id: Uuid,
@@ -11,6 +34,9 @@ pub struct EventoRow {
     pub mmdd: String,
     pub url: String,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub capacity: Option<i32>,
+    pub attendees_count: i64,
+    pub posts_count: i64,
 }
 
 impl From<EventoRow> for Evento {
@@ -22,6 +48,39 @@ impl From<EventoRow> for Evento {
             mmdd: row.mmdd,
             url: row.url,
             created_at: row.created_at,
+            capacity: row.capacity,
+            attendees_count: row.attendees_count,
+            posts_count: row.posts_count,
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct EventoAttendanceRow {
+    #[sqlx(flatten)]
+    evento: EventoRow,
+    status: String,
+}
+
+#[derive(FromRow)]
+struct EventoAsistenteRow {
+    evento_id: Uuid,
+    user_id: Uuid,
+    name: Option<String>,
+    url: Option<String>,
+    status: String,
+    created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<EventoAsistenteRow> for EventoAsistente {
+    fn from(row: EventoAsistenteRow) -> Self {
+        EventoAsistente {
+            evento_id: row.evento_id,
+            user_id: row.user_id,
+            name: row.name,
+            url: row.url,
+            status: AttendanceStatus::parse(&row.status).unwrap_or(AttendanceStatus::Waitlisted),
+            created_at: row.created_at,
         }
     }
 }
@@ -34,22 +93,120 @@ impl EventosRepositoryImpl {
     pub fn new(pool: sqlx::PgPool) -> Self {
         Self { pool }
     }
+
+    /// Confirma a los primeros de la lista de espera mientras haya cupo libre.
+    /// Debe llamarse dentro de la transacción que tiene bloqueada la fila del evento.
+    async fn promote_waitlist(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        evento_id: Uuid,
+    ) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            UPDATE evento_asistentes SET status = 'confirmed'
+            WHERE evento_id = $1 AND user_id IN (
+                SELECT w.user_id FROM evento_asistentes w
+                WHERE w.evento_id = $1 AND w.status = 'waitlisted'
+                ORDER BY w.created_at ASC
+                LIMIT (
+                    SELECT CASE WHEN e.capacity IS NULL THEN NULL
+                        ELSE GREATEST(e.capacity - (
+                            SELECT COUNT(*) FROM evento_asistentes c
+                            WHERE c.evento_id = $1 AND c.status = 'confirmed'
+                        ), 0) END
+                    FROM eventos e WHERE e.id = $1
+                )
+            )
+            "#,
+        )
+        .bind(evento_id)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(())
+    }
+
+    async fn get_attendee(
+        &self,
+        evento_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<EventoAsistente>, DomainError> {
+        let row = sqlx::query_as::<_, EventoAsistenteRow>(
+            r#"
+            SELECT a.evento_id, a.user_id, u.name, u.url, a.status, a.created_at
+            FROM evento_asistentes a
+            INNER JOIN usuarios u ON u.id = a.user_id
+            WHERE a.evento_id = $1 AND a.user_id = $2
+            "#,
+        )
+        .bind(evento_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.map(EventoAsistente::from))
+    }
 }
 
 #[async_trait]
 impl EventosRepository for EventosRepositoryImpl {
-    async fn get_all(&self) -> Result<Vec<Evento>, DomainError> {
-        let rows = sqlx::query_as::<_, EventoRow>("SELECT id, name, place, mmdd, url, created_at FROM eventos ORDER BY id ASC")
+    async fn get_all(&self, list: &ListQuery) -> Result<Vec<Evento>, DomainError> {
+        let list = ListSql::new(list, EVENTO_LIST_COLUMNS, 1)?;
+        let sql = format!(
+            "SELECT {} FROM eventos WHERE {} ORDER BY {}",
+            EVENTO_COLUMNS,
+            list.condition,
+            list.order_by("eventos.mmdd ASC, eventos.name ASC, eventos.id ASC")
+        );
+        let rows = list
+            .bind(sqlx::query_as::<_, EventoRow>(&sql))
             .fetch_all(&self.pool)
             .await
             .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
         Ok(rows.into_iter().map(Evento::from).collect())
     }
 
+    async fn get_paginated(
+        &self,
+        list: &ListQuery,
+        page: u32,
+        limit: u32,
+    ) -> Result<Vec<Evento>, DomainError> {
+        let offset = page.saturating_mul(limit);
+        let list = ListSql::new(list, EVENTO_LIST_COLUMNS, 1)?;
+        let sql = format!(
+            "SELECT {} FROM eventos WHERE {} ORDER BY {} LIMIT ${} OFFSET ${}",
+            EVENTO_COLUMNS,
+            list.condition,
+            list.order_by("eventos.mmdd ASC, eventos.name ASC, eventos.id ASC"),
+            list.next_param(),
+            list.next_param() + 1
+        );
+        let rows = list
+            .bind(sqlx::query_as::<_, EventoRow>(&sql))
+            .bind(limit as i64)
+            .bind(offset as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(rows.into_iter().map(Evento::from).collect())
+    }
+
+    async fn count(&self, list: &ListQuery) -> Result<u64, DomainError> {
+        let list = ListSql::new(list, EVENTO_LIST_COLUMNS, 1)?;
+        let sql = format!("SELECT COUNT(*) FROM eventos WHERE {}", list.condition);
+        let row: (i64,) = list
+            .bind(sqlx::query_as(&sql))
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.0 as u64)
+    }
+
     async fn get_by_id(&self, id: Uuid) -> Result<Option<Evento>, DomainError> {
-        let row = sqlx::query_as::<_, EventoRow>(
-            "SELECT id, name, place, mmdd, url, created_at FROM eventos WHERE id = $1",
-        )
+        let row = sqlx::query_as::<_, EventoRow>(&format!(
+            "SELECT {} FROM eventos WHERE id = $1",
+            EVENTO_COLUMNS
+        ))
         .bind(id)
         .fetch_optional(&self.pool)
         .await
@@ -64,19 +221,22 @@ impl EventosRepository for EventosRepositoryImpl {
         place: &str,
         url: &str,
         mmdd: &str,
+        capacity: Option<i32>,
     ) -> Result<Evento, DomainError> {
-        let row = sqlx::query_as::<_, EventoRow>(
+        let row = sqlx::query_as::<_, EventoRow>(&format!(
             r#"
-            INSERT INTO eventos (id, name, place, url, mmdd)
-            VALUES ($1, $2, $3, $4, $5)
-            RETURNING id, name, place, mmdd, url, created_at
+            INSERT INTO eventos (id, name, place, url, mmdd, capacity)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING {}
             "#,
-        )
+            EVENTO_COLUMNS
+        ))
         .bind(id)
         .bind(name)
         .bind(place)
         .bind(url)
         .bind(mmdd)
+        .bind(capacity)
         .fetch_one(&self.pool)
         .await
         .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
@@ -90,18 +250,29 @@ impl EventosRepository for EventosRepositoryImpl {
         place: Option<&str>,
         url: Option<&str>,
         mmdd: Option<&str>,
+        capacity: Option<i32>,
     ) -> Result<Option<Evento>, DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
         // Construir UPDATE dinámico según campos presentes
-        let row = sqlx::query_as::<_, EventoRow>(
+        let updated: Option<(Uuid,)> = sqlx::query_as(
             r#"
             UPDATE eventos
             SET
                 name = COALESCE($2, name),
                 place = COALESCE($3, place),
                 url = COALESCE($4, url),
-                mmdd = COALESCE($5, mmdd)
+                mmdd = COALESCE($5, mmdd),
+                capacity = CASE
+                    WHEN $6::INTEGER IS NULL THEN capacity
+                    WHEN $6 = 0 THEN NULL
+                    ELSE $6
+                END
             WHERE id = $1
-            RETURNING id, name, place, mmdd, url, created_at
+            RETURNING id
             "#,
         )
         .bind(id)
@@ -109,10 +280,20 @@ impl EventosRepository for EventosRepositoryImpl {
         .bind(place)
         .bind(url)
         .bind(mmdd)
-        .fetch_optional(&self.pool)
+        .bind(capacity)
+        .fetch_optional(&mut *tx)
         .await
         .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
-        Ok(row.map(Evento::from))
+        if updated.is_none() {
+            return Ok(None);
+        }
+        if capacity.is_some() {
+            Self::promote_waitlist(&mut tx, id).await?;
+        }
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        self.get_by_id(id).await
     }
 
     async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
@@ -123,4 +304,218 @@ impl EventosRepository for EventosRepositoryImpl {
             .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
         Ok(())
     }
+
+    async fn add_attendee(
+        &self,
+        evento_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<EventoAsistente>, DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        // Bloquea el evento para que dos altas simultáneas no superen el cupo.
+        let evento: Option<(Option<i32>,)> =
+            sqlx::query_as("SELECT capacity FROM eventos WHERE id = $1 FOR UPDATE")
+                .bind(evento_id)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        let Some((capacity,)) = evento else {
+            return Ok(None);
+        };
+        let confirmed: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM evento_asistentes WHERE evento_id = $1 AND status = 'confirmed'",
+        )
+        .bind(evento_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        let status = AttendanceStatus::for_new_attendee(capacity, confirmed.0);
+        sqlx::query(
+            r#"
+            INSERT INTO evento_asistentes (evento_id, user_id, status)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (evento_id, user_id) DO NOTHING
+            "#,
+        )
+        .bind(evento_id)
+        .bind(user_id)
+        .bind(status.as_str())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        self.get_attendee(evento_id, user_id).await
+    }
+
+    async fn remove_attendee(&self, evento_id: Uuid, user_id: Uuid) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        sqlx::query("SELECT id FROM eventos WHERE id = $1 FOR UPDATE")
+            .bind(evento_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        sqlx::query("DELETE FROM evento_asistentes WHERE evento_id = $1 AND user_id = $2")
+            .bind(evento_id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Self::promote_waitlist(&mut tx, evento_id).await?;
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(())
+    }
+
+    async fn get_attendees(&self, evento_id: Uuid) -> Result<Vec<EventoAsistente>, DomainError> {
+        let rows = sqlx::query_as::<_, EventoAsistenteRow>(
+            r#"
+            SELECT a.evento_id, a.user_id, u.name, u.url, a.status, a.created_at
+            FROM evento_asistentes a
+            INNER JOIN usuarios u ON u.id = a.user_id
+            WHERE a.evento_id = $1
+            ORDER BY (a.status = 'confirmed') DESC, a.created_at ASC
+            "#,
+        )
+        .bind(evento_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(rows.into_iter().map(EventoAsistente::from).collect())
+    }
+
+    async fn get_by_attendee(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<(Evento, AttendanceStatus)>, DomainError> {
+        let rows = sqlx::query_as::<_, EventoAttendanceRow>(&format!(
+            r#"
+            SELECT {}, ea.status
+            FROM eventos
+            INNER JOIN evento_asistentes ea ON ea.evento_id = eventos.id
+            WHERE ea.user_id = $1
+            "#,
+            EVENTO_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(rows
+            .into_iter()
+            .map(|r| {
+                let status =
+                    AttendanceStatus::parse(&r.status).unwrap_or(AttendanceStatus::Waitlisted);
+                (Evento::from(r.evento), status)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn create_usuario(pool: &sqlx::PgPool) -> Uuid {
+        sqlx::query_scalar(
+            "INSERT INTO usuarios (email, password_hash) VALUES ($1, '-') RETURNING id",
+        )
+        .bind(format!("{}@rsvp.test", Uuid::new_v4()))
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    async fn attendance(
+        repo: &EventosRepositoryImpl,
+        evento_id: Uuid,
+    ) -> Vec<(Uuid, AttendanceStatus)> {
+        repo.get_attendees(evento_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|a| (a.user_id, a.status))
+            .collect()
+    }
+
+    /// Contra una base de datos con las migraciones aplicadas (`DATABASE_URL=... cargo test -- --ignored`).
+    /// El repositorio hace commit de sus transacciones: el evento y los usuarios se borran al final.
+    #[tokio::test]
+    #[ignore = "necesita DATABASE_URL"]
+    async fn waitlist_is_promoted_in_arrival_order() {
+        use AttendanceStatus::{Confirmed, Waitlisted};
+
+        let pool = sqlx::PgPool::connect(&std::env::var("DATABASE_URL").unwrap())
+            .await
+            .unwrap();
+        let repo = EventosRepositoryImpl::new(pool.clone());
+        let evento = repo
+            .create_with_id(Uuid::new_v4(), "Taller", "Parque", "/api/eventos/t", "0615", Some(2))
+            .await
+            .unwrap();
+        let mut users = Vec::new();
+        for _ in 0..5 {
+            users.push(create_usuario(&pool).await);
+        }
+
+        let mut statuses = Vec::new();
+        for user in &users {
+            statuses.push(repo.add_attendee(evento.id, *user).await.unwrap().unwrap().status);
+        }
+        assert_eq!(statuses, [Confirmed, Confirmed, Waitlisted, Waitlisted, Waitlisted]);
+        // Volver a apuntarse no cambia el estado ni el turno.
+        let again = repo.add_attendee(evento.id, users[2]).await.unwrap().unwrap();
+        assert_eq!(again.status, Waitlisted);
+        assert_eq!(
+            attendance(&repo, evento.id).await,
+            users.iter().copied().zip(statuses).collect::<Vec<_>>()
+        );
+
+        // Un confirmado cancela: el primero en espera ocupa su lugar.
+        repo.remove_attendee(evento.id, users[0]).await.unwrap();
+        assert_eq!(
+            attendance(&repo, evento.id).await,
+            [
+                (users[1], Confirmed),
+                (users[2], Confirmed),
+                (users[3], Waitlisted),
+                (users[4], Waitlisted),
+            ]
+        );
+
+        // Cancelar desde la lista de espera no promueve a nadie.
+        repo.remove_attendee(evento.id, users[3]).await.unwrap();
+        assert_eq!(
+            attendance(&repo, evento.id).await,
+            [(users[1], Confirmed), (users[2], Confirmed), (users[4], Waitlisted)]
+        );
+
+        // Ampliar el cupo confirma a los que esperan.
+        let updated = repo
+            .update(evento.id, None, None, None, None, Some(3))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.attendees_count, 3);
+        assert_eq!(
+            attendance(&repo, evento.id).await,
+            [(users[1], Confirmed), (users[2], Confirmed), (users[4], Confirmed)]
+        );
+
+        repo.delete(evento.id).await.unwrap();
+        sqlx::query("DELETE FROM usuarios WHERE id = ANY($1)")
+            .bind(&users)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
 }
@@ -1,23 +1,43 @@
-use crate::domain::{DomainError, FavoritesRepository, Pose};
+use crate::domain::{DomainError, FavoritesRepository, Pose, UnitOfWork};
+use super::poses_repository::{PoseRow, POSE_COLUMNS};
+use super::unit_of_work::PgUnitOfWork;
 use async_trait::async_trait;
-use sqlx::FromRow;
+use sqlx::PgExecutor;
 use uuid::Uuid;
 
-#[derive(FromRow)]
-pub struct PoseRow {
-    pub id: Uuid,
-    pub url: String,
-    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+async fn delete_favorites(
+    executor: impl PgExecutor<'_>,
+    user_id: Uuid,
+    pose_ids: &[Uuid],
+) -> Result<(), DomainError> {
+    if pose_ids.is_empty() {
+        return Ok(());
+    }
+    // DELETE FROM favoritos WHERE user_id = $1 AND pose_id = ANY($2)
+    let mut q = sqlx::query("DELETE FROM favoritos WHERE user_id = $1 AND pose_id = ANY($2)");
+    q = q.bind(user_id).bind(pose_ids);
+    q.execute(executor)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+    Ok(())
 }
 
-impl From<PoseRow> for Pose {
-    fn from(row: PoseRow) -> Self {
-        Pose {
-            id: row.id,
-            url: row.url,
-            created_at: row.created_at,
-        }
-    }
+async fn select_favorite_poses(executor: impl PgExecutor<'_>, user_id: Uuid) -> Result<Vec<Pose>, DomainError> {
+    let rows = sqlx::query_as::<_, PoseRow>(&format!(
+        r#"
+        SELECT {}
+        FROM poses p
+        INNER JOIN favoritos f ON f.pose_id = p.id
+        WHERE f.user_id = $1
+        ORDER BY f.created_at DESC
+        "#,
+        POSE_COLUMNS
+    ))
+    .bind(user_id)
+    .fetch_all(executor)
+    .await
+    .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+    Ok(rows.into_iter().map(Pose::from).collect())
 }
 
 pub struct FavoritesRepositoryImpl {
@@ -45,6 +65,25 @@ impl FavoritesRepository for FavoritesRepositoryImpl {
         Ok(row.is_some())
     }
 
+    async fn get_favorite_pose_ids(
+        &self,
+        user_id: Uuid,
+        pose_ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, DomainError> {
+        if pose_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let rows: Vec<(Uuid,)> = sqlx::query_as(
+            "SELECT pose_id FROM favoritos WHERE user_id = $1 AND pose_id = ANY($2)",
+        )
+        .bind(user_id)
+        .bind(pose_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(rows.into_iter().map(|(id,)| id).collect())
+    }
+
     async fn add_pose_to_favorites(
         &self,
         user_id: Uuid,
@@ -84,32 +123,65 @@ impl FavoritesRepository for FavoritesRepositoryImpl {
         user_id: Uuid,
         pose_ids: &[Uuid],
     ) -> Result<(), DomainError> {
-        if pose_ids.is_empty() {
-            return Ok(());
-        }
-        // DELETE FROM favoritos WHERE user_id = $1 AND pose_id = ANY($2)
-        let mut q = sqlx::query("DELETE FROM favoritos WHERE user_id = $1 AND pose_id = ANY($2)");
-        q = q.bind(user_id).bind(pose_ids);
-        q.execute(&self.pool)
-            .await
-            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
-        Ok(())
+        delete_favorites(&self.pool, user_id, pose_ids).await
+    }
+
+    async fn remove_poses_from_favorites_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        user_id: Uuid,
+        pose_ids: &[Uuid],
+    ) -> Result<(), DomainError> {
+        delete_favorites(PgUnitOfWork::connection(uow)?, user_id, pose_ids).await
     }
 
     async fn get_favorite_poses(&self, user_id: Uuid) -> Result<Vec<Pose>, DomainError> {
-        let rows = sqlx::query_as::<_, PoseRow>(
+        select_favorite_poses(&self.pool, user_id).await
+    }
+
+    async fn get_favorite_poses_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        user_id: Uuid,
+    ) -> Result<Vec<Pose>, DomainError> {
+        select_favorite_poses(PgUnitOfWork::connection(uow)?, user_id).await
+    }
+
+    async fn get_favorite_poses_paginated(
+        &self,
+        user_id: Uuid,
+        page: u32,
+        limit: u32,
+    ) -> Result<Vec<Pose>, DomainError> {
+        let offset = page.saturating_mul(limit);
+        let rows = sqlx::query_as::<_, PoseRow>(&format!(
             r#"
-            SELECT p.id, p.url, p.created_at
+            SELECT {}
             FROM poses p
             INNER JOIN favoritos f ON f.pose_id = p.id
             WHERE f.user_id = $1
-            ORDER BY f.created_at DESC
+            ORDER BY f.created_at DESC, p.id DESC
+            LIMIT $2 OFFSET $3
             "#,
-        )
+            POSE_COLUMNS
+        ))
         .bind(user_id)
+        .bind(limit as i64)
+        .bind(offset as i64)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
         Ok(rows.into_iter().map(Pose::from).collect())
     }
+
+    async fn count_favorite_poses(&self, user_id: Uuid) -> Result<u64, DomainError> {
+        let row: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM favoritos f INNER JOIN poses p ON p.id = f.pose_id WHERE f.user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.0 as u64)
+    }
 }
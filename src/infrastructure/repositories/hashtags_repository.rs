@@ -1,36 +1,255 @@
-use crate::domain::{DomainError, Hashtag, HashtagsRepository, Pose};
+use crate::domain::{
+    DomainError, Hashtag, HashtagUsage, HashtagsRepository, ListQuery, Pose, PoseFilter,
+    PoseTagFilter, RelatedHashtag, UnitOfWork,
+};
 use async_trait::async_trait;
-use sqlx::FromRow;
+use sqlx::{FromRow, PgConnection, PgExecutor};
+use std::collections::HashMap;
 use uuid::Uuid;
 
+use super::db_error::unique_violation_as_validation;
+use super::list_query::ListSql;
+use super::poses_repository::{
+    bind_pose_filter, pose_filter_where, PoseRow, POSE_COLUMNS, POSE_FILTER_PARAMS, POSE_LIST_COLUMNS,
+};
+use super::unit_of_work::PgUnitOfWork;
+
+/// Hashtags con su número de poses y posts, como tabla derivada `u` (para poder ordenar por uso).
+const HASHTAG_USAGE_FROM: &str = r#"(
+    SELECT h.id, h.name, h.parent_id,
+        (SELECT COUNT(*) FROM hashtag_image hi WHERE hi.hashtag_id = h.id) AS poses_count,
+        (SELECT COUNT(*) FROM hashtag_pose hp WHERE hp.hashtag_id = h.id) AS posts_count
+    FROM hashtags h
+) u"#;
+
+/// Campos de `HashtagUsage::LIST_SPEC` sobre `HASHTAG_USAGE_FROM`.
+const HASHTAG_LIST_COLUMNS: &[(&str, &str)] = &[
+    ("name", "name"),
+    ("popular", "poses_count + posts_count"),
+    ("poses_count", "poses_count"),
+    ("posts_count", "posts_count"),
+    ("parent_id", "parent_id"),
+];
+
+/// Hashtag $1 y, si $2, todos sus descendientes (UNION evita bucles si hubiera un ciclo).
+const HASHTAG_TREE_CTE: &str = r#"
+    WITH RECURSIVE tags AS (
+        SELECT id FROM hashtags WHERE id = $1
+        UNION
+        SELECT h.id FROM hashtags h INNER JOIN tags ON h.parent_id = tags.id WHERE $2
+    )
+"#;
+
+/// Poses etiquetadas con alguno de `tags` (una fila por pose, con el etiquetado más reciente), como `t`.
+const POSES_TAGGED_FROM: &str = r#"(
+    SELECT hi.pose_id, MAX(hi.created_at) AS tagged_at
+    FROM hashtag_image hi
+    WHERE hi.hashtag_id IN (SELECT id FROM tags)
+    GROUP BY hi.pose_id
+) t"#;
+
+/// Parámetros de `POSE_TAG_FILTER_WHERE`.
+const POSE_TAG_FILTER_PARAMS: usize = 3;
+
+/// Condición del filtro booleano de poses `p` por hashtags: $1 = todas, $2 = alguna, $3 = ninguna.
+const POSE_TAG_FILTER_WHERE: &str = r#"
+    (cardinality($1::UUID[]) = 0 OR (
+        SELECT COUNT(DISTINCT hi.hashtag_id) FROM hashtag_image hi
+        WHERE hi.pose_id = p.id AND hi.hashtag_id = ANY($1)
+    ) = cardinality($1::UUID[]))
+    AND (cardinality($2::UUID[]) = 0 OR EXISTS (
+        SELECT 1 FROM hashtag_image hi WHERE hi.pose_id = p.id AND hi.hashtag_id = ANY($2)
+    ))
+    AND NOT EXISTS (
+        SELECT 1 FROM hashtag_image hi WHERE hi.pose_id = p.id AND hi.hashtag_id = ANY($3)
+    )
+"#;
+
 #[derive(FromRow)]
-pub struct PoseRow {
+pub struct HashtagRow {
     pub id: Uuid,
-    pub url: String,
-    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub name: String,
+    pub parent_id: Option<Uuid>,
 }
 
-impl From<PoseRow> for Pose {
-    fn from(row: PoseRow) -> Self {
-        Pose {
+#[derive(FromRow)]
+struct PoseHashtagRow {
+    pose_id: Uuid,
+    #[sqlx(flatten)]
+    hashtag: HashtagRow,
+}
+
+impl From<HashtagRow> for Hashtag {
+    fn from(row: HashtagRow) -> Self {
+        Hashtag {
             id: row.id,
-            url: row.url,
-            created_at: row.created_at,
+            name: row.name,
+            parent_id: row.parent_id,
+        }
+    }
+}
+
+async fn insert_pose_hashtag(
+    executor: impl PgExecutor<'_>,
+    pose_id: Uuid,
+    hashtag_id: Uuid,
+) -> Result<(), DomainError> {
+    sqlx::query(
+        r#"
+        INSERT INTO hashtag_image (pose_id, hashtag_id)
+        VALUES ($1, $2)
+        ON CONFLICT (hashtag_id, pose_id) DO NOTHING
+        "#,
+    )
+    .bind(pose_id)
+    .bind(hashtag_id)
+    .execute(executor)
+    .await
+    .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+    Ok(())
+}
+
+/// Hashtags por nombre normalizado, creando los que falten. `hashtag_key` (migración 0010) es la
+/// normalización de `Hashtag::normalize_name` en SQL y su índice único resuelve las altas concurrentes.
+async fn get_or_create_hashtags(
+    conn: &mut PgConnection,
+    names: &[String],
+) -> Result<Vec<Hashtag>, DomainError> {
+    sqlx::query(
+        r#"
+        INSERT INTO hashtags (name) SELECT UNNEST($1::TEXT[])
+        ON CONFLICT ((hashtag_key(name))) DO NOTHING
+        "#,
+    )
+    .bind(names)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+    let rows = sqlx::query_as::<_, HashtagRow>(
+        "SELECT id, name, parent_id FROM hashtags WHERE hashtag_key(name) = ANY($1)",
+    )
+    .bind(names)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+    let by_name: HashMap<String, Hashtag> = rows
+        .into_iter()
+        .map(|row| (Hashtag::normalize_name(&row.name), Hashtag::from(row)))
+        .collect();
+    Ok(names
+        .iter()
+        .filter_map(|name| by_name.get(name).cloned())
+        .collect())
+}
+
+/// Patrón `LIKE` para los nombres que empiezan por `prefix` (con `%`, `_` y `\\` escapados).
+fn like_prefix_pattern(prefix: &str) -> String {
+    let mut pattern = String::with_capacity(prefix.len() + 1);
+    for c in prefix.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            pattern.push('\\');
         }
+        pattern.push(c);
     }
+    pattern.push('%');
+    pattern
+}
+
+async fn insert_post_hashtags(
+    executor: impl PgExecutor<'_>,
+    post_id: Uuid,
+    hashtag_ids: &[Uuid],
+) -> Result<(), DomainError> {
+    sqlx::query(
+        r#"
+        INSERT INTO hashtag_pose (post_id, hashtag_id)
+        SELECT $1, UNNEST($2::UUID[])
+        ON CONFLICT (post_id, hashtag_id) DO NOTHING
+        "#,
+    )
+    .bind(post_id)
+    .bind(hashtag_ids)
+    .execute(executor)
+    .await
+    .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+    Ok(())
+}
+
+/// Hashtags de un post (tabla hashtag_pose), por nombre.
+async fn select_post_hashtags(
+    executor: impl PgExecutor<'_>,
+    post_id: Uuid,
+) -> Result<Vec<Hashtag>, DomainError> {
+    let rows = sqlx::query_as::<_, HashtagRow>(
+        r#"
+        SELECT h.id, h.name, h.parent_id
+        FROM hashtags h
+        INNER JOIN hashtag_pose hp ON hp.hashtag_id = h.id
+        WHERE hp.post_id = $1
+        ORDER BY h.name ASC, h.id
+        "#,
+    )
+    .bind(post_id)
+    .fetch_all(executor)
+    .await
+    .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+    Ok(rows.into_iter().map(Hashtag::from).collect())
+}
+
+async fn delete_post_hashtags(
+    executor: impl PgExecutor<'_>,
+    post_id: Uuid,
+    hashtag_ids: &[Uuid],
+) -> Result<(), DomainError> {
+    sqlx::query("DELETE FROM hashtag_pose WHERE post_id = $1 AND hashtag_id = ANY($2)")
+        .bind(post_id)
+        .bind(hashtag_ids)
+        .execute(executor)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+    Ok(())
 }
 
 #[derive(FromRow)]
-pub struct HashtagRow {
+pub struct HashtagUsageRow {
     pub id: Uuid,
     pub name: String,
+    pub parent_id: Option<Uuid>,
+    pub poses_count: i64,
+    pub posts_count: i64,
 }
 
-impl From<HashtagRow> for Hashtag {
-    fn from(row: HashtagRow) -> Self {
-        Hashtag {
-            id: row.id,
-            name: row.name,
+impl From<HashtagUsageRow> for HashtagUsage {
+    fn from(row: HashtagUsageRow) -> Self {
+        HashtagUsage {
+            hashtag: Hashtag {
+                id: row.id,
+                name: row.name,
+                parent_id: row.parent_id,
+            },
+            poses_count: row.poses_count,
+            posts_count: row.posts_count,
+        }
+    }
+}
+
+#[derive(FromRow)]
+pub struct RelatedHashtagRow {
+    pub id: Uuid,
+    pub name: String,
+    pub parent_id: Option<Uuid>,
+    pub shared_poses: i64,
+}
+
+impl From<RelatedHashtagRow> for RelatedHashtag {
+    fn from(row: RelatedHashtagRow) -> Self {
+        RelatedHashtag {
+            hashtag: Hashtag {
+                id: row.id,
+                name: row.name,
+                parent_id: row.parent_id,
+            },
+            shared_poses: row.shared_poses,
         }
     }
 }
@@ -47,40 +266,219 @@ impl HashtagsRepositoryImpl {
 
 #[async_trait]
 impl HashtagsRepository for HashtagsRepositoryImpl {
-    async fn get_all(&self) -> Result<Vec<Hashtag>, DomainError> {
-        let rows = sqlx::query_as::<_, HashtagRow>(
-            "SELECT id, name FROM hashtags ORDER BY name ASC",
+    async fn get_all(&self, list: &ListQuery) -> Result<Vec<HashtagUsage>, DomainError> {
+        let list = ListSql::new(list, HASHTAG_LIST_COLUMNS, 1)?;
+        let sql = format!(
+            "SELECT id, name, parent_id, poses_count, posts_count FROM {} WHERE {} ORDER BY {}",
+            HASHTAG_USAGE_FROM,
+            list.condition,
+            list.order_by("name ASC, id ASC")
+        );
+        let rows = list
+            .bind(sqlx::query_as::<_, HashtagUsageRow>(&sql))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(rows.into_iter().map(HashtagUsage::from).collect())
+    }
+
+    async fn get_paginated(
+        &self,
+        list: &ListQuery,
+        page: u32,
+        limit: u32,
+    ) -> Result<Vec<HashtagUsage>, DomainError> {
+        let offset = page.saturating_mul(limit);
+        let list = ListSql::new(list, HASHTAG_LIST_COLUMNS, 1)?;
+        let sql = format!(
+            r#"
+            SELECT id, name, parent_id, poses_count, posts_count FROM {}
+            WHERE {}
+            ORDER BY {}
+            LIMIT ${} OFFSET ${}
+            "#,
+            HASHTAG_USAGE_FROM,
+            list.condition,
+            list.order_by("name ASC, id ASC"),
+            list.next_param(),
+            list.next_param() + 1
+        );
+        let rows = list
+            .bind(sqlx::query_as::<_, HashtagUsageRow>(&sql))
+            .bind(limit as i64)
+            .bind(offset as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(rows.into_iter().map(HashtagUsage::from).collect())
+    }
+
+    async fn count(&self, list: &ListQuery) -> Result<u64, DomainError> {
+        let list = ListSql::new(list, HASHTAG_LIST_COLUMNS, 1)?;
+        let sql = format!("SELECT COUNT(*) FROM {} WHERE {}", HASHTAG_USAGE_FROM, list.condition);
+        let row: (i64,) = list
+            .bind(sqlx::query_as(&sql))
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.0 as u64)
+    }
+
+    async fn suggest(&self, prefix: &str, limit: u32) -> Result<Vec<HashtagUsage>, DomainError> {
+        let rows = sqlx::query_as::<_, HashtagUsageRow>(&format!(
+            r#"
+            SELECT id, name, parent_id, poses_count, posts_count FROM {}
+            WHERE $1 = '' OR hashtag_key(name) LIKE $3 OR hashtag_key(name) % $1
+            ORDER BY hashtag_key(name) LIKE $3 DESC,
+                poses_count + posts_count DESC,
+                similarity(hashtag_key(name), $1) DESC,
+                name ASC
+            LIMIT $2
+            "#,
+            HASHTAG_USAGE_FROM
+        ))
+        .bind(prefix)
+        .bind(limit as i64)
+        .bind(like_prefix_pattern(prefix))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(rows.into_iter().map(HashtagUsage::from).collect())
+    }
+
+    async fn get_related(
+        &self,
+        hashtag_id: Uuid,
+        limit: u32,
+    ) -> Result<Vec<RelatedHashtag>, DomainError> {
+        let rows = sqlx::query_as::<_, RelatedHashtagRow>(
+            r#"
+            SELECT h.id, h.name, h.parent_id, COUNT(*) AS shared_poses
+            FROM hashtag_image a
+            INNER JOIN hashtag_image b ON b.pose_id = a.pose_id AND b.hashtag_id <> a.hashtag_id
+            INNER JOIN hashtags h ON h.id = b.hashtag_id
+            WHERE a.hashtag_id = $1
+            GROUP BY h.id, h.name, h.parent_id
+            ORDER BY shared_poses DESC, h.name ASC
+            LIMIT $2
+            "#,
         )
+        .bind(hashtag_id)
+        .bind(limit as i64)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
-        Ok(rows.into_iter().map(Hashtag::from).collect())
+        Ok(rows.into_iter().map(RelatedHashtag::from).collect())
     }
 
     async fn get_by_id(&self, id: Uuid) -> Result<Option<Hashtag>, DomainError> {
-        let row = sqlx::query_as::<_, HashtagRow>("SELECT id, name FROM hashtags WHERE id = $1")
-            .bind(id)
-            .fetch_optional(&self.pool)
-            .await
-            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        let row = sqlx::query_as::<_, HashtagRow>(
+            "SELECT id, name, parent_id FROM hashtags WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
         Ok(row.map(Hashtag::from))
     }
 
-    async fn create(&self, name: &str) -> Result<Hashtag, DomainError> {
+    async fn get_by_name(&self, name: &str) -> Result<Option<Hashtag>, DomainError> {
         let row = sqlx::query_as::<_, HashtagRow>(
             r#"
-            INSERT INTO hashtags (name)
-            VALUES ($1)
-            RETURNING id, name
+            SELECT id, name, parent_id FROM hashtags
+            WHERE hashtag_key(name) = $1
             "#,
         )
         .bind(name)
-        .fetch_one(&self.pool)
+        .fetch_optional(&self.pool)
         .await
         .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.map(Hashtag::from))
+    }
+
+    async fn create(&self, name: &str, parent_id: Option<Uuid>) -> Result<Hashtag, DomainError> {
+        let row = sqlx::query_as::<_, HashtagRow>(
+            r#"
+            INSERT INTO hashtags (name, parent_id)
+            VALUES ($1, $2)
+            RETURNING id, name, parent_id
+            "#,
+        )
+        .bind(name)
+        .bind(parent_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| unique_violation_as_validation(e, || format!("Ya existe el hashtag {}", name)))?;
         Ok(Hashtag::from(row))
     }
 
+    async fn update(
+        &self,
+        id: Uuid,
+        name: Option<&str>,
+        parent_id: Option<Option<Uuid>>,
+    ) -> Result<Option<Hashtag>, DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        if let Some(Some(new_parent)) = parent_id {
+            // Sube desde el nuevo padre hasta la raíz bloqueando cada fila: si se llega al propio hashtag
+            // habría un ciclo. Con las filas bloqueadas nadie puede mover esa rama hasta el commit.
+            let mut current = Some(new_parent);
+            let mut visited = Vec::new();
+            while let Some(ancestor) = current {
+                if ancestor == id {
+                    return Err(DomainError::Validation(format!(
+                        "El hashtag {} no puede ser padre de {}: se crearía un ciclo",
+                        new_parent, id
+                    )));
+                }
+                if visited.contains(&ancestor) {
+                    break;
+                }
+                visited.push(ancestor);
+                current = sqlx::query_as::<_, (Option<Uuid>,)>(
+                    "SELECT parent_id FROM hashtags WHERE id = $1 FOR UPDATE",
+                )
+                .bind(ancestor)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?
+                .and_then(|(parent,)| parent);
+            }
+        }
+        let row = sqlx::query_as::<_, HashtagRow>(
+            r#"
+            UPDATE hashtags
+            SET
+                name = COALESCE($2, name),
+                parent_id = CASE WHEN $3 THEN $4 ELSE parent_id END
+            WHERE id = $1
+            RETURNING id, name, parent_id
+            "#,
+        )
+        .bind(id)
+        .bind(name)
+        .bind(parent_id.is_some())
+        .bind(parent_id.flatten())
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| {
+            unique_violation_as_validation(e, || {
+                format!(
+                    "Ya existe el hashtag: {} (usa merge para unirlos)",
+                    name.unwrap_or_default()
+                )
+            })
+        })?;
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.map(Hashtag::from))
+    }
+
     async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
         sqlx::query("DELETE FROM hashtags WHERE id = $1")
             .bind(id)
@@ -90,10 +488,82 @@ impl HashtagsRepository for HashtagsRepositoryImpl {
         Ok(())
     }
 
+    async fn merge(&self, source_id: Uuid, target_id: Uuid) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        // Si el destino cuelga del origen, sube al nivel del origen para no crear un ciclo al
+        // pasarle los hijos del origen.
+        sqlx::query(&format!(
+            r#"
+            {}
+            UPDATE hashtags SET parent_id = (SELECT parent_id FROM hashtags WHERE id = $1)
+            WHERE id = $3 AND id IN (SELECT id FROM tags)
+            "#,
+            HASHTAG_TREE_CTE
+        ))
+        .bind(source_id)
+        .bind(true)
+        .bind(target_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        // Se mueven los enlaces que el destino aún no tiene; los repetidos se borran con el origen.
+        for query in [
+            "UPDATE hashtags SET parent_id = $2 WHERE parent_id = $1 AND id <> $2",
+            r#"
+            UPDATE hashtag_image hi SET hashtag_id = $2
+            WHERE hi.hashtag_id = $1
+              AND NOT EXISTS (
+                  SELECT 1 FROM hashtag_image t WHERE t.hashtag_id = $2 AND t.pose_id = hi.pose_id
+              )
+            "#,
+            r#"
+            UPDATE hashtag_pose hp SET hashtag_id = $2
+            WHERE hp.hashtag_id = $1
+              AND NOT EXISTS (
+                  SELECT 1 FROM hashtag_pose t WHERE t.hashtag_id = $2 AND t.post_id = hp.post_id
+              )
+            "#,
+        ] {
+            sqlx::query(query)
+                .bind(source_id)
+                .bind(target_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        }
+        for query in [
+            "DELETE FROM hashtag_image WHERE hashtag_id = $1",
+            "DELETE FROM hashtag_pose WHERE hashtag_id = $1",
+            "DELETE FROM hashtags WHERE id = $1",
+        ] {
+            sqlx::query(query)
+                .bind(source_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        }
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(())
+    }
+
+    async fn get_or_create_by_names_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        names: &[String],
+    ) -> Result<Vec<Hashtag>, DomainError> {
+        get_or_create_hashtags(PgUnitOfWork::connection(uow)?, names).await
+    }
+
     async fn get_hashtags_by_pose(&self, pose_id: Uuid) -> Result<Vec<Hashtag>, DomainError> {
         let rows = sqlx::query_as::<_, HashtagRow>(
             r#"
-            SELECT h.id, h.name
+            SELECT h.id, h.name, h.parent_id
             FROM hashtags h
             INNER JOIN hashtag_image hi ON hi.hashtag_id = h.id
             WHERE hi.pose_id = $1
@@ -107,26 +577,68 @@ impl HashtagsRepository for HashtagsRepositoryImpl {
         Ok(rows.into_iter().map(Hashtag::from).collect())
     }
 
+    async fn get_hashtags_by_poses(
+        &self,
+        pose_ids: &[Uuid],
+    ) -> Result<Vec<(Uuid, Hashtag)>, DomainError> {
+        if pose_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let rows = sqlx::query_as::<_, PoseHashtagRow>(
+            r#"
+            SELECT hi.pose_id, h.id, h.name, h.parent_id
+            FROM hashtags h
+            INNER JOIN hashtag_image hi ON hi.hashtag_id = h.id
+            WHERE hi.pose_id = ANY($1)
+            ORDER BY hi.created_at DESC
+            "#,
+        )
+        .bind(pose_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.pose_id, Hashtag::from(row.hashtag)))
+            .collect())
+    }
+
+    async fn get_hashtags_by_post(&self, post_id: Uuid) -> Result<Vec<Hashtag>, DomainError> {
+        select_post_hashtags(&self.pool, post_id).await
+    }
+
+    async fn get_hashtags_by_post_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        post_id: Uuid,
+    ) -> Result<Vec<Hashtag>, DomainError> {
+        select_post_hashtags(PgUnitOfWork::connection(uow)?, post_id).await
+    }
+
     async fn add_hashtags_to_post(
         &self,
         post_id: Uuid,
         hashtag_ids: &[Uuid],
     ) -> Result<(), DomainError> {
-        for &hashtag_id in hashtag_ids {
-            sqlx::query(
-                r#"
-                INSERT INTO hashtag_pose (post_id, hashtag_id)
-                VALUES ($1, $2)
-                ON CONFLICT (post_id, hashtag_id) DO NOTHING
-                "#,
-            )
-            .bind(post_id)
-            .bind(hashtag_id)
-            .execute(&self.pool)
-            .await
-            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
-        }
-        Ok(())
+        insert_post_hashtags(&self.pool, post_id, hashtag_ids).await
+    }
+
+    async fn add_hashtags_to_post_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        post_id: Uuid,
+        hashtag_ids: &[Uuid],
+    ) -> Result<(), DomainError> {
+        insert_post_hashtags(PgUnitOfWork::connection(uow)?, post_id, hashtag_ids).await
+    }
+
+    async fn remove_hashtags_from_post_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        post_id: Uuid,
+        hashtag_ids: &[Uuid],
+    ) -> Result<(), DomainError> {
+        delete_post_hashtags(PgUnitOfWork::connection(uow)?, post_id, hashtag_ids).await
     }
 
     async fn add_hashtag_to_pose(
@@ -134,19 +646,16 @@ impl HashtagsRepository for HashtagsRepositoryImpl {
         pose_id: Uuid,
         hashtag_id: Uuid,
     ) -> Result<(), DomainError> {
-        sqlx::query(
-            r#"
-            INSERT INTO hashtag_image (pose_id, hashtag_id)
-            VALUES ($1, $2)
-            ON CONFLICT (hashtag_id, pose_id) DO NOTHING
-            "#,
-        )
-        .bind(pose_id)
-        .bind(hashtag_id)
-        .execute(&self.pool)
-        .await
-        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
-        Ok(())
+        insert_pose_hashtag(&self.pool, pose_id, hashtag_id).await
+    }
+
+    async fn add_hashtag_to_pose_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        pose_id: Uuid,
+        hashtag_id: Uuid,
+    ) -> Result<(), DomainError> {
+        insert_pose_hashtag(PgUnitOfWork::connection(uow)?, pose_id, hashtag_id).await
     }
 
     async fn remove_hashtag_from_pose(
@@ -165,26 +674,98 @@ impl HashtagsRepository for HashtagsRepositoryImpl {
         Ok(())
     }
 
-    async fn remove_all_hashtags_from_pose(&self, pose_id: Uuid) -> Result<(), DomainError> {
+    async fn remove_all_hashtags_from_pose_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        pose_id: Uuid,
+    ) -> Result<(), DomainError> {
         sqlx::query("DELETE FROM hashtag_image WHERE pose_id = $1")
             .bind(pose_id)
-            .execute(&self.pool)
+            .execute(PgUnitOfWork::connection(uow)?)
             .await
             .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
         Ok(())
     }
 
-    async fn get_poses_by_hashtag(&self, hashtag_id: Uuid) -> Result<Vec<Pose>, DomainError> {
-        let rows = sqlx::query_as::<_, PoseRow>(
+    async fn lock_existing_ids_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, DomainError> {
+        sqlx::query_scalar("SELECT id FROM hashtags WHERE id = ANY($1) ORDER BY id FOR SHARE")
+            .bind(ids)
+            .fetch_all(PgUnitOfWork::connection(uow)?)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))
+    }
+
+    async fn add_hashtags_to_poses_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        pose_ids: &[Uuid],
+        hashtag_ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, DomainError> {
+        sqlx::query_scalar(
             r#"
-            SELECT p.id, p.url, p.created_at
-            FROM poses p
-            INNER JOIN hashtag_image hi ON hi.pose_id = p.id
-            WHERE hi.hashtag_id = $1
-            ORDER BY hi.created_at DESC
+            INSERT INTO hashtag_image (pose_id, hashtag_id)
+            SELECT p.id, h.id FROM UNNEST($1::UUID[]) AS p(id) CROSS JOIN UNNEST($2::UUID[]) AS h(id)
+            ON CONFLICT (hashtag_id, pose_id) DO NOTHING
+            RETURNING pose_id
             "#,
         )
+        .bind(pose_ids)
+        .bind(hashtag_ids)
+        .fetch_all(PgUnitOfWork::connection(uow)?)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))
+    }
+
+    async fn remove_hashtags_from_poses_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        pose_ids: &[Uuid],
+        hashtag_ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, DomainError> {
+        sqlx::query_scalar(
+            "DELETE FROM hashtag_image WHERE pose_id = ANY($1) AND hashtag_id = ANY($2) RETURNING pose_id",
+        )
+        .bind(pose_ids)
+        .bind(hashtag_ids)
+        .fetch_all(PgUnitOfWork::connection(uow)?)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))
+    }
+
+    async fn remove_all_hashtags_from_poses_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        pose_ids: &[Uuid],
+    ) -> Result<(), DomainError> {
+        sqlx::query("DELETE FROM hashtag_image WHERE pose_id = ANY($1)")
+            .bind(pose_ids)
+            .execute(PgUnitOfWork::connection(uow)?)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(())
+    }
+
+    async fn get_poses_by_hashtag(
+        &self,
+        hashtag_id: Uuid,
+        include_children: bool,
+    ) -> Result<Vec<Pose>, DomainError> {
+        let rows = sqlx::query_as::<_, PoseRow>(&format!(
+            r#"
+            {}
+            SELECT {}
+            FROM poses p
+            INNER JOIN {} ON t.pose_id = p.id
+            ORDER BY t.tagged_at DESC
+            "#,
+            HASHTAG_TREE_CTE, POSE_COLUMNS, POSES_TAGGED_FROM
+        ))
         .bind(hashtag_id)
+        .bind(include_children)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
@@ -194,21 +775,24 @@ impl HashtagsRepository for HashtagsRepositoryImpl {
     async fn get_poses_by_hashtag_paginated(
         &self,
         hashtag_id: Uuid,
+        include_children: bool,
         page: u32,
         limit: u32,
     ) -> Result<Vec<Pose>, DomainError> {
         let offset = page.saturating_mul(limit);
-        let rows = sqlx::query_as::<_, PoseRow>(
+        let rows = sqlx::query_as::<_, PoseRow>(&format!(
             r#"
-            SELECT p.id, p.url, p.created_at
+            {}
+            SELECT {}
             FROM poses p
-            INNER JOIN hashtag_image hi ON hi.pose_id = p.id
-            WHERE hi.hashtag_id = $1
-            ORDER BY hi.created_at DESC
-            LIMIT $2 OFFSET $3
+            INNER JOIN {} ON t.pose_id = p.id
+            ORDER BY t.tagged_at DESC
+            LIMIT $3 OFFSET $4
             "#,
-        )
+            HASHTAG_TREE_CTE, POSE_COLUMNS, POSES_TAGGED_FROM
+        ))
         .bind(hashtag_id)
+        .bind(include_children)
         .bind(limit as i64)
         .bind(offset as i64)
         .fetch_all(&self.pool)
@@ -217,18 +801,189 @@ impl HashtagsRepository for HashtagsRepositoryImpl {
         Ok(rows.into_iter().map(Pose::from).collect())
     }
 
-    async fn count_poses_by_hashtag(&self, hashtag_id: Uuid) -> Result<u64, DomainError> {
-        let row: (i64,) = sqlx::query_as(
+    async fn count_poses_by_hashtag(
+        &self,
+        hashtag_id: Uuid,
+        include_children: bool,
+    ) -> Result<u64, DomainError> {
+        let row: (i64,) = sqlx::query_as(&format!(
             r#"
+            {}
             SELECT COUNT(*) FROM poses p
-            INNER JOIN hashtag_image hi ON hi.pose_id = p.id
-            WHERE hi.hashtag_id = $1
+            INNER JOIN {} ON t.pose_id = p.id
             "#,
-        )
+            HASHTAG_TREE_CTE, POSES_TAGGED_FROM
+        ))
         .bind(hashtag_id)
+        .bind(include_children)
         .fetch_one(&self.pool)
         .await
         .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
         Ok(row.0 as u64)
     }
+
+    async fn get_poses_by_tag_filter(
+        &self,
+        filter: &PoseTagFilter,
+        metadata: &PoseFilter,
+        list: &ListQuery,
+        page: u32,
+        limit: u32,
+    ) -> Result<Vec<Pose>, DomainError> {
+        let offset = page.saturating_mul(limit);
+        let list = ListSql::new(list, POSE_LIST_COLUMNS, POSE_TAG_FILTER_PARAMS + POSE_FILTER_PARAMS + 1)?;
+        let sql = format!(
+            r#"
+            SELECT {}
+            FROM poses p
+            WHERE {} AND {} AND {}
+            ORDER BY {}
+            LIMIT ${} OFFSET ${}
+            "#,
+            POSE_COLUMNS,
+            POSE_TAG_FILTER_WHERE,
+            pose_filter_where(POSE_TAG_FILTER_PARAMS + 1),
+            list.condition,
+            list.order_by("p.created_at DESC, p.id"),
+            list.next_param(),
+            list.next_param() + 1
+        );
+        let query = sqlx::query_as::<_, PoseRow>(&sql)
+            .bind(&filter.all)
+            .bind(&filter.any)
+            .bind(&filter.none);
+        let rows = list
+            .bind(bind_pose_filter(query, metadata))
+            .bind(limit as i64)
+            .bind(offset as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(rows.into_iter().map(Pose::from).collect())
+    }
+
+    async fn count_poses_by_tag_filter(
+        &self,
+        filter: &PoseTagFilter,
+        metadata: &PoseFilter,
+        list: &ListQuery,
+    ) -> Result<u64, DomainError> {
+        let list = ListSql::new(list, POSE_LIST_COLUMNS, POSE_TAG_FILTER_PARAMS + POSE_FILTER_PARAMS + 1)?;
+        let sql = format!(
+            "SELECT COUNT(*) FROM poses p WHERE {} AND {} AND {}",
+            POSE_TAG_FILTER_WHERE,
+            pose_filter_where(POSE_TAG_FILTER_PARAMS + 1),
+            list.condition
+        );
+        let query = sqlx::query_as(&sql)
+            .bind(&filter.all)
+            .bind(&filter.any)
+            .bind(&filter.none);
+        let row: (i64,) = list
+            .bind(bind_pose_filter(query, metadata))
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.0 as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Poses de `poses` que cumplen el filtro, en el mismo orden.
+    async fn tag_filter_matches(
+        conn: &mut sqlx::PgConnection,
+        poses: &[Uuid],
+        all: &[Uuid],
+        any: &[Uuid],
+        none: &[Uuid],
+    ) -> Vec<Uuid> {
+        let sql = format!(
+            "SELECT p.id FROM poses p WHERE {} AND p.id = ANY($4) ORDER BY array_position($4, p.id)",
+            POSE_TAG_FILTER_WHERE
+        );
+        sqlx::query_scalar(&sql)
+            .bind(all)
+            .bind(any)
+            .bind(none)
+            .bind(poses)
+            .fetch_all(conn)
+            .await
+            .unwrap()
+    }
+
+    /// Contra una base de datos con las migraciones aplicadas, en una transacción que se deshace:
+    /// `DATABASE_URL=... cargo test -- --ignored`.
+    #[tokio::test]
+    #[ignore = "necesita DATABASE_URL"]
+    async fn pose_tag_filter_combines_all_any_and_none() {
+        let pool = sqlx::PgPool::connect(&std::env::var("DATABASE_URL").unwrap())
+            .await
+            .unwrap();
+        let mut tx = pool.begin().await.unwrap();
+        let mut tags = Vec::new();
+        for name in ["pareja", "exterior", "sentados"] {
+            let id: Uuid = sqlx::query_scalar("INSERT INTO hashtags (name) VALUES ($1) RETURNING id")
+                .bind(format!("{}-{}", name, Uuid::new_v4()))
+                .fetch_one(&mut *tx)
+                .await
+                .unwrap();
+            tags.push(id);
+        }
+        let (pareja, exterior, sentados) = (tags[0], tags[1], tags[2]);
+        let tagged = [
+            vec![pareja, exterior],
+            vec![pareja],
+            vec![pareja, exterior, sentados],
+            vec![],
+        ];
+        let mut poses = Vec::new();
+        for pose_tags in tagged {
+            let id: Uuid = sqlx::query_scalar("INSERT INTO poses (url) VALUES ('/api/poses/t') RETURNING id")
+                .fetch_one(&mut *tx)
+                .await
+                .unwrap();
+            for tag in pose_tags {
+                sqlx::query("INSERT INTO hashtag_image (hashtag_id, pose_id) VALUES ($1, $2)")
+                    .bind(tag)
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await
+                    .unwrap();
+            }
+            poses.push(id);
+        }
+
+        let matches = tag_filter_matches(&mut tx, &poses, &[], &[], &[]).await;
+        assert_eq!(matches, poses);
+        let matches = tag_filter_matches(&mut tx, &poses, &[pareja, exterior], &[], &[]).await;
+        assert_eq!(matches, [poses[0], poses[2]]);
+        let matches = tag_filter_matches(&mut tx, &poses, &[], &[exterior, sentados], &[]).await;
+        assert_eq!(matches, [poses[0], poses[2]]);
+        let matches = tag_filter_matches(&mut tx, &poses, &[], &[], &[sentados]).await;
+        assert_eq!(matches, [poses[0], poses[1], poses[3]]);
+        let matches = tag_filter_matches(&mut tx, &poses, &[pareja], &[], &[sentados]).await;
+        assert_eq!(matches, [poses[0], poses[1]]);
+        let matches = tag_filter_matches(&mut tx, &poses, &[pareja, exterior], &[sentados], &[]).await;
+        assert_eq!(matches, [poses[2]]);
+    }
+
+    #[test]
+    fn pose_tag_filter_uses_its_declared_params() {
+        for n in 1..=POSE_TAG_FILTER_PARAMS {
+            assert!(POSE_TAG_FILTER_WHERE.contains(&format!("${}", n)));
+        }
+        assert!(!POSE_TAG_FILTER_WHERE.contains(&format!("${}", POSE_TAG_FILTER_PARAMS + 1)));
+    }
+
+    #[test]
+    fn like_prefix_pattern_escapes_wildcards() {
+        assert_eq!(like_prefix_pattern("boda"), "boda%");
+        assert_eq!(like_prefix_pattern("golden_h"), "golden\\_h%");
+        assert_eq!(like_prefix_pattern("100%"), "100\\%%");
+        assert_eq!(like_prefix_pattern("a\\b"), "a\\\\b%");
+        assert_eq!(like_prefix_pattern(""), "%");
+    }
 }
@@ -0,0 +1,222 @@
+use crate::domain::{DomainError, FilterKind, ListQuery, SortDirection};
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::postgres::PgArguments;
+use sqlx::query::QueryAs;
+use sqlx::Postgres;
+use uuid::Uuid;
+
+/// Parámetro de un listado, enlazado con el tipo de su columna.
+#[derive(Debug, Clone, PartialEq)]
+enum ListParam {
+    Text(String),
+    Uuid(Uuid),
+    Int(i64),
+    Date(NaiveDate),
+    Timestamp(DateTime<Utc>),
+}
+
+impl ListParam {
+    /// Valor de un filtro ya normalizado por `ListSpec`; si no se puede convertir es un error interno.
+    fn filter(field: &str, kind: FilterKind, value: &str) -> Result<Self, DomainError> {
+        let param = match kind {
+            FilterKind::Text => Some(ListParam::Text(value.to_string())),
+            FilterKind::Uuid => Uuid::parse_str(value).ok().map(ListParam::Uuid),
+            FilterKind::Int => value.parse().ok().map(ListParam::Int),
+            FilterKind::Date => NaiveDate::parse_from_str(value, "%Y-%m-%d").ok().map(ListParam::Date),
+        };
+        param.ok_or_else(|| {
+            DomainError::Repository(anyhow::anyhow!(
+                "valor de filtro sin normalizar en {}: {}",
+                field,
+                value
+            ))
+        })
+    }
+}
+
+/// SQL de un `ListQuery`: condición (siempre válida tras un WHERE), ORDER BY y sus parámetros,
+/// numerados a partir de `$first`. Cada parámetro se enlaza con el tipo de su columna.
+pub struct ListSql {
+    pub condition: String,
+    order: Option<String>,
+    params: Vec<ListParam>,
+    first: usize,
+}
+
+impl ListSql {
+    /// `columns` traduce cada campo de la lista blanca del recurso (incluido `created_at`) a su
+    /// expresión SQL. Un campo sin columna es un error del repositorio (`DomainError::Repository`).
+    /// `first` es el número del primer parámetro del listado: 1 + los que se enlazan antes.
+    pub fn new(query: &ListQuery, columns: &[(&str, &str)], first: usize) -> Result<Self, DomainError> {
+        let column = |field: &str| {
+            columns
+                .iter()
+                .find(|(f, _)| *f == field)
+                .map(|(_, c)| *c)
+                .ok_or_else(|| {
+                    DomainError::Repository(anyhow::anyhow!("campo sin columna en el listado: {}", field))
+                })
+        };
+        let mut conditions = vec!["TRUE".to_string()];
+        let mut params = Vec::new();
+        if let Some(after) = query.created_after {
+            conditions.push(format!("{} >= ${}", column("created_at")?, first + params.len()));
+            params.push(ListParam::Timestamp(after));
+        }
+        if let Some(before) = query.created_before {
+            conditions.push(format!("{} < ${}", column("created_at")?, first + params.len()));
+            params.push(ListParam::Timestamp(before));
+        }
+        for filter in &query.filters {
+            let n = first + params.len();
+            conditions.push(match filter.kind {
+                FilterKind::Text => format!("lower({}) = lower(${})", column(filter.field)?, n),
+                FilterKind::Uuid | FilterKind::Int | FilterKind::Date => {
+                    format!("{} = ${}", column(filter.field)?, n)
+                }
+            });
+            params.push(ListParam::filter(filter.field, filter.kind, &filter.value)?);
+        }
+        let order = match query.sort {
+            Some(s) => {
+                let direction = match s.direction {
+                    SortDirection::Asc => "ASC NULLS LAST",
+                    SortDirection::Desc => "DESC NULLS LAST",
+                };
+                Some(format!("{} {}", column(s.field)?, direction))
+            }
+            None => None,
+        };
+        Ok(ListSql {
+            condition: conditions.join(" AND "),
+            order,
+            params,
+            first,
+        })
+    }
+
+    /// ORDER BY pedido seguido del orden por defecto (que debe terminar en una columna única).
+    pub fn order_by(&self, default: &str) -> String {
+        match &self.order {
+            Some(order) => format!("{}, {}", order, default),
+            None => default.to_string(),
+        }
+    }
+
+    /// Primer número de parámetro libre después de los del listado.
+    pub fn next_param(&self) -> usize {
+        self.first + self.params.len()
+    }
+
+    pub fn bind<'q, O>(
+        &'q self,
+        mut query: QueryAs<'q, Postgres, O, PgArguments>,
+    ) -> QueryAs<'q, Postgres, O, PgArguments> {
+        for param in &self.params {
+            query = match param {
+                ListParam::Text(value) => query.bind(value.as_str()),
+                ListParam::Uuid(value) => query.bind(*value),
+                ListParam::Int(value) => query.bind(*value),
+                ListParam::Date(value) => query.bind(*value),
+                ListParam::Timestamp(value) => query.bind(*value),
+            };
+        }
+        query
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{FieldFilter, SortSpec};
+
+    const COLUMNS: &[(&str, &str)] = &[
+        ("created_at", "t.created_at"),
+        ("name", "t.name"),
+        ("owner_id", "t.owner_id"),
+        ("day", "t.day"),
+    ];
+
+    fn filter(field: &'static str, kind: FilterKind, value: &str) -> FieldFilter {
+        FieldFilter {
+            field,
+            kind,
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn empty_query_is_always_true() {
+        let list = ListSql::new(&ListQuery::default(), COLUMNS, 4).unwrap();
+        assert_eq!(list.condition, "TRUE");
+        assert_eq!(list.order_by("t.id"), "t.id");
+        assert_eq!(list.next_param(), 4);
+    }
+
+    #[test]
+    fn numbers_params_from_first_with_typed_values() {
+        let after = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let owner = Uuid::new_v4();
+        let query = ListQuery {
+            sort: Some(SortSpec {
+                field: "name",
+                direction: SortDirection::Asc,
+            }),
+            created_after: Some(after),
+            created_before: None,
+            filters: vec![
+                filter("name", FilterKind::Text, "Boda"),
+                filter("owner_id", FilterKind::Uuid, &owner.to_string()),
+                filter("day", FilterKind::Date, "2024-02-29"),
+            ],
+        };
+        let list = ListSql::new(&query, COLUMNS, 3).unwrap();
+        assert_eq!(
+            list.condition,
+            "TRUE AND t.created_at >= $3 AND lower(t.name) = lower($4) AND t.owner_id = $5 AND t.day = $6"
+        );
+        assert_eq!(list.order_by("t.id"), "t.name ASC NULLS LAST, t.id");
+        assert_eq!(list.next_param(), 7);
+        assert_eq!(
+            list.params,
+            vec![
+                ListParam::Timestamp(after),
+                ListParam::Text("Boda".to_string()),
+                ListParam::Uuid(owner),
+                ListParam::Date(NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_column_is_an_error() {
+        let query = ListQuery {
+            filters: vec![filter("size", FilterKind::Int, "3")],
+            ..ListQuery::default()
+        };
+        assert!(matches!(
+            ListSql::new(&query, COLUMNS, 1),
+            Err(DomainError::Repository(_))
+        ));
+        let query = ListQuery {
+            created_before: Some(Utc::now()),
+            ..ListQuery::default()
+        };
+        assert!(matches!(
+            ListSql::new(&query, &COLUMNS[1..], 1),
+            Err(DomainError::Repository(_))
+        ));
+    }
+
+    #[test]
+    fn unnormalized_filter_value_is_an_error() {
+        let query = ListQuery {
+            filters: vec![filter("owner_id", FilterKind::Uuid, "no-es-uuid")],
+            ..ListQuery::default()
+        };
+        assert!(matches!(
+            ListSql::new(&query, COLUMNS, 1),
+            Err(DomainError::Repository(_))
+        ));
+    }
+}
@@ -1,8 +1,20 @@
-use crate::domain::{DomainError, Place, PlacesRepository};
+use crate::domain::{DomainError, ListQuery, Place, PlacesRepository};
 use async_trait::async_trait;
 use sqlx::FromRow;
 use uuid::Uuid;
 
+use super::list_query::ListSql;
+
+const PLACE_COLUMNS: &str =
+    "id, name, description, address, location, latitude, longitude, instagram, website, url, created_at";
+
+/// Campos de `Place::LIST_SPEC`.
+const PLACE_LIST_COLUMNS: &[(&str, &str)] = &[
+    ("created_at", "created_at"),
+    ("name", "name"),
+    ("location", "location"),
+];
+
 #[derive(FromRow)]
 pub struct PlaceRow {
     pub id: Uuid,
@@ -48,20 +60,64 @@ impl PlacesRepositoryImpl {
 
 #[async_trait]
 impl PlacesRepository for PlacesRepositoryImpl {
-    async fn get_all(&self) -> Result<Vec<Place>, DomainError> {
-        let rows = sqlx::query_as::<_, PlaceRow>(
-            "SELECT id, name, description, address, location, latitude, longitude, instagram, website, url, created_at FROM places ORDER BY created_at DESC",
-        )
-        .fetch_all(&self.pool)
-        .await
-        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+    async fn get_all(&self, list: &ListQuery) -> Result<Vec<Place>, DomainError> {
+        let list = ListSql::new(list, PLACE_LIST_COLUMNS, 1)?;
+        let sql = format!(
+            "SELECT {} FROM places WHERE {} ORDER BY {}",
+            PLACE_COLUMNS,
+            list.condition,
+            list.order_by("created_at DESC, id DESC")
+        );
+        let rows = list
+            .bind(sqlx::query_as::<_, PlaceRow>(&sql))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(rows.into_iter().map(Place::from).collect())
+    }
+
+    async fn get_paginated(
+        &self,
+        list: &ListQuery,
+        page: u32,
+        limit: u32,
+    ) -> Result<Vec<Place>, DomainError> {
+        let offset = page.saturating_mul(limit);
+        let list = ListSql::new(list, PLACE_LIST_COLUMNS, 1)?;
+        let sql = format!(
+            "SELECT {} FROM places WHERE {} ORDER BY {} LIMIT ${} OFFSET ${}",
+            PLACE_COLUMNS,
+            list.condition,
+            list.order_by("created_at DESC, id DESC"),
+            list.next_param(),
+            list.next_param() + 1
+        );
+        let rows = list
+            .bind(sqlx::query_as::<_, PlaceRow>(&sql))
+            .bind(limit as i64)
+            .bind(offset as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
         Ok(rows.into_iter().map(Place::from).collect())
     }
 
+    async fn count(&self, list: &ListQuery) -> Result<u64, DomainError> {
+        let list = ListSql::new(list, PLACE_LIST_COLUMNS, 1)?;
+        let sql = format!("SELECT COUNT(*) FROM places WHERE {}", list.condition);
+        let row: (i64,) = list
+            .bind(sqlx::query_as(&sql))
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.0 as u64)
+    }
+
     async fn get_by_id(&self, id: Uuid) -> Result<Option<Place>, DomainError> {
-        let row = sqlx::query_as::<_, PlaceRow>(
-            "SELECT id, name, description, address, location, latitude, longitude, instagram, website, url, created_at FROM places WHERE id = $1",
-        )
+        let row = sqlx::query_as::<_, PlaceRow>(&format!(
+            "SELECT {} FROM places WHERE id = $1",
+            PLACE_COLUMNS
+        ))
         .bind(id)
         .fetch_optional(&self.pool)
         .await
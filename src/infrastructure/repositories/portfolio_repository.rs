@@ -1,5 +1,5 @@
 use crate::domain::{
-    DomainError, PortfolioCategory, PortfolioImage, PortfolioRepository,
+    DomainError, PageCursor, PortfolioCategory, PortfolioImage, PortfolioRepository,
 };
 use async_trait::async_trait;
 use sqlx::FromRow;
@@ -71,7 +71,7 @@ impl PortfolioRepository for PortfolioRepositoryImpl {
     ) -> Result<Vec<PortfolioImage>, DomainError> {
         let offset = page.saturating_mul(limit);
         let rows = sqlx::query_as::<_, PortfolioImageRow>(
-            "SELECT id, portfolio_category_id, url, created_at FROM portfolio_image WHERE portfolio_category_id = $1 ORDER BY created_at DESC LIMIT $2 OFFSET $3",
+            "SELECT id, portfolio_category_id, url, created_at FROM portfolio_image WHERE portfolio_category_id = $1 ORDER BY created_at DESC, id DESC LIMIT $2 OFFSET $3",
         )
         .bind(category_id)
         .bind(limit as i64)
@@ -82,6 +82,31 @@ impl PortfolioRepository for PortfolioRepositoryImpl {
         Ok(rows.into_iter().map(PortfolioImage::from).collect())
     }
 
+    async fn get_images_by_category_after(
+        &self,
+        category_id: Uuid,
+        after: Option<&PageCursor>,
+        limit: u32,
+    ) -> Result<Vec<PortfolioImage>, DomainError> {
+        let rows = sqlx::query_as::<_, PortfolioImageRow>(
+            r#"
+            SELECT id, portfolio_category_id, url, created_at FROM portfolio_image
+            WHERE portfolio_category_id = $1
+                AND ($2::TIMESTAMPTZ IS NULL OR (created_at, id) < ($2, $3))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(category_id)
+        .bind(after.map(|c| c.created_at))
+        .bind(after.map(|c| c.id))
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(rows.into_iter().map(PortfolioImage::from).collect())
+    }
+
     async fn count_images_by_category(&self, category_id: Uuid) -> Result<u64, DomainError> {
         let row: (i64,) = sqlx::query_as(
             "SELECT COUNT(*) FROM portfolio_image WHERE portfolio_category_id = $1",
@@ -1,12 +1,75 @@
-use crate::domain::{DomainError, Pose, PosesRepository};
+use crate::domain::{
+    DomainError, ListQuery, PageCursor, Pose, PoseDifficulty, PoseFilter, PoseMetadata,
+    PoseOrientation, PosesRepository, ScoredPose, UnitOfWork,
+};
 use async_trait::async_trait;
-use sqlx::FromRow;
+use sqlx::postgres::PgArguments;
+use sqlx::query::QueryAs;
+use sqlx::{FromRow, Postgres};
 use uuid::Uuid;
 
+use super::list_query::ListSql;
+use super::unit_of_work::PgUnitOfWork;
+
+/// Columnas de una pose sobre el alias `p` (compartidas con los repositorios que devuelven poses).
+pub const POSE_COLUMNS: &str = "p.id, p.url, p.title, p.description, p.people_count, p.difficulty, \
+    p.orientation, p.source_credit, p.created_at";
+
+/// Campos de `Pose::LIST_SPEC` sobre `p`.
+pub const POSE_LIST_COLUMNS: &[(&str, &str)] = &[
+    ("created_at", "p.created_at"),
+    ("title", "p.title"),
+    ("people_count", "p.people_count"),
+    ("difficulty", "p.difficulty"),
+    ("orientation", "p.orientation"),
+    ("source_credit", "p.source_credit"),
+];
+
+/// Parámetros que enlaza `bind_pose_filter`.
+pub const POSE_FILTER_PARAMS: usize = 5;
+
+/// Condición del filtro de metadatos sobre `p`, con sus `POSE_FILTER_PARAMS` parámetros a partir de
+/// `$first` (dificultad, orientación, mínimo y máximo de personas, texto). Se enlazan con `bind_pose_filter`.
+pub fn pose_filter_where(first: usize) -> String {
+    format!(
+        r#"
+        (${0}::TEXT IS NULL OR p.difficulty = ${0})
+        AND (${1}::TEXT IS NULL OR p.orientation = ${1})
+        AND (${2}::INT IS NULL OR p.people_count >= ${2})
+        AND (${3}::INT IS NULL OR p.people_count <= ${3})
+        AND (${4}::TEXT IS NULL
+            OR strpos(lower(concat_ws(' ', p.title, p.description)), lower(${4})) > 0)
+        "#,
+        first,
+        first + 1,
+        first + 2,
+        first + 3,
+        first + 4
+    )
+}
+
+pub fn bind_pose_filter<'q, O>(
+    query: QueryAs<'q, Postgres, O, PgArguments>,
+    filter: &'q PoseFilter,
+) -> QueryAs<'q, Postgres, O, PgArguments> {
+    query
+        .bind(filter.difficulty.map(|d| d.as_str()))
+        .bind(filter.orientation.map(|o| o.as_str()))
+        .bind(filter.min_people)
+        .bind(filter.max_people)
+        .bind(filter.q.as_deref())
+}
+
 #[derive(FromRow)]
 pub struct PoseRow {
     pub id: Uuid,
     pub url: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub people_count: Option<i32>,
+    pub difficulty: Option<String>,
+    pub orientation: Option<String>,
+    pub source_credit: Option<String>,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
@@ -15,11 +78,57 @@ impl From<PoseRow> for Pose {
         Pose {
             id: row.id,
             url: row.url,
+            metadata: PoseMetadata {
+                title: row.title,
+                description: row.description,
+                people_count: row.people_count,
+                difficulty: row.difficulty.as_deref().and_then(PoseDifficulty::parse),
+                orientation: row.orientation.as_deref().and_then(PoseOrientation::parse),
+                source_credit: row.source_credit,
+            },
             created_at: row.created_at,
         }
     }
 }
 
+/// Hashtags distintos por pose (`tagged`) y peso de cada hashtag según su rareza (`idf`), leído de la
+/// vista materializada `hashtag_idf` (0015): un hashtag raro pesa más que uno genérico.
+const HASHTAG_IDF_CTE: &str = r#"
+    tagged AS (SELECT DISTINCT pose_id, hashtag_id FROM hashtag_image),
+    idf AS (SELECT hashtag_id, weight FROM hashtag_idf)
+"#;
+
+/// Peso de un hashtag que aún no está en `hashtag_idf` (etiquetado después del último refresco): el
+/// de un hashtag usado en una sola pose, como se calcularía en la vista.
+const HASHTAG_IDF_DEFAULT: &str = "ln(1 + (SELECT GREATEST(COUNT(*), 1) FROM poses)::FLOAT8)";
+
+/// Distancia de Hamming entre los hashes de imagen de `c` y `own`. Cuenta los unos del XOR como texto
+/// en lugar de `bit_count`, que solo existe desde PostgreSQL 14.
+const IMAGE_HASH_DISTANCE: &str =
+    "length(replace((c.image_hash # own.image_hash)::BIT(64)::TEXT, '0', ''))";
+
+/// Máxima distancia de Hamming entre hashes de imagen para considerar dos poses visualmente parecidas.
+const IMAGE_HASH_MAX_DISTANCE: i32 = 12;
+
+/// Peso de la similitud visual (0..1) frente a la puntuación por hashtags.
+const IMAGE_HASH_WEIGHT: f64 = 3.0;
+
+#[derive(FromRow)]
+struct ScoredPoseRow {
+    #[sqlx(flatten)]
+    pose: PoseRow,
+    score: f64,
+}
+
+impl From<ScoredPoseRow> for ScoredPose {
+    fn from(row: ScoredPoseRow) -> Self {
+        ScoredPose {
+            pose: Pose::from(row.pose),
+            score: row.score,
+        }
+    }
+}
+
 pub struct PosesRepositoryImpl {
     pool: sqlx::PgPool,
 }
@@ -32,31 +141,82 @@ impl PosesRepositoryImpl {
 
 #[async_trait]
 impl PosesRepository for PosesRepositoryImpl {
-    async fn get_all(&self) -> Result<Vec<Pose>, DomainError> {
-        let rows = sqlx::query_as::<_, PoseRow>(
-            "SELECT id, url, created_at FROM poses ORDER BY created_at DESC",
-        )
-        .fetch_all(&self.pool)
-        .await
-        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+    async fn get_paginated(
+        &self,
+        filter: &PoseFilter,
+        list: &ListQuery,
+        page: u32,
+        limit: u32,
+    ) -> Result<Vec<Pose>, DomainError> {
+        let offset = page.saturating_mul(limit);
+        let list = ListSql::new(list, POSE_LIST_COLUMNS, POSE_FILTER_PARAMS + 1)?;
+        let sql = format!(
+            r#"
+            SELECT {} FROM poses p
+            WHERE {} AND {}
+            ORDER BY {}
+            LIMIT ${} OFFSET ${}
+            "#,
+            POSE_COLUMNS,
+            pose_filter_where(1),
+            list.condition,
+            list.order_by("p.created_at DESC, p.id DESC"),
+            list.next_param(),
+            list.next_param() + 1
+        );
+        let rows = list
+            .bind(bind_pose_filter(sqlx::query_as::<_, PoseRow>(&sql), filter))
+            .bind(limit as i64)
+            .bind(offset as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
         Ok(rows.into_iter().map(Pose::from).collect())
     }
 
-    async fn get_paginated(&self, page: u32, limit: u32) -> Result<Vec<Pose>, DomainError> {
-        let offset = page.saturating_mul(limit);
-        let rows = sqlx::query_as::<_, PoseRow>(
-            "SELECT id, url, created_at FROM poses ORDER BY created_at DESC LIMIT $1 OFFSET $2",
-        )
-        .bind(limit as i64)
-        .bind(offset as i64)
-        .fetch_all(&self.pool)
-        .await
-        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+    async fn get_page_after(
+        &self,
+        filter: &PoseFilter,
+        list: &ListQuery,
+        after: Option<&PageCursor>,
+        limit: u32,
+    ) -> Result<Vec<Pose>, DomainError> {
+        let list = ListSql::new(list, POSE_LIST_COLUMNS, POSE_FILTER_PARAMS + 1)?;
+        let sql = format!(
+            r#"
+            SELECT {0} FROM poses p
+            WHERE {1} AND {2}
+                AND (${3}::TIMESTAMPTZ IS NULL OR (p.created_at, p.id) < (${3}, ${4}))
+            ORDER BY p.created_at DESC, p.id DESC
+            LIMIT ${5}
+            "#,
+            POSE_COLUMNS,
+            pose_filter_where(1),
+            list.condition,
+            list.next_param(),
+            list.next_param() + 1,
+            list.next_param() + 2
+        );
+        let rows = list
+            .bind(bind_pose_filter(sqlx::query_as::<_, PoseRow>(&sql), filter))
+            .bind(after.map(|c| c.created_at))
+            .bind(after.map(|c| c.id))
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
         Ok(rows.into_iter().map(Pose::from).collect())
     }
 
-    async fn count(&self) -> Result<u64, DomainError> {
-        let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM poses")
+    async fn count(&self, filter: &PoseFilter, list: &ListQuery) -> Result<u64, DomainError> {
+        let list = ListSql::new(list, POSE_LIST_COLUMNS, POSE_FILTER_PARAMS + 1)?;
+        let sql = format!(
+            "SELECT COUNT(*) FROM poses p WHERE {} AND {}",
+            pose_filter_where(1),
+            list.condition
+        );
+        let row: (i64,) = list
+            .bind(bind_pose_filter(sqlx::query_as(&sql), filter))
             .fetch_one(&self.pool)
             .await
             .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
@@ -64,9 +224,10 @@ impl PosesRepository for PosesRepositoryImpl {
     }
 
     async fn get_by_id(&self, id: Uuid) -> Result<Option<Pose>, DomainError> {
-        let row = sqlx::query_as::<_, PoseRow>(
-            "SELECT id, url, created_at FROM poses WHERE id = $1",
-        )
+        let row = sqlx::query_as::<_, PoseRow>(&format!(
+            "SELECT {} FROM poses p WHERE p.id = $1",
+            POSE_COLUMNS
+        ))
         .bind(id)
         .fetch_optional(&self.pool)
         .await
@@ -74,28 +235,250 @@ impl PosesRepository for PosesRepositoryImpl {
         Ok(row.map(Pose::from))
     }
 
-    async fn create_with_id(&self, id: Uuid, url: &str) -> Result<Pose, DomainError> {
-        let row = sqlx::query_as::<_, PoseRow>(
+    async fn create_with_id_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        id: Uuid,
+        url: &str,
+        metadata: &PoseMetadata,
+        image_hash: Option<i64>,
+    ) -> Result<Pose, DomainError> {
+        let row = sqlx::query_as::<_, PoseRow>(&format!(
             r#"
-            INSERT INTO poses (id, url)
-            VALUES ($1, $2)
-            RETURNING id, url, created_at
+            INSERT INTO poses AS p
+                (id, url, title, description, people_count, difficulty, orientation, source_credit,
+                 image_hash)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING {}
             "#,
-        )
+            POSE_COLUMNS
+        ))
         .bind(id)
         .bind(url)
-        .fetch_one(&self.pool)
+        .bind(metadata.title.as_deref())
+        .bind(metadata.description.as_deref())
+        .bind(metadata.people_count)
+        .bind(metadata.difficulty.map(|d| d.as_str()))
+        .bind(metadata.orientation.map(|o| o.as_str()))
+        .bind(metadata.source_credit.as_deref())
+        .bind(image_hash)
+        .fetch_one(PgUnitOfWork::connection(uow)?)
         .await
         .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
         Ok(Pose::from(row))
     }
 
-    async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
-        sqlx::query("DELETE FROM poses WHERE id = $1")
+    async fn get_ids_without_image_hash(&self) -> Result<Vec<Uuid>, DomainError> {
+        sqlx::query_scalar::<_, Uuid>(
+            "SELECT id FROM poses WHERE image_hash IS NULL ORDER BY created_at, id",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))
+    }
+
+    async fn set_image_hash(&self, id: Uuid, image_hash: i64) -> Result<(), DomainError> {
+        sqlx::query("UPDATE poses SET image_hash = $2 WHERE id = $1")
             .bind(id)
+            .bind(image_hash)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(())
+    }
+
+    async fn refresh_hashtag_idf(&self) -> Result<(), DomainError> {
+        sqlx::query("REFRESH MATERIALIZED VIEW CONCURRENTLY hashtag_idf")
             .execute(&self.pool)
             .await
             .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
         Ok(())
     }
+
+    async fn update_metadata(
+        &self,
+        id: Uuid,
+        metadata: &PoseMetadata,
+    ) -> Result<Option<Pose>, DomainError> {
+        let row = sqlx::query_as::<_, PoseRow>(&format!(
+            r#"
+            UPDATE poses AS p
+            SET title = $2, description = $3, people_count = $4, difficulty = $5,
+                orientation = $6, source_credit = $7
+            WHERE p.id = $1
+            RETURNING {}
+            "#,
+            POSE_COLUMNS
+        ))
+        .bind(id)
+        .bind(metadata.title.as_deref())
+        .bind(metadata.description.as_deref())
+        .bind(metadata.people_count)
+        .bind(metadata.difficulty.map(|d| d.as_str()))
+        .bind(metadata.orientation.map(|o| o.as_str()))
+        .bind(metadata.source_credit.as_deref())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.map(Pose::from))
+    }
+
+    async fn delete_in(&self, uow: &mut dyn UnitOfWork, id: Uuid) -> Result<(), DomainError> {
+        sqlx::query("DELETE FROM poses WHERE id = $1")
+            .bind(id)
+            .execute(PgUnitOfWork::connection(uow)?)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(())
+    }
+
+    async fn delete_many_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, DomainError> {
+        sqlx::query_scalar("DELETE FROM poses WHERE id = ANY($1) RETURNING id")
+            .bind(ids)
+            .fetch_all(PgUnitOfWork::connection(uow)?)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))
+    }
+
+    async fn lock_existing_ids_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, DomainError> {
+        sqlx::query_scalar("SELECT id FROM poses WHERE id = ANY($1) ORDER BY id FOR UPDATE")
+            .bind(ids)
+            .fetch_all(PgUnitOfWork::connection(uow)?)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))
+    }
+
+    async fn get_similar(&self, id: Uuid, limit: u32) -> Result<Vec<ScoredPose>, DomainError> {
+        let rows = sqlx::query_as::<_, ScoredPoseRow>(&format!(
+            r#"
+            WITH {},
+            tag_scores AS (
+                SELECT t.pose_id, SUM(COALESCE(i.weight, {})) AS score
+                FROM tagged t
+                INNER JOIN tagged own ON own.hashtag_id = t.hashtag_id AND own.pose_id = $1
+                LEFT JOIN idf i ON i.hashtag_id = t.hashtag_id
+                WHERE t.pose_id <> $1
+                GROUP BY t.pose_id
+            ),
+            visual_scores AS (
+                SELECT pose_id, 1 - distance::FLOAT8 / 64 AS score
+                FROM (
+                    SELECT c.id AS pose_id, {} AS distance
+                    FROM poses c
+                    INNER JOIN poses own ON own.id = $1
+                    WHERE c.id <> $1
+                ) d
+                WHERE distance <= $2
+            )
+            SELECT {}, s.score
+            FROM poses p
+            INNER JOIN (
+                SELECT COALESCE(ts.pose_id, vs.pose_id) AS pose_id,
+                    (COALESCE(ts.score, 0) + COALESCE(vs.score, 0) * $3)::FLOAT8 AS score
+                FROM tag_scores ts
+                FULL JOIN visual_scores vs ON vs.pose_id = ts.pose_id
+            ) s ON s.pose_id = p.id
+            ORDER BY s.score DESC, p.created_at DESC, p.id
+            LIMIT $4
+            "#,
+            HASHTAG_IDF_CTE, HASHTAG_IDF_DEFAULT, IMAGE_HASH_DISTANCE, POSE_COLUMNS
+        ))
+        .bind(id)
+        .bind(IMAGE_HASH_MAX_DISTANCE)
+        .bind(IMAGE_HASH_WEIGHT)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(rows.into_iter().map(ScoredPose::from).collect())
+    }
+
+    async fn get_recommended(
+        &self,
+        user_id: Uuid,
+        limit: u32,
+    ) -> Result<Vec<ScoredPose>, DomainError> {
+        // Perfil del usuario: hashtags de sus favoritos (frecuencia × rareza). Además puntúan las poses
+        // que comparten sesión con algún favorito; el desempate es la popularidad en favoritos.
+        let rows = sqlx::query_as::<_, ScoredPoseRow>(&format!(
+            r#"
+            WITH {},
+            favs AS (SELECT pose_id FROM favoritos WHERE user_id = $1),
+            profile AS (
+                SELECT t.hashtag_id, COUNT(*) * COALESCE(MAX(i.weight), {}) AS weight
+                FROM tagged t
+                INNER JOIN favs f ON f.pose_id = t.pose_id
+                LEFT JOIN idf i ON i.hashtag_id = t.hashtag_id
+                GROUP BY t.hashtag_id
+            ),
+            tag_scores AS (
+                SELECT t.pose_id, SUM(pr.weight) AS score
+                FROM tagged t
+                INNER JOIN profile pr ON pr.hashtag_id = t.hashtag_id
+                GROUP BY t.pose_id
+            ),
+            sesion_scores AS (
+                SELECT si.pose_id, COUNT(DISTINCT si.sesion_id) AS score
+                FROM sesion_image si
+                WHERE si.sesion_id IN (
+                    SELECT sf.sesion_id FROM sesion_image sf INNER JOIN favs f ON f.pose_id = sf.pose_id
+                )
+                GROUP BY si.pose_id
+            ),
+            popularity AS (SELECT pose_id, COUNT(*) AS favorites FROM favoritos GROUP BY pose_id)
+            SELECT {}, (COALESCE(ts.score, 0) + COALESCE(ss.score, 0))::FLOAT8 AS score
+            FROM poses p
+            LEFT JOIN tag_scores ts ON ts.pose_id = p.id
+            LEFT JOIN sesion_scores ss ON ss.pose_id = p.id
+            LEFT JOIN popularity pop ON pop.pose_id = p.id
+            WHERE p.id NOT IN (SELECT pose_id FROM favs)
+            ORDER BY score DESC, COALESCE(pop.favorites, 0) DESC, p.created_at DESC, p.id
+            LIMIT $2
+            "#,
+            HASHTAG_IDF_CTE, HASHTAG_IDF_DEFAULT, POSE_COLUMNS
+        ))
+        .bind(user_id)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(rows.into_iter().map(ScoredPose::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Números de parámetro (`$n`) que aparecen en un SQL, sin repetir y ordenados.
+    fn placeholders(sql: &str) -> Vec<usize> {
+        let mut found: Vec<usize> = sql
+            .split('$')
+            .skip(1)
+            .filter_map(|rest| {
+                let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+                digits.parse().ok()
+            })
+            .collect();
+        found.sort_unstable();
+        found.dedup();
+        found
+    }
+
+    #[test]
+    fn pose_filter_where_uses_its_declared_params() {
+        assert_eq!(placeholders(&pose_filter_where(1)), (1..=POSE_FILTER_PARAMS).collect::<Vec<_>>());
+        assert_eq!(
+            placeholders(&pose_filter_where(4)),
+            (4..4 + POSE_FILTER_PARAMS).collect::<Vec<_>>()
+        );
+    }
 }
@@ -1,8 +1,48 @@
-use crate::domain::{DomainError, Post, PostsRepository};
+use crate::domain::{
+    DailyWinner, DomainError, Hashtag, ListQuery, PageCursor, Post, PostComment, PostImage,
+    PostsRepository, UnitOfWork,
+};
 use async_trait::async_trait;
 use sqlx::FromRow;
 use uuid::Uuid;
 
+use super::list_query::ListSql;
+use super::unit_of_work::PgUnitOfWork;
+
+/// Columnas de posts (incluye votos, likes y comentarios) para SELECT y RETURNING.
+const POST_COLUMNS: &str = r#"
+    posts.id, posts.description, posts.url, posts.user_id, posts.theme_of_the_day_id,
+    posts.theme_date, posts.evento_id, posts.created_at,
+    (SELECT COUNT(*) FROM post_votes v WHERE v.post_id = posts.id) AS votes_count,
+    (SELECT COUNT(*) FROM post_likes l WHERE l.post_id = posts.id) AS likes_count,
+    (SELECT COUNT(*) FROM post_comments c WHERE c.post_id = posts.id AND NOT c.deleted) AS comments_count,
+    ARRAY(SELECT i.id FROM post_images i WHERE i.post_id = posts.id ORDER BY i.position, i.id) AS image_ids,
+    (SELECT COALESCE(
+                json_agg(json_build_object('id', h.id, 'name', h.name, 'parent_id', h.parent_id)
+                         ORDER BY h.name, h.id),
+                '[]')
+     FROM hashtag_pose hp JOIN hashtags h ON h.id = hp.hashtag_id
+     WHERE hp.post_id = posts.id) AS hashtags
+"#;
+
+/// Campos de `Post::LIST_SPEC`: columnas de posts o alias de `POST_COLUMNS` (solo para ORDER BY).
+const POST_LIST_COLUMNS: &[(&str, &str)] = &[
+    ("created_at", "posts.created_at"),
+    ("votes_count", "votes_count"),
+    ("likes_count", "likes_count"),
+    ("comments_count", "comments_count"),
+    ("theme_date", "posts.theme_date"),
+    ("user_id", "posts.user_id"),
+    ("evento_id", "posts.evento_id"),
+    ("theme_of_the_day_id", "posts.theme_of_the_day_id"),
+];
+
+/// Columnas de post_comments (con nombre y avatar del autor); requiere `FROM post_comments c JOIN usuarios u`.
+const COMMENT_COLUMNS: &str = r#"
+    c.id, c.post_id, c.user_id, u.name AS user_name, u.url AS user_url, c.parent_id,
+    c.body, c.deleted, c.created_at, c.updated_at
+"#;
+
 #[derive(FromRow)]
 pub struct PostRow {
     pub id: Uuid,
@@ -10,7 +50,15 @@ pub struct PostRow {
     pub url: Option<String>,
     pub user_id: Option<Uuid>,
     pub theme_of_the_day_id: Option<String>,
+    pub theme_date: Option<chrono::NaiveDate>,
+    pub evento_id: Option<Uuid>,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub votes_count: i64,
+    pub likes_count: i64,
+    pub comments_count: i64,
+    pub image_ids: Vec<Uuid>,
+    /// Hashtags del post (id, name, parent_id) en un solo array JSON.
+    pub hashtags: sqlx::types::Json<Vec<Hashtag>>,
 }
 
 impl From<PostRow> for Post {
@@ -19,13 +67,77 @@ impl From<PostRow> for Post {
             id: row.id,
             description: row.description,
             url: row.url,
+            images: row
+                .image_ids
+                .into_iter()
+                .map(|image_id| PostImage::new(row.id, image_id))
+                .collect(),
             user_id: row.user_id,
             theme_of_the_day_id: row.theme_of_the_day_id,
+            theme_date: row.theme_date,
+            evento_id: row.evento_id,
+            hashtags: row.hashtags.0,
+            votes_count: row.votes_count,
+            likes_count: row.likes_count,
+            comments_count: row.comments_count,
+            liked_by_me: false,
             created_at: row.created_at,
         }
     }
 }
 
+#[derive(FromRow)]
+pub struct PostCommentRow {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub user_id: Uuid,
+    pub user_name: Option<String>,
+    pub user_url: Option<String>,
+    pub parent_id: Option<Uuid>,
+    pub body: String,
+    pub deleted: bool,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<PostCommentRow> for PostComment {
+    fn from(row: PostCommentRow) -> Self {
+        PostComment {
+            id: row.id,
+            post_id: row.post_id,
+            user_id: row.user_id,
+            user_name: row.user_name,
+            user_url: row.user_url,
+            parent_id: row.parent_id,
+            body: row.body,
+            deleted: row.deleted,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[derive(FromRow)]
+pub struct DailyWinnerRow {
+    pub theme_date: chrono::NaiveDate,
+    pub post_id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub votes: i64,
+    pub decided_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<DailyWinnerRow> for DailyWinner {
+    fn from(row: DailyWinnerRow) -> Self {
+        DailyWinner {
+            theme_date: row.theme_date,
+            post_id: row.post_id,
+            user_id: row.user_id,
+            votes: row.votes,
+            decided_at: row.decided_at,
+        }
+    }
+}
+
 pub struct PostsRepositoryImpl {
     pool: sqlx::PgPool,
 }
@@ -38,21 +150,116 @@ impl PostsRepositoryImpl {
 
 #[async_trait]
 impl PostsRepository for PostsRepositoryImpl {
-    async fn get_all(&self) -> Result<Vec<Post>, DomainError> {
-        let rows = sqlx::query_as::<_, PostRow>(
-            "SELECT id, description, url, user_id, theme_of_the_day_id, created_at FROM posts ORDER BY created_at DESC",
-        )
+    async fn get_paginated(
+        &self,
+        list: &ListQuery,
+        page: u32,
+        limit: u32,
+    ) -> Result<Vec<Post>, DomainError> {
+        let offset = page.saturating_mul(limit);
+        let list = ListSql::new(list, POST_LIST_COLUMNS, 1)?;
+        let sql = format!(
+            "SELECT {} FROM posts WHERE {} ORDER BY {} LIMIT ${} OFFSET ${}",
+            POST_COLUMNS,
+            list.condition,
+            list.order_by("posts.created_at DESC, posts.id DESC"),
+            list.next_param(),
+            list.next_param() + 1
+        );
+        let rows = list
+            .bind(sqlx::query_as::<_, PostRow>(&sql))
+            .bind(limit as i64)
+            .bind(offset as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(rows.into_iter().map(Post::from).collect())
+    }
+
+    async fn get_page_after(
+        &self,
+        list: &ListQuery,
+        after: Option<&PageCursor>,
+        limit: u32,
+    ) -> Result<Vec<Post>, DomainError> {
+        let list = ListSql::new(list, POST_LIST_COLUMNS, 1)?;
+        let sql = format!(
+            r#"
+            SELECT {0} FROM posts
+            WHERE {1} AND (${2}::TIMESTAMPTZ IS NULL OR (posts.created_at, posts.id) < (${2}, ${3}))
+            ORDER BY posts.created_at DESC, posts.id DESC
+            LIMIT ${4}
+            "#,
+            POST_COLUMNS,
+            list.condition,
+            list.next_param(),
+            list.next_param() + 1,
+            list.next_param() + 2
+        );
+        let rows = list
+            .bind(sqlx::query_as::<_, PostRow>(&sql))
+            .bind(after.map(|c| c.created_at))
+            .bind(after.map(|c| c.id))
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(rows.into_iter().map(Post::from).collect())
+    }
+
+    async fn count(&self, list: &ListQuery) -> Result<u64, DomainError> {
+        let list = ListSql::new(list, POST_LIST_COLUMNS, 1)?;
+        let sql = format!("SELECT COUNT(*) FROM posts WHERE {}", list.condition);
+        let row: (i64,) = list
+            .bind(sqlx::query_as(&sql))
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.0 as u64)
+    }
+
+    async fn get_by_theme_of_the_day_id(
+        &self,
+        theme_of_the_day_id: &str,
+    ) -> Result<Vec<Post>, DomainError> {
+        let rows = sqlx::query_as::<_, PostRow>(&format!(
+            "SELECT {} FROM posts WHERE theme_of_the_day_id = $1 ORDER BY created_at DESC",
+            POST_COLUMNS
+        ))
+        .bind(theme_of_the_day_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(rows.into_iter().map(Post::from).collect())
+    }
+
+    async fn get_by_theme_date(
+        &self,
+        theme_date: chrono::NaiveDate,
+    ) -> Result<Vec<Post>, DomainError> {
+        let rows = sqlx::query_as::<_, PostRow>(&format!(
+            "SELECT {} FROM posts WHERE theme_date = $1 ORDER BY created_at DESC",
+            POST_COLUMNS
+        ))
+        .bind(theme_date)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
         Ok(rows.into_iter().map(Post::from).collect())
     }
 
-    async fn get_paginated(&self, page: u32, limit: u32) -> Result<Vec<Post>, DomainError> {
+    async fn get_by_evento_paginated(
+        &self,
+        evento_id: Uuid,
+        page: u32,
+        limit: u32,
+    ) -> Result<Vec<Post>, DomainError> {
         let offset = page.saturating_mul(limit);
-        let rows = sqlx::query_as::<_, PostRow>(
-            "SELECT id, description, url, user_id, theme_of_the_day_id, created_at FROM posts ORDER BY created_at DESC LIMIT $1 OFFSET $2",
-        )
+        let rows = sqlx::query_as::<_, PostRow>(&format!(
+            "SELECT {} FROM posts WHERE evento_id = $1 ORDER BY created_at DESC LIMIT $2 OFFSET $3",
+            POST_COLUMNS
+        ))
+        .bind(evento_id)
         .bind(limit as i64)
         .bind(offset as i64)
         .fetch_all(&self.pool)
@@ -61,32 +268,55 @@ impl PostsRepository for PostsRepositoryImpl {
         Ok(rows.into_iter().map(Post::from).collect())
     }
 
-    async fn count(&self) -> Result<u64, DomainError> {
-        let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM posts")
+    async fn count_by_evento(&self, evento_id: Uuid) -> Result<u64, DomainError> {
+        let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM posts WHERE evento_id = $1")
+            .bind(evento_id)
             .fetch_one(&self.pool)
             .await
             .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
         Ok(row.0 as u64)
     }
 
-    async fn get_by_theme_of_the_day_id(
+    async fn get_by_hashtag_paginated(
         &self,
-        theme_of_the_day_id: &str,
+        hashtag_id: Uuid,
+        page: u32,
+        limit: u32,
     ) -> Result<Vec<Post>, DomainError> {
-        let rows = sqlx::query_as::<_, PostRow>(
-            "SELECT id, description, url, user_id, theme_of_the_day_id, created_at FROM posts WHERE theme_of_the_day_id = $1 ORDER BY created_at DESC",
-        )
-        .bind(theme_of_the_day_id)
+        let offset = page.saturating_mul(limit);
+        let rows = sqlx::query_as::<_, PostRow>(&format!(
+            r#"
+            SELECT {} FROM posts
+            INNER JOIN hashtag_pose hp ON hp.post_id = posts.id
+            WHERE hp.hashtag_id = $1
+            ORDER BY posts.created_at DESC, posts.id
+            LIMIT $2 OFFSET $3
+            "#,
+            POST_COLUMNS
+        ))
+        .bind(hashtag_id)
+        .bind(limit as i64)
+        .bind(offset as i64)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
         Ok(rows.into_iter().map(Post::from).collect())
     }
 
+    async fn count_by_hashtag(&self, hashtag_id: Uuid) -> Result<u64, DomainError> {
+        let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM hashtag_pose WHERE hashtag_id = $1")
+            .bind(hashtag_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.0 as u64)
+    }
+
     async fn get_by_id(&self, id: Uuid) -> Result<Option<Post>, DomainError> {
-        let row = sqlx::query_as::<_, PostRow>(
-            "SELECT id, description, url, user_id, theme_of_the_day_id, created_at FROM posts WHERE id = $1",
-        )
+        let row = sqlx::query_as::<_, PostRow>(&format!(
+            "SELECT {} FROM posts WHERE id = $1",
+            POST_COLUMNS
+        ))
         .bind(id)
         .fetch_optional(&self.pool)
         .await
@@ -94,19 +324,23 @@ impl PostsRepository for PostsRepositoryImpl {
         Ok(row.map(Post::from))
     }
 
-    async fn create_with_id(
+    async fn create_with_id_in(
         &self,
+        uow: &mut dyn UnitOfWork,
         id: Uuid,
         description: Option<&str>,
         url: Option<&str>,
         user_id: Option<Uuid>,
         theme_of_the_day_id: &str,
+        theme_date: Option<chrono::NaiveDate>,
+        evento_id: Option<Uuid>,
+        image_ids: &[Uuid],
     ) -> Result<Post, DomainError> {
-        let row = sqlx::query_as::<_, PostRow>(
+        let conn = PgUnitOfWork::connection(uow)?;
+        sqlx::query(
             r#"
-            INSERT INTO posts (id, description, url, user_id, theme_of_the_day_id)
-            VALUES ($1, $2, $3, $4, $5)
-            RETURNING id, description, url, user_id, theme_of_the_day_id, created_at
+            INSERT INTO posts (id, description, url, user_id, theme_of_the_day_id, theme_date, evento_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
             "#,
         )
         .bind(id)
@@ -114,12 +348,162 @@ impl PostsRepository for PostsRepositoryImpl {
         .bind(url)
         .bind(user_id)
         .bind(theme_of_the_day_id)
-        .fetch_one(&self.pool)
+        .bind(theme_date)
+        .bind(evento_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        sqlx::query(
+            r#"
+            INSERT INTO post_images (id, post_id, position)
+            SELECT image_id, $1, (ord - 1)::INTEGER
+            FROM UNNEST($2::UUID[]) WITH ORDINALITY AS t(image_id, ord)
+            "#,
+        )
+        .bind(id)
+        .bind(image_ids)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        let row = sqlx::query_as::<_, PostRow>(&format!(
+            "SELECT {} FROM posts WHERE id = $1",
+            POST_COLUMNS
+        ))
+        .bind(id)
+        .fetch_one(&mut *conn)
         .await
         .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
         Ok(Post::from(row))
     }
 
+    async fn add_image(
+        &self,
+        post_id: Uuid,
+        image_id: Uuid,
+        max_images: usize,
+    ) -> Result<bool, DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        // Bloquea el post: las altas concurrentes se serializan y no repiten posición ni pasan del máximo.
+        sqlx::query("SELECT 1 FROM posts WHERE id = $1 FOR UPDATE")
+            .bind(post_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        let result = sqlx::query(
+            r#"
+            INSERT INTO post_images (id, post_id, position)
+            SELECT $2, $1, COALESCE(MAX(position) + 1, 0) FROM post_images WHERE post_id = $1
+            HAVING COUNT(*) < $3
+            "#,
+        )
+        .bind(post_id)
+        .bind(image_id)
+        .bind(max_images as i64)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn reorder_images(&self, post_id: Uuid, image_ids: &[Uuid]) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            UPDATE post_images i
+            SET position = (t.ord - 1)::INTEGER
+            FROM UNNEST($2::UUID[]) WITH ORDINALITY AS t(image_id, ord)
+            WHERE i.post_id = $1 AND i.id = t.image_id
+            "#,
+        )
+        .bind(post_id)
+        .bind(image_ids)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(())
+    }
+
+    async fn remove_image(&self, post_id: Uuid, image_id: Uuid) -> Result<bool, DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        sqlx::query("SELECT 1 FROM posts WHERE id = $1 FOR UPDATE")
+            .bind(post_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        let result = sqlx::query("DELETE FROM post_images WHERE post_id = $1 AND id = $2")
+            .bind(post_id)
+            .bind(image_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        // Compacta las posiciones para que sigan siendo 0..n-1.
+        sqlx::query(
+            r#"
+            UPDATE post_images i
+            SET position = r.new_position
+            FROM (
+                SELECT id, (ROW_NUMBER() OVER (ORDER BY position, id) - 1)::INTEGER AS new_position
+                FROM post_images WHERE post_id = $1
+            ) r
+            WHERE i.id = r.id
+            "#,
+        )
+        .bind(post_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn update_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        id: Uuid,
+        description: Option<&str>,
+        url: Option<&str>,
+        theme: Option<(&str, Option<chrono::NaiveDate>)>,
+    ) -> Result<Option<Post>, DomainError> {
+        let (theme_of_the_day_id, theme_date) = match theme {
+            Some((mmdd, date)) => (Some(mmdd), date),
+            None => (None, None),
+        };
+        let row = sqlx::query_as::<_, PostRow>(&format!(
+            r#"
+            UPDATE posts
+            SET
+                description = CASE WHEN $2::TEXT IS NULL THEN description ELSE NULLIF($2, '') END,
+                url = COALESCE($3, url),
+                theme_of_the_day_id = COALESCE($4, theme_of_the_day_id),
+                theme_date = CASE WHEN $4::TEXT IS NULL THEN theme_date ELSE $5 END
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            POST_COLUMNS
+        ))
+        .bind(id)
+        .bind(description)
+        .bind(url)
+        .bind(theme_of_the_day_id)
+        .bind(theme_date)
+        .fetch_optional(PgUnitOfWork::connection(uow)?)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.map(Post::from))
+    }
+
     async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
         sqlx::query("DELETE FROM posts WHERE id = $1")
             .bind(id)
@@ -128,4 +512,300 @@ impl PostsRepository for PostsRepositoryImpl {
             .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
         Ok(())
     }
+
+    async fn add_vote(&self, post_id: Uuid, user_id: Uuid) -> Result<bool, DomainError> {
+        let result = sqlx::query(
+            "INSERT INTO post_votes (post_id, user_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+        )
+        .bind(post_id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn remove_vote(&self, post_id: Uuid, user_id: Uuid) -> Result<bool, DomainError> {
+        let result = sqlx::query("DELETE FROM post_votes WHERE post_id = $1 AND user_id = $2")
+            .bind(post_id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn get_leaderboard(
+        &self,
+        theme_date: chrono::NaiveDate,
+        limit: u32,
+    ) -> Result<Vec<Post>, DomainError> {
+        let rows = sqlx::query_as::<_, PostRow>(&format!(
+            r#"
+            SELECT {} FROM posts
+            WHERE theme_date = $1
+            ORDER BY votes_count DESC, created_at ASC, id ASC
+            LIMIT $2
+            "#,
+            POST_COLUMNS
+        ))
+        .bind(theme_date)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(rows.into_iter().map(Post::from).collect())
+    }
+
+    async fn decide_daily_winner(
+        &self,
+        theme_date: chrono::NaiveDate,
+    ) -> Result<Option<DailyWinner>, DomainError> {
+        // Misma ordenación que el ranking; ON CONFLICT conserva el ganador ya fijado.
+        sqlx::query(&format!(
+            r#"
+            INSERT INTO theme_of_the_day_winners (theme_date, post_id, user_id, votes)
+            SELECT theme_date, id, user_id, votes_count FROM (
+                SELECT {} FROM posts WHERE theme_date = $1
+            ) ranked
+            ORDER BY votes_count DESC, created_at ASC, id ASC
+            LIMIT 1
+            ON CONFLICT (theme_date) DO NOTHING
+            "#,
+            POST_COLUMNS
+        ))
+        .bind(theme_date)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        let row = sqlx::query_as::<_, DailyWinnerRow>(
+            r#"
+            SELECT theme_date, post_id, user_id, votes, decided_at
+            FROM theme_of_the_day_winners
+            WHERE theme_date = $1
+            "#,
+        )
+        .bind(theme_date)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.map(DailyWinner::from))
+    }
+
+    async fn decide_daily_winners_until(
+        &self,
+        last_closed: chrono::NaiveDate,
+    ) -> Result<u64, DomainError> {
+        // Misma ordenación que decide_daily_winner, un ganador por día pendiente.
+        let result = sqlx::query(&format!(
+            r#"
+            INSERT INTO theme_of_the_day_winners (theme_date, post_id, user_id, votes)
+            SELECT DISTINCT ON (theme_date) theme_date, id, user_id, votes_count FROM (
+                SELECT {} FROM posts
+                WHERE theme_date <= $1
+                  AND NOT EXISTS (
+                      SELECT 1 FROM theme_of_the_day_winners w WHERE w.theme_date = posts.theme_date
+                  )
+            ) ranked
+            ORDER BY theme_date, votes_count DESC, created_at ASC, id ASC
+            ON CONFLICT (theme_date) DO NOTHING
+            "#,
+            POST_COLUMNS
+        ))
+        .bind(last_closed)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(result.rows_affected())
+    }
+
+    async fn get_challenge_post_times(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<chrono::DateTime<chrono::Utc>>, DomainError> {
+        let rows: Vec<(chrono::DateTime<chrono::Utc>,)> = sqlx::query_as(
+            r#"
+            SELECT created_at FROM posts
+            WHERE user_id = $1 AND theme_date IS NOT NULL
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(rows.into_iter().map(|r| r.0).collect())
+    }
+
+    async fn add_like(&self, post_id: Uuid, user_id: Uuid) -> Result<bool, DomainError> {
+        let result = sqlx::query(
+            "INSERT INTO post_likes (post_id, user_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+        )
+        .bind(post_id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn remove_like(&self, post_id: Uuid, user_id: Uuid) -> Result<bool, DomainError> {
+        let result = sqlx::query("DELETE FROM post_likes WHERE post_id = $1 AND user_id = $2")
+            .bind(post_id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn get_liked_post_ids(
+        &self,
+        user_id: Uuid,
+        post_ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, DomainError> {
+        if post_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let rows: Vec<(Uuid,)> = sqlx::query_as(
+            "SELECT post_id FROM post_likes WHERE user_id = $1 AND post_id = ANY($2)",
+        )
+        .bind(user_id)
+        .bind(post_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(rows.into_iter().map(|r| r.0).collect())
+    }
+
+    async fn get_comments(&self, post_id: Uuid) -> Result<Vec<PostComment>, DomainError> {
+        let rows = sqlx::query_as::<_, PostCommentRow>(&format!(
+            r#"
+            SELECT {} FROM post_comments c
+            INNER JOIN usuarios u ON u.id = c.user_id
+            WHERE c.post_id = $1
+            ORDER BY c.created_at ASC, c.id ASC
+            "#,
+            COMMENT_COLUMNS
+        ))
+        .bind(post_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(rows.into_iter().map(PostComment::from).collect())
+    }
+
+    async fn get_comment_by_id(&self, id: Uuid) -> Result<Option<PostComment>, DomainError> {
+        let row = sqlx::query_as::<_, PostCommentRow>(&format!(
+            r#"
+            SELECT {} FROM post_comments c
+            INNER JOIN usuarios u ON u.id = c.user_id
+            WHERE c.id = $1
+            "#,
+            COMMENT_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.map(PostComment::from))
+    }
+
+    async fn get_comment_depth(&self, id: Uuid) -> Result<u32, DomainError> {
+        let (depth,): (i32,) = sqlx::query_as(
+            r#"
+            WITH RECURSIVE chain AS (
+                SELECT id, parent_id, 0 AS depth FROM post_comments WHERE id = $1
+                UNION ALL
+                SELECT c.id, c.parent_id, chain.depth + 1
+                FROM post_comments c INNER JOIN chain ON c.id = chain.parent_id
+            )
+            SELECT COALESCE(MAX(depth), 0) FROM chain
+            "#,
+        )
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(depth.max(0) as u32)
+    }
+
+    async fn create_comment(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        parent_id: Option<Uuid>,
+        body: &str,
+    ) -> Result<PostComment, DomainError> {
+        let row = sqlx::query_as::<_, PostCommentRow>(&format!(
+            r#"
+            WITH c AS (
+                INSERT INTO post_comments (id, post_id, user_id, parent_id, body)
+                VALUES ($1, $2, $3, $4, $5)
+                RETURNING *
+            )
+            SELECT {} FROM c
+            INNER JOIN usuarios u ON u.id = c.user_id
+            "#,
+            COMMENT_COLUMNS
+        ))
+        .bind(Uuid::new_v4())
+        .bind(post_id)
+        .bind(user_id)
+        .bind(parent_id)
+        .bind(body)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(PostComment::from(row))
+    }
+
+    async fn update_comment(&self, id: Uuid, body: &str) -> Result<Option<PostComment>, DomainError> {
+        let row = sqlx::query_as::<_, PostCommentRow>(&format!(
+            r#"
+            WITH c AS (
+                UPDATE post_comments SET body = $2, updated_at = now()
+                WHERE id = $1 AND NOT deleted
+                RETURNING *
+            )
+            SELECT {} FROM c
+            INNER JOIN usuarios u ON u.id = c.user_id
+            "#,
+            COMMENT_COLUMNS
+        ))
+        .bind(id)
+        .bind(body)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.map(PostComment::from))
+    }
+
+    async fn delete_comment(&self, id: Uuid) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        let has_replies: (bool,) =
+            sqlx::query_as("SELECT EXISTS (SELECT 1 FROM post_comments WHERE parent_id = $1)")
+                .bind(id)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        let sql = if has_replies.0 {
+            "UPDATE post_comments SET body = '', deleted = TRUE, updated_at = now() WHERE id = $1"
+        } else {
+            "DELETE FROM post_comments WHERE id = $1"
+        };
+        sqlx::query(sql)
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(())
+    }
 }
@@ -1,8 +1,14 @@
-use crate::domain::{DomainError, Pose, Sesion, SesionesRepository};
+use crate::domain::{DomainError, ListQuery, Pose, Sesion, SesionesRepository, UnitOfWork};
+use super::list_query::ListSql;
+use super::poses_repository::{PoseRow, POSE_COLUMNS};
+use super::unit_of_work::PgUnitOfWork;
 use async_trait::async_trait;
-use sqlx::FromRow;
+use sqlx::{FromRow, PgConnection, PgExecutor};
 use uuid::Uuid;
 
+/// Campos de `Sesion::LIST_SPEC`.
+const SESION_LIST_COLUMNS: &[(&str, &str)] = &[("created_at", "created_at"), ("name", "name")];
+
 #[derive(FromRow)]
 pub struct SesionRow {
     pub id: Uuid,
@@ -23,20 +29,54 @@ impl From<SesionRow> for Sesion {
 }
 
 #[derive(FromRow)]
-struct PoseRow {
-    id: Uuid,
-    url: String,
-    created_at: Option<chrono::DateTime<chrono::Utc>>,
+struct SesionPoseRow {
+    sesion_id: Uuid,
+    #[sqlx(flatten)]
+    pose: PoseRow,
 }
 
-impl From<PoseRow> for Pose {
-    fn from(row: PoseRow) -> Self {
-        Pose {
-            id: row.id,
-            url: row.url,
-            created_at: row.created_at,
-        }
-    }
+#[derive(FromRow)]
+struct PoseSesionRow {
+    pose_id: Uuid,
+    #[sqlx(flatten)]
+    sesion: SesionRow,
+}
+
+async fn insert_sesion(executor: impl PgExecutor<'_>, name: &str) -> Result<Sesion, DomainError> {
+    let row = sqlx::query_as::<_, SesionRow>(
+        r#"
+        INSERT INTO sesiones (name)
+        VALUES ($1)
+        RETURNING id, name, created_at, cover_url
+        "#,
+    )
+    .bind(name)
+    .fetch_one(executor)
+    .await
+    .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+    Ok(Sesion::from(row))
+}
+
+/// Una fila por pose (en el orden recibido) para conservar el orden de inserción en sesion_image.
+/// Devuelve las poses añadidas (las que ya estaban en la sesión no).
+async fn insert_sesion_poses(
+    conn: &mut PgConnection,
+    sesion_id: Uuid,
+    pose_ids: &[Uuid],
+) -> Result<Vec<Uuid>, DomainError> {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO sesion_image (sesion_id, pose_id)
+        SELECT $1, p.id FROM UNNEST($2::UUID[]) AS p(id)
+        ON CONFLICT (sesion_id, pose_id) DO NOTHING
+        RETURNING pose_id
+        "#,
+    )
+    .bind(sesion_id)
+    .bind(pose_ids)
+    .fetch_all(conn)
+    .await
+    .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))
 }
 
 pub struct SesionesRepositoryImpl {
@@ -51,16 +91,42 @@ impl SesionesRepositoryImpl {
 
 #[async_trait]
 impl SesionesRepository for SesionesRepositoryImpl {
-    async fn get_all(&self) -> Result<Vec<Sesion>, DomainError> {
-        let rows = sqlx::query_as::<_, SesionRow>(
-            "SELECT id, name, created_at, cover_url FROM sesiones ORDER BY created_at DESC",
-        )
-        .fetch_all(&self.pool)
-        .await
-        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+    async fn get_paginated(
+        &self,
+        list: &ListQuery,
+        page: u32,
+        limit: u32,
+    ) -> Result<Vec<Sesion>, DomainError> {
+        let offset = page.saturating_mul(limit);
+        let list = ListSql::new(list, SESION_LIST_COLUMNS, 1)?;
+        let sql = format!(
+            "SELECT id, name, created_at, cover_url FROM sesiones WHERE {} ORDER BY {} LIMIT ${} OFFSET ${}",
+            list.condition,
+            list.order_by("created_at DESC, id DESC"),
+            list.next_param(),
+            list.next_param() + 1
+        );
+        let rows = list
+            .bind(sqlx::query_as::<_, SesionRow>(&sql))
+            .bind(limit as i64)
+            .bind(offset as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
         Ok(rows.into_iter().map(Sesion::from).collect())
     }
 
+    async fn count(&self, list: &ListQuery) -> Result<u64, DomainError> {
+        let list = ListSql::new(list, SESION_LIST_COLUMNS, 1)?;
+        let sql = format!("SELECT COUNT(*) FROM sesiones WHERE {}", list.condition);
+        let row: (i64,) = list
+            .bind(sqlx::query_as(&sql))
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.0 as u64)
+    }
+
     async fn get_by_id(&self, id: Uuid) -> Result<Option<Sesion>, DomainError> {
         let row = sqlx::query_as::<_, SesionRow>(
             "SELECT id, name, created_at, cover_url FROM sesiones WHERE id = $1",
@@ -72,36 +138,103 @@ impl SesionesRepository for SesionesRepositoryImpl {
         Ok(row.map(Sesion::from))
     }
 
-    async fn get_poses_by_sesion(&self, sesion_id: Uuid) -> Result<Vec<Pose>, DomainError> {
-        let rows = sqlx::query_as::<_, PoseRow>(
+    async fn get_poses_by_sesion_paginated(
+        &self,
+        sesion_id: Uuid,
+        page: u32,
+        limit: u32,
+    ) -> Result<Vec<Pose>, DomainError> {
+        let offset = page.saturating_mul(limit);
+        let rows = sqlx::query_as::<_, PoseRow>(&format!(
             r#"
-            SELECT p.id, p.url, p.created_at
+            SELECT {}
             FROM poses p
             INNER JOIN sesion_image si ON si.pose_id = p.id
             WHERE si.sesion_id = $1
-            ORDER BY si.created_at ASC
+            ORDER BY si.created_at ASC, p.id ASC
+            LIMIT $2 OFFSET $3
             "#,
-        )
+            POSE_COLUMNS
+        ))
         .bind(sesion_id)
+        .bind(limit as i64)
+        .bind(offset as i64)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
         Ok(rows.into_iter().map(Pose::from).collect())
     }
 
-    async fn create(&self, name: &str) -> Result<Sesion, DomainError> {
-        let row = sqlx::query_as::<_, SesionRow>(
+    async fn count_poses_by_sesion(&self, sesion_id: Uuid) -> Result<u64, DomainError> {
+        let row: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM sesion_image si INNER JOIN poses p ON p.id = si.pose_id WHERE si.sesion_id = $1",
+        )
+        .bind(sesion_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.0 as u64)
+    }
+
+    async fn get_poses_by_sesiones(
+        &self,
+        sesion_ids: &[Uuid],
+    ) -> Result<Vec<(Uuid, Pose)>, DomainError> {
+        if sesion_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let rows = sqlx::query_as::<_, SesionPoseRow>(&format!(
             r#"
-            INSERT INTO sesiones (name)
-            VALUES ($1)
-            RETURNING id, name, created_at, cover_url
+            SELECT si.sesion_id, {}
+            FROM poses p
+            INNER JOIN sesion_image si ON si.pose_id = p.id
+            WHERE si.sesion_id = ANY($1)
+            ORDER BY si.created_at ASC, p.id ASC
+            "#,
+            POSE_COLUMNS
+        ))
+        .bind(sesion_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.sesion_id, Pose::from(row.pose)))
+            .collect())
+    }
+
+    async fn get_sesiones_by_poses(
+        &self,
+        pose_ids: &[Uuid],
+    ) -> Result<Vec<(Uuid, Sesion)>, DomainError> {
+        if pose_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let rows = sqlx::query_as::<_, PoseSesionRow>(
+            r#"
+            SELECT si.pose_id, s.id, s.name, s.created_at, s.cover_url
+            FROM sesiones s
+            INNER JOIN sesion_image si ON si.sesion_id = s.id
+            WHERE si.pose_id = ANY($1)
+            ORDER BY s.created_at DESC, s.id DESC
             "#,
         )
-        .bind(name)
-        .fetch_one(&self.pool)
+        .bind(pose_ids)
+        .fetch_all(&self.pool)
         .await
         .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
-        Ok(Sesion::from(row))
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.pose_id, Sesion::from(row.sesion)))
+            .collect())
+    }
+
+    async fn create(&self, name: &str) -> Result<Sesion, DomainError> {
+        insert_sesion(&self.pool, name).await
+    }
+
+    async fn create_in(&self, uow: &mut dyn UnitOfWork, name: &str) -> Result<Sesion, DomainError> {
+        insert_sesion(PgUnitOfWork::connection(uow)?, name).await
     }
 
     async fn delete(&self, id: Uuid) -> Result<(), DomainError> {
@@ -121,23 +254,36 @@ impl SesionesRepository for SesionesRepositoryImpl {
         if pose_ids.is_empty() {
             return Ok(());
         }
-        for pose_id in pose_ids {
-            sqlx::query(
-                r#"
-                INSERT INTO sesion_image (sesion_id, pose_id)
-                VALUES ($1, $2)
-                ON CONFLICT (sesion_id, pose_id) DO NOTHING
-                "#,
-            )
-            .bind(sesion_id)
-            .bind(pose_id)
-            .execute(&self.pool)
+        let mut conn = self
+            .pool
+            .acquire()
             .await
             .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
-        }
+        insert_sesion_poses(&mut conn, sesion_id, pose_ids).await?;
         Ok(())
     }
 
+    async fn add_poses_to_sesion_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        sesion_id: Uuid,
+        pose_ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, DomainError> {
+        insert_sesion_poses(PgUnitOfWork::connection(uow)?, sesion_id, pose_ids).await
+    }
+
+    async fn lock_existing_ids_in(
+        &self,
+        uow: &mut dyn UnitOfWork,
+        ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, DomainError> {
+        sqlx::query_scalar("SELECT id FROM sesiones WHERE id = ANY($1) ORDER BY id FOR SHARE")
+            .bind(ids)
+            .fetch_all(PgUnitOfWork::connection(uow)?)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))
+    }
+
     async fn remove_pose_from_sesion(
         &self,
         sesion_id: Uuid,
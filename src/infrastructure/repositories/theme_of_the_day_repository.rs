@@ -1,20 +1,60 @@
-use crate::domain::{DomainError, ThemeOfTheDay, ThemeOfTheDayRepository};
+use crate::domain::{
+    DomainError, MonthDay, ThemeFallback, ThemeOfTheDay, ThemeOfTheDayPoolEntry,
+    ThemeOfTheDayRepository,
+};
 use async_trait::async_trait;
+use chrono::NaiveDate;
 use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Columnas de theme_of_the_day (incluye posts enlazados a la instancia) para SELECT y RETURNING.
+const THEME_COLUMNS: &str = r#"
+    theme_of_the_day.date, theme_of_the_day.mmdd, theme_of_the_day.name, theme_of_the_day.url,
+    theme_of_the_day.fallback, theme_of_the_day.pool_entry_id,
+    (SELECT COUNT(*) FROM posts p WHERE p.theme_date = theme_of_the_day.date) AS posts_count
+"#;
 
 #[derive(FromRow)]
 pub struct ThemeOfTheDayRow {
-    pub id: String,
+    pub date: NaiveDate,
+    pub mmdd: String,
     pub name: String,
     pub url: String,
+    pub fallback: Option<String>,
+    pub pool_entry_id: Option<Uuid>,
+    pub posts_count: i64,
 }
 
 impl From<ThemeOfTheDayRow> for ThemeOfTheDay {
     fn from(row: ThemeOfTheDayRow) -> Self {
         ThemeOfTheDay {
+            id: row.date.format("%Y-%m-%d").to_string(),
+            date: row.date,
+            mmdd: row.mmdd,
+            name: row.name,
+            url: row.url,
+            posts_count: row.posts_count,
+            fallback: row.fallback.as_deref().and_then(ThemeFallback::parse),
+            pool_entry_id: row.pool_entry_id,
+        }
+    }
+}
+
+#[derive(FromRow)]
+pub struct ThemeOfTheDayPoolRow {
+    pub id: Uuid,
+    pub name: String,
+    pub url: String,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<ThemeOfTheDayPoolRow> for ThemeOfTheDayPoolEntry {
+    fn from(row: ThemeOfTheDayPoolRow) -> Self {
+        ThemeOfTheDayPoolEntry {
             id: row.id,
             name: row.name,
             url: row.url,
+            created_at: row.created_at,
         }
     }
 }
@@ -32,20 +72,62 @@ impl ThemeOfTheDayRepositoryImpl {
 #[async_trait]
 impl ThemeOfTheDayRepository for ThemeOfTheDayRepositoryImpl {
     async fn get_all(&self) -> Result<Vec<ThemeOfTheDay>, DomainError> {
-        let rows = sqlx::query_as::<_, ThemeOfTheDayRow>(
-            "SELECT id, name, url FROM theme_of_the_day ORDER BY id ASC",
-        )
+        let rows = sqlx::query_as::<_, ThemeOfTheDayRow>(&format!(
+            "SELECT {} FROM theme_of_the_day ORDER BY date ASC",
+            THEME_COLUMNS
+        ))
         .fetch_all(&self.pool)
         .await
         .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
         Ok(rows.into_iter().map(ThemeOfTheDay::from).collect())
     }
 
-    async fn get_by_id(&self, id: &str) -> Result<Option<ThemeOfTheDay>, DomainError> {
-        let row = sqlx::query_as::<_, ThemeOfTheDayRow>(
-            "SELECT id, name, url FROM theme_of_the_day WHERE id = $1",
-        )
-        .bind(id)
+    async fn get_by_year(&self, year: i32) -> Result<Vec<ThemeOfTheDay>, DomainError> {
+        let rows = sqlx::query_as::<_, ThemeOfTheDayRow>(&format!(
+            r#"
+            SELECT {} FROM theme_of_the_day
+            WHERE date >= make_date($1, 1, 1) AND date < make_date($1 + 1, 1, 1)
+            ORDER BY date ASC
+            "#,
+            THEME_COLUMNS
+        ))
+        .bind(year)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(rows.into_iter().map(ThemeOfTheDay::from).collect())
+    }
+
+    async fn get_by_date(&self, date: NaiveDate) -> Result<Option<ThemeOfTheDay>, DomainError> {
+        let row = sqlx::query_as::<_, ThemeOfTheDayRow>(&format!(
+            "SELECT {} FROM theme_of_the_day WHERE date = $1",
+            THEME_COLUMNS
+        ))
+        .bind(date)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.map(ThemeOfTheDay::from))
+    }
+
+    async fn get_latest_by_mmdd(
+        &self,
+        mmdd: MonthDay,
+        on_or_before: NaiveDate,
+    ) -> Result<Option<ThemeOfTheDay>, DomainError> {
+        let row = sqlx::query_as::<_, ThemeOfTheDayRow>(&format!(
+            r#"
+            SELECT {} FROM theme_of_the_day
+            WHERE mmdd = $1
+            ORDER BY (date <= $2) DESC,
+                CASE WHEN date <= $2 THEN date END DESC,
+                date ASC
+            LIMIT 1
+            "#,
+            THEME_COLUMNS
+        ))
+        .bind(mmdd.to_string())
+        .bind(on_or_before)
         .fetch_optional(&self.pool)
         .await
         .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
@@ -54,43 +136,80 @@ impl ThemeOfTheDayRepository for ThemeOfTheDayRepositoryImpl {
 
     async fn create(
         &self,
-        id: &str,
+        date: NaiveDate,
         name: &str,
         url: &str,
     ) -> Result<ThemeOfTheDay, DomainError> {
-        let row = sqlx::query_as::<_, ThemeOfTheDayRow>(
+        let row = sqlx::query_as::<_, ThemeOfTheDayRow>(&format!(
             r#"
-            INSERT INTO theme_of_the_day (id, name, url)
-            VALUES ($1, $2, $3)
-            RETURNING id, name, url
+            INSERT INTO theme_of_the_day (date, mmdd, name, url)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (date) DO UPDATE
+            SET name = EXCLUDED.name, url = EXCLUDED.url, fallback = NULL, pool_entry_id = NULL
+            WHERE theme_of_the_day.fallback IS NOT NULL
+            RETURNING {}
+            "#,
+            THEME_COLUMNS
+        ))
+        .bind(date)
+        .bind(MonthDay::from_date(date).to_string())
+        .bind(name)
+        .bind(url)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        row.map(ThemeOfTheDay::from).ok_or_else(|| {
+            DomainError::Validation(format!("Ya hay un tema programado para {}", date))
+        })
+    }
+
+    async fn create_if_missing(
+        &self,
+        date: NaiveDate,
+        name: &str,
+        url: &str,
+        fallback: ThemeFallback,
+        pool_entry_id: Option<Uuid>,
+    ) -> Result<ThemeOfTheDay, DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO theme_of_the_day (date, mmdd, name, url, fallback, pool_entry_id)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (date) DO NOTHING
             "#,
         )
-        .bind(id)
+        .bind(date)
+        .bind(MonthDay::from_date(date).to_string())
         .bind(name)
         .bind(url)
-        .fetch_one(&self.pool)
+        .bind(fallback.as_str())
+        .bind(pool_entry_id)
+        .execute(&self.pool)
         .await
         .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
-        Ok(ThemeOfTheDay::from(row))
+        self.get_by_date(date).await?.ok_or_else(|| {
+            DomainError::NotFound(format!("Tema del día no encontrado: {}", date))
+        })
     }
 
     async fn update(
         &self,
-        id: &str,
+        date: NaiveDate,
         name: Option<&str>,
         url: Option<&str>,
     ) -> Result<Option<ThemeOfTheDay>, DomainError> {
-        let row = sqlx::query_as::<_, ThemeOfTheDayRow>(
+        let row = sqlx::query_as::<_, ThemeOfTheDayRow>(&format!(
             r#"
             UPDATE theme_of_the_day
             SET
                 name = COALESCE($2, name),
                 url = COALESCE($3, url)
-            WHERE id = $1
-            RETURNING id, name, url
+            WHERE date = $1
+            RETURNING {}
             "#,
-        )
-        .bind(id)
+            THEME_COLUMNS
+        ))
+        .bind(date)
         .bind(name)
         .bind(url)
         .fetch_optional(&self.pool)
@@ -99,12 +218,102 @@ impl ThemeOfTheDayRepository for ThemeOfTheDayRepositoryImpl {
         Ok(row.map(ThemeOfTheDay::from))
     }
 
-    async fn delete(&self, id: &str) -> Result<(), DomainError> {
-        sqlx::query("DELETE FROM theme_of_the_day WHERE id = $1")
-            .bind(id)
+    async fn delete(&self, date: NaiveDate) -> Result<(), DomainError> {
+        sqlx::query("DELETE FROM theme_of_the_day WHERE date = $1")
+            .bind(date)
             .execute(&self.pool)
             .await
             .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
         Ok(())
     }
+
+    async fn get_most_popular_before(
+        &self,
+        before: NaiveDate,
+    ) -> Result<Option<ThemeOfTheDay>, DomainError> {
+        let row = sqlx::query_as::<_, ThemeOfTheDayRow>(&format!(
+            r#"
+            SELECT {} FROM theme_of_the_day
+            WHERE date < $1
+            ORDER BY posts_count DESC, date DESC
+            LIMIT 1
+            "#,
+            THEME_COLUMNS
+        ))
+        .bind(before)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.map(ThemeOfTheDay::from))
+    }
+
+    async fn get_pool(&self) -> Result<Vec<ThemeOfTheDayPoolEntry>, DomainError> {
+        let rows = sqlx::query_as::<_, ThemeOfTheDayPoolRow>(
+            "SELECT id, name, url, created_at FROM theme_of_the_day_pool ORDER BY created_at ASC, id ASC",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(rows.into_iter().map(ThemeOfTheDayPoolEntry::from).collect())
+    }
+
+    async fn next_pool_entry(&self) -> Result<Option<ThemeOfTheDayPoolEntry>, DomainError> {
+        let row = sqlx::query_as::<_, ThemeOfTheDayPoolRow>(
+            r#"
+            SELECT p.id, p.name, p.url, p.created_at
+            FROM theme_of_the_day_pool p
+            LEFT JOIN LATERAL (
+                SELECT MAX(t.date) AS last_used FROM theme_of_the_day t WHERE t.pool_entry_id = p.id
+            ) u ON TRUE
+            ORDER BY u.last_used ASC NULLS FIRST, p.created_at ASC, p.id ASC
+            LIMIT 1
+            "#,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.map(ThemeOfTheDayPoolEntry::from))
+    }
+
+    async fn is_pool_entry_used(&self, id: Uuid) -> Result<bool, DomainError> {
+        let (used,): (bool,) = sqlx::query_as(
+            "SELECT EXISTS (SELECT 1 FROM theme_of_the_day WHERE pool_entry_id = $1)",
+        )
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(used)
+    }
+
+    async fn add_to_pool(
+        &self,
+        id: Uuid,
+        name: &str,
+        url: &str,
+    ) -> Result<ThemeOfTheDayPoolEntry, DomainError> {
+        let row = sqlx::query_as::<_, ThemeOfTheDayPoolRow>(
+            r#"
+            INSERT INTO theme_of_the_day_pool (id, name, url)
+            VALUES ($1, $2, $3)
+            RETURNING id, name, url, created_at
+            "#,
+        )
+        .bind(id)
+        .bind(name)
+        .bind(url)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(ThemeOfTheDayPoolEntry::from(row))
+    }
+
+    async fn remove_from_pool(&self, id: Uuid) -> Result<bool, DomainError> {
+        let result = sqlx::query("DELETE FROM theme_of_the_day_pool WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(result.rows_affected() > 0)
+    }
 }
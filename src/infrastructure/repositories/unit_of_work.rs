@@ -0,0 +1,86 @@
+use crate::domain::{Compensation, DomainError, UnitOfWork, UnitOfWorkFactory};
+use async_trait::async_trait;
+use sqlx::{PgConnection, Postgres, Transaction};
+use std::any::Any;
+
+/// Unidad de trabajo sobre una transacción de Postgres.
+pub struct PgUnitOfWork {
+    /// None una vez confirmada.
+    tx: Option<Transaction<'static, Postgres>>,
+    compensations: Vec<Compensation>,
+}
+
+impl PgUnitOfWork {
+    /// Conexión de la transacción de `uow`, para que un repositorio ejecute sus consultas dentro de ella.
+    pub fn connection(uow: &mut dyn UnitOfWork) -> Result<&mut PgConnection, DomainError> {
+        uow.as_any_mut()
+            .downcast_mut::<PgUnitOfWork>()
+            .and_then(|uow| uow.tx.as_deref_mut())
+            .ok_or_else(|| {
+                DomainError::Repository(anyhow::anyhow!(
+                    "La unidad de trabajo no es de Postgres o ya se confirmó"
+                ))
+            })
+    }
+
+    fn compensate(&mut self) {
+        while let Some(compensation) = self.compensations.pop() {
+            compensation();
+        }
+    }
+}
+
+#[async_trait]
+impl UnitOfWork for PgUnitOfWork {
+    fn on_rollback(&mut self, compensation: Compensation) {
+        self.compensations.push(compensation);
+    }
+
+    async fn commit(mut self: Box<Self>) -> Result<(), DomainError> {
+        let tx = self.tx.take().ok_or_else(|| {
+            DomainError::Repository(anyhow::anyhow!("La unidad de trabajo ya se confirmó"))
+        })?;
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        self.compensations.clear();
+        Ok(())
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl Drop for PgUnitOfWork {
+    // Sin commit (o con commit fallido) sqlx deshace la transacción al soltarla; aquí se deshacen los
+    // efectos fuera de la base de datos.
+    fn drop(&mut self) {
+        self.compensate();
+    }
+}
+
+pub struct PgUnitOfWorkFactory {
+    pool: sqlx::PgPool,
+}
+
+impl PgUnitOfWorkFactory {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UnitOfWorkFactory for PgUnitOfWorkFactory {
+    async fn begin(&self) -> Result<Box<dyn UnitOfWork>, DomainError> {
+        let tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(Box::new(PgUnitOfWork {
+            tx: Some(tx),
+            compensations: Vec::new(),
+        }))
+    }
+}
@@ -9,6 +9,7 @@ pub struct UsuarioRow {
     pub name: Option<String>,
     pub email: Option<String>,
     pub url: Option<String>,
+    pub time_zone: Option<String>,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
@@ -19,6 +20,7 @@ impl From<UsuarioRow> for Usuario {
             name: row.name,
             email: row.email,
             url: row.url,
+            time_zone: row.time_zone,
             created_at: row.created_at,
         }
     }
@@ -38,7 +40,7 @@ impl UsuariosRepositoryImpl {
 impl UsuariosRepository for UsuariosRepositoryImpl {
     async fn get_by_id(&self, id: Uuid) -> Result<Option<Usuario>, DomainError> {
         let row = sqlx::query_as::<_, UsuarioRow>(
-            "SELECT id, name, email, url, created_at FROM usuarios WHERE id = $1",
+            "SELECT id, name, email, url, time_zone, created_at FROM usuarios WHERE id = $1",
         )
         .bind(id)
         .fetch_optional(&self.pool)
@@ -51,7 +53,7 @@ impl UsuariosRepository for UsuariosRepositoryImpl {
         let row = sqlx::query_as::<_, UsuarioRow>(
             r#"
             UPDATE usuarios SET name = $2 WHERE id = $1
-            RETURNING id, name, email, url, created_at
+            RETURNING id, name, email, url, time_zone, created_at
             "#,
         )
         .bind(id)
@@ -62,11 +64,30 @@ impl UsuariosRepository for UsuariosRepositoryImpl {
         Ok(row.map(Usuario::from))
     }
 
+    async fn update_time_zone(
+        &self,
+        id: Uuid,
+        time_zone: Option<&str>,
+    ) -> Result<Option<Usuario>, DomainError> {
+        let row = sqlx::query_as::<_, UsuarioRow>(
+            r#"
+            UPDATE usuarios SET time_zone = $2 WHERE id = $1
+            RETURNING id, name, email, url, time_zone, created_at
+            "#,
+        )
+        .bind(id)
+        .bind(time_zone)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.map(Usuario::from))
+    }
+
     async fn update_avatar(&self, id: Uuid, url: &str) -> Result<Option<Usuario>, DomainError> {
         let row = sqlx::query_as::<_, UsuarioRow>(
             r#"
             UPDATE usuarios SET url = $2 WHERE id = $1
-            RETURNING id, name, email, url, created_at
+            RETURNING id, name, email, url, time_zone, created_at
             "#,
         )
         .bind(id)
@@ -76,4 +97,46 @@ impl UsuariosRepository for UsuariosRepositoryImpl {
         .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
         Ok(row.map(Usuario::from))
     }
+
+    async fn get_or_create_calendar_token(
+        &self,
+        id: Uuid,
+        new_token: &str,
+    ) -> Result<Option<String>, DomainError> {
+        let row: Option<(String,)> = sqlx::query_as(
+            r#"
+            UPDATE usuarios SET calendar_token = COALESCE(calendar_token, $2) WHERE id = $1
+            RETURNING calendar_token
+            "#,
+        )
+        .bind(id)
+        .bind(new_token)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.map(|r| r.0))
+    }
+
+    async fn set_calendar_token(&self, id: Uuid, token: &str) -> Result<Option<String>, DomainError> {
+        let row: Option<(String,)> = sqlx::query_as(
+            "UPDATE usuarios SET calendar_token = $2 WHERE id = $1 RETURNING calendar_token",
+        )
+        .bind(id)
+        .bind(token)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.map(|r| r.0))
+    }
+
+    async fn get_by_calendar_token(&self, token: &str) -> Result<Option<Usuario>, DomainError> {
+        let row = sqlx::query_as::<_, UsuarioRow>(
+            "SELECT id, name, email, url, time_zone, created_at FROM usuarios WHERE calendar_token = $1",
+        )
+        .bind(token)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(anyhow::Error::from(e)))?;
+        Ok(row.map(Usuario::from))
+    }
 }
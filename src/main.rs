@@ -47,6 +47,10 @@ async fn main() -> anyhow::Result<()> {
         Arc::new(infrastructure::FavoritesRepositoryImpl::new(pool.clone()));
     let usuarios_repo: Arc<dyn domain::UsuariosRepository> =
         Arc::new(infrastructure::UsuariosRepositoryImpl::new(pool.clone()));
+
+    // Unidad de trabajo: escrituras de varios repositorios en una transacción
+    let unit_of_work: Arc<dyn domain::UnitOfWorkFactory> =
+        Arc::new(infrastructure::PgUnitOfWorkFactory::new(pool.clone()));
     let auth_repo: Arc<dyn domain::AuthRepository> =
         Arc::new(infrastructure::AuthRepositoryImpl::new(pool));
 
@@ -58,6 +62,68 @@ async fn main() -> anyhow::Result<()> {
     std::fs::create_dir_all(&config.places_images_dir).ok();
     std::fs::create_dir_all(&config.profile_avatars_dir).ok();
 
+    if config.public_base_url.is_none() {
+        eprintln!("PUBLIC_BASE_URL no está definida: no se darán las URLs de los feeds iCalendar");
+    }
+
+    // Fija los ganadores del reto diario al cerrar cada día, aunque nadie consulte el ranking.
+    let decide_winners = application::DecideDailyWinnersUseCase::new(Arc::clone(&posts_repo));
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(600));
+        loop {
+            interval.tick().await;
+            if let Err(e) = decide_winners.execute(chrono::Utc::now()).await {
+                eprintln!("No se pudieron fijar los ganadores del día: {}", e);
+            }
+        }
+    });
+
+    // Tema de los días sin programar (fallback), elegido antes de que empiecen en cualquier zona horaria.
+    let ensure_fallbacks =
+        application::EnsureThemeOfTheDayFallbacksUseCase::new(Arc::clone(&theme_of_the_day_repo));
+    let theme_fallback = domain::ThemeFallback::parse_list(&config.theme_fallback).unwrap_or_default();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(600));
+        loop {
+            interval.tick().await;
+            if let Err(e) = ensure_fallbacks.execute(chrono::Utc::now(), &theme_fallback).await {
+                eprintln!("No se pudo elegir el tema del día por fallback: {}", e);
+            }
+        }
+    });
+
+    // Hash de imagen de las poses subidas antes de calcularlo al crear (una vez, en segundo plano).
+    let backfill_hashes = application::BackfillPoseImageHashesUseCase::new(Arc::clone(&poses_repo));
+    let poses_images_dir = config.poses_images_dir.clone();
+    tokio::spawn(async move {
+        let hash_image = |id| {
+            let dir = poses_images_dir.clone();
+            async move {
+                tokio::task::spawn_blocking(move || api::image_hash::pose_image_hash(&dir, id))
+                    .await
+                    .ok()
+                    .flatten()
+            }
+        };
+        match backfill_hashes.execute(hash_image).await {
+            Ok(0) => {}
+            Ok(n) => println!("Hash de imagen calculado para {} poses", n),
+            Err(e) => eprintln!("No se pudo completar el hash de imagen de las poses: {}", e),
+        }
+    });
+
+    // Peso por rareza de los hashtags (vista materializada hashtag_idf) para poses similares y recomendadas.
+    let refresh_weights = application::RefreshHashtagWeightsUseCase::new(Arc::clone(&poses_repo));
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+        loop {
+            interval.tick().await;
+            if let Err(e) = refresh_weights.execute().await {
+                eprintln!("No se pudieron recalcular los pesos de los hashtags: {}", e);
+            }
+        }
+    });
+
     let state = api::AppState {
         eventos_repo,
         theme_of_the_day_repo,
@@ -69,7 +135,9 @@ async fn main() -> anyhow::Result<()> {
         places_repo,
         sesiones_repo,
         usuarios_repo,
+        unit_of_work,
         jwt_secret: config.jwt_secret.clone(),
+        admin_emails: config.admin_emails.clone(),
         auth_repository: auth_repo,
         theme_of_the_day_images_dir: config.theme_of_the_day_images_dir.clone(),
         poses_images_dir: config.poses_images_dir.clone(),
@@ -78,6 +146,9 @@ async fn main() -> anyhow::Result<()> {
         eventos_images_dir: config.eventos_images_dir.clone(),
         places_images_dir: config.places_images_dir.clone(),
         profile_avatars_dir: config.profile_avatars_dir.clone(),
+        public_base_url: config.public_base_url.clone(),
+        default_time_zone: domain::LocalTimeZone::parse(&config.default_time_zone)
+            .unwrap_or_default(),
     };
 
     let app: Router = api::create_router(state, &config).layer(TraceLayer::new_for_http());